@@ -1,13 +1,14 @@
 use crate::{
     path::{AssetPath, AssetPathId, SourcePathId},
-    Asset, AssetIo, AssetIoError, AssetLifecycle, AssetLifecycleChannel, AssetLifecycleEvent,
-    AssetLoader, Assets, Handle, HandleId, HandleUntyped, LabelId, LoadContext, LoadState,
-    RefChange, RefChangeChannel, SourceInfo, SourceMeta,
+    Asset, AssetEvent, AssetIo, AssetIoError, AssetLifecycle, AssetLifecycleChannel,
+    AssetLifecycleEvent, AssetLoader, AssetRetention, AssetSources, AssetUsage, Assets, Handle,
+    HandleId, HandleUntyped, LabelId, LoadContext, LoadState, RefChange, RefChangeChannel,
+    RetentionPolicy, SourceInfo, SourceMeta,
 };
 use anyhow::Result;
 use bevy_ecs::Res;
 use bevy_tasks::TaskPool;
-use bevy_utils::{HashMap, Uuid};
+use bevy_utils::{tracing::warn, HashMap, HashSet, Instant, Uuid};
 use crossbeam_channel::TryRecvError;
 use parking_lot::RwLock;
 use std::{collections::hash_map::Entry, path::Path, sync::Arc};
@@ -35,10 +36,11 @@ pub(crate) struct AssetRefCounter {
 }
 
 pub struct AssetServerInternal {
-    pub(crate) asset_io: Box<dyn AssetIo>,
+    pub(crate) asset_io: AssetSources,
     pub(crate) asset_ref_counter: AssetRefCounter,
     pub(crate) asset_sources: Arc<RwLock<HashMap<SourcePathId, SourceInfo>>>,
     pub(crate) asset_lifecycles: Arc<RwLock<HashMap<Uuid, Box<dyn AssetLifecycle>>>>,
+    pub(crate) asset_retention: RwLock<AssetRetention>,
     loaders: RwLock<Vec<Arc<Box<dyn AssetLoader>>>>,
     extension_to_loader_index: RwLock<HashMap<String, usize>>,
     handle_to_path: Arc<RwLock<HashMap<HandleId, AssetPath<'static>>>>,
@@ -60,6 +62,10 @@ impl Clone for AssetServer {
 
 impl AssetServer {
     pub fn new<T: AssetIo>(source_io: T, task_pool: TaskPool) -> Self {
+        Self::with_sources(AssetSources::new(source_io), task_pool)
+    }
+
+    pub fn with_sources(asset_sources: AssetSources, task_pool: TaskPool) -> Self {
         AssetServer {
             server: Arc::new(AssetServerInternal {
                 loaders: Default::default(),
@@ -68,8 +74,9 @@ impl AssetServer {
                 asset_ref_counter: Default::default(),
                 handle_to_path: Default::default(),
                 asset_lifecycles: Default::default(),
+                asset_retention: Default::default(),
                 task_pool,
-                asset_io: Box::new(source_io),
+                asset_io: asset_sources,
             }),
         }
     }
@@ -97,11 +104,76 @@ impl AssetServer {
         loaders.push(Arc::new(Box::new(loader)));
     }
 
+    /// Mounts `asset_io` on top of the source called `name`. Assets in that source can then be loaded
+    /// with paths like `"name://path/to/asset.png"`.
+    pub fn mount<T: AssetIo>(&self, name: &str, asset_io: T) {
+        self.server.asset_io.mount(name, asset_io);
+    }
+
+    pub fn asset_sources(&self) -> &AssetSources {
+        &self.server.asset_io
+    }
+
     pub fn watch_for_changes(&self) -> Result<(), AssetServerError> {
         self.server.asset_io.watch_for_changes()?;
         Ok(())
     }
 
+    /// Sets how long assets of type `T` stay loaded after their last strong handle is dropped
+    pub fn set_retention_policy<T: Asset>(&self, policy: RetentionPolicy) {
+        self.server
+            .asset_retention
+            .write()
+            .set_policy(T::TYPE_UUID, policy);
+    }
+
+    pub fn get_retention_policy<T: Asset>(&self) -> RetentionPolicy {
+        self.server.asset_retention.read().get_policy(T::TYPE_UUID)
+    }
+
+    /// Sets the function used to estimate the memory used by assets of type `T`, in bytes. This
+    /// is used by [RetentionPolicy::MemoryBudget] and [AssetServer::asset_usage]. By default, the
+    /// estimate is the size of `T`.
+    pub fn set_memory_estimator<T: Asset>(
+        &self,
+        estimator: impl Fn(&T) -> usize + Send + Sync + 'static,
+    ) {
+        self.server
+            .asset_retention
+            .write()
+            .set_memory_estimator(estimator);
+    }
+
+    /// Returns the live handle counts and memory estimates of all tracked assets
+    pub fn asset_usage(&self) -> Vec<AssetUsage> {
+        let ref_counts = self.server.asset_ref_counter.ref_counts.read();
+        let asset_sources = self.server.asset_sources.read();
+        let asset_retention = self.server.asset_retention.read();
+        let mut ids = ref_counts.keys().cloned().collect::<HashSet<_>>();
+        ids.extend(asset_retention.memory_estimates().map(|(id, _)| *id));
+        ids.into_iter()
+            .map(|id| AssetUsage {
+                id,
+                type_uuid: Self::get_asset_type(&asset_sources, id),
+                strong_handles: ref_counts.get(&id).cloned().unwrap_or(0),
+                memory_estimate: asset_retention.get_memory_estimate(id),
+                retained: asset_retention.is_retained(id),
+            })
+            .collect()
+    }
+
+    fn get_asset_type(
+        asset_sources: &HashMap<SourcePathId, SourceInfo>,
+        id: HandleId,
+    ) -> Option<Uuid> {
+        match id {
+            HandleId::Id(type_uuid, _) => Some(type_uuid),
+            HandleId::AssetPathId(id) => asset_sources
+                .get(&id.source_path_id())
+                .and_then(|source_info| source_info.get_asset_type(id.label_id())),
+        }
+    }
+
     pub fn get_handle<T: Asset, I: Into<HandleId>>(&self, id: I) -> Handle<T> {
         let sender = self.server.asset_ref_counter.channel.sender.clone();
         Handle::strong(id.into(), sender)
@@ -199,6 +271,7 @@ impl AssetServer {
                     load_state: LoadState::NotLoaded,
                     meta: None,
                     path: asset_path.path().to_owned(),
+                    file_dependencies: Default::default(),
                     version: 0,
                 }),
             };
@@ -219,20 +292,26 @@ impl AssetServer {
             source_info.version
         };
 
-        // load the asset bytes
-        let bytes = self.server.asset_io.load_path(asset_path.path()).await?;
-
         // load the asset source using the corresponding AssetLoader
         let mut load_context = LoadContext::new(
             asset_path.path(),
             &self.server.asset_ref_counter.channel,
-            &*self.server.asset_io,
+            &self.server.asset_io,
             version,
         );
-        asset_loader
-            .load(&bytes, &mut load_context)
-            .await
-            .map_err(AssetServerError::AssetLoaderError)?;
+        if asset_loader.streams_input() {
+            let mut reader = self.server.asset_io.read_path(asset_path.path()).await?;
+            asset_loader
+                .load_reader(&mut *reader, &mut load_context)
+                .await
+                .map_err(AssetServerError::AssetLoaderError)?;
+        } else {
+            let bytes = self.server.asset_io.load_path(asset_path.path()).await?;
+            asset_loader
+                .load(&bytes, &mut load_context)
+                .await
+                .map_err(AssetServerError::AssetLoaderError)?;
+        }
 
         // if version has changed since we loaded and grabbed a lock, return. theres is a newer version being loaded
         let mut asset_sources = self.server.asset_sources.write();
@@ -267,14 +346,82 @@ impl AssetServer {
             }
         }
 
-        self.server
+        if let Err(err) = self
+            .server
             .asset_io
             .watch_path_for_changes(asset_path.path())
-            .unwrap();
+        {
+            warn!("failed to watch {:?}: {}", asset_path.path(), err);
+        }
+        source_info.file_dependencies = std::mem::take(load_context.file_dependencies.get_mut());
+        for file_dependency in source_info.file_dependencies.iter() {
+            // the asset itself loaded fine, so only hot reloading is affected
+            if let Err(err) = self.server.asset_io.watch_path_for_changes(file_dependency) {
+                warn!(
+                    "failed to watch {:?}, a dependency of {:?}: {}",
+                    file_dependency,
+                    asset_path.path(),
+                    err
+                );
+            }
+        }
+        drop(asset_sources);
+
         self.create_assets_in_load_context(&mut load_context);
+        if force {
+            self.notify_dependents_modified(asset_path.path());
+        }
         Ok(asset_path_id)
     }
 
+    /// Reloads the source at `path` if it was loaded, along with every source that read `path`
+    /// while it was loading
+    pub fn reload_changed_path(&self, path: &Path) {
+        let mut reload_paths = Vec::new();
+        {
+            let asset_sources = self.server.asset_sources.read();
+            if asset_sources.contains_key(&SourcePathId::from(path)) {
+                reload_paths.push(path.to_owned());
+            }
+            for source_info in asset_sources.values() {
+                if source_info.file_dependencies.contains(path) {
+                    reload_paths.push(source_info.path.clone());
+                }
+            }
+        }
+
+        for reload_path in reload_paths {
+            let _ = self.load_untracked(reload_path, true);
+        }
+    }
+
+    /// Sends [AssetEvent::Modified](crate::AssetEvent::Modified) for every asset that depends on
+    /// an asset from the source at `path`, directly or through other assets
+    fn notify_dependents_modified(&self, path: &Path) {
+        let asset_sources = self.server.asset_sources.read();
+        let asset_lifecycles = self.server.asset_lifecycles.read();
+        let mut visited = HashSet::default();
+        visited.insert(SourcePathId::from(path));
+        let mut changed_paths = vec![path.to_owned()];
+        while let Some(changed_path) = changed_paths.pop() {
+            for (source_path_id, source_info) in asset_sources.iter() {
+                let mut is_dependent = false;
+                for asset_meta in source_info.get_dependent_assets(&changed_path) {
+                    is_dependent = true;
+                    let asset_path =
+                        AssetPath::new_ref(&source_info.path, asset_meta.label.as_deref());
+                    if let Some(asset_lifecycle) = asset_lifecycles.get(&asset_meta.type_uuid) {
+                        asset_lifecycle.modify_asset(asset_path.into());
+                    }
+                }
+
+                if is_dependent && visited.insert(*source_path_id) {
+                    changed_paths.push(source_info.path.clone());
+                }
+            }
+        }
+    }
+
     pub fn load_untyped<'a, P: Into<AssetPath<'a>>>(&self, path: P) -> HandleUntyped {
         let handle_id = self.load_untracked(path, false);
         self.get_handle_untyped(handle_id)
@@ -329,6 +476,7 @@ impl AssetServer {
         let receiver = &self.server.asset_ref_counter.channel.receiver;
         let mut ref_counts = self.server.asset_ref_counter.ref_counts.write();
         let asset_sources = self.server.asset_sources.read();
+        let mut asset_retention = self.server.asset_retention.write();
         let mut potential_frees = Vec::new();
         loop {
             let ref_change = match receiver.try_recv() {
@@ -337,7 +485,10 @@ impl AssetServer {
                 Err(TryRecvError::Disconnected) => panic!("RefChange channel disconnected."),
             };
             match ref_change {
-                RefChange::Increment(handle_id) => *ref_counts.entry(handle_id).or_insert(0) += 1,
+                RefChange::Increment(handle_id) => {
+                    *ref_counts.entry(handle_id).or_insert(0) += 1;
+                    asset_retention.reacquire(handle_id);
+                }
                 RefChange::Decrement(handle_id) => {
                     let entry = ref_counts.entry(handle_id).or_insert(0);
                     *entry -= 1;
@@ -348,27 +499,28 @@ impl AssetServer {
             }
         }
 
-        if !potential_frees.is_empty() {
-            let asset_lifecycles = self.server.asset_lifecycles.read();
-            for potential_free in potential_frees {
-                if let Some(i) = ref_counts.get(&potential_free).cloned() {
-                    if i == 0 {
-                        let type_uuid = match potential_free {
-                            HandleId::Id(type_uuid, _) => Some(type_uuid),
-                            HandleId::AssetPathId(id) => asset_sources
-                                .get(&id.source_path_id())
-                                .and_then(|source_info| source_info.get_asset_type(id.label_id())),
-                        };
-
-                        if let Some(type_uuid) = type_uuid {
-                            if let Some(asset_lifecycle) = asset_lifecycles.get(&type_uuid) {
-                                asset_lifecycle.free_asset(potential_free);
-                            }
-                        }
+        // unused assets are handed to their retention policy, which decides when they are freed
+        let now = Instant::now();
+        asset_retention.next_frame();
+        for potential_free in potential_frees {
+            if let Some(i) = ref_counts.get(&potential_free).cloned() {
+                if i == 0 {
+                    if let Some(type_uuid) = Self::get_asset_type(&asset_sources, potential_free) {
+                        asset_retention.release(potential_free, type_uuid, now);
                     }
                 }
             }
         }
+
+        let expired = asset_retention.collect_expired(now);
+        if !expired.is_empty() {
+            let asset_lifecycles = self.server.asset_lifecycles.read();
+            for (handle_id, type_uuid) in expired {
+                if let Some(asset_lifecycle) = asset_lifecycles.get(&type_uuid) {
+                    asset_lifecycle.free_asset(handle_id);
+                }
+            }
+        }
     }
 
     fn create_assets_in_load_context(&self, load_context: &mut LoadContext) {
@@ -388,6 +540,34 @@ impl AssetServer {
         }
     }
 
+    /// Re-estimates the memory used by tracked assets that were modified since they were created
+    pub(crate) fn update_memory_estimates<T: Asset>(
+        &self,
+        assets: &Assets<T>,
+        events: &[AssetEvent<T>],
+    ) {
+        let mut modified = events
+            .iter()
+            .filter_map(|event| match event {
+                AssetEvent::Modified { handle } => Some(handle.id),
+                _ => None,
+            })
+            .peekable();
+        if modified.peek().is_none() {
+            return;
+        }
+
+        let mut asset_retention = self.server.asset_retention.write();
+        for id in modified {
+            if asset_retention.get_memory_estimate(id).is_none() {
+                continue;
+            }
+            if let Some(asset) = assets.get(id) {
+                asset_retention.record_memory_estimate(id, asset);
+            }
+        }
+    }
+
     pub(crate) fn update_asset_storage<T: Asset>(&self, assets: &mut Assets<T>) {
         let asset_lifecycles = self.server.asset_lifecycles.read();
         let asset_lifecycle = asset_lifecycles.get(&T::TYPE_UUID).unwrap();
@@ -413,8 +593,15 @@ impl AssetServer {
                         }
                     }
 
+                    self.server
+                        .asset_retention
+                        .write()
+                        .record_memory_estimate(result.id, &result.asset);
                     assets.set(result.id, result.asset);
                 }
+                Ok(AssetLifecycleEvent::Modify(handle_id)) => {
+                    assets.mark_modified(handle_id);
+                }
                 Ok(AssetLifecycleEvent::Free(handle_id)) => {
                     if let HandleId::AssetPathId(id) = handle_id {
                         let asset_sources = asset_sources_guard
@@ -426,6 +613,7 @@ impl AssetServer {
                             }
                         }
                     }
+                    self.server.asset_retention.write().forget(handle_id);
                     assets.remove(handle_id);
                 }
                 Err(TryRecvError::Empty) => {
@@ -440,3 +628,310 @@ impl AssetServer {
 pub fn free_unused_assets_system(asset_server: Res<AssetServer>) {
     asset_server.free_unused_assets();
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        AddAsset, AssetEvent, AssetPlugin, AssetReader, LoadedAsset, MemoryAssetIo,
+        DEFAULT_ASSET_SOURCE,
+    };
+    use bevy_app::{prelude::*, App};
+    use bevy_reflect::{TypeRegistryArc, TypeUuid};
+    use bevy_tasks::IoTaskPool;
+    use bevy_utils::BoxedFuture;
+    use futures_lite::AsyncReadExt;
+
+    #[derive(TypeUuid)]
+    #[uuid = "5e4bd7a4-4f3c-4b2a-9d1e-33c3b8a0f6d1"]
+    struct Text(String);
+
+    #[derive(TypeUuid)]
+    #[uuid = "0b8cbe2e-7d7c-4b8e-8a47-8f0c5e3e6a52"]
+    struct Material {
+        _text: Handle<Text>,
+    }
+
+    /// Loads the file followed by the contents of `common.inc`
+    struct TextLoader;
+
+    impl AssetLoader for TextLoader {
+        fn load<'a>(
+            &'a self,
+            bytes: &'a [u8],
+            load_context: &'a mut LoadContext,
+        ) -> BoxedFuture<'a, Result<()>> {
+            Box::pin(async move {
+                let include = load_context.read_asset_bytes("common.inc").await?;
+                let text = String::from_utf8([bytes, &include].concat())?;
+                load_context.set_default_asset(LoadedAsset::new(Text(text)));
+                Ok(())
+            })
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["txt"]
+        }
+    }
+
+    /// Loads a material that uses the text at the path in the file
+    struct MaterialLoader;
+
+    impl AssetLoader for MaterialLoader {
+        fn load<'a>(
+            &'a self,
+            bytes: &'a [u8],
+            load_context: &'a mut LoadContext,
+        ) -> BoxedFuture<'a, Result<()>> {
+            Box::pin(async move {
+                let text_path = AssetPath::from(std::str::from_utf8(bytes)?);
+                let material = Material {
+                    _text: load_context.get_handle(text_path.get_id()),
+                };
+                load_context
+                    .set_default_asset(LoadedAsset::new(material).with_dependency(text_path));
+                Ok(())
+            })
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["mat"]
+        }
+    }
+
+    /// Streams a length byte followed by that many bytes of text, leaving the rest of the file unread
+    struct ChunkLoader;
+
+    impl AssetLoader for ChunkLoader {
+        fn load<'a>(
+            &'a self,
+            _bytes: &'a [u8],
+            _load_context: &'a mut LoadContext,
+        ) -> BoxedFuture<'a, Result<()>> {
+            Box::pin(async move { Err(anyhow::anyhow!("chunks are streamed")) })
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["chunk"]
+        }
+
+        fn streams_input(&self) -> bool {
+            true
+        }
+
+        fn load_reader<'a>(
+            &'a self,
+            reader: &'a mut dyn AssetReader,
+            load_context: &'a mut LoadContext,
+        ) -> BoxedFuture<'a, Result<()>> {
+            Box::pin(async move {
+                let mut len = [0];
+                reader.read_exact(&mut len).await?;
+                let mut text = vec![0; len[0] as usize];
+                reader.read_exact(&mut text).await?;
+                load_context.set_default_asset(LoadedAsset::new(Text(String::from_utf8(text)?)));
+                Ok(())
+            })
+        }
+    }
+
+    /// Streams its input with the default [AssetLoader::load_reader], which reads the whole file
+    struct StreamedTextLoader;
+
+    impl AssetLoader for StreamedTextLoader {
+        fn load<'a>(
+            &'a self,
+            bytes: &'a [u8],
+            load_context: &'a mut LoadContext,
+        ) -> BoxedFuture<'a, Result<()>> {
+            Box::pin(async move {
+                let text = String::from_utf8(bytes.to_vec())?;
+                load_context.set_default_asset(LoadedAsset::new(Text(text)));
+                Ok(())
+            })
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["stream"]
+        }
+
+        fn streams_input(&self) -> bool {
+            true
+        }
+    }
+
+    fn update_until(app: &mut App, mut done: impl FnMut(&mut App) -> bool) {
+        for _ in 0..1000 {
+            app.update();
+            if done(app) {
+                return;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(1));
+        }
+        panic!("timed out waiting for assets to load");
+    }
+
+    #[test]
+    fn reload_propagates_to_dependents() {
+        let mut app_builder = App::build();
+        app_builder
+            .add_resource(IoTaskPool(TaskPool::new()))
+            .add_resource(TypeRegistryArc::default())
+            .add_plugin(AssetPlugin)
+            .add_asset::<Text>()
+            .add_asset::<Material>()
+            .add_asset_loader(TextLoader)
+            .add_asset_loader(MaterialLoader);
+        let mut app = app_builder.app;
+
+        let asset_io = Arc::new(MemoryAssetIo::default());
+        asset_io.insert("shader.txt", b"main ".to_vec());
+        asset_io.insert("common.inc", b"v1".to_vec());
+        asset_io.insert("lit.mat", b"shader.txt".to_vec());
+        let asset_server = (*app.resources.get::<AssetServer>().unwrap()).clone();
+        asset_server
+            .asset_sources()
+            .mount_arc(DEFAULT_ASSET_SOURCE, asset_io.clone());
+
+        let text: Handle<Text> = asset_server.load("shader.txt");
+        let material: Handle<Material> = asset_server.load("lit.mat");
+        update_until(&mut app, |app| {
+            let materials = app.resources.get::<Assets<Material>>().unwrap();
+            let texts = app.resources.get::<Assets<Text>>().unwrap();
+            materials.get(&material).is_some() && texts.get(&text).is_some()
+        });
+        assert_eq!(
+            app.resources
+                .get::<Assets<Text>>()
+                .unwrap()
+                .get(&text)
+                .unwrap()
+                .0,
+            "main v1"
+        );
+
+        // changing the included file reloads the text, and the material that uses the text is
+        // modified along with it
+        let mut material_events = EventReader::<AssetEvent<Material>>::default();
+        let mut material_modified = false;
+        asset_io.insert("common.inc", b"v2".to_vec());
+        asset_server.reload_changed_path(Path::new("common.inc"));
+        update_until(&mut app, |app| {
+            let events = app.resources.get::<Events<AssetEvent<Material>>>().unwrap();
+            material_modified |= material_events.iter(&events).any(
+                |event| matches!(event, AssetEvent::Modified { handle } if *handle == material),
+            );
+            let texts = app.resources.get::<Assets<Text>>().unwrap();
+            material_modified && texts.get(&text).unwrap().0 == "main v2"
+        });
+    }
+
+    #[test]
+    fn stream_loader_input() {
+        let mut app_builder = App::build();
+        app_builder
+            .add_resource(IoTaskPool(TaskPool::new()))
+            .add_resource(TypeRegistryArc::default())
+            .add_plugin(AssetPlugin)
+            .add_asset::<Text>()
+            .add_asset_loader(ChunkLoader)
+            .add_asset_loader(StreamedTextLoader);
+        let mut app = app_builder.app;
+
+        let asset_io = Arc::new(MemoryAssetIo::default());
+        asset_io.insert("greeting.chunk", b"\x05hello world".to_vec());
+        asset_io.insert("greeting.stream", b"hello world".to_vec());
+        let asset_server = (*app.resources.get::<AssetServer>().unwrap()).clone();
+        asset_server
+            .asset_sources()
+            .mount_arc(DEFAULT_ASSET_SOURCE, asset_io);
+
+        let chunk: Handle<Text> = asset_server.load("greeting.chunk");
+        let text: Handle<Text> = asset_server.load("greeting.stream");
+        update_until(&mut app, |_app| {
+            asset_server.get_group_load_state(vec![chunk.id, text.id]) == LoadState::Loaded
+        });
+        let texts = app.resources.get::<Assets<Text>>().unwrap();
+        assert_eq!(texts.get(&chunk).unwrap().0, "hello");
+        assert_eq!(texts.get(&text).unwrap().0, "hello world");
+    }
+
+    #[test]
+    fn retain_unused_assets_for_frames() {
+        let mut app_builder = App::build();
+        app_builder
+            .add_resource(IoTaskPool(TaskPool::new()))
+            .add_resource(TypeRegistryArc::default())
+            .add_plugin(AssetPlugin)
+            .add_asset::<Text>()
+            .add_asset_loader(TextLoader);
+        let mut app = app_builder.app;
+
+        let asset_io = Arc::new(MemoryAssetIo::default());
+        asset_io.insert("greeting.txt", b"hello ".to_vec());
+        asset_io.insert("common.inc", b"world".to_vec());
+        let asset_server = (*app.resources.get::<AssetServer>().unwrap()).clone();
+        asset_server
+            .asset_sources()
+            .mount_arc(DEFAULT_ASSET_SOURCE, asset_io);
+        asset_server.set_retention_policy::<Text>(RetentionPolicy::KeepForFrames(2));
+        asset_server.set_memory_estimator::<Text>(|text| text.0.len());
+
+        let text: Handle<Text> = asset_server.load("greeting.txt");
+        let id = text.id;
+        update_until(&mut app, |app| {
+            app.resources
+                .get::<Assets<Text>>()
+                .unwrap()
+                .get(id)
+                .is_some()
+        });
+        let usage = |asset_server: &AssetServer| {
+            asset_server
+                .asset_usage()
+                .into_iter()
+                .find(|usage| usage.id == id)
+        };
+        let text_usage = usage(&asset_server).unwrap();
+        assert_eq!(text_usage.type_uuid, Some(Text::TYPE_UUID));
+        assert_eq!(text_usage.strong_handles, 1);
+        assert_eq!(text_usage.memory_estimate, Some("hello world".len()));
+        assert!(!text_usage.retained);
+
+        // modifying the asset updates its estimate
+        app.resources
+            .get_mut::<Assets<Text>>()
+            .unwrap()
+            .get_mut(id)
+            .unwrap()
+            .0
+            .push('!');
+        app.update();
+        assert_eq!(
+            usage(&asset_server).unwrap().memory_estimate,
+            Some("hello world!".len())
+        );
+
+        // the unused asset is kept for two frames before it is freed
+        drop(text);
+        app.update();
+        let text_usage = usage(&asset_server).unwrap();
+        assert_eq!(text_usage.strong_handles, 0);
+        assert!(text_usage.retained);
+        app.update();
+        assert!(app
+            .resources
+            .get::<Assets<Text>>()
+            .unwrap()
+            .get(id)
+            .is_some());
+        update_until(&mut app, |app| {
+            app.resources
+                .get::<Assets<Text>>()
+                .unwrap()
+                .get(id)
+                .is_none()
+        });
+        assert!(usage(&asset_server).map_or(true, |usage| usage.memory_estimate.is_none()));
+    }
+}
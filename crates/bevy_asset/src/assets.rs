@@ -2,7 +2,7 @@ use crate::{
     update_asset_storage_system, Asset, AssetLoader, AssetServer, Handle, HandleId, RefChange,
 };
 use bevy_app::{prelude::Events, AppBuilder};
-use bevy_ecs::{FromResources, ResMut};
+use bevy_ecs::{FromResources, Res, ResMut};
 use bevy_reflect::RegisterTypeBuilder;
 use bevy_utils::HashMap;
 use crossbeam_channel::Sender;
@@ -113,6 +113,15 @@ impl<T: Asset> Assets<T> {
         self.assets.get_mut(&id)
     }
 
+    pub(crate) fn mark_modified<H: Into<HandleId>>(&mut self, handle: H) {
+        let id: HandleId = handle.into();
+        if self.assets.contains_key(&id) {
+            self.events.send(AssetEvent::Modified {
+                handle: Handle::weak(id),
+            });
+        }
+    }
+
     pub fn get_handle<H: Into<HandleId>>(&self, handle: H) -> Handle<T> {
         Handle::strong(handle.into(), self.ref_change_sender.clone())
     }
@@ -181,8 +190,11 @@ impl<T: Asset> Assets<T> {
     pub fn asset_event_system(
         mut events: ResMut<Events<AssetEvent<T>>>,
         mut assets: ResMut<Assets<T>>,
+        asset_server: Res<AssetServer>,
     ) {
-        events.extend(assets.events.drain())
+        let new_events = assets.events.drain().collect::<Vec<_>>();
+        asset_server.update_memory_estimates(&assets, &new_events);
+        events.extend(new_events.into_iter())
     }
 
     pub fn len(&self) -> usize {
@@ -1,7 +1,7 @@
 use crate::{path::AssetPath, LabelId};
 use bevy_utils::{HashMap, HashSet, Uuid};
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct SourceMeta {
@@ -23,6 +23,8 @@ pub struct SourceInfo {
     pub asset_types: HashMap<LabelId, Uuid>,
     pub load_state: LoadState,
     pub committed_assets: HashSet<LabelId>,
+    /// Other files that were read while loading this source
+    pub file_dependencies: HashSet<PathBuf>,
     pub version: usize,
 }
 
@@ -36,6 +38,21 @@ impl SourceInfo {
     pub fn get_asset_type(&self, label_id: LabelId) -> Option<Uuid> {
         self.asset_types.get(&label_id).cloned()
     }
+
+    /// Returns the metas of assets in this source that depend on an asset from the source at `path`
+    pub fn get_dependent_assets<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> impl Iterator<Item = &'a AssetMeta> {
+        self.meta.iter().flat_map(move |meta| {
+            meta.assets.iter().filter(move |asset_meta| {
+                asset_meta
+                    .dependencies
+                    .iter()
+                    .any(|dependency| dependency.path() == path)
+            })
+        })
+    }
 }
 
 /// The load state of an asset
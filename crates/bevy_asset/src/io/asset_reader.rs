@@ -0,0 +1,65 @@
+use futures_lite::{AsyncRead, AsyncSeek};
+use std::{
+    io::{self, Read, Seek, SeekFrom},
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+/// An asynchronous reader over the bytes of an asset, which supports seeking
+pub trait AssetReader: AsyncRead + AsyncSeek + Send + Unpin {}
+
+impl<T: AsyncRead + AsyncSeek + Send + Unpin> AssetReader for T {}
+
+/// Adapts a blocking [Read] + [Seek] source (like a [File](std::fs::File)) to an [AssetReader].
+///
+/// Reads are performed synchronously when polled, which matches how [AssetIo](crate::AssetIo)
+/// implementations already perform blocking reads on the io task pool.
+pub struct BlockingAssetReader<R> {
+    inner: R,
+}
+
+impl<R> BlockingAssetReader<R> {
+    pub fn new(inner: R) -> Self {
+        BlockingAssetReader { inner }
+    }
+
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: Read + Unpin> AsyncRead for BlockingAssetReader<R> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        Poll::Ready(self.get_mut().inner.read(buf))
+    }
+}
+
+impl<R: Seek + Unpin> AsyncSeek for BlockingAssetReader<R> {
+    fn poll_seek(
+        self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+        pos: SeekFrom,
+    ) -> Poll<io::Result<u64>> {
+        Poll::Ready(self.get_mut().inner.seek(pos))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{AssetIo, MemoryAssetIo};
+    use futures_lite::future;
+    use std::path::Path;
+
+    #[test]
+    fn read_range() {
+        let asset_io = MemoryAssetIo::default();
+        asset_io.insert("data.bin", (0..32).collect::<Vec<u8>>());
+
+        let bytes = future::block_on(asset_io.read_range(Path::new("data.bin"), 8..12)).unwrap();
+        assert_eq!(bytes, vec![8, 9, 10, 11]);
+    }
+}
@@ -0,0 +1,299 @@
+use crate::{AssetIo, AssetIoError, AssetReader};
+use anyhow::Result;
+use bevy_ecs::bevy_utils::BoxedFuture;
+use bevy_utils::{HashMap, HashSet};
+use parking_lot::RwLock;
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+/// The name of the source used for asset paths without a `source://` prefix
+pub const DEFAULT_ASSET_SOURCE: &str = "";
+
+const SOURCE_SEPARATOR: &str = "://";
+
+/// Routes asset paths to named [AssetIo] sources.
+///
+/// Paths of the form `"name://some/file.png"` are loaded from the source registered as `name`.
+/// Paths without a prefix are loaded from the [DEFAULT_ASSET_SOURCE]. A source can have multiple
+/// mounts: mounts added later shadow files with the same path in earlier mounts, which allows mods
+/// to override base files.
+#[derive(Default)]
+pub struct AssetSources {
+    sources: RwLock<HashMap<String, Vec<Arc<dyn AssetIo>>>>,
+}
+
+impl AssetSources {
+    pub fn new<T: AssetIo>(default_source: T) -> Self {
+        let sources = AssetSources::default();
+        sources.mount(DEFAULT_ASSET_SOURCE, default_source);
+        sources
+    }
+
+    /// Adds `asset_io` as the topmost mount of the source called `name`
+    pub fn mount<T: AssetIo>(&self, name: &str, asset_io: T) {
+        self.mount_arc(name, Arc::new(asset_io));
+    }
+
+    pub fn mount_arc(&self, name: &str, asset_io: Arc<dyn AssetIo>) {
+        self.sources
+            .write()
+            .entry(name.to_string())
+            .or_insert_with(Vec::new)
+            .push(asset_io);
+    }
+
+    /// Removes all mounts of the source called `name`
+    pub fn unmount(&self, name: &str) -> Option<Vec<Arc<dyn AssetIo>>> {
+        self.sources.write().remove(name)
+    }
+
+    pub fn has_source(&self, name: &str) -> bool {
+        self.sources.read().contains_key(name)
+    }
+
+    /// Returns the mounts of the source called `name`, ordered from bottom to top
+    pub fn get_mounts(&self, name: &str) -> Vec<Arc<dyn AssetIo>> {
+        self.sources.read().get(name).cloned().unwrap_or_default()
+    }
+
+    /// Returns every mount of every source, along with the name of its source
+    pub fn iter_mounts(&self) -> Vec<(String, Arc<dyn AssetIo>)> {
+        let sources = self.sources.read();
+        sources
+            .iter()
+            .flat_map(|(name, mounts)| {
+                mounts
+                    .iter()
+                    .map(move |asset_io| (name.clone(), asset_io.clone()))
+            })
+            .collect()
+    }
+
+    /// Splits a path like `"name://some/file.png"` into its source name and the path within that source
+    pub fn split_path(path: &Path) -> (&str, &Path) {
+        if let Some(path_str) = path.to_str() {
+            if let Some(index) = path_str.find(SOURCE_SEPARATOR) {
+                let (name, rest) = path_str.split_at(index);
+                return (name, Path::new(&rest[SOURCE_SEPARATOR.len()..]));
+            }
+        }
+
+        (DEFAULT_ASSET_SOURCE, path)
+    }
+
+    /// Joins a source name and a path within that source into a routable asset path
+    pub fn join_path(name: &str, path: &Path) -> PathBuf {
+        if name == DEFAULT_ASSET_SOURCE {
+            path.to_owned()
+        } else {
+            PathBuf::from(format!(
+                "{}{}{}",
+                name,
+                SOURCE_SEPARATOR,
+                path.to_string_lossy()
+            ))
+        }
+    }
+
+    fn get_source_mounts(&self, name: &str) -> Result<Vec<Arc<dyn AssetIo>>, AssetIoError> {
+        self.sources
+            .read()
+            .get(name)
+            .cloned()
+            .ok_or_else(|| AssetIoError::MissingSource(name.to_string()))
+    }
+}
+
+impl AssetIo for AssetSources {
+    fn load_path<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, Result<Vec<u8>, AssetIoError>> {
+        Box::pin(async move {
+            let (name, source_path) = Self::split_path(path);
+            let mounts = self.get_source_mounts(name)?;
+            for asset_io in mounts.iter().rev() {
+                match asset_io.load_path(source_path).await {
+                    Err(AssetIoError::NotFound(_)) => continue,
+                    result => return result,
+                }
+            }
+
+            Err(AssetIoError::NotFound(path.to_owned()))
+        })
+    }
+
+    fn read_path<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> BoxedFuture<'a, Result<Box<dyn AssetReader>, AssetIoError>> {
+        Box::pin(async move {
+            let (name, source_path) = Self::split_path(path);
+            let mounts = self.get_source_mounts(name)?;
+            for asset_io in mounts.iter().rev() {
+                match asset_io.read_path(source_path).await {
+                    Err(AssetIoError::NotFound(_)) => continue,
+                    result => return result,
+                }
+            }
+
+            Err(AssetIoError::NotFound(path.to_owned()))
+        })
+    }
+
+    fn read_directory(
+        &self,
+        path: &Path,
+    ) -> Result<Box<dyn Iterator<Item = PathBuf>>, AssetIoError> {
+        let (name, source_path) = Self::split_path(path);
+        let mut seen = HashSet::default();
+        let mut paths = Vec::new();
+        for asset_io in self.get_source_mounts(name)?.iter().rev() {
+            if !asset_io.is_directory(source_path) {
+                continue;
+            }
+            for child_path in asset_io.read_directory(source_path)? {
+                if seen.insert(child_path.clone()) {
+                    paths.push(Self::join_path(name, &child_path));
+                }
+            }
+        }
+
+        Ok(Box::new(paths.into_iter()))
+    }
+
+    fn is_directory(&self, path: &Path) -> bool {
+        let (name, source_path) = Self::split_path(path);
+        self.get_mounts(name)
+            .iter()
+            .any(|asset_io| asset_io.is_directory(source_path))
+    }
+
+    fn watch_path_for_changes(&self, path: &Path) -> Result<(), AssetIoError> {
+        // a path usually only exists on some of the mounts of a source, so watching it only fails
+        // if it can't be watched on any of them
+        let (name, source_path) = Self::split_path(path);
+        let mut watched = false;
+        let mut first_error = None;
+        for asset_io in self.get_source_mounts(name)?.iter() {
+            match asset_io.watch_path_for_changes(source_path) {
+                Ok(()) => watched = true,
+                Err(err) => {
+                    first_error.get_or_insert(err);
+                }
+            }
+        }
+
+        match first_error {
+            Some(err) if !watched => Err(err),
+            _ => Ok(()),
+        }
+    }
+
+    fn watch_for_changes(&self) -> Result<(), AssetIoError> {
+        for (_name, asset_io) in self.iter_mounts() {
+            asset_io.watch_for_changes()?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MemoryAssetIo;
+    use futures_lite::future;
+
+    #[test]
+    fn routes_paths_to_sources() {
+        let base = MemoryAssetIo::default();
+        base.insert("ui/font.ttf", b"base".to_vec());
+        let embedded = MemoryAssetIo::default();
+        embedded.insert("ui/font.ttf", b"embedded".to_vec());
+
+        let sources = AssetSources::new(base);
+        sources.mount("embedded", embedded);
+
+        let load = |path: &str| future::block_on(sources.load_path(Path::new(path)));
+        assert_eq!(load("ui/font.ttf").unwrap(), b"base");
+        assert_eq!(load("embedded://ui/font.ttf").unwrap(), b"embedded");
+        assert!(matches!(
+            load("missing://ui/font.ttf"),
+            Err(AssetIoError::MissingSource(_))
+        ));
+    }
+
+    #[test]
+    fn overlay_mounts_shadow_earlier_mounts() {
+        let base = MemoryAssetIo::default();
+        base.insert("level/map.gltf", b"base map".to_vec());
+        base.insert("level/sky.png", b"base sky".to_vec());
+        let mods = MemoryAssetIo::default();
+        mods.insert("level/map.gltf", b"modded map".to_vec());
+
+        let sources = AssetSources::new(base);
+        sources.mount(DEFAULT_ASSET_SOURCE, mods);
+
+        let load = |path: &str| future::block_on(sources.load_path(Path::new(path)));
+        assert_eq!(load("level/map.gltf").unwrap(), b"modded map");
+        assert_eq!(load("level/sky.png").unwrap(), b"base sky");
+
+        let mut children = sources
+            .read_directory(Path::new("level"))
+            .unwrap()
+            .collect::<Vec<_>>();
+        children.sort();
+        assert_eq!(
+            children,
+            vec![
+                PathBuf::from("level/map.gltf"),
+                PathBuf::from("level/sky.png")
+            ]
+        );
+    }
+
+    struct UnwatchableAssetIo;
+
+    impl AssetIo for UnwatchableAssetIo {
+        fn load_path<'a>(
+            &'a self,
+            path: &'a Path,
+        ) -> BoxedFuture<'a, Result<Vec<u8>, AssetIoError>> {
+            Box::pin(async move { Err(AssetIoError::NotFound(path.to_owned())) })
+        }
+
+        fn read_directory(
+            &self,
+            path: &Path,
+        ) -> Result<Box<dyn Iterator<Item = PathBuf>>, AssetIoError> {
+            Err(AssetIoError::NotFound(path.to_owned()))
+        }
+
+        fn is_directory(&self, _path: &Path) -> bool {
+            false
+        }
+
+        fn watch_path_for_changes(&self, path: &Path) -> Result<(), AssetIoError> {
+            Err(AssetIoError::PathWatchError(path.to_owned()))
+        }
+
+        fn watch_for_changes(&self) -> Result<(), AssetIoError> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn watch_paths_that_only_some_mounts_can_watch() {
+        let sources = AssetSources::new(MemoryAssetIo::default());
+        sources.mount(DEFAULT_ASSET_SOURCE, UnwatchableAssetIo);
+        sources.mount("unwatchable", UnwatchableAssetIo);
+
+        assert!(sources
+            .watch_path_for_changes(Path::new("level/map.gltf"))
+            .is_ok());
+        assert!(matches!(
+            sources.watch_path_for_changes(Path::new("unwatchable://level/map.gltf")),
+            Err(AssetIoError::PathWatchError(_))
+        ));
+    }
+}
@@ -0,0 +1,279 @@
+#[cfg(all(
+    feature = "filesystem_watcher",
+    not(target_arch = "wasm32"),
+    not(target_os = "android")
+))]
+use crate::filesystem_watcher::FilesystemWatcher;
+use crate::{AssetIo, AssetIoError};
+use anyhow::Result;
+use bevy_ecs::bevy_utils::BoxedFuture;
+use bevy_utils::{HashMap, HashSet};
+use parking_lot::RwLock;
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+/// The name of the source that [EmbeddedAssetIo] is mounted as by the [AssetPlugin](crate::AssetPlugin)
+pub const EMBEDDED_ASSET_SOURCE: &str = "embedded";
+
+struct EmbeddedAsset {
+    bytes: &'static [u8],
+    source_path: PathBuf,
+}
+
+/// Serves assets that were compiled into the binary with the [embedded_asset] macro.
+///
+/// While watching for changes in debug builds, assets are read from their original source path on
+/// disk when it exists, which allows embedded assets to be hot-reloaded.
+#[derive(Clone, Default)]
+pub struct EmbeddedAssetIo {
+    assets: Arc<RwLock<HashMap<PathBuf, EmbeddedAsset>>>,
+    #[cfg(all(
+        feature = "filesystem_watcher",
+        not(target_arch = "wasm32"),
+        not(target_os = "android")
+    ))]
+    pub(crate) filesystem_watcher: Arc<RwLock<Option<FilesystemWatcher>>>,
+}
+
+impl EmbeddedAssetIo {
+    /// Registers `bytes` as the asset at `path`. `source_path` is the file the bytes were embedded from.
+    pub fn insert_static<P: Into<PathBuf>, S: Into<PathBuf>>(
+        &self,
+        path: P,
+        bytes: &'static [u8],
+        source_path: S,
+    ) {
+        self.assets.write().insert(
+            path.into(),
+            EmbeddedAsset {
+                bytes,
+                source_path: source_path.into(),
+            },
+        );
+    }
+
+    pub fn contains<P: AsRef<Path>>(&self, path: P) -> bool {
+        self.assets.read().contains_key(path.as_ref())
+    }
+
+    /// Returns the file the asset at `path` was embedded from
+    pub fn get_source_path<P: AsRef<Path>>(&self, path: P) -> Option<PathBuf> {
+        self.assets
+            .read()
+            .get(path.as_ref())
+            .map(|asset| asset.source_path.clone())
+    }
+
+    /// Returns the path of the asset that was embedded from `source_path`
+    pub fn get_asset_path<P: AsRef<Path>>(&self, source_path: P) -> Option<PathBuf> {
+        self.assets
+            .read()
+            .iter()
+            .find(|(_, asset)| asset.source_path == source_path.as_ref())
+            .map(|(path, _)| path.clone())
+    }
+}
+
+/// Only compiles the given items if embedded assets are read from their source files while
+/// watching for changes, or only if they aren't when the items are preceded by `not`
+macro_rules! cfg_reads_source_files {
+    (not $($item:item)*) => {
+        $(
+            #[cfg(not(all(
+                feature = "filesystem_watcher",
+                debug_assertions,
+                not(target_arch = "wasm32"),
+                not(target_os = "android")
+            )))]
+            $item
+        )*
+    };
+    ($($item:item)*) => {
+        $(
+            #[cfg(all(
+                feature = "filesystem_watcher",
+                debug_assertions,
+                not(target_arch = "wasm32"),
+                not(target_os = "android")
+            ))]
+            $item
+        )*
+    };
+}
+
+cfg_reads_source_files! {
+    impl EmbeddedAssetIo {
+        fn read_source_file(&self, source_path: &Path) -> Option<Vec<u8>> {
+            if self.filesystem_watcher.read().is_some() {
+                std::fs::read(source_path).ok()
+            } else {
+                None
+            }
+        }
+
+        fn watch_source_file(&self, path: &Path) -> Result<(), AssetIoError> {
+            // the source file is only there when running next to the crate the asset was embedded
+            // from, so assets without one can't be watched but still load from the embedded bytes
+            let source_path = match self.get_source_path(path) {
+                Some(source_path) if source_path.exists() => source_path,
+                _ => return Ok(()),
+            };
+            let mut watcher = self.filesystem_watcher.write();
+            if let Some(ref mut watcher) = *watcher {
+                watcher
+                    .watch(&source_path)
+                    .map_err(|_error| AssetIoError::PathWatchError(source_path))?;
+            }
+
+            Ok(())
+        }
+
+        fn start_watching_source_files(&self) {
+            *self.filesystem_watcher.write() = Some(FilesystemWatcher::default());
+        }
+    }
+}
+
+cfg_reads_source_files! { not
+    impl EmbeddedAssetIo {
+        fn read_source_file(&self, _source_path: &Path) -> Option<Vec<u8>> {
+            None
+        }
+
+        fn watch_source_file(&self, _path: &Path) -> Result<(), AssetIoError> {
+            Ok(())
+        }
+
+        fn start_watching_source_files(&self) {}
+    }
+}
+
+impl AssetIo for EmbeddedAssetIo {
+    fn load_path<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, Result<Vec<u8>, AssetIoError>> {
+        Box::pin(async move {
+            let (bytes, source_path) = self
+                .assets
+                .read()
+                .get(path)
+                .map(|asset| (asset.bytes, asset.source_path.clone()))
+                .ok_or_else(|| AssetIoError::NotFound(path.to_owned()))?;
+            Ok(self
+                .read_source_file(&source_path)
+                .unwrap_or_else(|| bytes.to_vec()))
+        })
+    }
+
+    fn read_directory(
+        &self,
+        path: &Path,
+    ) -> Result<Box<dyn Iterator<Item = PathBuf>>, AssetIoError> {
+        let assets = self.assets.read();
+        let mut children = HashSet::default();
+        for asset_path in assets.keys() {
+            if let Ok(relative_path) = asset_path.strip_prefix(path) {
+                if let Some(child) = relative_path.components().next() {
+                    children.insert(path.join(child));
+                }
+            }
+        }
+
+        Ok(Box::new(children.into_iter()))
+    }
+
+    fn is_directory(&self, path: &Path) -> bool {
+        self.assets
+            .read()
+            .keys()
+            .any(|asset_path| asset_path != path && asset_path.starts_with(path))
+    }
+
+    fn watch_path_for_changes(&self, path: &Path) -> Result<(), AssetIoError> {
+        self.watch_source_file(path)
+    }
+
+    fn watch_for_changes(&self) -> Result<(), AssetIoError> {
+        self.start_watching_source_files();
+        Ok(())
+    }
+}
+
+/// Embeds a file into the binary and registers it with the app's [EmbeddedAssetIo].
+///
+/// `$path` is relative to the `Cargo.toml` of the calling crate. The embedded file can then be
+/// loaded with `asset_server.load("embedded://<asset path>")`, where the asset path defaults to
+/// `$path`.
+///
+/// ```ignore
+/// embedded_asset!(app, "assets/ui/font.ttf");
+/// let font: Handle<Font> = asset_server.load("embedded://assets/ui/font.ttf");
+///
+/// embedded_asset!(app, "shaders/sprite.frag", "src/render/sprite.frag");
+/// let shader: Handle<Shader> = asset_server.load("embedded://shaders/sprite.frag");
+/// ```
+#[macro_export]
+macro_rules! embedded_asset {
+    ($app: expr, $path: expr) => {
+        $crate::embedded_asset!($app, $path, $path)
+    };
+    ($app: expr, $asset_path: expr, $path: expr) => {{
+        $app.resources_mut()
+            .get_or_insert_with($crate::EmbeddedAssetIo::default)
+            .insert_static(
+                $asset_path,
+                include_bytes!(concat!(env!("CARGO_MANIFEST_DIR"), "/", $path)),
+                concat!(env!("CARGO_MANIFEST_DIR"), "/", $path),
+            );
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_lite::future;
+
+    #[test]
+    fn falls_back_to_embedded_bytes() {
+        let embedded = EmbeddedAssetIo::default();
+        embedded.insert_static("ui/font.ttf", b"font", "/does/not/exist/font.ttf");
+
+        let bytes = future::block_on(embedded.load_path(Path::new("ui/font.ttf"))).unwrap();
+        assert_eq!(bytes, b"font");
+        assert!(embedded.is_directory(Path::new("ui")));
+        assert_eq!(
+            embedded.get_asset_path("/does/not/exist/font.ttf"),
+            Some(PathBuf::from("ui/font.ttf"))
+        );
+    }
+
+    #[test]
+    fn ignores_missing_source_files_while_watching() {
+        let embedded = EmbeddedAssetIo::default();
+        embedded.insert_static("ui/font.ttf", b"font", "/does/not/exist/font.ttf");
+
+        embedded.watch_for_changes().unwrap();
+        embedded
+            .watch_path_for_changes(Path::new("ui/font.ttf"))
+            .unwrap();
+        let bytes = future::block_on(embedded.load_path(Path::new("ui/font.ttf"))).unwrap();
+        assert_eq!(bytes, b"font");
+    }
+
+    cfg_reads_source_files! {
+        #[test]
+        fn reads_source_files_while_watching() {
+            let source_path = std::env::temp_dir().join("bevy_embedded_asset_io_test.txt");
+            std::fs::write(&source_path, b"changed").unwrap();
+            let embedded = EmbeddedAssetIo::default();
+            embedded.insert_static("text.txt", b"embedded", &source_path);
+
+            let bytes = future::block_on(embedded.load_path(Path::new("text.txt"))).unwrap();
+            assert_eq!(bytes, b"embedded");
+            embedded.watch_for_changes().unwrap();
+            let bytes = future::block_on(embedded.load_path(Path::new("text.txt"))).unwrap();
+            std::fs::remove_file(&source_path).unwrap();
+            assert_eq!(bytes, b"changed");
+        }
+    }
+}
@@ -1,15 +1,26 @@
-use crate::{filesystem_watcher::FilesystemWatcher, AssetIo, AssetIoError, AssetServer};
+#[cfg(feature = "filesystem_watcher")]
+use crate::{
+    filesystem_watcher::FilesystemWatcher, AssetServer, AssetServerSettings, AssetSources,
+    EmbeddedAssetIo,
+};
+use crate::{AssetIo, AssetIoError, AssetReader, BlockingAssetReader};
 use anyhow::Result;
-use bevy_ecs::{bevy_utils::BoxedFuture, Res};
-use bevy_utils::HashSet;
+use bevy_ecs::bevy_utils::BoxedFuture;
+#[cfg(feature = "filesystem_watcher")]
+use bevy_ecs::{Local, Res};
+#[cfg(feature = "filesystem_watcher")]
+use bevy_utils::{Duration, HashMap, Instant};
+#[cfg(feature = "filesystem_watcher")]
 use crossbeam_channel::TryRecvError;
 use fs::File;
 use io::Read;
+#[cfg(feature = "filesystem_watcher")]
 use parking_lot::RwLock;
+#[cfg(feature = "filesystem_watcher")]
+use std::sync::Arc;
 use std::{
     env, fs, io,
     path::{Path, PathBuf},
-    sync::Arc,
 };
 
 pub struct FileAssetIo {
@@ -21,6 +32,7 @@ pub struct FileAssetIo {
 impl FileAssetIo {
     pub fn new<P: AsRef<Path>>(path: P) -> Self {
         FileAssetIo {
+            #[cfg(feature = "filesystem_watcher")]
             filesystem_watcher: Default::default(),
             root_path: Self::get_root_path().join(path.as_ref()),
         }
@@ -62,6 +74,25 @@ impl AssetIo for FileAssetIo {
         })
     }
 
+    fn read_path<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> BoxedFuture<'a, Result<Box<dyn AssetReader>, AssetIoError>> {
+        Box::pin(async move {
+            let full_path = self.root_path.join(path);
+            match File::open(&full_path) {
+                Ok(file) => Ok(Box::new(BlockingAssetReader::new(file)) as Box<dyn AssetReader>),
+                Err(e) => {
+                    if e.kind() == std::io::ErrorKind::NotFound {
+                        Err(AssetIoError::NotFound(full_path))
+                    } else {
+                        Err(e.into())
+                    }
+                }
+            }
+        })
+    }
+
     fn read_directory(
         &self,
         path: &Path,
@@ -75,10 +106,10 @@ impl AssetIo for FileAssetIo {
         )))
     }
 
-    fn watch_path_for_changes(&self, path: &Path) -> Result<(), AssetIoError> {
+    fn watch_path_for_changes(&self, _path: &Path) -> Result<(), AssetIoError> {
         #[cfg(feature = "filesystem_watcher")]
         {
-            let path = self.root_path.join(path);
+            let path = self.root_path.join(_path);
             let mut watcher = self.filesystem_watcher.write();
             if let Some(ref mut watcher) = *watcher {
                 watcher
@@ -104,19 +135,73 @@ impl AssetIo for FileAssetIo {
     }
 }
 
-#[cfg(all(
-    feature = "filesystem_watcher",
-    all(not(target_arch = "wasm32"), not(target_os = "android"))
-))]
-pub fn filesystem_watcher_system(asset_server: Res<AssetServer>) {
-    let mut changed = HashSet::default();
-    let asset_io =
-        if let Some(asset_io) = asset_server.server.asset_io.downcast_ref::<FileAssetIo>() {
-            asset_io
-        } else {
-            return;
-        };
-    let watcher = asset_io.filesystem_watcher.read();
+/// Changed asset paths that are waiting for file writes to settle before they are reloaded
+#[cfg(feature = "filesystem_watcher")]
+#[derive(Default)]
+pub struct FilesystemWatcherState {
+    pending_changes: HashMap<PathBuf, Instant>,
+}
+
+#[cfg(feature = "filesystem_watcher")]
+impl FilesystemWatcherState {
+    /// Records a change to `path`, restarting its debounce if it is already pending
+    pub fn add_change(&mut self, path: PathBuf, changed_at: Instant) {
+        self.pending_changes.insert(path, changed_at);
+    }
+
+    /// Removes and returns the pending paths that haven't changed for at least `debounce`
+    pub fn take_settled_paths(&mut self, now: Instant, debounce: Duration) -> Vec<PathBuf> {
+        let settled_paths = self
+            .pending_changes
+            .iter()
+            .filter(|(_, changed_at)| now.duration_since(**changed_at) >= debounce)
+            .map(|(path, _)| path.clone())
+            .collect::<Vec<_>>();
+        for path in settled_paths.iter() {
+            self.pending_changes.remove(path);
+        }
+        settled_paths
+    }
+}
+
+#[cfg(feature = "filesystem_watcher")]
+pub fn filesystem_watcher_system(
+    mut state: Local<FilesystemWatcherState>,
+    asset_server: Res<AssetServer>,
+    settings: Res<AssetServerSettings>,
+) {
+    let now = Instant::now();
+    for (source_name, asset_io) in asset_server.server.asset_io.iter_mounts() {
+        if let Some(asset_io) = asset_io.downcast_ref::<FileAssetIo>() {
+            collect_changed_paths(&asset_io.filesystem_watcher, |path| {
+                if let Ok(relative_path) = path.strip_prefix(&asset_io.root_path) {
+                    let asset_path = AssetSources::join_path(&source_name, relative_path);
+                    state.add_change(asset_path, now);
+                }
+            });
+        } else if let Some(asset_io) = asset_io.downcast_ref::<EmbeddedAssetIo>() {
+            collect_changed_paths(&asset_io.filesystem_watcher, |path| {
+                if let Some(asset_path) = asset_io.get_asset_path(path) {
+                    let asset_path = AssetSources::join_path(&source_name, &asset_path);
+                    state.add_change(asset_path, now);
+                }
+            });
+        }
+    }
+
+    // editors often write a file several times in a row, so only reload once the writes settle
+    for path in state.take_settled_paths(now, settings.hot_reload_debounce) {
+        asset_server.reload_changed_path(&path);
+    }
+}
+
+/// Calls `on_changed` for every file that `watcher` reported as created or modified
+#[cfg(feature = "filesystem_watcher")]
+fn collect_changed_paths(
+    watcher: &RwLock<Option<FilesystemWatcher>>,
+    mut on_changed: impl FnMut(&Path),
+) {
+    let watcher = watcher.read();
     if let Some(ref watcher) = *watcher {
         loop {
             let event = match watcher.receiver.try_recv() {
@@ -124,20 +209,45 @@ pub fn filesystem_watcher_system(asset_server: Res<AssetServer>) {
                 Err(TryRecvError::Empty) => break,
                 Err(TryRecvError::Disconnected) => panic!("FilesystemWatcher disconnected."),
             };
-            if let notify::event::Event {
-                kind: notify::event::EventKind::Modify(_),
-                paths,
-                ..
-            } = event
-            {
-                for path in paths.iter() {
-                    if !changed.contains(path) {
-                        let relative_path = path.strip_prefix(&asset_io.root_path).unwrap();
-                        let _ = asset_server.load_untracked(relative_path, true);
+            match event.kind {
+                notify::event::EventKind::Modify(_) | notify::event::EventKind::Create(_) => {
+                    for path in event.paths.iter() {
+                        on_changed(path);
                     }
                 }
-                changed.extend(paths);
+                _ => {}
             }
         }
     }
 }
+
+#[cfg(all(test, feature = "filesystem_watcher"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn debounce_changes() {
+        let debounce = Duration::from_millis(50);
+        let start = Instant::now();
+        let mut state = FilesystemWatcherState::default();
+        state.add_change(PathBuf::from("a.png"), start);
+        state.add_change(PathBuf::from("b.png"), start);
+        assert!(state
+            .take_settled_paths(start + Duration::from_millis(30), debounce)
+            .is_empty());
+
+        // a second write restarts the debounce of that path
+        state.add_change(PathBuf::from("b.png"), start + Duration::from_millis(30));
+        assert_eq!(
+            state.take_settled_paths(start + Duration::from_millis(60), debounce),
+            vec![PathBuf::from("a.png")]
+        );
+        assert_eq!(
+            state.take_settled_paths(start + Duration::from_millis(80), debounce),
+            vec![PathBuf::from("b.png")]
+        );
+        assert!(state
+            .take_settled_paths(start + Duration::from_millis(200), debounce)
+            .is_empty());
+    }
+}
@@ -0,0 +1,80 @@
+use crate::{AssetIo, AssetIoError};
+use anyhow::Result;
+use bevy_ecs::bevy_utils::BoxedFuture;
+use bevy_utils::{HashMap, HashSet};
+use parking_lot::RwLock;
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+/// Serves assets from an in-memory map of paths to bytes. This is useful for tests and for assets
+/// that are generated at runtime.
+#[derive(Default)]
+pub struct MemoryAssetIo {
+    files: RwLock<HashMap<PathBuf, Arc<[u8]>>>,
+}
+
+impl MemoryAssetIo {
+    pub fn insert<P: Into<PathBuf>, B: Into<Vec<u8>>>(&self, path: P, bytes: B) {
+        self.files
+            .write()
+            .insert(path.into(), Arc::from(bytes.into().into_boxed_slice()));
+    }
+
+    pub fn remove<P: AsRef<Path>>(&self, path: P) -> Option<Arc<[u8]>> {
+        self.files.write().remove(path.as_ref())
+    }
+
+    pub fn contains<P: AsRef<Path>>(&self, path: P) -> bool {
+        self.files.read().contains_key(path.as_ref())
+    }
+
+    pub fn clear(&self) {
+        self.files.write().clear();
+    }
+}
+
+impl AssetIo for MemoryAssetIo {
+    fn load_path<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, Result<Vec<u8>, AssetIoError>> {
+        Box::pin(async move {
+            self.files
+                .read()
+                .get(path)
+                .map(|bytes| bytes.to_vec())
+                .ok_or_else(|| AssetIoError::NotFound(path.to_owned()))
+        })
+    }
+
+    fn read_directory(
+        &self,
+        path: &Path,
+    ) -> Result<Box<dyn Iterator<Item = PathBuf>>, AssetIoError> {
+        let files = self.files.read();
+        let mut children = HashSet::default();
+        for file_path in files.keys() {
+            if let Ok(relative_path) = file_path.strip_prefix(path) {
+                if let Some(child) = relative_path.components().next() {
+                    children.insert(path.join(child));
+                }
+            }
+        }
+
+        Ok(Box::new(children.into_iter()))
+    }
+
+    fn is_directory(&self, path: &Path) -> bool {
+        self.files
+            .read()
+            .keys()
+            .any(|file_path| file_path != path && file_path.starts_with(path))
+    }
+
+    fn watch_path_for_changes(&self, _path: &Path) -> Result<(), AssetIoError> {
+        Ok(())
+    }
+
+    fn watch_for_changes(&self) -> Result<(), AssetIoError> {
+        Ok(())
+    }
+}
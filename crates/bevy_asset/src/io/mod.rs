@@ -1,22 +1,36 @@
 #[cfg(target_os = "android")]
 mod android_asset_io;
+mod asset_reader;
+mod asset_sources;
+mod embedded_asset_io;
 #[cfg(all(not(target_arch = "wasm32"), not(target_os = "android")))]
 mod file_asset_io;
+mod memory_asset_io;
 #[cfg(target_arch = "wasm32")]
 mod wasm_asset_io;
+#[cfg(feature = "zip_archive")]
+mod zip_asset_io;
 
 #[cfg(target_os = "android")]
 pub use android_asset_io::*;
+pub use asset_reader::*;
+pub use asset_sources::*;
+pub use embedded_asset_io::*;
 #[cfg(all(not(target_arch = "wasm32"), not(target_os = "android")))]
 pub use file_asset_io::*;
+pub use memory_asset_io::*;
 #[cfg(target_arch = "wasm32")]
 pub use wasm_asset_io::*;
+#[cfg(feature = "zip_archive")]
+pub use zip_asset_io::*;
 
 use anyhow::Result;
 use bevy_ecs::bevy_utils::BoxedFuture;
 use downcast_rs::{impl_downcast, Downcast};
+use futures_lite::{io::Cursor, AsyncReadExt, AsyncSeekExt};
 use std::{
-    io,
+    io::{self, SeekFrom},
+    ops::Range,
     path::{Path, PathBuf},
 };
 use thiserror::Error;
@@ -30,6 +44,8 @@ pub enum AssetIoError {
     Io(#[from] io::Error),
     #[error("failed to watch path")]
     PathWatchError(PathBuf),
+    #[error("no asset source is mounted with the given name")]
+    MissingSource(String),
 }
 
 /// Handles load requests from an AssetServer
@@ -42,6 +58,33 @@ pub trait AssetIo: Downcast + Send + Sync + 'static {
     fn is_directory(&self, path: &Path) -> bool;
     fn watch_path_for_changes(&self, path: &Path) -> Result<(), AssetIoError>;
     fn watch_for_changes(&self) -> Result<(), AssetIoError>;
+
+    /// Opens a streaming reader for the asset at `path`. The default implementation reads the
+    /// whole file with [AssetIo::load_path], so implementations that can stream should override it.
+    fn read_path<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> BoxedFuture<'a, Result<Box<dyn AssetReader>, AssetIoError>> {
+        Box::pin(async move {
+            let bytes = self.load_path(path).await?;
+            Ok(Box::new(Cursor::new(bytes)) as Box<dyn AssetReader>)
+        })
+    }
+
+    /// Reads the given byte range of the asset at `path`
+    fn read_range<'a>(
+        &'a self,
+        path: &'a Path,
+        range: Range<u64>,
+    ) -> BoxedFuture<'a, Result<Vec<u8>, AssetIoError>> {
+        Box::pin(async move {
+            let mut reader = self.read_path(path).await?;
+            reader.seek(SeekFrom::Start(range.start)).await?;
+            let mut bytes = vec![0; range.end.saturating_sub(range.start) as usize];
+            reader.read_exact(&mut bytes).await?;
+            Ok(bytes)
+        })
+    }
 }
 
 impl_downcast!(AssetIo);
@@ -0,0 +1,175 @@
+use crate::{AssetIo, AssetIoError};
+use anyhow::Result;
+use bevy_ecs::bevy_utils::BoxedFuture;
+use bevy_utils::HashSet;
+use parking_lot::Mutex;
+use std::{
+    fs::File,
+    io::{self, Cursor, Read, Seek},
+    path::{Path, PathBuf},
+};
+use zip::{result::ZipError, ZipArchive};
+
+trait ArchiveReader: Read + Seek + Send {}
+
+impl<T: Read + Seek + Send> ArchiveReader for T {}
+
+/// Serves assets from a zip (or zip-based "pak") archive
+pub struct ZipAssetIo {
+    archive: Mutex<ZipArchive<Box<dyn ArchiveReader>>>,
+    entries: HashSet<PathBuf>,
+}
+
+impl ZipAssetIo {
+    /// Opens the archive at the given filesystem path
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, AssetIoError> {
+        let file = File::open(path.as_ref()).map_err(|e| {
+            if e.kind() == io::ErrorKind::NotFound {
+                AssetIoError::NotFound(path.as_ref().to_owned())
+            } else {
+                e.into()
+            }
+        })?;
+        Self::from_reader(Box::new(file))
+    }
+
+    /// Reads the archive from an in-memory buffer
+    pub fn from_bytes(bytes: Vec<u8>) -> Result<Self, AssetIoError> {
+        Self::from_reader(Box::new(Cursor::new(bytes)))
+    }
+
+    fn from_reader(reader: Box<dyn ArchiveReader>) -> Result<Self, AssetIoError> {
+        let mut archive = ZipArchive::new(reader).map_err(zip_error)?;
+        let mut entries = HashSet::default();
+        for i in 0..archive.len() {
+            let file = archive.by_index(i).map_err(zip_error)?;
+            if !file.is_dir() {
+                entries.insert(PathBuf::from(file.name()));
+            }
+        }
+
+        Ok(ZipAssetIo {
+            archive: Mutex::new(archive),
+            entries,
+        })
+    }
+}
+
+fn zip_error(error: ZipError) -> AssetIoError {
+    match error {
+        ZipError::Io(error) => AssetIoError::Io(error),
+        error => AssetIoError::Io(io::Error::new(io::ErrorKind::InvalidData, error)),
+    }
+}
+
+impl AssetIo for ZipAssetIo {
+    fn load_path<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, Result<Vec<u8>, AssetIoError>> {
+        Box::pin(async move {
+            // zip entry names always use forward slashes
+            let name = path.to_string_lossy().replace('\\', "/");
+            let mut archive = self.archive.lock();
+            let mut file = match archive.by_name(&name) {
+                Ok(file) => file,
+                Err(ZipError::FileNotFound) => return Err(AssetIoError::NotFound(path.to_owned())),
+                Err(error) => return Err(zip_error(error)),
+            };
+            let mut bytes = Vec::with_capacity(file.size() as usize);
+            file.read_to_end(&mut bytes)?;
+            Ok(bytes)
+        })
+    }
+
+    fn read_directory(
+        &self,
+        path: &Path,
+    ) -> Result<Box<dyn Iterator<Item = PathBuf>>, AssetIoError> {
+        let mut children = HashSet::default();
+        for entry in self.entries.iter() {
+            if let Ok(relative_path) = entry.strip_prefix(path) {
+                if let Some(child) = relative_path.components().next() {
+                    children.insert(path.join(child));
+                }
+            }
+        }
+
+        Ok(Box::new(children.into_iter()))
+    }
+
+    fn is_directory(&self, path: &Path) -> bool {
+        self.entries
+            .iter()
+            .any(|entry| entry != path && entry.starts_with(path))
+    }
+
+    fn watch_path_for_changes(&self, _path: &Path) -> Result<(), AssetIoError> {
+        Ok(())
+    }
+
+    fn watch_for_changes(&self) -> Result<(), AssetIoError> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_lite::future;
+    use std::io::Write;
+    use zip::{write::FileOptions, ZipWriter};
+
+    fn archive() -> ZipAssetIo {
+        let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+        writer
+            .add_directory("textures/", FileOptions::default())
+            .unwrap();
+        for (name, bytes) in [
+            ("textures/grass.png", &b"grass"[..]),
+            ("textures/ui/button.png", b"button"),
+            ("scene.scn", b"scene"),
+        ]
+        .iter()
+        {
+            writer.start_file(*name, FileOptions::default()).unwrap();
+            writer.write_all(bytes).unwrap();
+        }
+        let bytes = writer.finish().unwrap().into_inner();
+        ZipAssetIo::from_bytes(bytes).unwrap()
+    }
+
+    #[test]
+    fn read_files() {
+        let zip = archive();
+        let bytes = future::block_on(zip.load_path(Path::new("textures/ui/button.png"))).unwrap();
+        assert_eq!(bytes, b"button");
+        let bytes = future::block_on(zip.load_path(Path::new("scene.scn"))).unwrap();
+        assert_eq!(bytes, b"scene");
+    }
+
+    #[test]
+    fn list_directories() {
+        let zip = archive();
+        let mut children = zip
+            .read_directory(Path::new("textures"))
+            .unwrap()
+            .collect::<Vec<_>>();
+        children.sort();
+        assert_eq!(
+            children,
+            vec![
+                PathBuf::from("textures/grass.png"),
+                PathBuf::from("textures/ui")
+            ]
+        );
+        assert!(zip.is_directory(Path::new("textures/ui")));
+        assert!(!zip.is_directory(Path::new("scene.scn")));
+    }
+
+    #[test]
+    fn missing_paths() {
+        let zip = archive();
+        let result = future::block_on(zip.load_path(Path::new("textures/dirt.png")));
+        assert!(
+            matches!(result, Err(AssetIoError::NotFound(path)) if path == Path::new("textures/dirt.png"))
+        );
+    }
+}
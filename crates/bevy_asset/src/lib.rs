@@ -10,17 +10,20 @@ mod info;
 mod io;
 mod loader;
 mod path;
+mod retention;
 
 pub use asset_server::*;
 pub use assets::*;
 use bevy_ecs::SystemStage;
 use bevy_reflect::RegisterTypeBuilder;
 use bevy_tasks::IoTaskPool;
+use bevy_utils::Duration;
 pub use handle::*;
 pub use info::*;
 pub use io::*;
 pub use loader::*;
 pub use path::*;
+pub use retention::*;
 
 /// The names of asset stages in an App Schedule
 pub mod stage {
@@ -41,12 +44,15 @@ pub struct AssetPlugin;
 
 pub struct AssetServerSettings {
     pub asset_folder: String,
+    /// How long a changed file must go without further changes before it is hot-reloaded
+    pub hot_reload_debounce: Duration,
 }
 
 impl Default for AssetServerSettings {
     fn default() -> Self {
         Self {
             asset_folder: "assets".to_string(),
+            hot_reload_debounce: Duration::from_millis(50),
         }
     }
 }
@@ -74,6 +80,12 @@ impl Plugin for AssetPlugin {
             AssetServer::new(source, task_pool)
         };
 
+        let embedded_asset_io = app
+            .resources_mut()
+            .get_or_insert_with(EmbeddedAssetIo::default)
+            .clone();
+        asset_server.mount(EMBEDDED_ASSET_SOURCE, embedded_asset_io);
+
         app.add_stage_before(
             bevy_app::stage::PRE_UPDATE,
             stage::LOAD_ASSETS,
@@ -1,14 +1,19 @@
 use crate::{
-    path::AssetPath, AssetIo, AssetIoError, AssetMeta, AssetServer, Assets, Handle, HandleId,
-    RefChangeChannel,
+    path::AssetPath, AssetIo, AssetIoError, AssetMeta, AssetReader, AssetServer, Assets, Handle,
+    HandleId, RefChangeChannel,
 };
 use anyhow::Result;
 use bevy_ecs::{Res, ResMut, Resource};
 use bevy_reflect::{TypeUuid, TypeUuidDynamic};
-use bevy_utils::{BoxedFuture, HashMap};
+use bevy_utils::{BoxedFuture, HashMap, HashSet};
 use crossbeam_channel::{Receiver, Sender};
 use downcast_rs::{impl_downcast, Downcast};
-use std::path::Path;
+use futures_lite::AsyncReadExt;
+use parking_lot::Mutex;
+use std::{
+    ops::Range,
+    path::{Path, PathBuf},
+};
 
 /// A loader for an asset source
 pub trait AssetLoader: Send + Sync + 'static {
@@ -18,6 +23,26 @@ pub trait AssetLoader: Send + Sync + 'static {
         load_context: &'a mut LoadContext,
     ) -> BoxedFuture<'a, Result<(), anyhow::Error>>;
     fn extensions(&self) -> &[&str];
+
+    /// Loaders that return `true` receive their input through [AssetLoader::load_reader] instead
+    /// of having the whole file read into memory before loading starts
+    fn streams_input(&self) -> bool {
+        false
+    }
+
+    /// Loads an asset from a streaming reader. The default implementation reads the whole input
+    /// and passes it to [AssetLoader::load].
+    fn load_reader<'a>(
+        &'a self,
+        reader: &'a mut dyn AssetReader,
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), anyhow::Error>> {
+        Box::pin(async move {
+            let mut bytes = Vec::new();
+            reader.read_to_end(&mut bytes).await?;
+            self.load(&bytes, load_context).await
+        })
+    }
 }
 
 pub trait Asset: TypeUuid + AssetDynamic {}
@@ -57,6 +82,7 @@ pub struct LoadContext<'a> {
     pub(crate) ref_change_channel: &'a RefChangeChannel,
     pub(crate) asset_io: &'a dyn AssetIo,
     pub(crate) labeled_assets: HashMap<Option<String>, LoadedAsset>,
+    pub(crate) file_dependencies: Mutex<HashSet<PathBuf>>,
     pub(crate) path: &'a Path,
     pub(crate) version: usize,
 }
@@ -72,6 +98,7 @@ impl<'a> LoadContext<'a> {
             ref_change_channel,
             asset_io,
             labeled_assets: Default::default(),
+            file_dependencies: Default::default(),
             version,
             path,
         }
@@ -98,10 +125,36 @@ impl<'a> LoadContext<'a> {
         Handle::strong(id.into(), self.ref_change_channel.sender.clone())
     }
 
+    /// Reads the bytes of another file. The file is tracked as a dependency of the asset source
+    /// being loaded, so changes to it will reload this source.
     pub async fn read_asset_bytes<P: AsRef<Path>>(&self, path: P) -> Result<Vec<u8>, AssetIoError> {
+        self.add_file_dependency(path.as_ref());
         self.asset_io.load_path(path.as_ref()).await
     }
 
+    pub async fn read_asset_stream<P: AsRef<Path>>(
+        &self,
+        path: P,
+    ) -> Result<Box<dyn AssetReader>, AssetIoError> {
+        self.add_file_dependency(path.as_ref());
+        self.asset_io.read_path(path.as_ref()).await
+    }
+
+    pub async fn read_asset_range<P: AsRef<Path>>(
+        &self,
+        path: P,
+        range: Range<u64>,
+    ) -> Result<Vec<u8>, AssetIoError> {
+        self.add_file_dependency(path.as_ref());
+        self.asset_io.read_range(path.as_ref(), range).await
+    }
+
+    fn add_file_dependency(&self, path: &Path) {
+        if path != self.path {
+            self.file_dependencies.lock().insert(path.to_owned());
+        }
+    }
+
     pub fn get_asset_metas(&self) -> Vec<AssetMeta> {
         let mut asset_metas = Vec::new();
         for (label, asset) in self.labeled_assets.iter() {
@@ -132,11 +185,13 @@ pub struct AssetLifecycleChannel<T: Resource> {
 
 pub enum AssetLifecycleEvent<T: Resource> {
     Create(AssetResult<T>),
+    Modify(HandleId),
     Free(HandleId),
 }
 
 pub trait AssetLifecycle: Downcast + Send + Sync + 'static {
     fn create_asset(&self, id: HandleId, asset: Box<dyn AssetDynamic>, version: usize);
+    fn modify_asset(&self, id: HandleId);
     fn free_asset(&self, id: HandleId);
 }
 impl_downcast!(AssetLifecycle);
@@ -159,6 +214,10 @@ impl<T: AssetDynamic> AssetLifecycle for AssetLifecycleChannel<T> {
         }
     }
 
+    fn modify_asset(&self, id: HandleId) {
+        self.sender.send(AssetLifecycleEvent::Modify(id)).unwrap();
+    }
+
     fn free_asset(&self, id: HandleId) {
         self.sender.send(AssetLifecycleEvent::Free(id)).unwrap();
     }
@@ -0,0 +1,252 @@
+use crate::{Asset, AssetDynamic, HandleId};
+use bevy_utils::{Duration, HashMap, Instant, Uuid};
+
+/// Decides how long an asset stays loaded after its last strong [Handle](crate::Handle) is dropped.
+///
+/// Retaining unused assets for a while avoids freeing and immediately reloading them, for example
+/// when a level transition drops and re-requests the same textures in consecutive frames.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RetentionPolicy {
+    /// Free the asset as soon as it is unused
+    Immediate,
+    /// Keep the asset for the given time after it becomes unused
+    KeepFor(Duration),
+    /// Keep the asset for the given number of frames after it becomes unused
+    KeepForFrames(u32),
+    /// Keep unused assets while their estimated total memory (in bytes) fits in the budget,
+    /// evicting the least recently used assets first
+    MemoryBudget(usize),
+    /// Never free the asset
+    Pinned,
+}
+
+impl Default for RetentionPolicy {
+    fn default() -> Self {
+        RetentionPolicy::Immediate
+    }
+}
+
+/// Live usage information about an asset, as reported by [AssetServer::asset_usage](crate::AssetServer::asset_usage)
+#[derive(Debug, Clone)]
+pub struct AssetUsage {
+    pub id: HandleId,
+    pub type_uuid: Option<Uuid>,
+    pub strong_handles: usize,
+    /// Estimated memory used by the asset, in bytes. This is only known for assets created
+    /// through the [AssetServer](crate::AssetServer), and is updated whenever they are modified.
+    pub memory_estimate: Option<usize>,
+    /// Whether the asset is unused, but still kept loaded by its [RetentionPolicy]
+    pub retained: bool,
+}
+
+type MemoryEstimator = Box<dyn Fn(&dyn AssetDynamic) -> usize + Send + Sync>;
+
+struct ReleasedAsset {
+    type_uuid: Uuid,
+    released_at: Instant,
+    released_frame: u64,
+}
+
+/// Tracks unused assets that are kept alive by their type's [RetentionPolicy]
+#[derive(Default)]
+pub(crate) struct AssetRetention {
+    policies: HashMap<Uuid, RetentionPolicy>,
+    memory_estimators: HashMap<Uuid, MemoryEstimator>,
+    memory_estimates: HashMap<HandleId, usize>,
+    released: HashMap<HandleId, ReleasedAsset>,
+    frame: u64,
+}
+
+impl AssetRetention {
+    pub fn set_policy(&mut self, type_uuid: Uuid, policy: RetentionPolicy) {
+        self.policies.insert(type_uuid, policy);
+    }
+
+    pub fn get_policy(&self, type_uuid: Uuid) -> RetentionPolicy {
+        self.policies.get(&type_uuid).cloned().unwrap_or_default()
+    }
+
+    pub fn set_memory_estimator<T: Asset>(
+        &mut self,
+        estimator: impl Fn(&T) -> usize + Send + Sync + 'static,
+    ) {
+        self.memory_estimators.insert(
+            T::TYPE_UUID,
+            Box::new(move |asset| {
+                asset
+                    .downcast_ref::<T>()
+                    .map_or_else(std::mem::size_of::<T>, &estimator)
+            }),
+        );
+    }
+
+    /// Records the memory estimate of a newly created asset. Types without an estimator use the
+    /// size of `T`.
+    pub fn record_memory_estimate<T: Asset>(&mut self, id: HandleId, asset: &T) {
+        let estimate = match self.memory_estimators.get(&T::TYPE_UUID) {
+            Some(estimator) => estimator(asset),
+            None => std::mem::size_of::<T>(),
+        };
+        self.memory_estimates.insert(id, estimate);
+    }
+
+    pub fn get_memory_estimate(&self, id: HandleId) -> Option<usize> {
+        self.memory_estimates.get(&id).cloned()
+    }
+
+    pub fn is_retained(&self, id: HandleId) -> bool {
+        self.released.contains_key(&id)
+    }
+
+    pub fn memory_estimates(&self) -> impl Iterator<Item = (&HandleId, &usize)> {
+        self.memory_estimates.iter()
+    }
+
+    pub fn next_frame(&mut self) {
+        self.frame += 1;
+    }
+
+    /// Marks the asset as unused as of `now`
+    pub fn release(&mut self, id: HandleId, type_uuid: Uuid, now: Instant) {
+        self.released.insert(
+            id,
+            ReleasedAsset {
+                type_uuid,
+                released_at: now,
+                released_frame: self.frame,
+            },
+        );
+    }
+
+    /// Marks the asset as used again, which cancels any pending free
+    pub fn reacquire(&mut self, id: HandleId) {
+        self.released.remove(&id);
+    }
+
+    pub fn forget(&mut self, id: HandleId) {
+        self.released.remove(&id);
+        self.memory_estimates.remove(&id);
+    }
+
+    /// Removes and returns the unused assets whose retention has run out
+    pub fn collect_expired(&mut self, now: Instant) -> Vec<(HandleId, Uuid)> {
+        let mut expired = Vec::new();
+        let mut budgets = HashMap::default();
+        for (id, released) in self.released.iter() {
+            let is_expired = match self.get_policy(released.type_uuid) {
+                RetentionPolicy::Immediate => true,
+                RetentionPolicy::KeepFor(duration) => {
+                    now.duration_since(released.released_at) >= duration
+                }
+                RetentionPolicy::KeepForFrames(frames) => {
+                    self.frame - released.released_frame >= frames as u64
+                }
+                RetentionPolicy::MemoryBudget(budget) => {
+                    budgets
+                        .entry(released.type_uuid)
+                        .or_insert_with(|| (budget, Vec::new()))
+                        .1
+                        .push(*id);
+                    false
+                }
+                RetentionPolicy::Pinned => false,
+            };
+            if is_expired {
+                expired.push((*id, released.type_uuid));
+            }
+        }
+
+        for (type_uuid, (budget, mut ids)) in budgets {
+            let mut total = ids
+                .iter()
+                .map(|id| self.get_memory_estimate(*id).unwrap_or(0))
+                .sum::<usize>();
+            // evict the assets that have been unused the longest first
+            ids.sort_by_key(|id| {
+                let released = &self.released[id];
+                (released.released_frame, released.released_at)
+            });
+            for id in ids {
+                if total <= budget {
+                    break;
+                }
+                total -= self.get_memory_estimate(id).unwrap_or(0);
+                expired.push((id, type_uuid));
+            }
+        }
+
+        for (id, _) in expired.iter() {
+            self.forget(*id);
+        }
+
+        expired
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TYPE: Uuid = Uuid::from_bytes([1; 16]);
+
+    fn handle(id: u64) -> HandleId {
+        HandleId::new(TYPE, id)
+    }
+
+    #[test]
+    fn keep_for_frames() {
+        let mut retention = AssetRetention::default();
+        retention.set_policy(TYPE, RetentionPolicy::KeepForFrames(2));
+        let now = Instant::now();
+        retention.release(handle(0), TYPE, now);
+
+        retention.next_frame();
+        assert!(retention.collect_expired(now).is_empty());
+        retention.next_frame();
+        assert_eq!(retention.collect_expired(now), vec![(handle(0), TYPE)]);
+        assert!(!retention.is_retained(handle(0)));
+    }
+
+    #[test]
+    fn reacquired_assets_are_not_freed() {
+        let mut retention = AssetRetention::default();
+        retention.set_policy(TYPE, RetentionPolicy::KeepForFrames(1));
+        let now = Instant::now();
+        retention.release(handle(0), TYPE, now);
+        retention.reacquire(handle(0));
+
+        retention.next_frame();
+        assert!(retention.collect_expired(now).is_empty());
+    }
+
+    #[test]
+    fn memory_budget_evicts_least_recently_used() {
+        let mut retention = AssetRetention::default();
+        retention.set_policy(TYPE, RetentionPolicy::MemoryBudget(100));
+        let now = Instant::now();
+        for i in 0..3 {
+            retention.memory_estimates.insert(handle(i), 40);
+            retention.release(handle(i), TYPE, now);
+            retention.next_frame();
+        }
+
+        assert_eq!(retention.collect_expired(now), vec![(handle(0), TYPE)]);
+        assert!(retention.is_retained(handle(1)));
+        assert!(retention.is_retained(handle(2)));
+    }
+
+    #[test]
+    fn pinned_assets_are_never_freed() {
+        let mut retention = AssetRetention::default();
+        retention.set_policy(TYPE, RetentionPolicy::Pinned);
+        let now = Instant::now();
+        retention.release(handle(0), TYPE, now);
+        for _ in 0..10 {
+            retention.next_frame();
+        }
+
+        assert!(retention
+            .collect_expired(now + Duration::from_secs(60))
+            .is_empty());
+    }
+}
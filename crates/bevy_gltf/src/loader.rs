@@ -142,6 +142,7 @@ async fn load_gltf<'a, 'b>(
                     dimension: TextureDimension::D2,
                     format: TextureFormat::Rgba8Unorm,
                     sampler: texture_sampler(&texture)?,
+                    ..Default::default()
                 }),
             );
         }
@@ -0,0 +1,185 @@
+use bevy_asset::HandleId;
+use bevy_reflect::{Reflect, VariantInfo};
+use bevy_utils::HashMap;
+
+/// The kind of widget an inspector should use to edit a value
+#[derive(Debug, Clone)]
+pub enum EditorKind {
+    Checkbox,
+    /// `range` is set from `#[reflect(range(min, max))]` on the field holding the number
+    Number {
+        integer: bool,
+        range: Option<(f64, f64)>,
+    },
+    Text,
+    Color,
+    Handle,
+    /// A choice between the variants of an enum. Only variants without fields can be selected.
+    Enum {
+        variants: Vec<VariantInfo>,
+    },
+    /// A struct, tuple, list or enum whose fields are shown as children
+    Group,
+    /// A value that has no editor
+    ReadOnly,
+}
+
+impl EditorKind {
+    pub fn is_editable(&self) -> bool {
+        !matches!(self, EditorKind::Group | EditorKind::ReadOnly)
+    }
+}
+
+/// The value shown by, and submitted from, an editor
+#[derive(Debug, Clone, PartialEq)]
+pub enum InspectorValue {
+    Bool(bool),
+    Number(f64),
+    Text(String),
+    /// Non-linear sRGBA
+    Color([f32; 4]),
+    Handle(HandleId),
+    Variant(String),
+}
+
+/// Converts between a reflected type and the [InspectorValue] of its editor
+#[derive(Clone)]
+pub struct ValueEditor {
+    kind: EditorKind,
+    read: fn(&dyn Reflect) -> Option<InspectorValue>,
+    to_reflect: fn(&InspectorValue) -> Option<Box<dyn Reflect>>,
+}
+
+impl ValueEditor {
+    pub fn new(
+        kind: EditorKind,
+        read: fn(&dyn Reflect) -> Option<InspectorValue>,
+        to_reflect: fn(&InspectorValue) -> Option<Box<dyn Reflect>>,
+    ) -> Self {
+        ValueEditor {
+            kind,
+            read,
+            to_reflect,
+        }
+    }
+
+    pub fn kind(&self) -> &EditorKind {
+        &self.kind
+    }
+
+    pub fn read(&self, value: &dyn Reflect) -> Option<InspectorValue> {
+        (self.read)(value)
+    }
+
+    /// Creates a value of the edited type, which can be applied to the edited value with [Reflect::apply]
+    pub fn to_reflect(&self, value: &InspectorValue) -> Option<Box<dyn Reflect>> {
+        (self.to_reflect)(value)
+    }
+}
+
+/// The [ValueEditor]s used by inspectors, keyed by type name
+///
+/// Editors are looked up by type name rather than [TypeId](std::any::TypeId) so that they also match the
+/// dynamic clones of reflected values.
+pub struct InspectorEditors {
+    editors: HashMap<&'static str, ValueEditor>,
+}
+
+impl InspectorEditors {
+    pub fn register<T: Reflect>(&mut self, editor: ValueEditor) {
+        self.editors.insert(std::any::type_name::<T>(), editor);
+    }
+
+    pub fn get(&self, type_name: &str) -> Option<&ValueEditor> {
+        self.editors.get(type_name)
+    }
+}
+
+macro_rules! register_number_editors {
+    ($editors:expr, $integer:expr, $($ty:ty),*) => {
+        $(
+            $editors.register::<$ty>(ValueEditor::new(
+                EditorKind::Number {
+                    integer: $integer,
+                    range: None,
+                },
+                |value| {
+                    value
+                        .downcast_ref::<$ty>()
+                        .map(|value| InspectorValue::Number(*value as f64))
+                },
+                |value| match value {
+                    InspectorValue::Number(number) if $integer => Some(Box::new(number.round() as $ty)),
+                    InspectorValue::Number(number) => Some(Box::new(*number as $ty)),
+                    _ => None,
+                },
+            ));
+        )*
+    };
+}
+
+impl Default for InspectorEditors {
+    fn default() -> Self {
+        let mut editors = InspectorEditors {
+            editors: Default::default(),
+        };
+
+        register_number_editors!(editors, true, u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+        register_number_editors!(editors, false, f32, f64);
+        editors.register::<bool>(ValueEditor::new(
+            EditorKind::Checkbox,
+            |value| {
+                value
+                    .downcast_ref::<bool>()
+                    .map(|value| InspectorValue::Bool(*value))
+            },
+            |value| match value {
+                InspectorValue::Bool(value) => Some(Box::new(*value)),
+                _ => None,
+            },
+        ));
+        editors.register::<String>(ValueEditor::new(
+            EditorKind::Text,
+            |value| {
+                value
+                    .downcast_ref::<String>()
+                    .map(|value| InspectorValue::Text(value.clone()))
+            },
+            |value| match value {
+                InspectorValue::Text(value) => Some(Box::new(value.clone())),
+                _ => None,
+            },
+        ));
+        editors.register::<HandleId>(ValueEditor::new(
+            EditorKind::Handle,
+            |value| {
+                value
+                    .downcast_ref::<HandleId>()
+                    .map(|value| InspectorValue::Handle(*value))
+            },
+            |value| match value {
+                InspectorValue::Handle(value) => Some(Box::new(*value)),
+                _ => None,
+            },
+        ));
+        #[cfg(feature = "bevy_render")]
+        editors.register::<bevy_render::color::Color>(ValueEditor::new(
+            EditorKind::Color,
+            |value| {
+                value
+                    .downcast_ref::<bevy_render::color::Color>()
+                    .map(|color| {
+                        InspectorValue::Color([color.r(), color.g(), color.b(), color.a()])
+                    })
+            },
+            |value| match value {
+                InspectorValue::Color([r, g, b, a]) => {
+                    Some(Box::new(bevy_render::color::Color::rgba(*r, *g, *b, *a)))
+                }
+                _ => None,
+            },
+        ));
+
+        editors
+    }
+}
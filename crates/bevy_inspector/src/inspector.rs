@@ -0,0 +1,534 @@
+use crate::{EditorKind, InspectorEditors, InspectorValue};
+use bevy_ecs::{Entity, World};
+use bevy_reflect::{
+    DynamicEnum, FieldInfo, GetPath, Reflect, ReflectComponent, ReflectRef, TypeRegistry,
+    VariantType,
+};
+use thiserror::Error;
+
+/// A reflected value shown by an inspector. Values with a [ValueEditor](crate::ValueEditor) are
+/// editable leaves, other values are shown as a group of their fields.
+#[derive(Debug, Clone)]
+pub struct InspectorField {
+    pub label: String,
+    /// The path of the field in its component, in the format used by [GetPath]
+    pub path: String,
+    pub type_name: String,
+    pub docs: Option<&'static str>,
+    pub editor: EditorKind,
+    pub value: Option<InspectorValue>,
+    pub children: Vec<InspectorField>,
+}
+
+impl InspectorField {
+    /// Finds the field with the given path among this field and its descendants
+    pub fn find(&self, path: &str) -> Option<&InspectorField> {
+        if self.path == path {
+            Some(self)
+        } else {
+            self.children.iter().find_map(|child| child.find(path))
+        }
+    }
+}
+
+/// The inspected state of a single component
+#[derive(Debug, Clone)]
+pub struct ComponentInspector {
+    pub type_name: String,
+    pub short_name: String,
+    pub root: InspectorField,
+}
+
+impl ComponentInspector {
+    pub fn field(&self, path: &str) -> Option<&InspectorField> {
+        self.root.find(path)
+    }
+}
+
+/// The inspected state of all reflected components of an entity
+///
+/// This is a UI-agnostic snapshot: build it, show it however you like, and submit changes as
+/// [InspectorEdit]s.
+#[derive(Debug, Clone)]
+pub struct EntityInspector {
+    pub entity: Entity,
+    pub components: Vec<ComponentInspector>,
+}
+
+impl EntityInspector {
+    /// Inspects every component of `entity` that is registered with `#[reflect(Component)]`.
+    /// Returns `None` if the entity doesn't exist.
+    pub fn new(
+        world: &World,
+        type_registry: &TypeRegistry,
+        editors: &InspectorEditors,
+        entity: Entity,
+    ) -> Option<Self> {
+        let location = world.get_entity_location(entity)?;
+        let archetype = world.archetypes().nth(location.archetype as usize)?;
+        let mut components = Vec::new();
+        for type_info in archetype.types() {
+            let registration = match type_registry.get(type_info.id()) {
+                Some(registration) => registration,
+                None => continue,
+            };
+            if let Some(reflect_component) = registration.data::<ReflectComponent>() {
+                // SAFE: the location comes directly from a currently live entity
+                let component =
+                    unsafe { reflect_component.reflect_component(archetype, location.index) };
+                let context = InspectContext {
+                    type_registry,
+                    editors,
+                };
+                let mut root = context.inspect(
+                    registration.short_name().to_string(),
+                    String::new(),
+                    component,
+                    None,
+                );
+                root.docs = registration.type_info().docs();
+                components.push(ComponentInspector {
+                    type_name: registration.name().to_string(),
+                    short_name: registration.short_name().to_string(),
+                    root,
+                });
+            }
+        }
+
+        components.sort_by(|a, b| a.short_name.cmp(&b.short_name));
+        Some(EntityInspector { entity, components })
+    }
+
+    pub fn component(&self, type_name: &str) -> Option<&ComponentInspector> {
+        self.components
+            .iter()
+            .find(|component| component.type_name == type_name)
+    }
+}
+
+struct InspectContext<'a> {
+    type_registry: &'a TypeRegistry,
+    editors: &'a InspectorEditors,
+}
+
+impl<'a> InspectContext<'a> {
+    fn inspect(
+        &self,
+        label: String,
+        path: String,
+        value: &dyn Reflect,
+        range: Option<(f64, f64)>,
+    ) -> InspectorField {
+        let mut field = InspectorField {
+            label,
+            path,
+            type_name: value.type_name().to_string(),
+            docs: None,
+            editor: EditorKind::Group,
+            value: None,
+            children: Vec::new(),
+        };
+
+        if let Some(editor) = self.editors.get(value.type_name()) {
+            if let Some(editor_value) = editor.read(value) {
+                field.editor = match editor.kind() {
+                    EditorKind::Number { integer, .. } => EditorKind::Number {
+                        integer: *integer,
+                        range,
+                    },
+                    kind => kind.clone(),
+                };
+                field.value = Some(editor_value);
+                return field;
+            }
+        }
+
+        match value.reflect_ref() {
+            ReflectRef::Struct(value_struct) => {
+                for (i, field_value) in value_struct.iter_fields().enumerate() {
+                    let name = value_struct.name_at(i).unwrap();
+                    field
+                        .children
+                        .push(self.inspect_field(&field.path, name, value, field_value));
+                }
+            }
+            ReflectRef::TupleStruct(tuple_struct) => {
+                for (i, field_value) in tuple_struct.iter_fields().enumerate() {
+                    field.children.push(self.inspect_field(
+                        &field.path,
+                        &i.to_string(),
+                        value,
+                        field_value,
+                    ));
+                }
+            }
+            ReflectRef::Tuple(tuple) => {
+                for (i, field_value) in tuple.iter_fields().enumerate() {
+                    field.children.push(self.inspect_field(
+                        &field.path,
+                        &i.to_string(),
+                        value,
+                        field_value,
+                    ));
+                }
+            }
+            ReflectRef::List(list) => {
+                for (i, item) in list.iter().enumerate() {
+                    field.children.push(self.inspect(
+                        format!("[{}]", i),
+                        format!("{}[{}]", field.path, i),
+                        item,
+                        None,
+                    ));
+                }
+            }
+            ReflectRef::Array(array) => {
+                for (i, item) in array.iter().enumerate() {
+                    field.children.push(self.inspect(
+                        format!("[{}]", i),
+                        format!("{}[{}]", field.path, i),
+                        item,
+                        None,
+                    ));
+                }
+            }
+            // map values can't be addressed with a path, so they are not editable
+            ReflectRef::Map(_) => field.editor = EditorKind::ReadOnly,
+            ReflectRef::Enum(enum_value) => {
+                field.editor = EditorKind::Enum {
+                    variants: self
+                        .type_registry
+                        .get_with_name(value.type_name())
+                        .map(|registration| registration.type_info().variants().to_vec())
+                        .unwrap_or_default(),
+                };
+                field.value = Some(InspectorValue::Variant(
+                    enum_value.variant_name().to_string(),
+                ));
+                for (i, field_value) in enum_value.iter_fields().enumerate() {
+                    let name = enum_value
+                        .name_at(i)
+                        .map(str::to_string)
+                        .unwrap_or_else(|| i.to_string());
+                    field
+                        .children
+                        .push(self.inspect_field(&field.path, &name, value, field_value));
+                }
+            }
+            ReflectRef::Value(_) => field.editor = EditorKind::ReadOnly,
+        }
+
+        field
+    }
+
+    fn inspect_field(
+        &self,
+        parent_path: &str,
+        name: &str,
+        parent: &dyn Reflect,
+        value: &dyn Reflect,
+    ) -> InspectorField {
+        let field_info = find_field_info(self.type_registry, parent, name);
+        let mut field = self.inspect(
+            name.to_string(),
+            field_path(parent_path, name),
+            value,
+            field_info.and_then(|info| info.range()),
+        );
+        field.docs = field_info.and_then(|info| info.docs());
+        field
+    }
+}
+
+fn field_path(parent_path: &str, name: &str) -> String {
+    if parent_path.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}.{}", parent_path, name)
+    }
+}
+
+/// Looks up the static info of a field of `parent` using the [TypeInfo](bevy_reflect::TypeInfo) of
+/// its registration. Tuple-like fields are named by their index.
+fn find_field_info<'a>(
+    type_registry: &'a TypeRegistry,
+    parent: &dyn Reflect,
+    name: &str,
+) -> Option<&'a FieldInfo> {
+    let type_info = type_registry.get_with_name(parent.type_name())?.type_info();
+    let fields = match parent.reflect_ref() {
+        ReflectRef::Enum(value) => type_info.variant(value.variant_name())?.fields(),
+        _ => type_info.fields(),
+    };
+    let index = name.parse::<usize>().ok();
+    fields.iter().find(|field| match field.name() {
+        Some(field_name) => field_name == name,
+        None => Some(field.index()) == index,
+    })
+}
+
+/// A change of a single inspected value
+#[derive(Debug, Clone, PartialEq)]
+pub struct InspectorEdit {
+    pub entity: Entity,
+    /// The full type name of the edited component
+    pub component: String,
+    /// The path of the edited field, see [InspectorField::path]
+    pub path: String,
+    pub value: InspectorValue,
+}
+
+#[derive(Error, Debug)]
+pub enum InspectorError {
+    #[error("the entity does not exist")]
+    NoSuchEntity,
+    #[error("`{type_name}` is not registered as a component. consider adding `#[reflect(Component)]` to your type")]
+    UnregisteredComponent { type_name: String },
+    #[error("the entity does not have the component `{type_name}`")]
+    MissingComponent { type_name: String },
+    #[error("the component does not have a field at `{path}`: {message}")]
+    InvalidPath { path: String, message: String },
+    #[error("`{type_name}` has no editor that accepts the given value")]
+    InvalidValue { type_name: String },
+    #[error("the enum `{type_name}` is not registered. consider registering the type using `app.register_type::<T>()`")]
+    UnregisteredEnum { type_name: String },
+    #[error("the enum `{type_name}` does not have a variant named `{variant}`")]
+    InvalidVariant { type_name: String, variant: String },
+    #[error("the variant `{variant}` of `{type_name}` has fields. only variants without fields can be selected")]
+    VariantHasFields { type_name: String, variant: String },
+}
+
+impl InspectorEdit {
+    /// Applies the edit to a copy of the component, then applies the copy using
+    /// [ReflectComponent::apply_component]. Numbers are clamped to the range of their field.
+    pub fn apply(
+        &self,
+        world: &mut World,
+        type_registry: &TypeRegistry,
+        editors: &InspectorEditors,
+    ) -> Result<(), InspectorError> {
+        let registration = type_registry
+            .get_with_name(&self.component)
+            .ok_or_else(|| InspectorError::UnregisteredComponent {
+                type_name: self.component.clone(),
+            })?;
+        let reflect_component = registration.data::<ReflectComponent>().ok_or_else(|| {
+            InspectorError::UnregisteredComponent {
+                type_name: self.component.clone(),
+            }
+        })?;
+
+        let location = world
+            .get_entity_location(self.entity)
+            .ok_or(InspectorError::NoSuchEntity)?;
+        let archetype = world
+            .archetypes()
+            .nth(location.archetype as usize)
+            .ok_or(InspectorError::NoSuchEntity)?;
+        if !archetype.has_type(registration.type_id()) {
+            return Err(InspectorError::MissingComponent {
+                type_name: self.component.clone(),
+            });
+        }
+        // SAFE: the component type was checked above and the location comes from a live entity
+        let mut component =
+            unsafe { reflect_component.reflect_component(archetype, location.index) }.clone_value();
+
+        let (parent_path, name) = match self.path.rfind('.') {
+            Some(index) => (&self.path[..index], &self.path[index + 1..]),
+            None => ("", self.path.as_str()),
+        };
+        let range = component
+            .path(parent_path)
+            .ok()
+            .and_then(|parent| find_field_info(type_registry, parent, name))
+            .and_then(|info| info.range());
+
+        let target = component
+            .path_mut(&self.path)
+            .map_err(|err| InspectorError::InvalidPath {
+                path: self.path.clone(),
+                message: err.to_string(),
+            })?;
+        if let InspectorValue::Variant(variant) = &self.value {
+            set_variant(type_registry, target, variant)?;
+        } else {
+            let editor =
+                editors
+                    .get(target.type_name())
+                    .ok_or_else(|| InspectorError::InvalidValue {
+                        type_name: target.type_name().to_string(),
+                    })?;
+            let value = match (&self.value, range) {
+                (InspectorValue::Number(number), Some((min, max))) => {
+                    InspectorValue::Number(number.max(min).min(max))
+                }
+                (value, _) => value.clone(),
+            };
+            let value = editor
+                .to_reflect(&value)
+                .ok_or_else(|| InspectorError::InvalidValue {
+                    type_name: target.type_name().to_string(),
+                })?;
+            target.apply(&*value);
+        }
+
+        reflect_component.apply_component(world, self.entity, &*component);
+        Ok(())
+    }
+}
+
+fn set_variant(
+    type_registry: &TypeRegistry,
+    target: &mut dyn Reflect,
+    variant: &str,
+) -> Result<(), InspectorError> {
+    let type_name = target.type_name().to_string();
+    let registration = type_registry.get_with_name(&type_name).ok_or_else(|| {
+        InspectorError::UnregisteredEnum {
+            type_name: type_name.clone(),
+        }
+    })?;
+    let variants = registration.type_info().variants();
+    let variant_index = variants
+        .iter()
+        .position(|info| info.name() == variant)
+        .ok_or_else(|| InspectorError::InvalidVariant {
+            type_name: type_name.clone(),
+            variant: variant.to_string(),
+        })?;
+    if variants[variant_index].variant_type() != VariantType::Unit {
+        return Err(InspectorError::VariantHasFields {
+            type_name,
+            variant: variant.to_string(),
+        });
+    }
+
+    let mut value = DynamicEnum::default();
+    value.set_name(type_name);
+    value.set_variant(variant.to_string(), VariantType::Unit);
+    value.set_variant_index(variant_index);
+    target.apply(&value);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Reflect, Default)]
+    #[reflect(Component)]
+    struct Health {
+        #[reflect(range(0, 100))]
+        value: u32,
+        regenerate: bool,
+        mode: Mode,
+    }
+
+    #[derive(Reflect)]
+    enum Mode {
+        Normal,
+        Frozen,
+        Poisoned(f32),
+    }
+
+    impl Default for Mode {
+        fn default() -> Self {
+            Mode::Normal
+        }
+    }
+
+    fn setup() -> (World, TypeRegistry, InspectorEditors, Entity) {
+        let mut world = World::new();
+        let mut type_registry = TypeRegistry::default();
+        type_registry.register::<Health>();
+        type_registry.register::<Mode>();
+        let entity = world.spawn((Health {
+            value: 50,
+            regenerate: false,
+            mode: Mode::Normal,
+        },));
+        (world, type_registry, InspectorEditors::default(), entity)
+    }
+
+    #[test]
+    fn inspect_entity() {
+        let (world, type_registry, editors, entity) = setup();
+        let inspector = EntityInspector::new(&world, &type_registry, &editors, entity).unwrap();
+        let health = inspector
+            .component(std::any::type_name::<Health>())
+            .unwrap();
+        assert_eq!(health.root.children.len(), 3);
+
+        let value = health.field("value").unwrap();
+        assert_eq!(value.value, Some(InspectorValue::Number(50.0)));
+        assert!(matches!(
+            value.editor,
+            EditorKind::Number {
+                integer: true,
+                range: Some((min, max))
+            } if min == 0.0 && max == 100.0
+        ));
+
+        let regenerate = health.field("regenerate").unwrap();
+        assert_eq!(regenerate.value, Some(InspectorValue::Bool(false)));
+        assert!(matches!(regenerate.editor, EditorKind::Checkbox));
+
+        let mode = health.field("mode").unwrap();
+        assert_eq!(
+            mode.value,
+            Some(InspectorValue::Variant("Normal".to_string()))
+        );
+        assert!(matches!(&mode.editor, EditorKind::Enum { variants } if variants.len() == 3));
+    }
+
+    #[test]
+    fn apply_edits() {
+        let (mut world, type_registry, editors, entity) = setup();
+        let edit = |path: &str, value| InspectorEdit {
+            entity,
+            component: std::any::type_name::<Health>().to_string(),
+            path: path.to_string(),
+            value,
+        };
+
+        edit("value", InspectorValue::Number(250.0))
+            .apply(&mut world, &type_registry, &editors)
+            .unwrap();
+        edit("regenerate", InspectorValue::Bool(true))
+            .apply(&mut world, &type_registry, &editors)
+            .unwrap();
+        edit("mode", InspectorValue::Variant("Frozen".to_string()))
+            .apply(&mut world, &type_registry, &editors)
+            .unwrap();
+
+        let health = world.get::<Health>(entity).unwrap();
+        assert_eq!(
+            health.value, 100,
+            "numbers are clamped to the field's range"
+        );
+        assert!(health.regenerate);
+        assert!(matches!(health.mode, Mode::Frozen));
+
+        assert!(matches!(
+            edit("mode", InspectorValue::Variant("Poisoned".to_string())).apply(
+                &mut world,
+                &type_registry,
+                &editors
+            ),
+            Err(InspectorError::VariantHasFields { .. })
+        ));
+        assert!(matches!(
+            edit("speed", InspectorValue::Number(1.0)).apply(&mut world, &type_registry, &editors),
+            Err(InspectorError::InvalidPath { .. })
+        ));
+        assert!(matches!(
+            edit("regenerate", InspectorValue::Number(1.0)).apply(
+                &mut world,
+                &type_registry,
+                &editors
+            ),
+            Err(InspectorError::InvalidValue { .. })
+        ));
+    }
+}
@@ -0,0 +1,53 @@
+mod editor;
+mod inspector;
+#[cfg(feature = "ui")]
+mod ui;
+
+pub use editor::*;
+pub use inspector::*;
+#[cfg(feature = "ui")]
+pub use ui::*;
+
+pub mod prelude {
+    pub use crate::{EntityInspector, InspectorEdit, InspectorEditors, InspectorPlugin};
+}
+
+use bevy_app::prelude::*;
+use bevy_ecs::{Resources, World};
+use bevy_reflect::TypeRegistryArc;
+use bevy_utils::tracing::warn;
+
+/// Adds the [InspectorEditors] resource and applies [InspectorEdit] events
+#[derive(Default)]
+pub struct InspectorPlugin;
+
+impl Plugin for InspectorPlugin {
+    fn build(&self, app: &mut AppBuilder) {
+        app.init_resource::<InspectorEditors>()
+            .init_resource::<InspectorEditReader>()
+            .add_event::<InspectorEdit>()
+            .add_system_to_stage(stage::PRE_UPDATE, inspector_edit_system);
+    }
+}
+
+#[derive(Default)]
+pub struct InspectorEditReader {
+    reader: EventReader<InspectorEdit>,
+}
+
+pub fn inspector_edit_system(world: &mut World, resources: &mut Resources) {
+    let mut edit_reader = resources.get_mut::<InspectorEditReader>().unwrap();
+    let edit_events = resources.get::<Events<InspectorEdit>>().unwrap();
+    let type_registry = resources.get::<TypeRegistryArc>().unwrap();
+    let type_registry = type_registry.read();
+    let editors = resources.get::<InspectorEditors>().unwrap();
+
+    for edit in edit_reader.reader.iter(&edit_events) {
+        if let Err(err) = edit.apply(world, &type_registry, &editors) {
+            warn!(
+                "Failed to apply inspector edit to {}: {}",
+                edit.component, err
+            );
+        }
+    }
+}
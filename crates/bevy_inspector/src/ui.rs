@@ -0,0 +1,311 @@
+use crate::{
+    EditorKind, EntityInspector, InspectorEdit, InspectorEditors, InspectorField, InspectorValue,
+};
+use bevy_app::prelude::*;
+use bevy_asset::{Assets, Handle};
+use bevy_ecs::{
+    Entity, FromResources, Mutated, Query, ResMut, Resources, World, WorldBuilderSource,
+};
+use bevy_math::{Rect, Size};
+use bevy_reflect::{TypeRegistryArc, VariantType};
+use bevy_render::color::Color;
+use bevy_sprite::ColorMaterial;
+use bevy_text::{Font, TextStyle};
+use bevy_transform::prelude::*;
+use bevy_ui::{
+    entity::{ButtonBundle, NodeBundle, TextBundle},
+    widget::Text,
+    AlignItems, FlexDirection, Interaction, PositionType, Style, Val,
+};
+
+/// Shows the [EntityInspector] of `entity` as a bevy_ui panel. Numbers, checkboxes and enums get
+/// buttons that send [InspectorEdit]s.
+///
+/// The panel doesn't show any text until `font` is set.
+pub struct InspectorUi {
+    pub entity: Option<Entity>,
+    pub font: Handle<Font>,
+    pub font_size: f32,
+    pub background: Handle<ColorMaterial>,
+    pub button: Handle<ColorMaterial>,
+    panel: Option<Entity>,
+    shown_rows: Vec<InspectorRow>,
+    /// The inspected entity and its archetype when the rows were last built
+    shown_location: Option<(Entity, u32)>,
+}
+
+impl FromResources for InspectorUi {
+    fn from_resources(resources: &Resources) -> Self {
+        let mut materials = resources.get_mut::<Assets<ColorMaterial>>().unwrap();
+        InspectorUi {
+            entity: None,
+            font: Default::default(),
+            font_size: 16.0,
+            background: materials.add(Color::rgba(0.1, 0.1, 0.1, 0.9).into()),
+            button: materials.add(Color::rgb(0.25, 0.25, 0.25).into()),
+            panel: None,
+            shown_rows: Vec::new(),
+            shown_location: None,
+        }
+    }
+}
+
+/// Adds an [InspectorUi]. This requires the [InspectorPlugin](crate::InspectorPlugin) and the `UiPlugin`.
+#[derive(Default)]
+pub struct InspectorUiPlugin;
+
+impl Plugin for InspectorUiPlugin {
+    fn build(&self, app: &mut AppBuilder) {
+        app.init_resource::<InspectorUi>()
+            .add_system(inspector_button_system)
+            .add_system_to_stage(stage::POST_UPDATE, inspector_ui_system);
+    }
+}
+
+/// A button of the inspector panel, which sends its edit when clicked
+pub struct InspectorButton {
+    pub edit: InspectorEdit,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct InspectorRow {
+    text: String,
+    buttons: Vec<(String, InspectorEdit)>,
+}
+
+pub fn inspector_button_system(
+    mut edits: ResMut<Events<InspectorEdit>>,
+    query: Query<(&Interaction, &InspectorButton), Mutated<Interaction>>,
+) {
+    for (interaction, button) in query.iter() {
+        if let Interaction::Clicked = interaction {
+            edits.send(button.edit.clone());
+        }
+    }
+}
+
+/// Rebuilds the inspector panel whenever the inspected values change
+pub fn inspector_ui_system(world: &mut World, resources: &mut Resources) {
+    let mut inspector_ui = resources.get_mut::<InspectorUi>().unwrap();
+    let location = inspector_ui.entity.and_then(|entity| {
+        world
+            .get_entity_location(entity)
+            .map(|location| (entity, location.archetype))
+    });
+    // the components of the entity are only read again when one of them was added, removed or mutated
+    if location == inspector_ui.shown_location
+        && !location.map_or(false, |(entity, _)| is_changed(world, entity))
+    {
+        return;
+    }
+    inspector_ui.shown_location = location;
+
+    let type_registry = resources.get::<TypeRegistryArc>().unwrap();
+    let editors = resources.get::<InspectorEditors>().unwrap();
+    let inspector = inspector_ui
+        .entity
+        .and_then(|entity| EntityInspector::new(world, &type_registry.read(), &editors, entity));
+    let rows = inspector
+        .map(|inspector| get_rows(&inspector))
+        .unwrap_or_default();
+    if rows == inspector_ui.shown_rows {
+        return;
+    }
+
+    if let Some(panel) = inspector_ui.panel.take() {
+        despawn_with_children_recursive(world, panel);
+    }
+    if rows.is_empty() {
+        inspector_ui.shown_rows = rows;
+        return;
+    }
+
+    let text_style = TextStyle {
+        font_size: inspector_ui.font_size,
+        color: Color::WHITE,
+        ..Default::default()
+    };
+    let mut builder = world.build();
+    builder
+        .spawn(NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                position: Rect {
+                    left: Val::Px(0.0),
+                    top: Val::Px(0.0),
+                    ..Default::default()
+                },
+                flex_direction: FlexDirection::ColumnReverse,
+                padding: Rect::all(Val::Px(4.0)),
+                ..Default::default()
+            },
+            material: inspector_ui.background.clone(),
+            ..Default::default()
+        })
+        .with_children(|panel| {
+            for row in rows.iter() {
+                panel
+                    .spawn(NodeBundle {
+                        style: Style {
+                            align_items: AlignItems::Center,
+                            ..Default::default()
+                        },
+                        material: inspector_ui.background.clone(),
+                        ..Default::default()
+                    })
+                    .with_children(|row_node| {
+                        row_node.spawn(TextBundle {
+                            text: Text {
+                                value: row.text.clone(),
+                                font: inspector_ui.font.clone(),
+                                style: text_style.clone(),
+                            },
+                            ..Default::default()
+                        });
+                        for (label, edit) in row.buttons.iter() {
+                            row_node
+                                .spawn(ButtonBundle {
+                                    style: Style {
+                                        margin: Rect::all(Val::Px(2.0)),
+                                        padding: Rect::all(Val::Px(2.0)),
+                                        min_size: Size::new(
+                                            Val::Px(inspector_ui.font_size),
+                                            Val::Auto,
+                                        ),
+                                        ..Default::default()
+                                    },
+                                    material: inspector_ui.button.clone(),
+                                    ..Default::default()
+                                })
+                                .with(InspectorButton { edit: edit.clone() })
+                                .with_children(|button| {
+                                    button.spawn(TextBundle {
+                                        text: Text {
+                                            value: label.clone(),
+                                            font: inspector_ui.font.clone(),
+                                            style: text_style.clone(),
+                                        },
+                                        ..Default::default()
+                                    });
+                                });
+                        }
+                    });
+            }
+        });
+
+    inspector_ui.panel = builder.current_entity;
+    inspector_ui.shown_rows = rows;
+}
+
+/// Returns true if a component of `entity` was added or mutated this frame
+fn is_changed(world: &World, entity: Entity) -> bool {
+    let location = match world.get_entity_location(entity) {
+        Some(location) => location,
+        None => return false,
+    };
+    let archetype = world.archetypes().nth(location.archetype as usize).unwrap();
+    archetype.types().iter().any(|ty| {
+        let type_state = archetype.get_type_state(ty.id()).unwrap();
+        // SAFE: the entity's location is within the bounds of its archetype
+        let flags = unsafe { *type_state.component_flags().as_ptr().add(location.index) };
+        !flags.is_empty()
+    })
+}
+
+fn get_rows(inspector: &EntityInspector) -> Vec<InspectorRow> {
+    let mut rows = Vec::new();
+    for component in inspector.components.iter() {
+        add_rows(
+            inspector.entity,
+            &component.type_name,
+            &component.root,
+            0,
+            &mut rows,
+        );
+    }
+    rows
+}
+
+fn add_rows(
+    entity: Entity,
+    component: &str,
+    field: &InspectorField,
+    depth: usize,
+    rows: &mut Vec<InspectorRow>,
+) {
+    let indent = "  ".repeat(depth);
+    let text = match &field.value {
+        Some(value) => format!(
+            "{}{}: {}",
+            indent,
+            field.label,
+            format_value(&field.editor, value)
+        ),
+        None if matches!(field.editor, EditorKind::ReadOnly) => {
+            format!("{}{}: {}", indent, field.label, field.type_name)
+        }
+        None => format!("{}{}", indent, field.label),
+    };
+    let edit = |value: InspectorValue| InspectorEdit {
+        entity,
+        component: component.to_string(),
+        path: field.path.clone(),
+        value,
+    };
+
+    let mut buttons = Vec::new();
+    match (&field.editor, &field.value) {
+        (EditorKind::Checkbox, Some(InspectorValue::Bool(value))) => {
+            buttons.push(("toggle".to_string(), edit(InspectorValue::Bool(!value))));
+        }
+        (EditorKind::Number { integer, range }, Some(InspectorValue::Number(value))) => {
+            let step = match range {
+                Some((min, max)) => (max - min) / 20.0,
+                None if *integer => 1.0,
+                None => 0.1,
+            };
+            buttons.push(("-".to_string(), edit(InspectorValue::Number(value - step))));
+            buttons.push(("+".to_string(), edit(InspectorValue::Number(value + step))));
+        }
+        (EditorKind::Enum { variants }, Some(InspectorValue::Variant(current))) => {
+            let unit_variants = variants
+                .iter()
+                .filter(|variant| variant.variant_type() == VariantType::Unit)
+                .map(|variant| variant.name())
+                .collect::<Vec<_>>();
+            let next = unit_variants
+                .iter()
+                .position(|variant| variant == current)
+                .map(|index| unit_variants[(index + 1) % unit_variants.len()])
+                .or_else(|| unit_variants.first().cloned());
+            if let Some(next) = next.filter(|next| *next != current.as_str()) {
+                buttons.push((
+                    "next".to_string(),
+                    edit(InspectorValue::Variant(next.to_string())),
+                ));
+            }
+        }
+        _ => {}
+    }
+
+    rows.push(InspectorRow { text, buttons });
+    for child in field.children.iter() {
+        add_rows(entity, component, child, depth + 1, rows);
+    }
+}
+
+fn format_value(editor: &EditorKind, value: &InspectorValue) -> String {
+    match value {
+        InspectorValue::Bool(value) => value.to_string(),
+        InspectorValue::Number(value) => match editor {
+            EditorKind::Number { integer: true, .. } => format!("{}", value),
+            _ => format!("{:.3}", value),
+        },
+        InspectorValue::Text(value) => format!("{:?}", value),
+        InspectorValue::Color([r, g, b, a]) => {
+            format!("rgba({:.2}, {:.2}, {:.2}, {:.2})", r, g, b, a)
+        }
+        InspectorValue::Handle(value) => format!("{:?}", value),
+        InspectorValue::Variant(value) => value.clone(),
+    }
+}
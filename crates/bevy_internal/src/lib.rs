@@ -88,6 +88,12 @@ pub mod gltf {
     pub use bevy_gltf::*;
 }
 
+#[cfg(feature = "bevy_inspector")]
+pub mod inspector {
+    //! UI-agnostic, reflection based inspection and editing of components.
+    pub use bevy_inspector::*;
+}
+
 #[cfg(feature = "bevy_pbr")]
 pub mod pbr {
     //! Physically based rendering.
@@ -1,5 +1,5 @@
 use bevy_core::Byteable;
-use bevy_reflect::{Reflect, ReflectComponent};
+use bevy_reflect::{Reflect, ReflectComponent, ReflectResource};
 use bevy_render::{
     camera::{CameraProjection, PerspectiveProjection},
     color::Color,
@@ -56,7 +56,8 @@ impl LightRaw {
 }
 
 // Ambient light color.
-#[derive(Debug)]
+#[derive(Debug, Reflect)]
+#[reflect(Resource)]
 pub struct AmbientLight {
     pub color: Color,
 }
@@ -4,6 +4,13 @@ layout(location = 0) in vec3 Vertex_Position;
 layout(location = 1) in vec3 Vertex_Normal;
 layout(location = 2) in vec2 Vertex_Uv;
 
+# ifdef INSTANCING
+layout(location = 3) in vec4 I_Model_0;
+layout(location = 4) in vec4 I_Model_1;
+layout(location = 5) in vec4 I_Model_2;
+layout(location = 6) in vec4 I_Model_3;
+# endif
+
 layout(location = 0) out vec3 v_Position;
 layout(location = 1) out vec3 v_Normal;
 layout(location = 2) out vec2 v_Uv;
@@ -12,11 +19,19 @@ layout(set = 0, binding = 0) uniform Camera {
     mat4 ViewProj;
 };
 
+// instanced pipelines don't read the Transform uniform, but still declare it so their bind groups match non-instanced pipelines
 layout(set = 2, binding = 0) uniform Transform {
+# ifdef INSTANCING
+    mat4 EntityModel;
+# else
     mat4 Model;
+# endif
 };
 
 void main() {
+# ifdef INSTANCING
+    mat4 Model = mat4(I_Model_0, I_Model_1, I_Model_2, I_Model_3);
+# endif
     v_Normal = mat3(Model) * Vertex_Normal;
     v_Position = (Model * vec4(Vertex_Position, 1.0)).xyz;
     v_Uv = Vertex_Uv;
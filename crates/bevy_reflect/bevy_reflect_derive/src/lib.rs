@@ -15,13 +15,14 @@ use syn::{
     parse_macro_input,
     punctuated::Punctuated,
     token::{Comma, Paren, Where},
-    Data, DataStruct, DeriveInput, Field, Fields, Generics, Ident, Index, Member, Meta, NestedMeta,
-    Path,
+    Attribute, Data, DataEnum, DataStruct, DeriveInput, Expr, Field, Fields, Generics, Ident,
+    Index, Lit, Member, Meta, MetaNameValue, NestedMeta, Path, Variant,
 };
 
 #[derive(Default)]
 struct PropAttributeArgs {
     pub ignore: Option<bool>,
+    pub range: Option<(Expr, Expr)>,
 }
 
 #[derive(Clone)]
@@ -41,6 +42,7 @@ enum DeriveType {
     Struct,
     TupleStruct,
     UnitStruct,
+    Enum,
     Value,
 }
 
@@ -51,6 +53,7 @@ static REFLECT_VALUE_ATTRIBUTE_NAME: &str = "reflect_value";
 pub fn derive_reflect(input: TokenStream) -> TokenStream {
     let ast = parse_macro_input!(input as DeriveInput);
     let unit_struct_punctuated = Punctuated::new();
+    let mut enum_variants = None;
     let (fields, mut derive_type) = match &ast.data {
         Data::Struct(DataStruct {
             fields: Fields::Named(fields),
@@ -64,47 +67,14 @@ pub fn derive_reflect(input: TokenStream) -> TokenStream {
             fields: Fields::Unit,
             ..
         }) => (&unit_struct_punctuated, DeriveType::UnitStruct),
+        Data::Enum(DataEnum { variants, .. }) => {
+            enum_variants = Some(variants);
+            (&unit_struct_punctuated, DeriveType::Enum)
+        }
         _ => (&unit_struct_punctuated, DeriveType::Value),
     };
 
-    let fields_and_args = fields
-        .iter()
-        .enumerate()
-        .map(|(i, f)| {
-            (
-                f,
-                f.attrs
-                    .iter()
-                    .find(|a| *a.path.get_ident().as_ref().unwrap() == REFLECT_ATTRIBUTE_NAME)
-                    .map(|a| {
-                        syn::custom_keyword!(ignore);
-                        let mut attribute_args = PropAttributeArgs { ignore: None };
-                        a.parse_args_with(|input: ParseStream| {
-                            if input.parse::<Option<ignore>>()?.is_some() {
-                                attribute_args.ignore = Some(true);
-                                return Ok(());
-                            }
-                            Ok(())
-                        })
-                        .expect("Invalid 'property' attribute format.");
-
-                        attribute_args
-                    }),
-                i,
-            )
-        })
-        .collect::<Vec<(&Field, Option<PropAttributeArgs>, usize)>>();
-    let active_fields = fields_and_args
-        .iter()
-        .filter(|(_field, attrs, _i)| {
-            attrs.is_none()
-                || match attrs.as_ref().unwrap().ignore {
-                    Some(ignore) => !ignore,
-                    None => true,
-                }
-        })
-        .map(|(f, _attr, i)| (*f, *i))
-        .collect::<Vec<(&Field, usize)>>();
+    let active_fields = get_active_fields(fields);
 
     let modules = get_modules();
     let bevy_reflect_path = get_path(&modules.bevy_reflect);
@@ -128,13 +98,10 @@ pub fn derive_reflect(input: TokenStream) -> TokenStream {
         }
     }
 
-    let registration_data = &reflect_attrs.data;
-    let get_type_registration_impl = impl_get_type_registration(
-        type_name,
-        &bevy_reflect_path,
-        registration_data,
-        &ast.generics,
-    );
+    let get_type_registration_impl =
+        impl_get_type_registration(type_name, &bevy_reflect_path, &reflect_attrs, &ast.generics);
+
+    let docs = get_docs(&ast.attrs);
 
     match derive_type {
         DeriveType::Struct | DeriveType::UnitStruct => impl_struct(
@@ -143,6 +110,7 @@ pub fn derive_reflect(input: TokenStream) -> TokenStream {
             get_type_registration_impl,
             &bevy_reflect_path,
             &reflect_attrs,
+            docs,
             &active_fields,
         ),
         DeriveType::TupleStruct => impl_tuple_struct(
@@ -151,24 +119,149 @@ pub fn derive_reflect(input: TokenStream) -> TokenStream {
             get_type_registration_impl,
             &bevy_reflect_path,
             &reflect_attrs,
+            docs,
             &active_fields,
         ),
+        DeriveType::Enum => impl_enum(
+            type_name,
+            &ast.generics,
+            get_type_registration_impl,
+            &bevy_reflect_path,
+            &reflect_attrs,
+            docs,
+            enum_variants.unwrap(),
+        ),
         DeriveType::Value => impl_value(
             type_name,
             &ast.generics,
             get_type_registration_impl,
             &bevy_reflect_path,
             &reflect_attrs,
+            docs,
         ),
     }
 }
 
+fn get_field_attribute_args(field: &Field) -> PropAttributeArgs {
+    field
+        .attrs
+        .iter()
+        .find(|a| *a.path.get_ident().as_ref().unwrap() == REFLECT_ATTRIBUTE_NAME)
+        .map(|a| {
+            syn::custom_keyword!(ignore);
+            syn::custom_keyword!(range);
+            let mut attribute_args = PropAttributeArgs::default();
+            a.parse_args_with(|input: ParseStream| {
+                while !input.is_empty() {
+                    if input.parse::<Option<ignore>>()?.is_some() {
+                        attribute_args.ignore = Some(true);
+                    } else if input.parse::<Option<range>>()?.is_some() {
+                        let content;
+                        parenthesized!(content in input);
+                        let min = content.parse::<Expr>()?;
+                        content.parse::<Comma>()?;
+                        let max = content.parse::<Expr>()?;
+                        attribute_args.range = Some((min, max));
+                    } else {
+                        return Err(input.error("expected `ignore` or `range(min, max)`"));
+                    }
+                    if !input.is_empty() {
+                        input.parse::<Comma>()?;
+                    }
+                }
+                Ok(())
+            })
+            .expect("Invalid 'property' attribute format.");
+
+            attribute_args
+        })
+        .unwrap_or_default()
+}
+
+fn get_active_fields(fields: &Punctuated<Field, Comma>) -> Vec<(&Field, usize)> {
+    fields
+        .iter()
+        .enumerate()
+        .filter(|(_i, field)| get_field_attribute_args(field).ignore != Some(true))
+        .map(|(i, field)| (field, i))
+        .collect::<Vec<(&Field, usize)>>()
+}
+
+fn impl_typed(
+    type_name: &Ident,
+    generics: &Generics,
+    bevy_reflect_path: &Path,
+    docs: proc_macro2::TokenStream,
+    kind: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    quote! {
+        impl #impl_generics #bevy_reflect_path::Typed for #type_name#ty_generics #where_clause {
+            fn type_info() -> #bevy_reflect_path::TypeInfo {
+                #bevy_reflect_path::TypeInfo::new::<Self>(#kind).with_docs(#docs)
+            }
+        }
+    }
+}
+
+fn get_field_infos(
+    active_fields: &[(&Field, usize)],
+    bevy_reflect_path: &Path,
+) -> Vec<proc_macro2::TokenStream> {
+    active_fields
+        .iter()
+        .enumerate()
+        .map(|(index, (field, _field_index))| {
+            let ty = &field.ty;
+            let docs = get_docs(&field.attrs);
+            let field_info = match &field.ident {
+                Some(ident) => {
+                    let name = ident.to_string();
+                    quote!(#bevy_reflect_path::FieldInfo::named::<#ty>(#index, #name).with_docs(#docs))
+                }
+                None => {
+                    quote!(#bevy_reflect_path::FieldInfo::unnamed::<#ty>(#index).with_docs(#docs))
+                }
+            };
+            match get_field_attribute_args(field).range {
+                Some((min, max)) => quote!(#field_info.with_range((#min) as f64, (#max) as f64)),
+                None => field_info,
+            }
+        })
+        .collect()
+}
+
+/// Collects the `///` doc comments of an item into `Some("...")`, or `None` if it has none
+fn get_docs(attrs: &[Attribute]) -> proc_macro2::TokenStream {
+    let lines = attrs
+        .iter()
+        .filter_map(|attr| match attr.parse_meta() {
+            Ok(Meta::NameValue(MetaNameValue {
+                path,
+                lit: Lit::Str(lit),
+                ..
+            })) if path.is_ident("doc") => {
+                let line = lit.value();
+                Some(line.strip_prefix(' ').map(str::to_string).unwrap_or(line))
+            }
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    if lines.is_empty() {
+        quote!(None)
+    } else {
+        let docs = lines.join("\n");
+        quote!(Some(#docs))
+    }
+}
+
 fn impl_struct(
     struct_name: &Ident,
     generics: &Generics,
     get_type_registration_impl: proc_macro2::TokenStream,
     bevy_reflect_path: &Path,
     reflect_attrs: &ReflectAttrs,
+    docs: proc_macro2::TokenStream,
     active_fields: &[(&Field, usize)],
 ) -> TokenStream {
     let field_names = active_fields
@@ -204,11 +297,24 @@ fn impl_struct(
         TraitImpl::Implemented | TraitImpl::Custom(_) => reflect_attrs.get_partial_eq_impl(),
     };
 
+    let field_infos = get_field_infos(active_fields, bevy_reflect_path);
+    let typed_impl = impl_typed(
+        struct_name,
+        generics,
+        bevy_reflect_path,
+        docs,
+        quote!(#bevy_reflect_path::TypeKind::Struct {
+            fields: vec![#(#field_infos),*],
+        }),
+    );
+
     let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
     TokenStream::from(quote! {
         #get_type_registration_impl
 
+        #typed_impl
+
         impl #impl_generics #bevy_reflect_path::Struct for #struct_name#ty_generics #where_clause {
             fn field(&self, name: &str) -> Option<&dyn #bevy_reflect_path::Reflect> {
                 match name {
@@ -328,6 +434,7 @@ fn impl_tuple_struct(
     get_type_registration_impl: proc_macro2::TokenStream,
     bevy_reflect_path: &Path,
     reflect_attrs: &ReflectAttrs,
+    docs: proc_macro2::TokenStream,
     active_fields: &[(&Field, usize)],
 ) -> TokenStream {
     let field_idents = active_fields
@@ -347,10 +454,23 @@ fn impl_tuple_struct(
         TraitImpl::Implemented | TraitImpl::Custom(_) => reflect_attrs.get_partial_eq_impl(),
     };
 
+    let field_infos = get_field_infos(active_fields, bevy_reflect_path);
+    let typed_impl = impl_typed(
+        struct_name,
+        generics,
+        bevy_reflect_path,
+        docs,
+        quote!(#bevy_reflect_path::TypeKind::TupleStruct {
+            fields: vec![#(#field_infos),*],
+        }),
+    );
+
     let (impl_generics, ty_generics, _where_clause) = generics.split_for_impl();
     TokenStream::from(quote! {
         #get_type_registration_impl
 
+        #typed_impl
+
         impl #impl_generics #bevy_reflect_path::TupleStruct for #struct_name#ty_generics {
             fn field(&self, index: usize) -> Option<&dyn #bevy_reflect_path::Reflect> {
                 match index {
@@ -376,6 +496,7 @@ fn impl_tuple_struct(
 
             fn clone_dynamic(&self) -> #bevy_reflect_path::DynamicTupleStruct {
                 let mut dynamic = #bevy_reflect_path::DynamicTupleStruct::default();
+                dynamic.set_name(self.type_name().to_string());
                 #(dynamic.insert_boxed(self.#field_idents.clone_value());)*
                 dynamic
             }
@@ -441,21 +562,417 @@ fn impl_tuple_struct(
     })
 }
 
+fn impl_enum(
+    enum_name: &Ident,
+    generics: &Generics,
+    get_type_registration_impl: proc_macro2::TokenStream,
+    bevy_reflect_path: &Path,
+    reflect_attrs: &ReflectAttrs,
+    docs: proc_macro2::TokenStream,
+    variants: &Punctuated<Variant, Comma>,
+) -> TokenStream {
+    let mut variant_names = Vec::new();
+    let mut variant_indices = Vec::new();
+    let mut variant_types = Vec::new();
+    let mut variant_patterns = Vec::new();
+    let mut field_arms = Vec::new();
+    let mut field_at_arms = Vec::new();
+    let mut name_at_arms = Vec::new();
+    let mut field_counts = Vec::new();
+    let mut clone_arms = Vec::new();
+    let mut constructors = Vec::new();
+    let mut required_constructors = Vec::new();
+    let mut variant_infos = Vec::new();
+    for (variant_index, variant) in variants.iter().enumerate() {
+        let variant_ident = &variant.ident;
+        let variant_name = variant_ident.to_string();
+        let (fields, variant_type) = match &variant.fields {
+            Fields::Named(fields) => (Some(&fields.named), quote!(Struct)),
+            Fields::Unnamed(fields) => (Some(&fields.unnamed), quote!(Tuple)),
+            Fields::Unit => (None, quote!(Unit)),
+        };
+        let active_fields = fields.map(get_active_fields).unwrap_or_default();
+        let field_bindings = active_fields
+            .iter()
+            .map(|(_field, index)| Ident::new(&format!("__field{}", index), Span::call_site()))
+            .collect::<Vec<_>>();
+        let field_indices = (0..active_fields.len()).collect::<Vec<usize>>();
+        let field_count = active_fields.len();
+
+        let (pattern, field_arm, name_at_arm, clone_arm, constructor, required_constructor) =
+            match &variant.fields {
+                Fields::Named(_) => {
+                    let field_idents = active_fields
+                        .iter()
+                        .map(|(field, _index)| field.ident.clone().unwrap())
+                        .collect::<Vec<_>>();
+                    let field_names = field_idents
+                        .iter()
+                        .map(|ident| ident.to_string())
+                        .collect::<Vec<_>>();
+                    let field_values = |require_fields: bool| {
+                        fields
+                            .unwrap()
+                            .iter()
+                            .map(|field| {
+                                let ident = field.ident.as_ref().unwrap();
+                                let name = ident.to_string();
+                                if field_names.contains(&name) {
+                                    let value = variant_field_value(
+                                        bevy_reflect_path,
+                                        &field.ty,
+                                        quote!(enum_value.field(#name)),
+                                        &variant_name,
+                                        &name,
+                                        require_fields,
+                                    );
+                                    quote!(#ident: #value)
+                                } else {
+                                    quote!(#ident: Default::default())
+                                }
+                            })
+                            .collect::<Vec<_>>()
+                    };
+                    let (field_values, required_field_values) =
+                        (field_values(false), field_values(true));
+                    (
+                        quote!(Self::#variant_ident { #(#field_idents: #field_bindings,)* .. }),
+                        quote!(match name {
+                            #(#field_names => Some(#field_bindings),)*
+                            _ => None,
+                        }),
+                        quote!(match index {
+                            #(#field_indices => Some(#field_names),)*
+                            _ => None,
+                        }),
+                        quote!(#(dynamic.insert_boxed(#field_names, #field_bindings.clone_value());)*),
+                        quote!(Self::#variant_ident { #(#field_values,)* }),
+                        quote!(Self::#variant_ident { #(#required_field_values,)* }),
+                    )
+                }
+                Fields::Unnamed(unnamed) => {
+                    let patterns = (0..unnamed.unnamed.len())
+                        .map(|index| {
+                            match active_fields.iter().position(|(_field, i)| *i == index) {
+                                Some(active_index) => {
+                                    let binding = &field_bindings[active_index];
+                                    quote!(#binding)
+                                }
+                                None => quote!(_),
+                            }
+                        })
+                        .collect::<Vec<_>>();
+                    let field_values = |require_fields: bool| {
+                        unnamed
+                            .unnamed
+                            .iter()
+                            .enumerate()
+                            .map(|(index, field)| {
+                                match active_fields.iter().position(|(_field, i)| *i == index) {
+                                    Some(active_index) => variant_field_value(
+                                        bevy_reflect_path,
+                                        &field.ty,
+                                        quote!(enum_value.field_at(#active_index)),
+                                        &variant_name,
+                                        &active_index.to_string(),
+                                        require_fields,
+                                    ),
+                                    None => quote!(Default::default()),
+                                }
+                            })
+                            .collect::<Vec<_>>()
+                    };
+                    let (field_values, required_field_values) =
+                        (field_values(false), field_values(true));
+                    (
+                        quote!(Self::#variant_ident(#(#patterns),*)),
+                        quote!(None),
+                        quote!(None),
+                        quote!(#(dynamic.push_boxed(#field_bindings.clone_value());)*),
+                        quote!(Self::#variant_ident(#(#field_values),*)),
+                        quote!(Self::#variant_ident(#(#required_field_values),*)),
+                    )
+                }
+                Fields::Unit => (
+                    quote!(Self::#variant_ident),
+                    quote!(None),
+                    quote!(None),
+                    quote!(),
+                    quote!(Self::#variant_ident),
+                    quote!(Self::#variant_ident),
+                ),
+            };
+
+        field_at_arms.push(quote! {
+            #pattern => match index {
+                #(#field_indices => Some(#field_bindings),)*
+                _ => None,
+            }
+        });
+        field_arms.push(quote!(#pattern => #field_arm));
+        name_at_arms.push(quote!(Self::#variant_ident { .. } => #name_at_arm));
+        clone_arms.push(quote!(#pattern => { #clone_arm }));
+        constructors.push(quote!(#variant_name => #constructor));
+        required_constructors.push(quote!(#variant_name => #required_constructor));
+        let field_infos = get_field_infos(&active_fields, bevy_reflect_path);
+        let variant_docs = get_docs(&variant.attrs);
+        variant_infos.push(quote! {
+            #bevy_reflect_path::VariantInfo::new(
+                #variant_name,
+                #bevy_reflect_path::VariantType::#variant_type,
+                vec![#(#field_infos),*],
+            )
+            .with_docs(#variant_docs)
+        });
+        field_counts.push(field_count);
+        variant_names.push(variant_name);
+        variant_indices.push(variant_index);
+        variant_types.push(variant_type);
+        variant_patterns.push(quote!(Self::#variant_ident { .. }));
+    }
+
+    let hash_fn = reflect_attrs.get_hash_impl(&bevy_reflect_path);
+    let serialize_fn = reflect_attrs.get_serialize_impl(&bevy_reflect_path);
+    let partial_eq_fn = match reflect_attrs.reflect_partial_eq {
+        TraitImpl::NotImplemented => quote! {
+            #bevy_reflect_path::enum_partial_eq(self, value)
+        },
+        TraitImpl::Implemented | TraitImpl::Custom(_) => reflect_attrs.get_partial_eq_impl(),
+    };
+
+    let typed_impl = impl_typed(
+        enum_name,
+        generics,
+        bevy_reflect_path,
+        docs,
+        quote!(#bevy_reflect_path::TypeKind::Enum {
+            variants: vec![#(#variant_infos),*],
+        }),
+    );
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    TokenStream::from(quote! {
+        #get_type_registration_impl
+
+        #typed_impl
+
+        impl #impl_generics #bevy_reflect_path::Enum for #enum_name#ty_generics #where_clause {
+            fn variant_name(&self) -> &str {
+                match self {
+                    #(#variant_patterns => #variant_names,)*
+                }
+            }
+
+            fn variant_index(&self) -> usize {
+                match self {
+                    #(#variant_patterns => #variant_indices,)*
+                }
+            }
+
+            fn variant_type(&self) -> #bevy_reflect_path::VariantType {
+                match self {
+                    #(#variant_patterns => #bevy_reflect_path::VariantType::#variant_types,)*
+                }
+            }
+
+            #[allow(unused_variables)]
+            fn field(&self, name: &str) -> Option<&dyn #bevy_reflect_path::Reflect> {
+                match self {
+                    #(#field_arms,)*
+                }
+            }
+
+            #[allow(unused_variables)]
+            fn field_mut(&mut self, name: &str) -> Option<&mut dyn #bevy_reflect_path::Reflect> {
+                match self {
+                    #(#field_arms,)*
+                }
+            }
+
+            fn field_at(&self, index: usize) -> Option<&dyn #bevy_reflect_path::Reflect> {
+                match self {
+                    #(#field_at_arms,)*
+                }
+            }
+
+            fn field_at_mut(&mut self, index: usize) -> Option<&mut dyn #bevy_reflect_path::Reflect> {
+                match self {
+                    #(#field_at_arms,)*
+                }
+            }
+
+            #[allow(unused_variables)]
+            fn name_at(&self, index: usize) -> Option<&str> {
+                match self {
+                    #(#name_at_arms,)*
+                }
+            }
+
+            fn field_len(&self) -> usize {
+                match self {
+                    #(#variant_patterns => #field_counts,)*
+                }
+            }
+
+            fn iter_fields(&self) -> #bevy_reflect_path::VariantFieldIter {
+                #bevy_reflect_path::VariantFieldIter::new(self)
+            }
+
+            #[allow(unused_variables)]
+            fn set_variant_from(&mut self, enum_value: &dyn #bevy_reflect_path::Enum) -> Result<(), #bevy_reflect_path::EnumVariantError> {
+                *self = match enum_value.variant_name() {
+                    #(#required_constructors,)*
+                    name => return Err(#bevy_reflect_path::EnumVariantError::InvalidVariant {
+                        enum_name: std::any::type_name::<Self>().to_string(),
+                        variant: name.to_string(),
+                    }),
+                };
+                Ok(())
+            }
+
+            fn clone_dynamic(&self) -> #bevy_reflect_path::DynamicEnum {
+                use #bevy_reflect_path::Enum;
+                let mut dynamic = #bevy_reflect_path::DynamicEnum::default();
+                dynamic.set_name(self.type_name().to_string());
+                dynamic.set_variant(self.variant_name().to_string(), self.variant_type());
+                dynamic.set_variant_index(self.variant_index());
+                match self {
+                    #(#clone_arms)*
+                }
+                dynamic
+            }
+        }
+
+        impl #impl_generics #bevy_reflect_path::Reflect for #enum_name#ty_generics #where_clause {
+            #[inline]
+            fn type_name(&self) -> &str {
+                std::any::type_name::<Self>()
+            }
+
+            #[inline]
+            fn any(&self) -> &dyn std::any::Any {
+                self
+            }
+            #[inline]
+            fn any_mut(&mut self) -> &mut dyn std::any::Any {
+                self
+            }
+            #[inline]
+            fn clone_value(&self) -> Box<dyn #bevy_reflect_path::Reflect> {
+                use #bevy_reflect_path::Enum;
+                Box::new(self.clone_dynamic())
+            }
+            #[inline]
+            fn set(&mut self, value: Box<dyn #bevy_reflect_path::Reflect>) -> Result<(), Box<dyn #bevy_reflect_path::Reflect>> {
+                *self = value.take()?;
+                Ok(())
+            }
+
+            #[inline]
+            fn apply(&mut self, value: &dyn #bevy_reflect_path::Reflect) {
+                use #bevy_reflect_path::Enum;
+                if let #bevy_reflect_path::ReflectRef::Enum(enum_value) = value.reflect_ref() {
+                    if self.variant_name() == enum_value.variant_name() {
+                        for (i, value) in enum_value.iter_fields().enumerate() {
+                            let field = match enum_value.name_at(i) {
+                                Some(name) => self.field_mut(name),
+                                None => self.field_at_mut(i),
+                            };
+                            if let Some(v) = field {
+                                v.apply(value);
+                            }
+                        }
+                    } else {
+                        // like missing struct fields, missing variant fields are left at their default values
+                        *self = match enum_value.variant_name() {
+                            #(#constructors,)*
+                            name => panic!("Variant {} does not exist on {}.", name, std::any::type_name::<Self>()),
+                        };
+                    }
+                } else {
+                    panic!("Attempted to apply non-enum type to enum type.");
+                }
+            }
+
+            fn reflect_ref(&self) -> #bevy_reflect_path::ReflectRef {
+                #bevy_reflect_path::ReflectRef::Enum(self)
+            }
+
+            fn reflect_mut(&mut self) -> #bevy_reflect_path::ReflectMut {
+                #bevy_reflect_path::ReflectMut::Enum(self)
+            }
+
+            fn serializable(&self) -> Option<#bevy_reflect_path::serde::Serializable> {
+                #serialize_fn
+            }
+
+            fn reflect_hash(&self) -> Option<u64> {
+                #hash_fn
+            }
+
+            fn reflect_partial_eq(&self, value: &dyn #bevy_reflect_path::Reflect) -> Option<bool> {
+                #partial_eq_fn
+            }
+        }
+    })
+}
+
+/// Builds a field of a variant that is being switched to from its default value, with the reflected field applied
+/// onto it like fields of structs are. This also works for fields whose reflected values are dynamic proxies.
+fn variant_field_value(
+    bevy_reflect_path: &Path,
+    ty: &syn::Type,
+    field: proc_macro2::TokenStream,
+    variant_name: &str,
+    field_name: &str,
+    require_fields: bool,
+) -> proc_macro2::TokenStream {
+    let on_missing = if require_fields {
+        quote! {
+            return Err(#bevy_reflect_path::EnumVariantError::InvalidField {
+                variant: #variant_name.to_string(),
+                field: #field_name.to_string(),
+            })
+        }
+    } else {
+        quote!({})
+    };
+    quote! {{
+        let mut value: #ty = Default::default();
+        match #field {
+            Some(field) => #bevy_reflect_path::Reflect::apply(&mut value, field),
+            None => #on_missing,
+        }
+        value
+    }}
+}
+
 fn impl_value(
     type_name: &Ident,
     generics: &Generics,
     get_type_registration_impl: proc_macro2::TokenStream,
     bevy_reflect_path: &Path,
     reflect_attrs: &ReflectAttrs,
+    docs: proc_macro2::TokenStream,
 ) -> TokenStream {
     let hash_fn = reflect_attrs.get_hash_impl(&bevy_reflect_path);
     let partial_eq_fn = reflect_attrs.get_partial_eq_impl();
     let serialize_fn = reflect_attrs.get_serialize_impl(&bevy_reflect_path);
+    let typed_impl = impl_typed(
+        type_name,
+        generics,
+        bevy_reflect_path,
+        docs,
+        quote!(#bevy_reflect_path::TypeKind::Value),
+    );
 
     let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
     TokenStream::from(quote! {
         #get_type_registration_impl
 
+        #typed_impl
+
         impl #impl_generics #bevy_reflect_path::Reflect for #type_name#ty_generics #where_clause  {
             #[inline]
             fn type_name(&self) -> &str {
@@ -567,11 +1084,10 @@ pub fn impl_reflect_value(input: TokenStream) -> TokenStream {
     let reflect_attrs = reflect_value_def
         .attrs
         .unwrap_or_else(ReflectAttrs::default);
-    let registration_data = &reflect_attrs.data;
     let get_type_registration_impl = impl_get_type_registration(
         ty,
         &bevy_reflect_path,
-        registration_data,
+        &reflect_attrs,
         &reflect_value_def.generics,
     );
     impl_value(
@@ -580,6 +1096,7 @@ pub fn impl_reflect_value(input: TokenStream) -> TokenStream {
         get_type_registration_impl,
         &bevy_reflect_path,
         &reflect_attrs,
+        quote!(None),
     )
 }
 
@@ -588,6 +1105,7 @@ struct ReflectAttrs {
     reflect_hash: TraitImpl,
     reflect_partial_eq: TraitImpl,
     serialize: TraitImpl,
+    skip_serializing: bool,
     data: Vec<Ident>,
 }
 
@@ -605,6 +1123,7 @@ impl ReflectAttrs {
                                 "PartialEq" => attrs.reflect_partial_eq = TraitImpl::Implemented,
                                 "Hash" => attrs.reflect_hash = TraitImpl::Implemented,
                                 "Serialize" => attrs.serialize = TraitImpl::Implemented,
+                                "skip_serializing" => attrs.skip_serializing = true,
                                 _ => attrs.data.push(Ident::new(
                                     &format!("Reflect{}", segment.ident),
                                     Span::call_site(),
@@ -718,16 +1237,23 @@ impl Parse for ReflectAttrs {
 fn impl_get_type_registration(
     type_name: &Ident,
     bevy_reflect_path: &Path,
-    registration_data: &[Ident],
+    reflect_attrs: &ReflectAttrs,
     generics: &Generics,
 ) -> proc_macro2::TokenStream {
     let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let registration_data = &reflect_attrs.data;
+    let skip_serializing = if reflect_attrs.skip_serializing {
+        quote!(registration.insert(#bevy_reflect_path::SkipSerializing);)
+    } else {
+        quote!()
+    };
     quote! {
         #[allow(unused_mut)]
         impl #impl_generics #bevy_reflect_path::GetTypeRegistration for #type_name#ty_generics #where_clause {
             fn get_type_registration() -> #bevy_reflect_path::TypeRegistration {
-                let mut registration = #bevy_reflect_path::TypeRegistration::of::<#type_name#ty_generics>();
+                let mut registration = #bevy_reflect_path::TypeRegistration::of_typed::<#type_name#ty_generics>();
                 #(registration.insert::<#registration_data>(#bevy_reflect_path::FromType::<#type_name#ty_generics>::from_type());)*
+                #skip_serializing
                 registration
             }
         }
@@ -0,0 +1,273 @@
+use crate::{serde::Serializable, Reflect, ReflectMut, ReflectRef, TypeInfo, TypeKind, Typed};
+use std::any::Any;
+
+/// A fixed-size sequence of [Reflect] items. This corresponds to rust arrays like `[u8; 16]`.
+///
+/// [Reflect] and [Array] are only implemented for arrays with lengths from 0 to 32 (the same lengths std implements
+/// its traits for). Longer arrays can be wrapped in a type that implements [Reflect] itself, or stored in a [Vec].
+pub trait Array: Reflect {
+    fn get(&self, index: usize) -> Option<&dyn Reflect>;
+    fn get_mut(&mut self, index: usize) -> Option<&mut dyn Reflect>;
+    fn len(&self) -> usize;
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    fn iter(&self) -> ArrayIter;
+    fn clone_dynamic(&self) -> DynamicArray {
+        DynamicArray {
+            name: self.type_name().to_string(),
+            values: self.iter().map(|value| value.clone_value()).collect(),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct DynamicArray {
+    name: String,
+    values: Vec<Box<dyn Reflect>>,
+}
+
+impl DynamicArray {
+    pub fn new(values: Vec<Box<dyn Reflect>>) -> Self {
+        DynamicArray {
+            name: String::default(),
+            values,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn set_name(&mut self, name: String) {
+        self.name = name;
+    }
+}
+
+impl Array for DynamicArray {
+    fn get(&self, index: usize) -> Option<&dyn Reflect> {
+        self.values.get(index).map(|value| &**value)
+    }
+
+    fn get_mut(&mut self, index: usize) -> Option<&mut dyn Reflect> {
+        self.values.get_mut(index).map(|value| &mut **value)
+    }
+
+    fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    fn iter(&self) -> ArrayIter {
+        ArrayIter {
+            array: self,
+            index: 0,
+        }
+    }
+
+    fn clone_dynamic(&self) -> DynamicArray {
+        DynamicArray {
+            name: self.name.clone(),
+            values: self
+                .values
+                .iter()
+                .map(|value| value.clone_value())
+                .collect(),
+        }
+    }
+}
+
+impl Reflect for DynamicArray {
+    #[inline]
+    fn type_name(&self) -> &str {
+        &self.name
+    }
+
+    #[inline]
+    fn any(&self) -> &dyn Any {
+        self
+    }
+
+    #[inline]
+    fn any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn apply(&mut self, value: &dyn Reflect) {
+        array_apply(self, value);
+    }
+
+    #[inline]
+    fn set(&mut self, value: Box<dyn Reflect>) -> Result<(), Box<dyn Reflect>> {
+        *self = value.take()?;
+        Ok(())
+    }
+
+    #[inline]
+    fn reflect_ref(&self) -> ReflectRef {
+        ReflectRef::Array(self)
+    }
+
+    #[inline]
+    fn reflect_mut(&mut self) -> ReflectMut {
+        ReflectMut::Array(self)
+    }
+
+    #[inline]
+    fn clone_value(&self) -> Box<dyn Reflect> {
+        Box::new(self.clone_dynamic())
+    }
+
+    #[inline]
+    fn reflect_hash(&self) -> Option<u64> {
+        None
+    }
+
+    fn reflect_partial_eq(&self, value: &dyn Reflect) -> Option<bool> {
+        array_partial_eq(self, value)
+    }
+
+    fn serializable(&self) -> Option<Serializable> {
+        None
+    }
+}
+
+pub struct ArrayIter<'a> {
+    pub(crate) array: &'a dyn Array,
+    pub(crate) index: usize,
+}
+
+impl<'a> Iterator for ArrayIter<'a> {
+    type Item = &'a dyn Reflect;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let value = self.array.get(self.index);
+        self.index += 1;
+        value
+    }
+}
+
+/// Applies each item of `b` to the item of `a` at the same index. Like [tuple_apply], items of `b` past the end of `a`
+/// are ignored and items of `a` past the end of `b` are left as they are.
+#[inline]
+pub fn array_apply<A: Array>(a: &mut A, b: &dyn Reflect) {
+    if let ReflectRef::Array(array_value) = b.reflect_ref() {
+        for (i, value) in array_value.iter().enumerate() {
+            if let Some(v) = a.get_mut(i) {
+                v.apply(value);
+            }
+        }
+    } else {
+        panic!("Attempted to apply a non-array type to an array type.");
+    }
+}
+
+#[inline]
+pub fn array_partial_eq<A: Array>(a: &A, b: &dyn Reflect) -> Option<bool> {
+    let array = if let ReflectRef::Array(array) = b.reflect_ref() {
+        array
+    } else {
+        return Some(false);
+    };
+
+    if a.len() != array.len() {
+        return Some(false);
+    }
+
+    for (a_value, b_value) in a.iter().zip(array.iter()) {
+        if let Some(false) | None = a_value.reflect_partial_eq(b_value) {
+            return Some(false);
+        }
+    }
+
+    Some(true)
+}
+
+macro_rules! impl_reflect_array {
+    ($($len:expr),*) => {
+        $(
+            impl<T: Reflect> Array for [T; $len] {
+                #[inline]
+                fn get(&self, index: usize) -> Option<&dyn Reflect> {
+                    <[T]>::get(self, index).map(|value| value as &dyn Reflect)
+                }
+
+                #[inline]
+                fn get_mut(&mut self, index: usize) -> Option<&mut dyn Reflect> {
+                    <[T]>::get_mut(self, index).map(|value| value as &mut dyn Reflect)
+                }
+
+                #[inline]
+                fn len(&self) -> usize {
+                    $len
+                }
+
+                #[inline]
+                fn iter(&self) -> ArrayIter {
+                    ArrayIter {
+                        array: self,
+                        index: 0,
+                    }
+                }
+            }
+
+            impl<T: Reflect> Reflect for [T; $len] {
+                fn type_name(&self) -> &str {
+                    std::any::type_name::<Self>()
+                }
+
+                fn any(&self) -> &dyn Any {
+                    self
+                }
+
+                fn any_mut(&mut self) -> &mut dyn Any {
+                    self
+                }
+
+                fn apply(&mut self, value: &dyn Reflect) {
+                    array_apply(self, value);
+                }
+
+                fn set(&mut self, value: Box<dyn Reflect>) -> Result<(), Box<dyn Reflect>> {
+                    *self = value.take()?;
+                    Ok(())
+                }
+
+                fn reflect_ref(&self) -> ReflectRef {
+                    ReflectRef::Array(self)
+                }
+
+                fn reflect_mut(&mut self) -> ReflectMut {
+                    ReflectMut::Array(self)
+                }
+
+                fn clone_value(&self) -> Box<dyn Reflect> {
+                    Box::new(self.clone_dynamic())
+                }
+
+                fn reflect_hash(&self) -> Option<u64> {
+                    None
+                }
+
+                fn reflect_partial_eq(&self, value: &dyn Reflect) -> Option<bool> {
+                    array_partial_eq(self, value)
+                }
+
+                fn serializable(&self) -> Option<Serializable> {
+                    None
+                }
+            }
+
+            impl<T: Reflect> Typed for [T; $len] {
+                fn type_info() -> TypeInfo {
+                    TypeInfo::new::<Self>(TypeKind::array::<T>($len))
+                }
+            }
+        )*
+    };
+}
+
+// matches the array lengths that std implements its traits for. keep the `Array` docs in sync when changing this
+impl_reflect_array!(
+    0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25,
+    26, 27, 28, 29, 30, 31, 32
+);
@@ -0,0 +1,320 @@
+use crate::{serde::Serializable, Reflect, ReflectMut, ReflectRef};
+use std::{any::Any, borrow::Cow};
+use thiserror::Error;
+
+/// The shape of an enum variant
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VariantType {
+    /// A variant without fields, like `Mode::Idle`
+    Unit,
+    /// A variant with unnamed fields, like `Mode::Wait(f32)`
+    Tuple,
+    /// A variant with named fields, like `Mode::Chase { target: Entity }`
+    Struct,
+}
+
+/// A rust "enum" reflection. Fields are those of the currently active variant.
+///
+/// Derived enums need the field types of their variants to implement [Default], which fields start out as when the
+/// enum switches to their variant.
+pub trait Enum: Reflect {
+    fn variant_name(&self) -> &str;
+    fn variant_index(&self) -> usize;
+    fn variant_type(&self) -> VariantType;
+    /// Returns the field with the given name. Only struct variants have named fields.
+    fn field(&self, name: &str) -> Option<&dyn Reflect>;
+    fn field_mut(&mut self, name: &str) -> Option<&mut dyn Reflect>;
+    fn field_at(&self, index: usize) -> Option<&dyn Reflect>;
+    fn field_at_mut(&mut self, index: usize) -> Option<&mut dyn Reflect>;
+    /// Returns the name of the field at the given index. Tuple variant fields don't have names.
+    fn name_at(&self, index: usize) -> Option<&str>;
+    fn field_len(&self) -> usize;
+    fn iter_fields(&self) -> VariantFieldIter;
+    /// Switches to the variant of `value`. Each field of the variant starts out as its default value, and the field of
+    /// `value` is applied onto it. Unlike [Reflect::apply], which leaves fields that `value` doesn't have at their
+    /// default values, this returns an error for them.
+    fn set_variant_from(&mut self, value: &dyn Enum) -> Result<(), EnumVariantError>;
+    fn clone_dynamic(&self) -> DynamicEnum;
+}
+
+/// An error returned when an enum can't switch to another variant
+#[derive(Debug, PartialEq, Eq, Error)]
+pub enum EnumVariantError {
+    #[error("the variant {variant} does not exist on {enum_name}")]
+    InvalidVariant { enum_name: String, variant: String },
+    #[error("the field {field} of variant {variant} is missing or has the wrong type")]
+    InvalidField { variant: String, field: String },
+}
+
+pub struct VariantFieldIter<'a> {
+    pub(crate) enum_value: &'a dyn Enum,
+    pub(crate) index: usize,
+}
+
+impl<'a> VariantFieldIter<'a> {
+    pub fn new(value: &'a dyn Enum) -> Self {
+        VariantFieldIter {
+            enum_value: value,
+            index: 0,
+        }
+    }
+}
+
+impl<'a> Iterator for VariantFieldIter<'a> {
+    type Item = &'a dyn Reflect;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let value = self.enum_value.field_at(self.index);
+        self.index += 1;
+        value
+    }
+}
+
+/// A dynamic enum value. Applying it to an enum of a different variant switches the variant.
+///
+/// The variant index is only meaningful when the value was created with [Enum::clone_dynamic].
+/// Deserialized values identify their variant by name.
+pub struct DynamicEnum {
+    name: String,
+    variant_name: String,
+    variant_index: usize,
+    variant_type: VariantType,
+    fields: Vec<Box<dyn Reflect>>,
+    field_names: Vec<Cow<'static, str>>,
+}
+
+impl Default for DynamicEnum {
+    fn default() -> Self {
+        DynamicEnum {
+            name: String::default(),
+            variant_name: String::default(),
+            variant_index: 0,
+            variant_type: VariantType::Unit,
+            fields: Vec::new(),
+            field_names: Vec::new(),
+        }
+    }
+}
+
+impl DynamicEnum {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn set_name(&mut self, name: String) {
+        self.name = name;
+    }
+
+    /// Switches to the given variant and removes all fields
+    pub fn set_variant(&mut self, variant_name: String, variant_type: VariantType) {
+        self.variant_name = variant_name;
+        self.variant_type = variant_type;
+        self.fields.clear();
+        self.field_names.clear();
+    }
+
+    pub fn set_variant_index(&mut self, variant_index: usize) {
+        self.variant_index = variant_index;
+    }
+
+    /// Adds a named field to a struct variant
+    pub fn insert_boxed(&mut self, name: &str, value: Box<dyn Reflect>) {
+        if let Some(index) = self.field_names.iter().position(|field| field == name) {
+            self.fields[index] = value;
+        } else {
+            self.fields.push(value);
+            self.field_names.push(Cow::Owned(name.to_string()));
+        }
+    }
+
+    pub fn insert<T: Reflect>(&mut self, name: &str, value: T) {
+        self.insert_boxed(name, Box::new(value));
+    }
+
+    /// Adds an unnamed field to a tuple variant
+    pub fn push_boxed(&mut self, value: Box<dyn Reflect>) {
+        self.fields.push(value);
+    }
+
+    pub fn push<T: Reflect>(&mut self, value: T) {
+        self.push_boxed(Box::new(value));
+    }
+}
+
+impl Enum for DynamicEnum {
+    #[inline]
+    fn variant_name(&self) -> &str {
+        &self.variant_name
+    }
+
+    #[inline]
+    fn variant_index(&self) -> usize {
+        self.variant_index
+    }
+
+    #[inline]
+    fn variant_type(&self) -> VariantType {
+        self.variant_type
+    }
+
+    fn field(&self, name: &str) -> Option<&dyn Reflect> {
+        self.field_names
+            .iter()
+            .position(|field| field == name)
+            .map(|index| &*self.fields[index])
+    }
+
+    fn field_mut(&mut self, name: &str) -> Option<&mut dyn Reflect> {
+        if let Some(index) = self.field_names.iter().position(|field| field == name) {
+            Some(&mut *self.fields[index])
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    fn field_at(&self, index: usize) -> Option<&dyn Reflect> {
+        self.fields.get(index).map(|value| &**value)
+    }
+
+    #[inline]
+    fn field_at_mut(&mut self, index: usize) -> Option<&mut dyn Reflect> {
+        self.fields.get_mut(index).map(|value| &mut **value)
+    }
+
+    #[inline]
+    fn name_at(&self, index: usize) -> Option<&str> {
+        self.field_names.get(index).map(|name| name.as_ref())
+    }
+
+    #[inline]
+    fn field_len(&self) -> usize {
+        self.fields.len()
+    }
+
+    #[inline]
+    fn iter_fields(&self) -> VariantFieldIter {
+        VariantFieldIter {
+            enum_value: self,
+            index: 0,
+        }
+    }
+
+    fn set_variant_from(&mut self, value: &dyn Enum) -> Result<(), EnumVariantError> {
+        *self = value.clone_dynamic();
+        Ok(())
+    }
+
+    fn clone_dynamic(&self) -> DynamicEnum {
+        DynamicEnum {
+            name: self.name.clone(),
+            variant_name: self.variant_name.clone(),
+            variant_index: self.variant_index,
+            variant_type: self.variant_type,
+            field_names: self.field_names.clone(),
+            fields: self
+                .fields
+                .iter()
+                .map(|value| value.clone_value())
+                .collect(),
+        }
+    }
+}
+
+impl Reflect for DynamicEnum {
+    #[inline]
+    fn type_name(&self) -> &str {
+        &self.name
+    }
+
+    #[inline]
+    fn any(&self) -> &dyn Any {
+        self
+    }
+
+    #[inline]
+    fn any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    #[inline]
+    fn clone_value(&self) -> Box<dyn Reflect> {
+        Box::new(self.clone_dynamic())
+    }
+
+    #[inline]
+    fn reflect_ref(&self) -> ReflectRef {
+        ReflectRef::Enum(self)
+    }
+
+    #[inline]
+    fn reflect_mut(&mut self) -> ReflectMut {
+        ReflectMut::Enum(self)
+    }
+
+    fn apply(&mut self, value: &dyn Reflect) {
+        if let ReflectRef::Enum(enum_value) = value.reflect_ref() {
+            if self.variant_name == enum_value.variant_name() {
+                for (i, value) in enum_value.iter_fields().enumerate() {
+                    let field = match enum_value.name_at(i) {
+                        Some(name) => self.field_mut(name),
+                        None => self.field_at_mut(i),
+                    };
+                    if let Some(v) = field {
+                        v.apply(value)
+                    }
+                }
+            } else {
+                *self = enum_value.clone_dynamic();
+            }
+        } else {
+            panic!("Attempted to apply non-enum type to enum type.");
+        }
+    }
+
+    fn set(&mut self, value: Box<dyn Reflect>) -> Result<(), Box<dyn Reflect>> {
+        *self = value.take()?;
+        Ok(())
+    }
+
+    fn reflect_hash(&self) -> Option<u64> {
+        None
+    }
+
+    fn reflect_partial_eq(&self, value: &dyn Reflect) -> Option<bool> {
+        enum_partial_eq(self, value)
+    }
+
+    fn serializable(&self) -> Option<Serializable> {
+        None
+    }
+}
+
+#[inline]
+pub fn enum_partial_eq<E: Enum>(a: &E, b: &dyn Reflect) -> Option<bool> {
+    let enum_value = if let ReflectRef::Enum(enum_value) = b.reflect_ref() {
+        enum_value
+    } else {
+        return Some(false);
+    };
+
+    if a.variant_name() != enum_value.variant_name() || a.field_len() != enum_value.field_len() {
+        return Some(false);
+    }
+
+    for (i, value) in enum_value.iter_fields().enumerate() {
+        let field_value = match enum_value.name_at(i) {
+            Some(name) => a.field(name),
+            None => a.field_at(i),
+        };
+        if let Some(field_value) = field_value {
+            if let Some(false) | None = field_value.reflect_partial_eq(value) {
+                return Some(false);
+            }
+        } else {
+            return Some(false);
+        }
+    }
+
+    Some(true)
+}
@@ -0,0 +1,422 @@
+use crate::Reflect;
+use std::{any::TypeId, sync::Arc};
+use thiserror::Error;
+
+/// The arguments of a [ReflectFunction] or [ReflectMethod] call. Each argument must be a value of the concrete
+/// argument type. [TypeRegistry::call_function](crate::TypeRegistry::call_function) and
+/// [TypeRegistry::call_method](crate::TypeRegistry::call_method) also accept dynamic proxies like
+/// [DynamicStruct](crate::DynamicStruct) for argument types registered with [ReflectDefault](crate::ReflectDefault).
+pub type Args = Vec<Box<dyn Reflect>>;
+
+pub type FunctionResult = Result<Box<dyn Reflect>, FunctionError>;
+
+type FunctionFn = dyn Fn(Args) -> FunctionResult + Send + Sync;
+type MethodFn = dyn Fn(&mut dyn Reflect, Args) -> FunctionResult + Send + Sync;
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum FunctionError {
+    #[error("there is no function or method named {name}")]
+    NotFound { name: String },
+    #[error("the type {type_name} is not registered")]
+    UnregisteredType { type_name: String },
+    #[error("expected {expected} arguments, but received {received}")]
+    ArgCount { expected: usize, received: usize },
+    #[error("expected argument {index} to be a {expected}, but received a {received}")]
+    ArgType {
+        index: usize,
+        expected: &'static str,
+        received: String,
+    },
+    #[error("expected the receiver to be a {expected}, but received a {received}")]
+    ReceiverType {
+        expected: &'static str,
+        received: String,
+    },
+}
+
+/// The type of an argument, receiver or return value
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ArgInfo {
+    pub type_id: TypeId,
+    pub type_name: &'static str,
+}
+
+impl ArgInfo {
+    pub fn of<T: Reflect>() -> Self {
+        ArgInfo {
+            type_id: TypeId::of::<T>(),
+            type_name: std::any::type_name::<T>(),
+        }
+    }
+}
+
+/// A free function that can be called by name with reflected arguments. Created from any `Fn` with up to 8 [Reflect]
+/// arguments and a [Reflect] return value. Functions without a return value return `()`.
+#[derive(Clone)]
+pub struct ReflectFunction {
+    name: String,
+    args: Vec<ArgInfo>,
+    return_info: ArgInfo,
+    func: Arc<FunctionFn>,
+}
+
+impl ReflectFunction {
+    pub fn new<Marker, F: IntoReflectFunction<Marker>>(name: &str, function: F) -> Self {
+        function.into_function(name.to_string())
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn args(&self) -> &[ArgInfo] {
+        &self.args
+    }
+
+    pub fn return_info(&self) -> &ArgInfo {
+        &self.return_info
+    }
+
+    pub fn call(&self, args: Args) -> FunctionResult {
+        (self.func)(args)
+    }
+}
+
+/// A method of a registered type, called with a receiver and reflected arguments. Created from any `Fn` that takes
+/// `&mut T` or `&T` followed by up to 8 [Reflect] arguments, like `Transform::look_at`.
+#[derive(Clone)]
+pub struct ReflectMethod {
+    name: String,
+    receiver: ArgInfo,
+    args: Vec<ArgInfo>,
+    return_info: ArgInfo,
+    func: Arc<MethodFn>,
+}
+
+impl ReflectMethod {
+    pub fn new<T, Marker, F: IntoReflectMethod<T, Marker>>(name: &str, method: F) -> Self {
+        method.into_method(name.to_string())
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn receiver(&self) -> &ArgInfo {
+        &self.receiver
+    }
+
+    pub fn args(&self) -> &[ArgInfo] {
+        &self.args
+    }
+
+    pub fn return_info(&self) -> &ArgInfo {
+        &self.return_info
+    }
+
+    pub fn call(&self, receiver: &mut dyn Reflect, args: Args) -> FunctionResult {
+        (self.func)(receiver, args)
+    }
+}
+
+pub trait IntoReflectFunction<Marker> {
+    fn into_function(self, name: String) -> ReflectFunction;
+}
+
+pub trait IntoReflectMethod<T, Marker> {
+    fn into_method(self, name: String) -> ReflectMethod;
+}
+
+fn check_arg_count(expected: usize, args: &Args) -> Result<(), FunctionError> {
+    if args.len() == expected {
+        Ok(())
+    } else {
+        Err(FunctionError::ArgCount {
+            expected,
+            received: args.len(),
+        })
+    }
+}
+
+fn check_receiver<T: Reflect>(receiver: &dyn Reflect) -> Result<(), FunctionError> {
+    if receiver.is::<T>() {
+        Ok(())
+    } else {
+        Err(FunctionError::ReceiverType {
+            expected: std::any::type_name::<T>(),
+            received: receiver.type_name().to_string(),
+        })
+    }
+}
+
+fn take_arg<T: Reflect>(
+    args: &mut impl Iterator<Item = (usize, Box<dyn Reflect>)>,
+) -> Result<T, FunctionError> {
+    let (index, arg) = args.next().unwrap();
+    arg.take::<T>().map_err(|arg| FunctionError::ArgType {
+        index,
+        expected: std::any::type_name::<T>(),
+        received: arg.type_name().to_string(),
+    })
+}
+
+macro_rules! impl_into_reflect_function {
+    ($($arg:ident),*) => {
+        impl<Func, Ret, $($arg),*> IntoReflectFunction<fn($($arg),*) -> Ret> for Func
+        where
+            Func: Fn($($arg),*) -> Ret + Send + Sync + 'static,
+            Ret: Reflect,
+            $($arg: Reflect,)*
+        {
+            fn into_function(self, name: String) -> ReflectFunction {
+                let args: Vec<ArgInfo> = vec![$(ArgInfo::of::<$arg>()),*];
+                let arg_count = args.len();
+                ReflectFunction {
+                    name,
+                    args,
+                    return_info: ArgInfo::of::<Ret>(),
+                    func: Arc::new(move |args: Args| {
+                        check_arg_count(arg_count, &args)?;
+                        #[allow(unused_mut, unused_variables)]
+                        let mut args = args.into_iter().enumerate();
+                        Ok(Box::new((self)($(take_arg::<$arg>(&mut args)?),*)))
+                    }),
+                }
+            }
+        }
+
+        impl<T, Func, Ret, $($arg),*> IntoReflectMethod<T, fn(&mut T, $($arg),*) -> Ret> for Func
+        where
+            Func: Fn(&mut T, $($arg),*) -> Ret + Send + Sync + 'static,
+            T: Reflect,
+            Ret: Reflect,
+            $($arg: Reflect,)*
+        {
+            fn into_method(self, name: String) -> ReflectMethod {
+                let args: Vec<ArgInfo> = vec![$(ArgInfo::of::<$arg>()),*];
+                let arg_count = args.len();
+                ReflectMethod {
+                    name,
+                    receiver: ArgInfo::of::<T>(),
+                    args,
+                    return_info: ArgInfo::of::<Ret>(),
+                    func: Arc::new(move |receiver: &mut dyn Reflect, args: Args| {
+                        check_receiver::<T>(receiver)?;
+                        check_arg_count(arg_count, &args)?;
+                        #[allow(unused_mut, unused_variables)]
+                        let mut args = args.into_iter().enumerate();
+                        let receiver = receiver.downcast_mut::<T>().unwrap();
+                        Ok(Box::new((self)(receiver, $(take_arg::<$arg>(&mut args)?),*)))
+                    }),
+                }
+            }
+        }
+
+        impl<T, Func, Ret, $($arg),*> IntoReflectMethod<T, fn(&T, $($arg),*) -> Ret> for Func
+        where
+            Func: Fn(&T, $($arg),*) -> Ret + Send + Sync + 'static,
+            T: Reflect,
+            Ret: Reflect,
+            $($arg: Reflect,)*
+        {
+            fn into_method(self, name: String) -> ReflectMethod {
+                let args: Vec<ArgInfo> = vec![$(ArgInfo::of::<$arg>()),*];
+                let arg_count = args.len();
+                ReflectMethod {
+                    name,
+                    receiver: ArgInfo::of::<T>(),
+                    args,
+                    return_info: ArgInfo::of::<Ret>(),
+                    func: Arc::new(move |receiver: &mut dyn Reflect, args: Args| {
+                        check_receiver::<T>(receiver)?;
+                        check_arg_count(arg_count, &args)?;
+                        #[allow(unused_mut, unused_variables)]
+                        let mut args = args.into_iter().enumerate();
+                        let receiver = receiver.downcast_ref::<T>().unwrap();
+                        Ok(Box::new((self)(receiver, $(take_arg::<$arg>(&mut args)?),*)))
+                    }),
+                }
+            }
+        }
+    };
+}
+
+impl_into_reflect_function!();
+impl_into_reflect_function!(A);
+impl_into_reflect_function!(A, B);
+impl_into_reflect_function!(A, B, C);
+impl_into_reflect_function!(A, B, C, D);
+impl_into_reflect_function!(A, B, C, D, E);
+impl_into_reflect_function!(A, B, C, D, E, F);
+impl_into_reflect_function!(A, B, C, D, E, F, G);
+impl_into_reflect_function!(A, B, C, D, E, F, G, H);
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        DynamicStruct, FunctionError, GetTypeRegistration, Reflect, ReflectDefault, Struct,
+        TypeRegistry,
+    };
+
+    #[derive(Reflect, Default)]
+    struct Counter {
+        value: u32,
+    }
+
+    #[derive(Reflect, Default, Debug, PartialEq)]
+    #[reflect(Default)]
+    struct Range {
+        min: u32,
+        max: u32,
+    }
+
+    fn width(range: Range) -> u32 {
+        range.max - range.min
+    }
+
+    impl Counter {
+        fn add(&mut self, amount: u32) {
+            self.value += amount;
+        }
+
+        fn get(&self) -> u32 {
+            self.value
+        }
+    }
+
+    fn scale(value: f32, factor: f32) -> f32 {
+        value * factor
+    }
+
+    #[test]
+    fn call_function() {
+        let mut registry = TypeRegistry::default();
+        registry.register_function("scale", scale);
+        registry.register_function("answer", || 42u8);
+
+        let result = registry
+            .call_function("scale", vec![Box::new(2.0f32), Box::new(3.0f32)])
+            .unwrap();
+        assert_eq!(result.take::<f32>().unwrap(), 6.0);
+        let result = registry.call_function("answer", Vec::new()).unwrap();
+        assert_eq!(result.take::<u8>().unwrap(), 42);
+
+        let function = registry.get_function("scale").unwrap();
+        assert_eq!(function.args().len(), 2);
+        assert_eq!(function.return_info().type_name, "f32");
+
+        assert_eq!(
+            registry
+                .call_function("scale", vec![Box::new(2.0f32)])
+                .err(),
+            Some(FunctionError::ArgCount {
+                expected: 2,
+                received: 1
+            })
+        );
+        assert_eq!(
+            registry
+                .call_function("scale", vec![Box::new(2.0f32), Box::new(3u32)])
+                .err(),
+            Some(FunctionError::ArgType {
+                index: 1,
+                expected: "f32",
+                received: "u32".to_string(),
+            })
+        );
+        assert_eq!(
+            registry.call_function("missing", Vec::new()).err(),
+            Some(FunctionError::NotFound {
+                name: "missing".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn call_method() {
+        let mut registry = TypeRegistry::default();
+        registry.register_method("add", Counter::add);
+        registry.register_method("get", Counter::get);
+
+        let mut counter = Counter::default();
+        registry
+            .call_method(&mut counter, "add", vec![Box::new(5u32)])
+            .unwrap();
+        let value = registry
+            .call_method(&mut counter, "get", Vec::new())
+            .unwrap();
+        assert_eq!(value.take::<u32>().unwrap(), 5);
+
+        let method = registry
+            .get_with_short_name("Counter")
+            .and_then(|registration| registration.method("add"))
+            .unwrap();
+        assert_eq!(
+            method.call(&mut 1u32, vec![Box::new(5u32)]).err(),
+            Some(FunctionError::ReceiverType {
+                expected: std::any::type_name::<Counter>(),
+                received: "u32".to_string(),
+            })
+        );
+        assert_eq!(
+            registry.call_method(&mut 1u32, "add", Vec::new()).err(),
+            Some(FunctionError::UnregisteredType {
+                type_name: "u32".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn convert_dynamic_args() {
+        let mut registry = TypeRegistry::default();
+        registry.register_function("width", width);
+        registry.register_method("add", Counter::add);
+
+        let mut range = DynamicStruct::default();
+        range.set_name(std::any::type_name::<Range>().to_string());
+        range.insert("max", 7u32);
+
+        // the argument type must be registered with ReflectDefault to construct it from a proxy
+        assert_eq!(
+            registry
+                .call_function("width", vec![Box::new(range.clone_dynamic())])
+                .err(),
+            Some(FunctionError::ArgType {
+                index: 0,
+                expected: std::any::type_name::<Range>(),
+                received: std::any::type_name::<Range>().to_string(),
+            })
+        );
+
+        registry.register::<Range>();
+        let result = registry
+            .call_function("width", vec![Box::new(range)])
+            .unwrap();
+        assert_eq!(result.take::<u32>().unwrap(), 7);
+
+        // proxies of other types are still rejected
+        let mut other = DynamicStruct::default();
+        other.set_name("Other".to_string());
+        assert_eq!(
+            registry
+                .call_method(&mut Counter::default(), "add", vec![Box::new(other)])
+                .err(),
+            Some(FunctionError::ArgType {
+                index: 0,
+                expected: "u32",
+                received: "Other".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn reregistering_keeps_methods() {
+        let mut registry = TypeRegistry::default();
+        registry.register_method("add", Counter::add);
+        registry.register::<Counter>();
+        registry.add_registration(Counter::get_type_registration());
+
+        let registration = registry.get_with_short_name("Counter").unwrap();
+        assert!(registration.method("add").is_some());
+    }
+}
@@ -1,4 +1,7 @@
-use crate::{impl_reflect_value, GetTypeRegistration, ReflectDeserialize, TypeRegistryArc};
+use crate::{
+    impl_reflect_value, GetTypeRegistration, IntoReflectFunction, IntoReflectMethod, Reflect,
+    ReflectDeserialize, TypeRegistryArc,
+};
 use bevy_app::{AppBuilder, Plugin};
 use bevy_ecs::Entity;
 
@@ -44,6 +47,15 @@ impl_reflect_value!(Entity(Hash, PartialEq, Serialize, Deserialize));
 
 pub trait RegisterTypeBuilder {
     fn register_type<T: GetTypeRegistration>(&mut self) -> &mut Self;
+    fn register_function<Marker, F: IntoReflectFunction<Marker>>(
+        &mut self,
+        name: &str,
+        function: F,
+    ) -> &mut Self;
+    fn register_method<T, Marker, F>(&mut self, name: &str, method: F) -> &mut Self
+    where
+        T: GetTypeRegistration + Reflect,
+        F: IntoReflectMethod<T, Marker>;
 }
 
 impl RegisterTypeBuilder for AppBuilder {
@@ -54,4 +66,27 @@ impl RegisterTypeBuilder for AppBuilder {
         }
         self
     }
+    fn register_function<Marker, F: IntoReflectFunction<Marker>>(
+        &mut self,
+        name: &str,
+        function: F,
+    ) -> &mut Self {
+        {
+            let registry = self.resources().get_mut::<TypeRegistryArc>().unwrap();
+            registry.write().register_function(name, function);
+        }
+        self
+    }
+
+    fn register_method<T, Marker, F>(&mut self, name: &str, method: F) -> &mut Self
+    where
+        T: GetTypeRegistration + Reflect,
+        F: IntoReflectMethod<T, Marker>,
+    {
+        {
+            let registry = self.resources().get_mut::<TypeRegistryArc>().unwrap();
+            registry.write().register_method(name, method);
+        }
+        self
+    }
 }
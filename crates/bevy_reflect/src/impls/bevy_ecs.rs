@@ -1,7 +1,7 @@
 use crate::{FromType, Reflect};
 use bevy_ecs::{
     Archetype, Component, Entity, EntityMap, FromResources, MapEntities, MapEntitiesError,
-    Resources, World,
+    Resource, Resources, World,
 };
 use std::marker::PhantomData;
 
@@ -91,6 +91,58 @@ impl<C: Component + Reflect + FromResources> FromType<C> for ReflectComponent {
     }
 }
 
+#[derive(Clone)]
+pub struct ReflectResource {
+    insert_resource: fn(&mut Resources, &dyn Reflect),
+    apply_resource: fn(&Resources, &dyn Reflect),
+    clone_resource: fn(&Resources) -> Option<Box<dyn Reflect>>,
+    contains_resource: fn(&Resources) -> bool,
+}
+
+impl ReflectResource {
+    /// Patches the resource if it already exists. Otherwise the resource is created with [FromResources]
+    /// and then patched.
+    pub fn insert_resource(&self, resources: &mut Resources, resource: &dyn Reflect) {
+        (self.insert_resource)(resources, resource);
+    }
+
+    /// Patches an existing resource. Panics if the resource does not exist.
+    pub fn apply_resource(&self, resources: &Resources, resource: &dyn Reflect) {
+        (self.apply_resource)(resources, resource);
+    }
+
+    pub fn clone_resource(&self, resources: &Resources) -> Option<Box<dyn Reflect>> {
+        (self.clone_resource)(resources)
+    }
+
+    pub fn contains_resource(&self, resources: &Resources) -> bool {
+        (self.contains_resource)(resources)
+    }
+}
+
+impl<R: Resource + Reflect + FromResources> FromType<R> for ReflectResource {
+    fn from_type() -> Self {
+        ReflectResource {
+            insert_resource: |resources, reflected_resource| {
+                if let Some(mut resource) = resources.get_mut::<R>() {
+                    resource.apply(reflected_resource);
+                    return;
+                }
+
+                let mut resource = R::from_resources(resources);
+                resource.apply(reflected_resource);
+                resources.insert(resource);
+            },
+            apply_resource: |resources, reflected_resource| {
+                let mut resource = resources.get_mut::<R>().unwrap();
+                resource.apply(reflected_resource);
+            },
+            clone_resource: |resources| resources.get::<R>().map(|resource| resource.clone_value()),
+            contains_resource: |resources| resources.contains::<R>(),
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct SceneComponent<Scene: Component, Runtime: Component> {
     copy_scene_to_runtime: fn(&World, &mut World, &Resources, Entity, Entity),
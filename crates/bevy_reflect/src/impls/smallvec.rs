@@ -1,7 +1,9 @@
 use smallvec::{Array, SmallVec};
 use std::any::Any;
 
-use crate::{serde::Serializable, List, ListIter, Reflect, ReflectMut, ReflectRef};
+use crate::{
+    serde::Serializable, List, ListIter, Reflect, ReflectMut, ReflectRef, TypeInfo, TypeKind, Typed,
+};
 
 impl<T: Array + Send + Sync + 'static> List for SmallVec<T>
 where
@@ -94,3 +96,12 @@ where
         None
     }
 }
+
+impl<T: Array + Send + Sync + 'static> Typed for SmallVec<T>
+where
+    T::Item: Reflect + Clone,
+{
+    fn type_info() -> TypeInfo {
+        TypeInfo::new::<Self>(TypeKind::list::<T::Item>())
+    }
+}
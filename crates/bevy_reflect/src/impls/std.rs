@@ -1,6 +1,6 @@
 use crate::{
     map_partial_eq, serde::Serializable, DynamicMap, List, ListIter, Map, MapIter, Reflect,
-    ReflectDeserialize, ReflectMut, ReflectRef,
+    ReflectDeserialize, ReflectMut, ReflectRef, TypeInfo, TypeKind, Typed,
 };
 
 use bevy_reflect_derive::impl_reflect_value;
@@ -106,6 +106,12 @@ impl<T: Reflect> Reflect for Vec<T> {
     }
 }
 
+impl<T: Reflect> Typed for Vec<T> {
+    fn type_info() -> TypeInfo {
+        TypeInfo::new::<Self>(TypeKind::list::<T>())
+    }
+}
+
 impl<K: Reflect + Clone + Eq + Hash, V: Reflect + Clone> Map for HashMap<K, V> {
     fn get(&self, key: &dyn Reflect) -> Option<&dyn Reflect> {
         key.downcast_ref::<K>()
@@ -199,3 +205,9 @@ impl<K: Reflect + Clone + Eq + Hash, V: Reflect + Clone> Reflect for HashMap<K,
         None
     }
 }
+
+impl<K: Reflect + Clone + Eq + Hash, V: Reflect + Clone> Typed for HashMap<K, V> {
+    fn type_info() -> TypeInfo {
+        TypeInfo::new::<Self>(TypeKind::map::<K, V>())
+    }
+}
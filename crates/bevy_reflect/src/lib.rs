@@ -1,9 +1,16 @@
+mod array;
+mod enum_trait;
+mod function;
 mod list;
 mod map;
 mod path;
 mod reflect;
+#[cfg(feature = "json_schema")]
+mod schema;
 mod struct_trait;
+mod tuple;
 mod tuple_struct;
+mod type_info;
 mod type_registry;
 mod type_uuid;
 mod impls {
@@ -30,23 +37,28 @@ mod impls {
 
 pub mod serde;
 pub mod prelude {
-    #[cfg(feature = "bevy_ecs")]
-    pub use crate::ReflectComponent;
     #[cfg(feature = "bevy_app")]
     pub use crate::RegisterTypeBuilder;
     pub use crate::{
-        reflect_trait, GetField, GetTupleStructField, Reflect, ReflectDeserialize, Struct,
-        TupleStruct,
+        reflect_trait, Enum, GetField, GetTupleStructField, Reflect, ReflectDefault,
+        ReflectDeserialize, Struct, TupleStruct,
     };
+    #[cfg(feature = "bevy_ecs")]
+    pub use crate::{ReflectComponent, ReflectResource};
 }
 
+pub use array::*;
+pub use enum_trait::*;
+pub use function::*;
 pub use impls::*;
 pub use list::*;
 pub use map::*;
 pub use path::*;
 pub use reflect::*;
 pub use struct_trait::*;
+pub use tuple::*;
 pub use tuple_struct::*;
+pub use type_info::*;
 pub use type_registry::*;
 pub use type_uuid::*;
 
@@ -198,6 +210,34 @@ mod tests {
         assert_eq!(values, vec![1]);
     }
 
+    #[test]
+    fn reflect_skip_serializing() {
+        #[derive(Reflect)]
+        #[reflect(skip_serializing)]
+        struct Foo {
+            a: u32,
+        }
+
+        #[derive(Reflect)]
+        struct Bar {
+            a: u32,
+        }
+
+        let mut registry = TypeRegistry::default();
+        registry.register::<Foo>();
+        registry.register::<Bar>();
+        assert!(registry
+            .get(std::any::TypeId::of::<Foo>())
+            .unwrap()
+            .data::<SkipSerializing>()
+            .is_some());
+        assert!(registry
+            .get(std::any::TypeId::of::<Bar>())
+            .unwrap()
+            .data::<SkipSerializing>()
+            .is_none());
+    }
+
     #[test]
     fn reflect_complex_patch() {
         #[derive(Reflect, Eq, PartialEq, Debug)]
@@ -309,6 +349,189 @@ mod tests {
         assert!(foo.reflect_partial_eq(&dynamic_struct).unwrap());
     }
 
+    #[test]
+    fn reflect_enum() {
+        #[derive(Reflect, Debug, PartialEq)]
+        enum Mode {
+            Idle,
+            Wait(f32),
+            Chase {
+                target: u32,
+                #[reflect(ignore)]
+                _ticks: u32,
+            },
+        }
+
+        let mut mode = Mode::Chase {
+            target: 1,
+            _ticks: 2,
+        };
+        assert_eq!(mode.variant_name(), "Chase");
+        assert_eq!(mode.variant_index(), 2);
+        assert_eq!(mode.variant_type(), VariantType::Struct);
+        assert_eq!(mode.field_len(), 1);
+        assert_eq!(
+            *mode.field("target").unwrap().downcast_ref::<u32>().unwrap(),
+            1
+        );
+
+        // patch a field of the current variant
+        let mut patch = DynamicEnum::default();
+        patch.set_variant("Chase".to_string(), VariantType::Struct);
+        patch.insert("target", 3u32);
+        mode.apply(&patch);
+        assert_eq!(
+            mode,
+            Mode::Chase {
+                target: 3,
+                _ticks: 2
+            }
+        );
+
+        // switch variants
+        let mut patch = DynamicEnum::default();
+        patch.set_variant("Wait".to_string(), VariantType::Tuple);
+        patch.push(0.5f32);
+        mode.apply(&patch);
+        assert_eq!(mode, Mode::Wait(0.5));
+
+        mode.apply(&Mode::Idle);
+        assert_eq!(mode, Mode::Idle);
+        assert!(mode.reflect_partial_eq(&mode.clone_dynamic()).unwrap());
+        assert!(!mode.reflect_partial_eq(&Mode::Wait(0.5)).unwrap());
+
+        // missing fields are errors, and are left at their default values when applied
+        let mut patch = DynamicEnum::default();
+        patch.set_variant("Wait".to_string(), VariantType::Tuple);
+        assert_eq!(
+            mode.set_variant_from(&patch),
+            Err(EnumVariantError::InvalidField {
+                variant: "Wait".to_string(),
+                field: "0".to_string(),
+            })
+        );
+        assert_eq!(mode, Mode::Idle);
+        mode.apply(&patch);
+        assert_eq!(mode, Mode::Wait(0.0));
+        patch.set_variant("Flee".to_string(), VariantType::Unit);
+        assert!(matches!(
+            mode.set_variant_from(&patch),
+            Err(EnumVariantError::InvalidVariant { .. })
+        ));
+        mode.set_variant_from(&Mode::Wait(1.0)).unwrap();
+        assert_eq!(mode, Mode::Wait(1.0));
+    }
+
+    #[test]
+    fn reflect_enum_variant_fields() {
+        #[derive(Reflect, Debug, Default, PartialEq)]
+        struct Target {
+            x: f32,
+            name: String,
+        }
+
+        #[derive(Reflect, Debug, PartialEq)]
+        enum Mode {
+            Idle,
+            Chase { target: Target },
+            Patrol(Vec<u32>, Option<u32>),
+        }
+
+        // struct and list fields are reflected as dynamic proxies, which are applied onto default values
+        let mut mode = Mode::Idle;
+        let chase = Mode::Chase {
+            target: Target {
+                x: 1.0,
+                name: "player".to_string(),
+            },
+        };
+        mode.apply(&chase);
+        assert_eq!(mode, chase);
+
+        let patrol = Mode::Patrol(vec![1, 2, 3], Some(4));
+        let mut mode = Mode::Idle;
+        mode.set_variant_from(&patrol.clone_dynamic()).unwrap();
+        assert_eq!(mode, patrol);
+    }
+
+    #[test]
+    fn reflect_serialize_enum() {
+        #[derive(Reflect)]
+        enum Mode {
+            Idle,
+            Wait(i32),
+            Chase { target: u32 },
+        }
+
+        let mut registry = TypeRegistry::default();
+        registry.register::<u32>();
+        registry.register::<i32>();
+
+        for mode in vec![Mode::Idle, Mode::Wait(-2), Mode::Chase { target: 4 }] {
+            let serializer = ReflectSerializer::new(&mode, &registry);
+            let serialized = to_string_pretty(&serializer, PrettyConfig::default()).unwrap();
+
+            let mut deserializer = Deserializer::from_str(&serialized).unwrap();
+            let reflect_deserializer = ReflectDeserializer::new(&registry);
+            let value = reflect_deserializer.deserialize(&mut deserializer).unwrap();
+            let dynamic_enum = value.take::<DynamicEnum>().unwrap();
+
+            assert!(mode.reflect_partial_eq(&dynamic_enum).unwrap());
+
+            let mut deserialized = Mode::Idle;
+            deserialized.apply(&dynamic_enum);
+            assert!(mode.reflect_partial_eq(&deserialized).unwrap());
+        }
+    }
+
+    #[test]
+    fn reflect_tuple_and_array() {
+        #[derive(Reflect)]
+        struct Foo {
+            a: (u32, String),
+            b: [i8; 3],
+        }
+
+        let mut foo = Foo {
+            a: (1, "hi".to_string()),
+            b: [1, 2, 3],
+        };
+        assert_eq!(*foo.a.field(0).unwrap().downcast_ref::<u32>().unwrap(), 1);
+        assert_eq!(
+            *Array::get(&foo.b, 2).unwrap().downcast_ref::<i8>().unwrap(),
+            3
+        );
+
+        let mut registry = TypeRegistry::default();
+        registry.register::<u32>();
+        registry.register::<String>();
+        registry.register::<i8>();
+
+        let patch = Foo {
+            a: (2, "hello".to_string()),
+            b: [4, 5, 6],
+        };
+        let serializer = ReflectSerializer::new(&patch, &registry);
+        let serialized = to_string_pretty(&serializer, PrettyConfig::default()).unwrap();
+
+        let mut deserializer = Deserializer::from_str(&serialized).unwrap();
+        let reflect_deserializer = ReflectDeserializer::new(&registry);
+        let value = reflect_deserializer.deserialize(&mut deserializer).unwrap();
+        assert!(patch.reflect_partial_eq(&*value).unwrap());
+
+        foo.apply(&*value);
+        assert_eq!(foo.a, (2, "hello".to_string()));
+        assert_eq!(foo.b, [4, 5, 6]);
+
+        // tuples and arrays of different lengths apply the items they have in common
+        foo.a.apply(&(3u32,));
+        assert_eq!(foo.a, (3, "hello".to_string()));
+        foo.b.apply(&[7i8, 8, 9, 10]);
+        assert_eq!(foo.b, [7, 8, 9]);
+        foo.b.apply(&[1i8]);
+        assert_eq!(foo.b, [1, 8, 9]);
+    }
+
     #[test]
     fn reflect_take() {
         #[derive(Reflect, Debug, PartialEq)]
@@ -1,6 +1,6 @@
 use std::num::ParseIntError;
 
-use crate::{Reflect, ReflectMut, ReflectRef};
+use crate::{Reflect, ReflectMut, ReflectRef, VariantType};
 use thiserror::Error;
 
 #[derive(Debug, PartialEq, Eq, Error)]
@@ -14,15 +14,21 @@ pub enum ReflectPathError<'a> {
         index: usize,
         tuple_struct_index: usize,
     },
+    #[error("the current enum variant doesn't have a field with the given name or index")]
+    InvalidVariantField { index: usize, field: &'a str },
+    #[error("the current tuple doesn't have a field with the given index")]
+    InvalidTupleIndex { index: usize, tuple_index: usize },
     #[error("the current list doesn't have a value at the given index")]
     InvalidListIndex { index: usize, list_index: usize },
+    #[error("the current array doesn't have a value at the given index")]
+    InvalidArrayIndex { index: usize, array_index: usize },
     #[error("encountered an unexpected token")]
     UnexpectedToken { index: usize, token: &'a str },
     #[error("expected a token, but it wasn't there.")]
     ExpectedToken { index: usize, token: &'a str },
     #[error("expected a struct, but found a different reflect value")]
     ExpectedStruct { index: usize },
-    #[error("expected a list, but found a different reflect value")]
+    #[error("expected a list or an array, but found a different reflect value")]
     ExpectedList { index: usize },
     #[error("failed to parse a usize")]
     IndexParseError(#[from] ParseIntError),
@@ -100,6 +106,16 @@ impl GetPath for dyn Reflect {
                                 )?;
                                 current = list_item;
                             }
+                            ReflectRef::Array(reflect_array) => {
+                                let array_index = value.parse::<usize>()?;
+                                let array_item = reflect_array.get(array_index).ok_or(
+                                    ReflectPathError::InvalidArrayIndex {
+                                        index: current_index,
+                                        array_index,
+                                    },
+                                )?;
+                                current = array_item;
+                            }
                             _ => {
                                 return Err(ReflectPathError::ExpectedList {
                                     index: current_index,
@@ -166,6 +182,16 @@ impl GetPath for dyn Reflect {
                                 )?;
                                 current = list_item;
                             }
+                            ReflectMut::Array(reflect_array) => {
+                                let array_index = value.parse::<usize>()?;
+                                let array_item = reflect_array.get_mut(array_index).ok_or(
+                                    ReflectPathError::InvalidArrayIndex {
+                                        index: current_index,
+                                        array_index,
+                                    },
+                                )?;
+                                current = array_item;
+                            }
                             _ => {
                                 return Err(ReflectPathError::ExpectedStruct {
                                     index: current_index,
@@ -225,6 +251,26 @@ fn read_field<'r, 'p>(
                 },
             )?)
         }
+        ReflectRef::Tuple(reflect_tuple) => {
+            let tuple_index = field.parse::<usize>()?;
+            Ok(reflect_tuple
+                .field(tuple_index)
+                .ok_or(ReflectPathError::InvalidTupleIndex {
+                    index: current_index,
+                    tuple_index,
+                })?)
+        }
+        ReflectRef::Enum(reflect_enum) => {
+            let variant_field = if reflect_enum.variant_type() == VariantType::Tuple {
+                reflect_enum.field_at(field.parse::<usize>()?)
+            } else {
+                reflect_enum.field(field)
+            };
+            Ok(variant_field.ok_or(ReflectPathError::InvalidVariantField {
+                index: current_index,
+                field,
+            })?)
+        }
         _ => Err(ReflectPathError::ExpectedStruct {
             index: current_index,
         }),
@@ -254,6 +300,26 @@ fn read_field_mut<'r, 'p>(
                 },
             )?)
         }
+        ReflectMut::Tuple(reflect_tuple) => {
+            let tuple_index = field.parse::<usize>()?;
+            Ok(reflect_tuple
+                .field_mut(tuple_index)
+                .ok_or(ReflectPathError::InvalidTupleIndex {
+                    index: current_index,
+                    tuple_index,
+                })?)
+        }
+        ReflectMut::Enum(reflect_enum) => {
+            let variant_field = if reflect_enum.variant_type() == VariantType::Tuple {
+                reflect_enum.field_at_mut(field.parse::<usize>()?)
+            } else {
+                reflect_enum.field_mut(field)
+            };
+            Ok(variant_field.ok_or(ReflectPathError::InvalidVariantField {
+                index: current_index,
+                field,
+            })?)
+        }
         _ => Err(ReflectPathError::ExpectedStruct {
             index: current_index,
         }),
@@ -316,6 +382,9 @@ mod tests {
             x: B,
             y: Vec<C>,
             z: D,
+            mode: F,
+            size: (f32, f32),
+            bytes: [u8; 4],
         }
 
         #[derive(Reflect)]
@@ -324,7 +393,7 @@ mod tests {
             bar: C,
         }
 
-        #[derive(Reflect)]
+        #[derive(Reflect, Default)]
         struct C {
             baz: f32,
         }
@@ -335,6 +404,12 @@ mod tests {
         #[derive(Reflect)]
         struct E(f32, usize);
 
+        #[derive(Reflect)]
+        enum F {
+            Chase { target: C },
+            Wait(f32),
+        }
+
         let mut a = A {
             w: 1,
             x: B {
@@ -343,6 +418,11 @@ mod tests {
             },
             y: vec![C { baz: 1.0 }, C { baz: 2.0 }],
             z: D(E(10.0, 42)),
+            mode: F::Chase {
+                target: C { baz: 5.0 },
+            },
+            size: (1.0, 2.0),
+            bytes: [1, 2, 3, 4],
         };
 
         assert_eq!(*a.get_path::<usize>("w").unwrap(), 1);
@@ -354,6 +434,30 @@ mod tests {
         *a.get_path_mut::<f32>("y[1].baz").unwrap() = 3.0;
         assert_eq!(a.y[1].baz, 3.0);
 
+        assert_eq!(*a.get_path::<f32>("size.1").unwrap(), 2.0);
+        assert_eq!(*a.get_path::<u8>("bytes[2]").unwrap(), 3);
+        *a.get_path_mut::<u8>("bytes[3]").unwrap() = 8;
+        assert_eq!(a.bytes, [1, 2, 3, 8]);
+        assert_eq!(
+            a.path("bytes[4]").err().unwrap(),
+            ReflectPathError::InvalidArrayIndex {
+                index: 6,
+                array_index: 4
+            }
+        );
+
+        assert_eq!(*a.get_path::<f32>("mode.target.baz").unwrap(), 5.0);
+        a.mode = F::Wait(1.0);
+        *a.get_path_mut::<f32>("mode.0").unwrap() = 2.0;
+        assert!(matches!(a.mode, F::Wait(wait) if wait == 2.0));
+        assert_eq!(
+            a.path("mode.1").err().unwrap(),
+            ReflectPathError::InvalidVariantField {
+                index: 5,
+                field: "1"
+            }
+        );
+
         assert_eq!(
             a.path("x.notreal").err().unwrap(),
             ReflectPathError::InvalidField {
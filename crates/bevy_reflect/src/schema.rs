@@ -0,0 +1,230 @@
+use crate::{FieldInfo, TypeInfo, TypeKind, TypeRegistry, VariantInfo, VariantType};
+use serde_json::{json, Map, Value};
+use std::any::TypeId;
+
+impl TypeRegistry {
+    /// Exports every registered type as a JSON schema (draft 7).
+    ///
+    /// The schema describes the format written by [ReflectSerializer](crate::serde::ReflectSerializer),
+    /// which is also the format of scene files. Each registered type gets an entry in `definitions`,
+    /// keyed by its full type name. Fields whose type isn't registered accept any value.
+    pub fn json_schema(&self) -> Value {
+        let mut registrations = self.iter().collect::<Vec<_>>();
+        registrations.sort_by_key(|registration| registration.name());
+
+        let mut definitions = Map::new();
+        let mut refs = Vec::new();
+        for registration in registrations {
+            let type_info = registration.type_info();
+            definitions.insert(registration.name().to_string(), self.type_schema(type_info));
+            refs.push(json!({ "$ref": definition_ref(registration.name()) }));
+        }
+
+        json!({
+            "$schema": "http://json-schema.org/draft-07/schema#",
+            "definitions": definitions,
+            "anyOf": refs,
+        })
+    }
+
+    fn type_schema(&self, type_info: &TypeInfo) -> Value {
+        let (kind_field, value_schema) = match type_info.kind() {
+            TypeKind::Struct { fields } => ("struct", self.struct_schema(fields)),
+            TypeKind::TupleStruct { fields } => ("tuple_struct", self.tuple_schema(fields)),
+            TypeKind::Tuple { fields } => ("tuple", self.tuple_schema(fields)),
+            TypeKind::List {
+                item_type_name,
+                item_type_id,
+            } => (
+                "list",
+                json!({
+                    "type": "array",
+                    "items": self.field_type_schema(*item_type_id, item_type_name),
+                }),
+            ),
+            TypeKind::Array {
+                item_type_name,
+                item_type_id,
+                len,
+            } => (
+                "array",
+                json!({
+                    "type": "array",
+                    "items": self.field_type_schema(*item_type_id, item_type_name),
+                    "minItems": len,
+                    "maxItems": len,
+                }),
+            ),
+            // map keys are reflected values, which can't be described as json object keys
+            TypeKind::Map { .. } => ("map", json!({ "type": "object" })),
+            TypeKind::Enum { variants } => (
+                "enum",
+                json!({
+                    "oneOf": variants
+                        .iter()
+                        .map(|variant| self.variant_schema(variant))
+                        .collect::<Vec<_>>(),
+                }),
+            ),
+            TypeKind::Value => ("value", value_schema(type_info.type_name())),
+        };
+
+        with_description(
+            json!({
+                "type": "object",
+                "properties": {
+                    "type": { "const": type_info.type_name() },
+                    kind_field: value_schema,
+                },
+                "required": ["type", kind_field],
+                "additionalProperties": false,
+            }),
+            type_info.docs(),
+        )
+    }
+
+    fn variant_schema(&self, variant: &VariantInfo) -> Value {
+        let mut properties = Map::new();
+        let mut required = vec![json!("variant")];
+        properties.insert("variant".to_string(), json!({ "const": variant.name() }));
+        match variant.variant_type() {
+            VariantType::Unit => {}
+            VariantType::Tuple => {
+                properties.insert("tuple".to_string(), self.tuple_schema(variant.fields()));
+                required.push(json!("tuple"));
+            }
+            VariantType::Struct => {
+                properties.insert("struct".to_string(), self.struct_schema(variant.fields()));
+                required.push(json!("struct"));
+            }
+        }
+
+        with_description(
+            json!({
+                "type": "object",
+                "properties": properties,
+                "required": required,
+                "additionalProperties": false,
+            }),
+            variant.docs(),
+        )
+    }
+
+    fn struct_schema(&self, fields: &[FieldInfo]) -> Value {
+        let mut properties = Map::new();
+        for field in fields {
+            properties.insert(
+                field.name().unwrap_or_default().to_string(),
+                with_description(
+                    self.field_type_schema(field.type_id(), field.type_name()),
+                    field.docs(),
+                ),
+            );
+        }
+
+        json!({
+            "type": "object",
+            "properties": properties,
+            "additionalProperties": false,
+        })
+    }
+
+    fn tuple_schema(&self, fields: &[FieldInfo]) -> Value {
+        json!({
+            "type": "array",
+            "items": fields
+                .iter()
+                .map(|field| {
+                    with_description(
+                        self.field_type_schema(field.type_id(), field.type_name()),
+                        field.docs(),
+                    )
+                })
+                .collect::<Vec<_>>(),
+            "minItems": fields.len(),
+            "maxItems": fields.len(),
+        })
+    }
+
+    fn field_type_schema(&self, type_id: TypeId, type_name: &str) -> Value {
+        if let Some(registration) = self.get(type_id) {
+            json!({ "$ref": definition_ref(registration.name()) })
+        } else {
+            json!({ "$comment": format!("{} is not registered", type_name) })
+        }
+    }
+}
+
+/// The json schema of the `value` of common [TypeKind::Value] types
+fn value_schema(type_name: &str) -> Value {
+    match type_name {
+        "bool" => json!({ "type": "boolean" }),
+        "u8" | "u16" | "u32" | "u64" | "u128" | "usize" | "i8" | "i16" | "i32" | "i64" | "i128"
+        | "isize" => json!({ "type": "integer" }),
+        "f32" | "f64" => json!({ "type": "number" }),
+        "alloc::string::String" => json!({ "type": "string" }),
+        _ => json!({}),
+    }
+}
+
+fn definition_ref(type_name: &str) -> String {
+    // escape the type name as a json pointer token
+    format!(
+        "#/definitions/{}",
+        type_name.replace('~', "~0").replace('/', "~1")
+    )
+}
+
+fn with_description(mut schema: Value, docs: Option<&str>) -> Value {
+    if let (Some(docs), Value::Object(schema)) = (docs, &mut schema) {
+        schema.insert("description".to_string(), json!(docs));
+    }
+    schema
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Reflect, TypeRegistry};
+
+    /// A creature
+    #[derive(Reflect)]
+    struct Creature {
+        /// Current health points
+        #[reflect(range(0, 100))]
+        health: u32,
+        mode: Mode,
+        #[reflect(ignore)]
+        _cache: Vec<u8>,
+    }
+
+    #[derive(Reflect)]
+    enum Mode {
+        Idle,
+        Wait(f32),
+    }
+
+    #[test]
+    fn json_schema() {
+        let mut registry = TypeRegistry::default();
+        registry.register::<u32>();
+        registry.register::<Creature>();
+        registry.register::<Mode>();
+
+        let schema = registry.json_schema();
+        let creature = &schema["definitions"][std::any::type_name::<Creature>()];
+        assert_eq!(creature["description"], "A creature");
+        let fields = &creature["properties"]["struct"]["properties"];
+        assert_eq!(fields["health"]["$ref"], "#/definitions/u32");
+        assert_eq!(fields["health"]["description"], "Current health points");
+        assert!(fields.get("_cache").is_none());
+
+        let mode = &schema["definitions"][std::any::type_name::<Mode>()];
+        let variants = mode["properties"]["enum"]["oneOf"].as_array().unwrap();
+        assert_eq!(variants.len(), 2);
+        assert_eq!(variants[1]["properties"]["variant"]["const"], "Wait");
+        assert_eq!(
+            schema["definitions"]["u32"]["properties"]["value"]["type"],
+            "integer"
+        );
+    }
+}
@@ -1,6 +1,6 @@
 use crate::{
-    serde::type_fields, DynamicList, DynamicMap, DynamicStruct, DynamicTupleStruct, Reflect,
-    ReflectDeserialize, TypeRegistry,
+    serde::type_fields, DynamicArray, DynamicEnum, DynamicList, DynamicMap, DynamicStruct,
+    DynamicTuple, DynamicTupleStruct, Enum, Reflect, ReflectDeserialize, TypeRegistry, VariantType,
 };
 use erased_serde::Deserializer;
 use serde::de::{self, DeserializeSeed, MapAccess, SeqAccess, Visitor};
@@ -176,6 +176,36 @@ impl<'a, 'de> Visitor<'de> for ReflectVisitor<'a> {
                     tuple_struct.set_name(type_name);
                     return Ok(Box::new(tuple_struct));
                 }
+                type_fields::TUPLE => {
+                    let type_name = type_name
+                        .take()
+                        .ok_or_else(|| de::Error::missing_field(type_fields::TYPE))?;
+                    let mut tuple = map.next_value_seed(TupleDeserializer {
+                        registry: self.registry,
+                    })?;
+                    tuple.set_name(type_name);
+                    return Ok(Box::new(tuple));
+                }
+                type_fields::ARRAY => {
+                    let type_name = type_name
+                        .take()
+                        .ok_or_else(|| de::Error::missing_field(type_fields::TYPE))?;
+                    let mut array = map.next_value_seed(ArrayDeserializer {
+                        registry: self.registry,
+                    })?;
+                    array.set_name(type_name);
+                    return Ok(Box::new(array));
+                }
+                type_fields::ENUM => {
+                    let type_name = type_name
+                        .take()
+                        .ok_or_else(|| de::Error::missing_field(type_fields::TYPE))?;
+                    let mut dynamic_enum = map.next_value_seed(EnumDeserializer {
+                        registry: self.registry,
+                    })?;
+                    dynamic_enum.set_name(type_name);
+                    return Ok(Box::new(dynamic_enum));
+                }
                 type_fields::LIST => {
                     let _type_name = type_name
                         .take()
@@ -209,7 +239,7 @@ impl<'a, 'de> Visitor<'de> for ReflectVisitor<'a> {
             }
         }
 
-        Err(de::Error::custom("Maps in this location must have the \'type\' field and one of the following fields: \'map\', \'seq\', \'value\'"))
+        Err(de::Error::custom("Maps in this location must have the \'type\' field and one of the following fields: \'map\', \'struct\', \'tuple_struct\', \'tuple\', \'list\', \'array\', \'enum\', \'value\'"))
     }
 }
 
@@ -401,3 +431,213 @@ impl<'a, 'de> Visitor<'de> for TupleStructVisitor<'a> {
         Ok(tuple_struct)
     }
 }
+
+struct TupleDeserializer<'a> {
+    registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for TupleDeserializer<'a> {
+    type Value = DynamicTuple;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(TupleVisitor {
+            registry: self.registry,
+        })
+    }
+}
+
+struct TupleVisitor<'a> {
+    registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> Visitor<'de> for TupleVisitor<'a> {
+    type Value = DynamicTuple;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("tuple value")
+    }
+
+    fn visit_seq<V>(self, mut seq: V) -> Result<Self::Value, V::Error>
+    where
+        V: SeqAccess<'de>,
+    {
+        let mut tuple = DynamicTuple::default();
+        while let Some(value) = seq.next_element_seed(ReflectDeserializer {
+            registry: self.registry,
+        })? {
+            tuple.insert_boxed(value);
+        }
+        Ok(tuple)
+    }
+}
+
+struct ArrayDeserializer<'a> {
+    registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for ArrayDeserializer<'a> {
+    type Value = DynamicArray;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(ArrayVisitor {
+            registry: self.registry,
+        })
+    }
+}
+
+struct ArrayVisitor<'a> {
+    registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> Visitor<'de> for ArrayVisitor<'a> {
+    type Value = DynamicArray;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("array value")
+    }
+
+    fn visit_seq<V>(self, mut seq: V) -> Result<Self::Value, V::Error>
+    where
+        V: SeqAccess<'de>,
+    {
+        let mut values = Vec::new();
+        while let Some(value) = seq.next_element_seed(ReflectDeserializer {
+            registry: self.registry,
+        })? {
+            values.push(value);
+        }
+        Ok(DynamicArray::new(values))
+    }
+}
+
+struct EnumDeserializer<'a> {
+    registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for EnumDeserializer<'a> {
+    type Value = DynamicEnum;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_map(EnumVisitor {
+            registry: self.registry,
+        })
+    }
+}
+
+struct EnumVisitor<'a> {
+    registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> Visitor<'de> for EnumVisitor<'a> {
+    type Value = DynamicEnum;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("enum value")
+    }
+
+    fn visit_map<V>(self, mut map: V) -> Result<Self::Value, V::Error>
+    where
+        V: MapAccess<'de>,
+    {
+        let mut variant_name: Option<String> = None;
+        let mut dynamic_enum = DynamicEnum::default();
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                type_fields::VARIANT => {
+                    variant_name = Some(map.next_value()?);
+                }
+                type_fields::TUPLE => {
+                    let variant_name = variant_name
+                        .take()
+                        .ok_or_else(|| de::Error::missing_field(type_fields::VARIANT))?;
+                    dynamic_enum.set_variant(variant_name, VariantType::Tuple);
+                    map.next_value_seed(VariantFieldsDeserializer {
+                        registry: self.registry,
+                        dynamic_enum: &mut dynamic_enum,
+                    })?;
+                    return Ok(dynamic_enum);
+                }
+                type_fields::STRUCT => {
+                    let variant_name = variant_name
+                        .take()
+                        .ok_or_else(|| de::Error::missing_field(type_fields::VARIANT))?;
+                    dynamic_enum.set_variant(variant_name, VariantType::Struct);
+                    map.next_value_seed(VariantFieldsDeserializer {
+                        registry: self.registry,
+                        dynamic_enum: &mut dynamic_enum,
+                    })?;
+                    return Ok(dynamic_enum);
+                }
+                _ => return Err(de::Error::unknown_field(key.as_str(), &[])),
+            }
+        }
+
+        let variant_name =
+            variant_name.ok_or_else(|| de::Error::missing_field(type_fields::VARIANT))?;
+        dynamic_enum.set_variant(variant_name, VariantType::Unit);
+        Ok(dynamic_enum)
+    }
+}
+
+/// Deserializes the fields of the variant that `dynamic_enum` is set to
+struct VariantFieldsDeserializer<'a, 'b> {
+    registry: &'a TypeRegistry,
+    dynamic_enum: &'b mut DynamicEnum,
+}
+
+impl<'a, 'b, 'de> DeserializeSeed<'de> for VariantFieldsDeserializer<'a, 'b> {
+    type Value = ();
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        if self.dynamic_enum.variant_type() == VariantType::Struct {
+            deserializer.deserialize_map(self)
+        } else {
+            deserializer.deserialize_seq(self)
+        }
+    }
+}
+
+impl<'a, 'b, 'de> Visitor<'de> for VariantFieldsDeserializer<'a, 'b> {
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("enum variant fields")
+    }
+
+    fn visit_seq<V>(self, mut seq: V) -> Result<Self::Value, V::Error>
+    where
+        V: SeqAccess<'de>,
+    {
+        while let Some(value) = seq.next_element_seed(ReflectDeserializer {
+            registry: self.registry,
+        })? {
+            self.dynamic_enum.push_boxed(value);
+        }
+        Ok(())
+    }
+
+    fn visit_map<V>(self, mut map: V) -> Result<Self::Value, V::Error>
+    where
+        V: MapAccess<'de>,
+    {
+        while let Some(key) = map.next_key::<String>()? {
+            let value = map.next_value_seed(ReflectDeserializer {
+                registry: self.registry,
+            })?;
+            self.dynamic_enum.insert_boxed(&key, value);
+        }
+        Ok(())
+    }
+}
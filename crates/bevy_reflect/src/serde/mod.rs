@@ -10,5 +10,9 @@ pub(crate) mod type_fields {
     pub const STRUCT: &str = "struct";
     pub const TUPLE_STRUCT: &str = "tuple_struct";
     pub const LIST: &str = "list";
+    pub const ARRAY: &str = "array";
     pub const VALUE: &str = "value";
+    pub const ENUM: &str = "enum";
+    pub const VARIANT: &str = "variant";
+    pub const TUPLE: &str = "tuple";
 }
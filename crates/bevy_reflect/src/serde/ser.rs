@@ -1,5 +1,6 @@
 use crate::{
-    serde::type_fields, List, Map, Reflect, ReflectRef, Struct, TupleStruct, TypeRegistry,
+    serde::type_fields, Array, Enum, List, Map, Reflect, ReflectRef, Struct, Tuple, TupleStruct,
+    TypeRegistry, VariantType,
 };
 use serde::{
     ser::{SerializeMap, SerializeSeq},
@@ -57,16 +58,31 @@ impl<'a> Serialize for ReflectSerializer<'a> {
                 registry: self.registry,
             }
             .serialize(serializer),
+            ReflectRef::Tuple(value) => TupleSerializer {
+                tuple: value,
+                registry: self.registry,
+            }
+            .serialize(serializer),
             ReflectRef::List(value) => ListSerializer {
                 list: value,
                 registry: self.registry,
             }
             .serialize(serializer),
+            ReflectRef::Array(value) => ArraySerializer {
+                array: value,
+                registry: self.registry,
+            }
+            .serialize(serializer),
             ReflectRef::Map(value) => MapSerializer {
                 map: value,
                 registry: self.registry,
             }
             .serialize(serializer),
+            ReflectRef::Enum(value) => EnumSerializer {
+                enum_value: value,
+                registry: self.registry,
+            }
+            .serialize(serializer),
             ReflectRef::Value(value) => ReflectValueSerializer {
                 registry: self.registry,
                 value,
@@ -181,6 +197,139 @@ impl<'a> Serialize for TupleStructValueSerializer<'a> {
     }
 }
 
+pub struct TupleSerializer<'a> {
+    pub tuple: &'a dyn Tuple,
+    pub registry: &'a TypeRegistry,
+}
+
+impl<'a> Serialize for TupleSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_map(Some(2))?;
+
+        state.serialize_entry(type_fields::TYPE, self.tuple.type_name())?;
+        state.serialize_entry(
+            type_fields::TUPLE,
+            &TupleValueSerializer {
+                tuple: self.tuple,
+                registry: self.registry,
+            },
+        )?;
+        state.end()
+    }
+}
+
+pub struct TupleValueSerializer<'a> {
+    pub tuple: &'a dyn Tuple,
+    pub registry: &'a TypeRegistry,
+}
+
+impl<'a> Serialize for TupleValueSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_seq(Some(self.tuple.field_len()))?;
+        for value in self.tuple.iter_fields() {
+            state.serialize_element(&ReflectSerializer::new(value, self.registry))?;
+        }
+        state.end()
+    }
+}
+
+pub struct EnumSerializer<'a> {
+    pub enum_value: &'a dyn Enum,
+    pub registry: &'a TypeRegistry,
+}
+
+impl<'a> Serialize for EnumSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_map(Some(2))?;
+
+        state.serialize_entry(type_fields::TYPE, self.enum_value.type_name())?;
+        state.serialize_entry(
+            type_fields::ENUM,
+            &EnumValueSerializer {
+                enum_value: self.enum_value,
+                registry: self.registry,
+            },
+        )?;
+        state.end()
+    }
+}
+
+pub struct EnumValueSerializer<'a> {
+    pub enum_value: &'a dyn Enum,
+    pub registry: &'a TypeRegistry,
+}
+
+impl<'a> Serialize for EnumValueSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let variant_type = self.enum_value.variant_type();
+        let len = if variant_type == VariantType::Unit {
+            1
+        } else {
+            2
+        };
+        let mut state = serializer.serialize_map(Some(len))?;
+        state.serialize_entry(type_fields::VARIANT, self.enum_value.variant_name())?;
+        match variant_type {
+            VariantType::Unit => {}
+            VariantType::Tuple => state.serialize_entry(
+                type_fields::TUPLE,
+                &VariantFieldsSerializer {
+                    enum_value: self.enum_value,
+                    registry: self.registry,
+                },
+            )?,
+            VariantType::Struct => state.serialize_entry(
+                type_fields::STRUCT,
+                &VariantFieldsSerializer {
+                    enum_value: self.enum_value,
+                    registry: self.registry,
+                },
+            )?,
+        }
+        state.end()
+    }
+}
+
+/// Serializes the fields of an enum variant as a sequence (tuple variants) or a map (struct variants)
+pub struct VariantFieldsSerializer<'a> {
+    pub enum_value: &'a dyn Enum,
+    pub registry: &'a TypeRegistry,
+}
+
+impl<'a> Serialize for VariantFieldsSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        if self.enum_value.variant_type() == VariantType::Struct {
+            let mut state = serializer.serialize_map(Some(self.enum_value.field_len()))?;
+            for (index, value) in self.enum_value.iter_fields().enumerate() {
+                let key = self.enum_value.name_at(index).unwrap();
+                state.serialize_entry(key, &ReflectSerializer::new(value, self.registry))?;
+            }
+            state.end()
+        } else {
+            let mut state = serializer.serialize_seq(Some(self.enum_value.field_len()))?;
+            for value in self.enum_value.iter_fields() {
+                state.serialize_element(&ReflectSerializer::new(value, self.registry))?;
+            }
+            state.end()
+        }
+    }
+}
+
 pub struct MapSerializer<'a> {
     pub map: &'a dyn Map,
     pub registry: &'a TypeRegistry,
@@ -266,3 +415,44 @@ impl<'a> Serialize for ListValueSerializer<'a> {
         state.end()
     }
 }
+
+pub struct ArraySerializer<'a> {
+    pub array: &'a dyn Array,
+    pub registry: &'a TypeRegistry,
+}
+
+impl<'a> Serialize for ArraySerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_map(Some(2))?;
+        state.serialize_entry(type_fields::TYPE, self.array.type_name())?;
+        state.serialize_entry(
+            type_fields::ARRAY,
+            &ArrayValueSerializer {
+                array: self.array,
+                registry: self.registry,
+            },
+        )?;
+        state.end()
+    }
+}
+
+pub struct ArrayValueSerializer<'a> {
+    pub array: &'a dyn Array,
+    pub registry: &'a TypeRegistry,
+}
+
+impl<'a> Serialize for ArrayValueSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_seq(Some(self.array.len()))?;
+        for value in self.array.iter() {
+            state.serialize_element(&ReflectSerializer::new(value, self.registry))?;
+        }
+        state.end()
+    }
+}
@@ -0,0 +1,302 @@
+use crate::{
+    serde::Serializable, FieldInfo, Reflect, ReflectMut, ReflectRef, TypeInfo, TypeKind, Typed,
+};
+use std::any::Any;
+
+/// A rust "tuple" reflection, like `(f32, f32)`
+pub trait Tuple: Reflect {
+    fn field(&self, index: usize) -> Option<&dyn Reflect>;
+    fn field_mut(&mut self, index: usize) -> Option<&mut dyn Reflect>;
+    fn field_len(&self) -> usize;
+    fn iter_fields(&self) -> TupleFieldIter;
+    fn clone_dynamic(&self) -> DynamicTuple;
+}
+
+pub struct TupleFieldIter<'a> {
+    pub(crate) tuple: &'a dyn Tuple,
+    pub(crate) index: usize,
+}
+
+impl<'a> TupleFieldIter<'a> {
+    pub fn new(value: &'a dyn Tuple) -> Self {
+        TupleFieldIter {
+            tuple: value,
+            index: 0,
+        }
+    }
+}
+
+impl<'a> Iterator for TupleFieldIter<'a> {
+    type Item = &'a dyn Reflect;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let value = self.tuple.field(self.index);
+        self.index += 1;
+        value
+    }
+}
+
+#[derive(Default)]
+pub struct DynamicTuple {
+    name: String,
+    fields: Vec<Box<dyn Reflect>>,
+}
+
+impl DynamicTuple {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn set_name(&mut self, name: String) {
+        self.name = name;
+    }
+
+    pub fn insert_boxed(&mut self, value: Box<dyn Reflect>) {
+        self.fields.push(value);
+    }
+
+    pub fn insert<T: Reflect>(&mut self, value: T) {
+        self.insert_boxed(Box::new(value));
+    }
+}
+
+impl Tuple for DynamicTuple {
+    #[inline]
+    fn field(&self, index: usize) -> Option<&dyn Reflect> {
+        self.fields.get(index).map(|field| &**field)
+    }
+
+    #[inline]
+    fn field_mut(&mut self, index: usize) -> Option<&mut dyn Reflect> {
+        self.fields.get_mut(index).map(|field| &mut **field)
+    }
+
+    #[inline]
+    fn field_len(&self) -> usize {
+        self.fields.len()
+    }
+
+    #[inline]
+    fn iter_fields(&self) -> TupleFieldIter {
+        TupleFieldIter {
+            tuple: self,
+            index: 0,
+        }
+    }
+
+    fn clone_dynamic(&self) -> DynamicTuple {
+        DynamicTuple {
+            name: self.name.clone(),
+            fields: self
+                .fields
+                .iter()
+                .map(|value| value.clone_value())
+                .collect(),
+        }
+    }
+}
+
+impl Reflect for DynamicTuple {
+    #[inline]
+    fn type_name(&self) -> &str {
+        &self.name
+    }
+
+    #[inline]
+    fn any(&self) -> &dyn Any {
+        self
+    }
+
+    #[inline]
+    fn any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    #[inline]
+    fn clone_value(&self) -> Box<dyn Reflect> {
+        Box::new(self.clone_dynamic())
+    }
+
+    #[inline]
+    fn reflect_ref(&self) -> ReflectRef {
+        ReflectRef::Tuple(self)
+    }
+
+    #[inline]
+    fn reflect_mut(&mut self) -> ReflectMut {
+        ReflectMut::Tuple(self)
+    }
+
+    fn apply(&mut self, value: &dyn Reflect) {
+        tuple_apply(self, value);
+    }
+
+    fn set(&mut self, value: Box<dyn Reflect>) -> Result<(), Box<dyn Reflect>> {
+        *self = value.take()?;
+        Ok(())
+    }
+
+    fn reflect_hash(&self) -> Option<u64> {
+        None
+    }
+
+    fn reflect_partial_eq(&self, value: &dyn Reflect) -> Option<bool> {
+        tuple_partial_eq(self, value)
+    }
+
+    fn serializable(&self) -> Option<Serializable> {
+        None
+    }
+}
+
+/// Applies each field of `b` to the field of `a` at the same index. Fields of `b` past the end of `a` are ignored and
+/// fields of `a` past the end of `b` are left as they are.
+#[inline]
+pub fn tuple_apply<T: Tuple>(a: &mut T, b: &dyn Reflect) {
+    if let ReflectRef::Tuple(tuple) = b.reflect_ref() {
+        for (i, value) in tuple.iter_fields().enumerate() {
+            if let Some(v) = a.field_mut(i) {
+                v.apply(value)
+            }
+        }
+    } else {
+        panic!("Attempted to apply non-Tuple type to Tuple type.");
+    }
+}
+
+#[inline]
+pub fn tuple_partial_eq<T: Tuple>(a: &T, b: &dyn Reflect) -> Option<bool> {
+    let tuple = if let ReflectRef::Tuple(tuple) = b.reflect_ref() {
+        tuple
+    } else {
+        return Some(false);
+    };
+
+    if a.field_len() != tuple.field_len() {
+        return Some(false);
+    }
+
+    for (a_value, b_value) in a.iter_fields().zip(tuple.iter_fields()) {
+        if let Some(false) | None = a_value.reflect_partial_eq(b_value) {
+            return Some(false);
+        }
+    }
+
+    Some(true)
+}
+
+macro_rules! impl_reflect_tuple {
+    {$($index:tt : $name:tt),*} => {
+        impl<$($name: Reflect),*> Tuple for ($($name,)*) {
+            #[inline]
+            fn field(&self, index: usize) -> Option<&dyn Reflect> {
+                match index {
+                    $($index => Some(&self.$index as &dyn Reflect),)*
+                    _ => None,
+                }
+            }
+
+            #[inline]
+            fn field_mut(&mut self, index: usize) -> Option<&mut dyn Reflect> {
+                match index {
+                    $($index => Some(&mut self.$index as &mut dyn Reflect),)*
+                    _ => None,
+                }
+            }
+
+            #[inline]
+            fn field_len(&self) -> usize {
+                let indices: &[usize] = &[$($index as usize),*];
+                indices.len()
+            }
+
+            #[inline]
+            fn iter_fields(&self) -> TupleFieldIter {
+                TupleFieldIter {
+                    tuple: self,
+                    index: 0,
+                }
+            }
+
+            #[inline]
+            fn clone_dynamic(&self) -> DynamicTuple {
+                DynamicTuple {
+                    name: self.type_name().to_string(),
+                    fields: self
+                        .iter_fields()
+                        .map(|value| value.clone_value())
+                        .collect(),
+                }
+            }
+        }
+
+        impl<$($name: Reflect),*> Reflect for ($($name,)*) {
+            fn type_name(&self) -> &str {
+                std::any::type_name::<Self>()
+            }
+
+            fn any(&self) -> &dyn Any {
+                self
+            }
+
+            fn any_mut(&mut self) -> &mut dyn Any {
+                self
+            }
+
+            fn apply(&mut self, value: &dyn Reflect) {
+                tuple_apply(self, value);
+            }
+
+            fn set(&mut self, value: Box<dyn Reflect>) -> Result<(), Box<dyn Reflect>> {
+                *self = value.take()?;
+                Ok(())
+            }
+
+            fn reflect_ref(&self) -> ReflectRef {
+                ReflectRef::Tuple(self)
+            }
+
+            fn reflect_mut(&mut self) -> ReflectMut {
+                ReflectMut::Tuple(self)
+            }
+
+            fn clone_value(&self) -> Box<dyn Reflect> {
+                Box::new(self.clone_dynamic())
+            }
+
+            fn reflect_hash(&self) -> Option<u64> {
+                None
+            }
+
+            fn reflect_partial_eq(&self, value: &dyn Reflect) -> Option<bool> {
+                tuple_partial_eq(self, value)
+            }
+
+            fn serializable(&self) -> Option<Serializable> {
+                None
+            }
+        }
+
+        impl<$($name: Reflect),*> Typed for ($($name,)*) {
+            fn type_info() -> TypeInfo {
+                TypeInfo::new::<Self>(TypeKind::Tuple {
+                    fields: vec![$(FieldInfo::unnamed::<$name>($index),)*],
+                })
+            }
+        }
+    }
+}
+
+impl_reflect_tuple! {}
+impl_reflect_tuple! {0: A}
+impl_reflect_tuple! {0: A, 1: B}
+impl_reflect_tuple! {0: A, 1: B, 2: C}
+impl_reflect_tuple! {0: A, 1: B, 2: C, 3: D}
+impl_reflect_tuple! {0: A, 1: B, 2: C, 3: D, 4: E}
+impl_reflect_tuple! {0: A, 1: B, 2: C, 3: D, 4: E, 5: F}
+impl_reflect_tuple! {0: A, 1: B, 2: C, 3: D, 4: E, 5: F, 6: G}
+impl_reflect_tuple! {0: A, 1: B, 2: C, 3: D, 4: E, 5: F, 6: G, 7: H}
+impl_reflect_tuple! {0: A, 1: B, 2: C, 3: D, 4: E, 5: F, 6: G, 7: H, 8: I}
+impl_reflect_tuple! {0: A, 1: B, 2: C, 3: D, 4: E, 5: F, 6: G, 7: H, 8: I, 9: J}
+impl_reflect_tuple! {0: A, 1: B, 2: C, 3: D, 4: E, 5: F, 6: G, 7: H, 8: I, 9: J, 10: K}
+impl_reflect_tuple! {0: A, 1: B, 2: C, 3: D, 4: E, 5: F, 6: G, 7: H, 8: I, 9: J, 10: K, 11: L}
@@ -0,0 +1,309 @@
+use crate::{Reflect, VariantType};
+use std::any::{Any, TypeId};
+
+/// A [Reflect] type whose shape is known without an instance.
+///
+/// This is implemented automatically by `#[derive(Reflect)]` and `impl_reflect_value!`, and is
+/// stored in each [TypeRegistration](crate::TypeRegistration).
+pub trait Typed: Reflect {
+    fn type_info() -> TypeInfo;
+}
+
+/// Static information about a reflected type: its name, its doc comment and its [TypeKind]
+#[derive(Debug, Clone)]
+pub struct TypeInfo {
+    type_name: &'static str,
+    type_id: TypeId,
+    docs: Option<&'static str>,
+    kind: TypeKind,
+}
+
+impl TypeInfo {
+    pub fn new<T: Any>(kind: TypeKind) -> Self {
+        TypeInfo {
+            type_name: std::any::type_name::<T>(),
+            type_id: TypeId::of::<T>(),
+            docs: None,
+            kind,
+        }
+    }
+
+    pub fn with_docs(mut self, docs: Option<&'static str>) -> Self {
+        self.docs = docs;
+        self
+    }
+
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+
+    pub fn type_id(&self) -> TypeId {
+        self.type_id
+    }
+
+    /// The doc comment of the type, if it has one
+    pub fn docs(&self) -> Option<&'static str> {
+        self.docs
+    }
+
+    pub fn kind(&self) -> &TypeKind {
+        &self.kind
+    }
+
+    /// Returns the fields of structs, tuple structs and tuples. Other kinds of types have no fields.
+    pub fn fields(&self) -> &[FieldInfo] {
+        match &self.kind {
+            TypeKind::Struct { fields }
+            | TypeKind::TupleStruct { fields }
+            | TypeKind::Tuple { fields } => fields,
+            _ => &[],
+        }
+    }
+
+    /// Returns the named field of a struct
+    pub fn field(&self, name: &str) -> Option<&FieldInfo> {
+        self.fields()
+            .iter()
+            .find(|field| field.name() == Some(name))
+    }
+
+    /// Returns the variants of an enum. Other kinds of types have no variants.
+    pub fn variants(&self) -> &[VariantInfo] {
+        match &self.kind {
+            TypeKind::Enum { variants } => variants,
+            _ => &[],
+        }
+    }
+
+    pub fn variant(&self, name: &str) -> Option<&VariantInfo> {
+        self.variants()
+            .iter()
+            .find(|variant| variant.name() == name)
+    }
+}
+
+/// The shape of a reflected type. This matches the [ReflectRef](crate::ReflectRef) variant that
+/// values of the type produce.
+#[derive(Debug, Clone)]
+pub enum TypeKind {
+    Struct {
+        fields: Vec<FieldInfo>,
+    },
+    TupleStruct {
+        fields: Vec<FieldInfo>,
+    },
+    Tuple {
+        fields: Vec<FieldInfo>,
+    },
+    List {
+        item_type_name: &'static str,
+        item_type_id: TypeId,
+    },
+    Array {
+        item_type_name: &'static str,
+        item_type_id: TypeId,
+        len: usize,
+    },
+    Map {
+        key_type_name: &'static str,
+        key_type_id: TypeId,
+        value_type_name: &'static str,
+        value_type_id: TypeId,
+    },
+    Enum {
+        variants: Vec<VariantInfo>,
+    },
+    Value,
+}
+
+impl TypeKind {
+    pub fn list<T: Any>() -> Self {
+        TypeKind::List {
+            item_type_name: std::any::type_name::<T>(),
+            item_type_id: TypeId::of::<T>(),
+        }
+    }
+
+    pub fn array<T: Any>(len: usize) -> Self {
+        TypeKind::Array {
+            item_type_name: std::any::type_name::<T>(),
+            item_type_id: TypeId::of::<T>(),
+            len,
+        }
+    }
+
+    pub fn map<K: Any, V: Any>() -> Self {
+        TypeKind::Map {
+            key_type_name: std::any::type_name::<K>(),
+            key_type_id: TypeId::of::<K>(),
+            value_type_name: std::any::type_name::<V>(),
+            value_type_id: TypeId::of::<V>(),
+        }
+    }
+}
+
+/// A field of a struct, tuple struct, tuple or enum variant. Fields of tuple-like types don't have names.
+#[derive(Debug, Clone)]
+pub struct FieldInfo {
+    name: Option<&'static str>,
+    index: usize,
+    type_name: &'static str,
+    type_id: TypeId,
+    docs: Option<&'static str>,
+    range: Option<(f64, f64)>,
+}
+
+impl FieldInfo {
+    pub fn named<T: Any>(index: usize, name: &'static str) -> Self {
+        FieldInfo {
+            name: Some(name),
+            ..Self::unnamed::<T>(index)
+        }
+    }
+
+    pub fn unnamed<T: Any>(index: usize) -> Self {
+        FieldInfo {
+            name: None,
+            index,
+            type_name: std::any::type_name::<T>(),
+            type_id: TypeId::of::<T>(),
+            docs: None,
+            range: None,
+        }
+    }
+
+    pub fn with_docs(mut self, docs: Option<&'static str>) -> Self {
+        self.docs = docs;
+        self
+    }
+
+    /// Sets the inclusive range of valid values of a numeric field. The derive sets this from
+    /// `#[reflect(range(min, max))]`.
+    pub fn with_range(mut self, min: f64, max: f64) -> Self {
+        self.range = Some((min, max));
+        self
+    }
+
+    pub fn name(&self) -> Option<&'static str> {
+        self.name
+    }
+
+    /// The index of the field among the reflected fields of its type
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+
+    pub fn type_id(&self) -> TypeId {
+        self.type_id
+    }
+
+    pub fn docs(&self) -> Option<&'static str> {
+        self.docs
+    }
+
+    pub fn range(&self) -> Option<(f64, f64)> {
+        self.range
+    }
+}
+
+/// A variant of an enum
+#[derive(Debug, Clone)]
+pub struct VariantInfo {
+    name: &'static str,
+    variant_type: VariantType,
+    fields: Vec<FieldInfo>,
+    docs: Option<&'static str>,
+}
+
+impl VariantInfo {
+    pub fn new(name: &'static str, variant_type: VariantType, fields: Vec<FieldInfo>) -> Self {
+        VariantInfo {
+            name,
+            variant_type,
+            fields,
+            docs: None,
+        }
+    }
+
+    pub fn with_docs(mut self, docs: Option<&'static str>) -> Self {
+        self.docs = docs;
+        self
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    pub fn variant_type(&self) -> VariantType {
+        self.variant_type
+    }
+
+    pub fn fields(&self) -> &[FieldInfo] {
+        &self.fields
+    }
+
+    pub fn field(&self, name: &str) -> Option<&FieldInfo> {
+        self.fields.iter().find(|field| field.name() == Some(name))
+    }
+
+    pub fn docs(&self) -> Option<&'static str> {
+        self.docs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Reflect, TypeKind, TypeRegistration, TypeRegistry, Typed};
+    use std::any::{type_name, TypeId};
+
+    /// A creature
+    #[derive(Reflect)]
+    struct Creature {
+        /// Current health points
+        #[reflect(range(0, 100))]
+        health: u32,
+        mode: Mode,
+        #[reflect(ignore)]
+        _cache: Vec<u8>,
+    }
+
+    #[derive(Reflect)]
+    enum Mode {
+        Idle,
+        Wait(f32),
+    }
+
+    #[test]
+    fn type_info_from_registry() {
+        let mut registry = TypeRegistry::default();
+        registry.register::<Creature>();
+        let type_info = registry.get_type_info(TypeId::of::<Creature>()).unwrap();
+
+        assert_eq!(type_info.docs(), Some("A creature"));
+        assert_eq!(type_info.fields().len(), 2);
+        let health = type_info.field("health").unwrap();
+        assert_eq!(health.type_name(), "u32");
+        assert_eq!(health.docs(), Some("Current health points"));
+        assert_eq!(health.range(), Some((0.0, 100.0)));
+        assert_eq!(type_info.field("mode").unwrap().range(), None);
+
+        let mode_info = Mode::type_info();
+        assert!(matches!(mode_info.kind(), TypeKind::Enum { .. }));
+        assert_eq!(
+            mode_info.variant("Wait").unwrap().fields()[0].type_name(),
+            "f32"
+        );
+
+        // the shape of types registered without type info isn't known
+        let registration = TypeRegistration::of::<Creature>();
+        assert!(matches!(registration.type_info().kind(), TypeKind::Value));
+        assert_eq!(
+            registration.type_info().type_name(),
+            type_name::<Creature>()
+        );
+    }
+}
@@ -1,4 +1,7 @@
-use crate::Reflect;
+use crate::{
+    ArgInfo, Args, FunctionError, FunctionResult, IntoReflectFunction, IntoReflectMethod, Reflect,
+    ReflectFunction, ReflectMethod, TypeInfo, TypeKind, Typed,
+};
 use bevy_utils::{HashMap, HashSet};
 use downcast_rs::{impl_downcast, Downcast};
 use parking_lot::{RwLock, RwLockReadGuard, RwLockWriteGuard};
@@ -11,6 +14,7 @@ pub struct TypeRegistry {
     short_name_to_id: HashMap<String, TypeId>,
     full_name_to_id: HashMap<String, TypeId>,
     ambiguous_names: HashSet<String>,
+    functions: HashMap<String, ReflectFunction>,
 }
 
 // TODO:  remove this wrapper once we migrate to Atelier Assets and the Scene AssetLoader doesn't need a TypeRegistry ref
@@ -37,11 +41,21 @@ impl TypeRegistry {
         self.add_registration(T::get_type_registration());
     }
 
-    pub fn add_registration(&mut self, registration: TypeRegistration) {
+    /// Adds the registration of a type. Re-registering a type replaces its type data, but keeps the methods that
+    /// the new registration doesn't define.
+    pub fn add_registration(&mut self, mut registration: TypeRegistration) {
+        if let Some(existing) = self.registrations.remove(&registration.type_id) {
+            for (name, method) in existing.methods {
+                registration.methods.entry(name).or_insert(method);
+            }
+        }
+
         let short_name = registration.short_name.to_string();
-        if self.short_name_to_id.contains_key(&short_name)
-            || self.ambiguous_names.contains(&short_name)
-        {
+        let other_type_has_name = self
+            .short_name_to_id
+            .get(&short_name)
+            .map_or(false, |type_id| *type_id != registration.type_id);
+        if other_type_has_name || self.ambiguous_names.contains(&short_name) {
             // name is ambiguous. fall back to long names for all ambiguous types
             self.short_name_to_id.remove(&short_name);
             self.ambiguous_names.insert(short_name);
@@ -82,6 +96,12 @@ impl TypeRegistry {
             .and_then(|id| self.registrations.get(id))
     }
 
+    /// Returns the static [TypeInfo] of the registered type with the given id
+    pub fn get_type_info(&self, type_id: TypeId) -> Option<&TypeInfo> {
+        self.get(type_id)
+            .map(|registration| registration.type_info())
+    }
+
     pub fn get_type_data<T: TypeData>(&self, type_id: TypeId) -> Option<&T> {
         self.get(type_id)
             .and_then(|registration| registration.data::<T>())
@@ -90,6 +110,89 @@ impl TypeRegistry {
     pub fn iter(&self) -> impl Iterator<Item = &TypeRegistration> {
         self.registrations.values()
     }
+
+    /// Registers a free function that can be called by name, e.g. from a script or a developer console
+    pub fn register_function<Marker, F: IntoReflectFunction<Marker>>(
+        &mut self,
+        name: &str,
+        function: F,
+    ) {
+        self.functions
+            .insert(name.to_string(), ReflectFunction::new(name, function));
+    }
+
+    pub fn get_function(&self, name: &str) -> Option<&ReflectFunction> {
+        self.functions.get(name)
+    }
+
+    pub fn iter_functions(&self) -> impl Iterator<Item = &ReflectFunction> {
+        self.functions.values()
+    }
+
+    pub fn call_function(&self, name: &str, mut args: Args) -> FunctionResult {
+        let function = self
+            .get_function(name)
+            .ok_or_else(|| FunctionError::NotFound {
+                name: name.to_string(),
+            })?;
+        self.convert_dynamic_args(function.args(), &mut args);
+        function.call(args)
+    }
+
+    /// Registers a method of `T`, registering `T` first if necessary
+    pub fn register_method<T, Marker, F>(&mut self, name: &str, method: F)
+    where
+        T: GetTypeRegistration + Reflect,
+        F: IntoReflectMethod<T, Marker>,
+    {
+        let type_id = TypeId::of::<T>();
+        if !self.registrations.contains_key(&type_id) {
+            self.register::<T>();
+        }
+        self.get_mut(type_id)
+            .unwrap()
+            .add_method(ReflectMethod::new(name, method));
+    }
+
+    /// Calls the method `name` of the receiver's registered type
+    pub fn call_method(
+        &self,
+        receiver: &mut dyn Reflect,
+        name: &str,
+        mut args: Args,
+    ) -> FunctionResult {
+        let registration = self.get_with_name(receiver.type_name()).ok_or_else(|| {
+            FunctionError::UnregisteredType {
+                type_name: receiver.type_name().to_string(),
+            }
+        })?;
+        let method = registration
+            .method(name)
+            .ok_or_else(|| FunctionError::NotFound {
+                name: name.to_string(),
+            })?;
+        self.convert_dynamic_args(method.args(), &mut args);
+        method.call(receiver, args)
+    }
+
+    /// Replaces dynamic proxies, like a [DynamicStruct](crate::DynamicStruct) named after the argument type, with
+    /// a default value of the argument type that the proxy is applied to. This needs the argument type to be
+    /// registered with [ReflectDefault]. Other arguments are left as they are.
+    fn convert_dynamic_args(&self, arg_infos: &[ArgInfo], args: &mut Args) {
+        for (arg, arg_info) in args.iter_mut().zip(arg_infos.iter()) {
+            if arg.any().type_id() == arg_info.type_id || arg.type_name() != arg_info.type_name {
+                continue;
+            }
+            if let Some(reflect_default) = self
+                .get(arg_info.type_id)
+                .and_then(|registration| registration.data::<ReflectDefault>())
+            {
+                let mut value = reflect_default.default();
+                value.apply(&**arg);
+                *arg = value;
+            }
+        }
+    }
 }
 
 impl TypeRegistryArc {
@@ -106,7 +209,9 @@ pub struct TypeRegistration {
     type_id: TypeId,
     short_name: String,
     name: &'static str,
+    type_info: TypeInfo,
     data: HashMap<TypeId, Box<dyn TypeData>>,
+    methods: HashMap<String, ReflectMethod>,
 }
 
 impl TypeRegistration {
@@ -131,17 +236,52 @@ impl TypeRegistration {
         self.data.insert(TypeId::of::<T>(), Box::new(data));
     }
 
+    /// Adds a method to the type. Panics if the method's receiver is a different type.
+    pub fn add_method(&mut self, method: ReflectMethod) {
+        assert_eq!(
+            method.receiver().type_id,
+            self.type_id,
+            "the receiver of {} is not a {}",
+            method.name(),
+            self.name
+        );
+        self.methods.insert(method.name().to_string(), method);
+    }
+
+    pub fn method(&self, name: &str) -> Option<&ReflectMethod> {
+        self.methods.get(name)
+    }
+
+    pub fn iter_methods(&self) -> impl Iterator<Item = &ReflectMethod> {
+        self.methods.values()
+    }
+
+    /// Creates the registration of a type whose shape isn't known. Its [TypeInfo] describes it as a [TypeKind::Value].
+    /// Use [TypeRegistration::of_typed] for types that implement [Typed].
     pub fn of<T: Reflect>() -> Self {
         let ty = TypeId::of::<T>();
         let type_name = std::any::type_name::<T>();
         Self {
             type_id: ty,
             data: HashMap::default(),
+            methods: HashMap::default(),
             name: type_name,
             short_name: Self::get_short_name(type_name),
+            type_info: TypeInfo::new::<T>(TypeKind::Value),
         }
     }
 
+    pub fn of_typed<T: Typed>() -> Self {
+        let mut registration = Self::of::<T>();
+        registration.type_info = T::type_info();
+        registration
+    }
+
+    /// Returns the static shape of the type, like its fields or variants
+    pub fn type_info(&self) -> &TypeInfo {
+        &self.type_info
+    }
+
     pub fn short_name(&self) -> &str {
         &self.short_name
     }
@@ -200,6 +340,8 @@ impl Clone for TypeRegistration {
             name: self.name,
             short_name: self.short_name.clone(),
             type_id: self.type_id,
+            type_info: self.type_info.clone(),
+            methods: self.methods.clone(),
         }
     }
 }
@@ -248,6 +390,30 @@ impl<T: for<'a> Deserialize<'a> + Reflect> FromType<T> for ReflectDeserialize {
     }
 }
 
+/// Creates default values of a type. Added with `#[reflect(Default)]`.
+#[derive(Clone)]
+pub struct ReflectDefault {
+    default: fn() -> Box<dyn Reflect>,
+}
+
+impl ReflectDefault {
+    pub fn default(&self) -> Box<dyn Reflect> {
+        (self.default)()
+    }
+}
+
+impl<T: Reflect + Default> FromType<T> for ReflectDefault {
+    fn from_type() -> Self {
+        ReflectDefault {
+            default: || Box::new(T::default()),
+        }
+    }
+}
+
+/// Marks a type whose values are left out when scenes are serialized. Added with `#[reflect(skip_serializing)]`.
+#[derive(Clone, Default)]
+pub struct SkipSerializing;
+
 #[cfg(test)]
 mod test {
     use crate::TypeRegistration;
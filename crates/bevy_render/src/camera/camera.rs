@@ -1,8 +1,11 @@
 use super::CameraProjection;
+use crate::{primitives::Frustum, texture::Texture};
 use bevy_app::prelude::{EventReader, Events};
+use bevy_asset::{AssetEvent, Assets, Handle};
 use bevy_ecs::{Added, Component, Entity, Local, Query, QuerySet, Res};
 use bevy_math::Mat4;
 use bevy_reflect::{Reflect, ReflectComponent};
+use bevy_transform::components::GlobalTransform;
 use bevy_window::{WindowCreated, WindowId, WindowResized, Windows};
 
 #[derive(Default, Debug, Reflect)]
@@ -11,11 +14,34 @@ pub struct Camera {
     pub projection_matrix: Mat4,
     pub name: Option<String>,
     #[reflect(ignore)]
-    pub window: WindowId,
+    pub target: RenderTarget,
     #[reflect(ignore)]
     pub depth_calculation: DepthCalculation,
 }
 
+impl Camera {
+    /// Returns the world space volume visible to the camera, or `None` if the camera has no valid projection yet
+    pub fn frustum(&self, global_transform: &GlobalTransform) -> Option<Frustum> {
+        let view_projection = self.projection_matrix * global_transform.compute_matrix().inverse();
+        Frustum::from_view_projection(&view_projection)
+    }
+}
+
+/// Where a camera renders to. Cameras that render to a [Texture] get their own pass in the render graph, which draws
+/// [MainPass](crate::render_graph::base::MainPass) entities into the texture. Create the texture with
+/// [Texture::new_render_target] and give the camera a unique name.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RenderTarget {
+    Window(WindowId),
+    Texture(Handle<Texture>),
+}
+
+impl Default for RenderTarget {
+    fn default() -> Self {
+        RenderTarget::Window(WindowId::primary())
+    }
+}
+
 #[derive(Debug)]
 pub enum DepthCalculation {
     Distance,
@@ -32,6 +58,7 @@ impl Default for DepthCalculation {
 pub struct CameraSystemState {
     window_resized_event_reader: EventReader<WindowResized>,
     window_created_event_reader: EventReader<WindowCreated>,
+    texture_event_reader: EventReader<AssetEvent<Texture>>,
 }
 
 pub fn camera_system<T: CameraProjection + Component>(
@@ -39,6 +66,8 @@ pub fn camera_system<T: CameraProjection + Component>(
     window_resized_events: Res<Events<WindowResized>>,
     window_created_events: Res<Events<WindowCreated>>,
     windows: Res<Windows>,
+    textures: Res<Assets<Texture>>,
+    texture_events: Res<Events<AssetEvent<Texture>>>,
     mut queries: QuerySet<(
         Query<(Entity, &mut Camera, &mut T)>,
         Query<Entity, Added<Camera>>,
@@ -71,17 +100,97 @@ pub fn camera_system<T: CameraProjection + Component>(
         changed_window_ids.push(event.id);
     }
 
+    let mut changed_texture_handles = Vec::new();
+    for event in state.texture_event_reader.iter(&texture_events) {
+        if let AssetEvent::Created { handle } | AssetEvent::Modified { handle } = event {
+            changed_texture_handles.push(handle.clone_weak());
+        }
+    }
+
     let mut added_cameras = vec![];
     for entity in &mut queries.q1().iter() {
         added_cameras.push(entity);
     }
     for (entity, mut camera, mut camera_projection) in queries.q0_mut().iter_mut() {
-        if let Some(window) = windows.get(camera.window) {
-            if changed_window_ids.contains(&window.id()) || added_cameras.contains(&entity) {
-                camera_projection.update(window.logical_width(), window.logical_height());
-                camera.projection_matrix = camera_projection.get_projection_matrix();
-                camera.depth_calculation = camera_projection.depth_calculation();
-            }
+        let size = match camera.target {
+            RenderTarget::Window(window_id) => windows.get(window_id).and_then(|window| {
+                if changed_window_ids.contains(&window.id()) || added_cameras.contains(&entity) {
+                    Some((window.logical_width(), window.logical_height()))
+                } else {
+                    None
+                }
+            }),
+            RenderTarget::Texture(ref handle) => textures.get(handle).and_then(|texture| {
+                if changed_texture_handles.contains(handle) || added_cameras.contains(&entity) {
+                    Some((texture.size.width as f32, texture.size.height as f32))
+                } else {
+                    None
+                }
+            }),
+        };
+
+        if let Some((width, height)) = size {
+            camera_projection.update(width, height);
+            camera.projection_matrix = camera_projection.get_projection_matrix();
+            camera.depth_calculation = camera_projection.depth_calculation();
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{camera::PerspectiveProjection, texture::Extent3d};
+    use bevy_app::App;
+    use bevy_asset::{AddAsset, AssetPlugin};
+    use bevy_core::CorePlugin;
+    use bevy_reflect::ReflectPlugin;
+
+    #[test]
+    fn texture_target_projection() {
+        let mut app_builder = App::build();
+        app_builder
+            .add_plugin(ReflectPlugin)
+            .add_plugin(CorePlugin)
+            .add_plugin(AssetPlugin)
+            .add_asset::<Texture>()
+            .add_event::<WindowResized>()
+            .add_event::<WindowCreated>()
+            .init_resource::<Windows>()
+            .add_system(camera_system::<PerspectiveProjection>);
+        let mut app = app_builder.app;
+        let texture = app
+            .resources
+            .get_mut::<Assets<Texture>>()
+            .unwrap()
+            .add(Texture::new_render_target(Extent3d::new(200, 100, 1)));
+        let camera = app.world.spawn((
+            Camera {
+                target: RenderTarget::Texture(texture.clone()),
+                ..Default::default()
+            },
+            PerspectiveProjection::default(),
+        ));
+
+        // the projection uses the size of the texture instead of the primary window's
+        app.update();
+        let aspect_ratio = |app: &App| {
+            app.world
+                .get::<PerspectiveProjection>(camera)
+                .unwrap()
+                .aspect_ratio
+        };
+        assert_eq!(aspect_ratio(&app), 2.0);
+
+        // and follows resizes of the texture, once its modified event is sent at the end of the frame
+        app.resources
+            .get_mut::<Assets<Texture>>()
+            .unwrap()
+            .get_mut(&texture)
+            .unwrap()
+            .resize(Extent3d::new(100, 100, 1));
+        app.update();
+        app.update();
+        assert_eq!(aspect_ratio(&app), 1.0);
+    }
+}
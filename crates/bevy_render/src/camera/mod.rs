@@ -2,9 +2,11 @@ mod active_cameras;
 #[allow(clippy::module_inception)]
 mod camera;
 mod projection;
+mod render_layers;
 mod visible_entities;
 
 pub use active_cameras::*;
 pub use camera::*;
 pub use projection::*;
+pub use render_layers::*;
 pub use visible_entities::*;
@@ -0,0 +1,99 @@
+use bevy_reflect::{Reflect, ReflectComponent};
+
+pub type Layer = u8;
+
+/// The layers an entity is on, or the layers a camera renders. A camera only sees entities that share at least one
+/// layer with it. Entities and cameras without [RenderLayers] are on layer 0.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+#[reflect(Component, PartialEq)]
+pub struct RenderLayers(u32);
+
+impl Default for RenderLayers {
+    fn default() -> Self {
+        RenderLayers::layer(0)
+    }
+}
+
+impl std::iter::FromIterator<Layer> for RenderLayers {
+    fn from_iter<T: IntoIterator<Item = Layer>>(layers: T) -> Self {
+        layers
+            .into_iter()
+            .fold(RenderLayers::none(), |render_layers, layer| {
+                render_layers.with(layer)
+            })
+    }
+}
+
+impl RenderLayers {
+    pub const TOTAL_LAYERS: usize = std::mem::size_of::<u32>() * 8;
+
+    /// Panics if `layer` is not below [RenderLayers::TOTAL_LAYERS]
+    pub fn layer(layer: Layer) -> Self {
+        RenderLayers::none().with(layer)
+    }
+
+    pub fn all() -> Self {
+        RenderLayers(u32::MAX)
+    }
+
+    pub fn none() -> Self {
+        RenderLayers(0)
+    }
+
+    /// Adds `layer`. Panics if `layer` is not below [RenderLayers::TOTAL_LAYERS].
+    pub fn with(mut self, layer: Layer) -> Self {
+        assert!(
+            (layer as usize) < Self::TOTAL_LAYERS,
+            "layer {} is out of range",
+            layer
+        );
+        self.0 |= 1 << layer;
+        self
+    }
+
+    /// Removes `layer`. Panics if `layer` is not below [RenderLayers::TOTAL_LAYERS].
+    pub fn without(mut self, layer: Layer) -> Self {
+        assert!(
+            (layer as usize) < Self::TOTAL_LAYERS,
+            "layer {} is out of range",
+            layer
+        );
+        self.0 &= !(1 << layer);
+        self
+    }
+
+    pub fn contains(&self, layer: Layer) -> bool {
+        (layer as usize) < Self::TOTAL_LAYERS && self.0 & (1 << layer) != 0
+    }
+
+    /// Returns true if `self` and `other` share at least one layer
+    pub fn intersects(&self, other: &RenderLayers) -> bool {
+        self.0 & other.0 != 0
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Layer> + '_ {
+        (0..Self::TOTAL_LAYERS as Layer).filter(move |layer| self.contains(*layer))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RenderLayers;
+
+    #[test]
+    fn render_layers() {
+        let layers = RenderLayers::layer(1).with(5).with(31);
+        assert!(layers.contains(5));
+        assert!(!layers.contains(0));
+        assert_eq!(layers.iter().collect::<Vec<_>>(), vec![1, 5, 31]);
+        assert_eq!(
+            layers.without(5),
+            vec![1, 31].into_iter().collect::<RenderLayers>()
+        );
+
+        assert!(layers.intersects(&RenderLayers::layer(31)));
+        assert!(!layers.intersects(&RenderLayers::default()));
+        assert!(RenderLayers::all().intersects(&RenderLayers::default()));
+        assert!(!RenderLayers::none().intersects(&RenderLayers::all()));
+    }
+}
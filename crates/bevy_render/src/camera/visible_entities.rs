@@ -1,9 +1,12 @@
-use super::{Camera, DepthCalculation};
-use crate::prelude::Visible;
+use super::{Camera, DepthCalculation, RenderLayers};
+use crate::{mesh::Mesh, pipeline::Instanced, prelude::Visible, primitives::Aabb};
+use bevy_app::prelude::{EventReader, Events};
+use bevy_asset::{AssetEvent, Assets, Handle};
 use bevy_core::FloatOrd;
-use bevy_ecs::{Entity, Query, With};
+use bevy_ecs::{Changed, Commands, Entity, Local, Query, QuerySet, Res, With, Without};
 use bevy_reflect::{Reflect, ReflectComponent};
 use bevy_transform::prelude::GlobalTransform;
+use bevy_utils::HashSet;
 
 #[derive(Debug)]
 pub struct VisibleEntity {
@@ -12,7 +15,7 @@ pub struct VisibleEntity {
 }
 
 #[derive(Default, Debug, Reflect)]
-#[reflect(Component)]
+#[reflect(Component, skip_serializing)]
 pub struct VisibleEntities {
     #[reflect(ignore)]
     pub value: Vec<VisibleEntity>,
@@ -24,23 +27,110 @@ impl VisibleEntities {
     }
 }
 
+/// Opts an entity out of frustum culling. Its [Aabb] is neither computed nor checked against camera frustums.
+#[derive(Debug, Default, Clone, Copy, Reflect)]
+#[reflect(Component)]
+pub struct NoFrustumCulling;
+
+#[derive(Default)]
+pub struct CalculateBoundsState {
+    mesh_event_reader: EventReader<AssetEvent<Mesh>>,
+}
+
+/// Adds an [Aabb] to entities with a `Handle<Mesh>` and keeps it in sync with changes to the mesh
+pub fn calculate_bounds_system(
+    commands: &mut Commands,
+    mut state: Local<CalculateBoundsState>,
+    meshes: Res<Assets<Mesh>>,
+    mesh_events: Res<Events<AssetEvent<Mesh>>>,
+    mut queries: QuerySet<(
+        Query<(Entity, &Handle<Mesh>), (Without<Aabb>, Without<NoFrustumCulling>)>,
+        Query<(&Handle<Mesh>, &mut Aabb), Changed<Handle<Mesh>>>,
+        Query<(&Handle<Mesh>, &mut Aabb)>,
+    )>,
+) {
+    for (entity, handle) in queries.q0().iter() {
+        if let Some(aabb) = meshes.get(handle).and_then(|mesh| mesh.compute_aabb()) {
+            commands.insert_one(entity, aabb);
+        }
+    }
+
+    for (handle, mut aabb) in queries.q1_mut().iter_mut() {
+        if let Some(mesh_aabb) = meshes.get(handle).and_then(|mesh| mesh.compute_aabb()) {
+            *aabb = mesh_aabb;
+        }
+    }
+
+    let mut changed_meshes = HashSet::default();
+    for event in state.mesh_event_reader.iter(&mesh_events) {
+        if let AssetEvent::Modified { handle } = event {
+            changed_meshes.insert(handle.clone_weak());
+        }
+    }
+    if changed_meshes.is_empty() {
+        return;
+    }
+    for (handle, mut aabb) in queries.q2_mut().iter_mut() {
+        if !changed_meshes.contains(handle) {
+            continue;
+        }
+        if let Some(mesh_aabb) = meshes.get(handle).and_then(|mesh| mesh.compute_aabb()) {
+            *aabb = mesh_aabb;
+        }
+    }
+}
+
+/// Collects the visible entities of each camera. Entities that don't share a [RenderLayers] layer with the camera are
+/// skipped. Entities with an [Aabb] and a [GlobalTransform] are skipped if they are outside of the camera's frustum,
+/// unless they have the [NoFrustumCulling] marker or are [Instanced].
 pub fn visible_entities_system(
-    mut camera_query: Query<(&Camera, &GlobalTransform, &mut VisibleEntities)>,
-    visible_query: Query<(Entity, &Visible)>,
+    mut camera_query: Query<(
+        &Camera,
+        &GlobalTransform,
+        &mut VisibleEntities,
+        Option<&RenderLayers>,
+    )>,
+    visible_query: Query<(
+        Entity,
+        &Visible,
+        Option<&RenderLayers>,
+        Option<&Aabb>,
+        Option<&NoFrustumCulling>,
+        Option<&Instanced>,
+    )>,
     visible_transform_query: Query<&GlobalTransform, With<Visible>>,
 ) {
-    for (camera, camera_global_transform, mut visible_entities) in camera_query.iter_mut() {
+    for (camera, camera_global_transform, mut visible_entities, camera_layers) in
+        camera_query.iter_mut()
+    {
         visible_entities.value.clear();
         let camera_position = camera_global_transform.translation;
+        let camera_layers = camera_layers.cloned().unwrap_or_default();
+        let frustum = camera.frustum(camera_global_transform);
 
         let mut no_transform_order = 0.0;
         let mut transparent_entities = Vec::new();
-        for (entity, visible) in visible_query.iter() {
+        for (entity, visible, render_layers, aabb, no_frustum_culling, instanced) in
+            visible_query.iter()
+        {
             if !visible.is_visible {
                 continue;
             }
 
+            if !camera_layers.intersects(&render_layers.cloned().unwrap_or_default()) {
+                continue;
+            }
+
             let order = if let Ok(global_transform) = visible_transform_query.get(entity) {
+                // cameras without a valid projection can't cull anything
+                if let (Some(frustum), Some(aabb), None, None) =
+                    (&frustum, aabb, no_frustum_culling, instanced)
+                {
+                    if !frustum.intersects_obb(aabb, &global_transform.compute_matrix()) {
+                        continue;
+                    }
+                }
+
                 let position = global_transform.translation;
                 // smaller distances are sorted to lower indices by using the distance from the camera
                 FloatOrd(match camera.depth_calculation {
@@ -70,3 +160,172 @@ pub fn visible_entities_system(
         // TODO: check for big changes in visible entities len() vs capacity() (ex: 2x) and resize to prevent holding unneeded memory
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        camera::{CameraProjection, PerspectiveProjection},
+        pipeline::PrimitiveTopology,
+    };
+    use bevy_app::App;
+    use bevy_asset::{AddAsset, AssetPlugin};
+    use bevy_core::CorePlugin;
+    use bevy_ecs::{Resources, Schedule, SystemStage, World};
+    use bevy_math::Vec3;
+    use bevy_reflect::ReflectPlugin;
+
+    #[test]
+    fn frustum_culling() {
+        let mut world = World::default();
+        let mut resources = Resources::default();
+        let mut schedule = Schedule::default();
+        let mut update_stage = SystemStage::parallel();
+        update_stage.add_system(visible_entities_system);
+        schedule.add_stage("update", update_stage);
+
+        let camera = world.spawn((
+            Camera {
+                projection_matrix: PerspectiveProjection::default().get_projection_matrix(),
+                ..Default::default()
+            },
+            GlobalTransform::identity(),
+            VisibleEntities::default(),
+        ));
+        // a camera without a projection can't cull anything
+        let unprojected_camera = world.spawn((
+            Camera::default(),
+            GlobalTransform::identity(),
+            VisibleEntities::default(),
+        ));
+        let aabb = Aabb::from_min_max(Vec3::splat(-1.0), Vec3::splat(1.0));
+        let in_front = world.spawn((
+            Visible::default(),
+            aabb,
+            GlobalTransform::from_translation(Vec3::new(0.0, 0.0, -10.0)),
+        ));
+        world.spawn((
+            Visible::default(),
+            aabb,
+            GlobalTransform::from_translation(Vec3::new(0.0, 0.0, 10.0)),
+        ));
+        let not_culled = world.spawn((
+            Visible::default(),
+            aabb,
+            NoFrustumCulling,
+            GlobalTransform::from_translation(Vec3::new(0.0, 0.0, 12.0)),
+        ));
+        let without_bounds = world.spawn((
+            Visible::default(),
+            GlobalTransform::from_translation(Vec3::new(0.0, 0.0, 20.0)),
+        ));
+
+        schedule.initialize_and_run(&mut world, &mut resources);
+
+        let visible_entities = world.get::<VisibleEntities>(camera).unwrap();
+        let entities = visible_entities
+            .iter()
+            .map(|visible_entity| visible_entity.entity)
+            .collect::<Vec<_>>();
+        assert_eq!(entities, vec![in_front, not_culled, without_bounds]);
+        let visible_entities = world.get::<VisibleEntities>(unprojected_camera).unwrap();
+        assert_eq!(visible_entities.iter().count(), 4);
+    }
+
+    fn mesh(min: f32, max: f32) -> Mesh {
+        let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
+        mesh.set_attribute(Mesh::ATTRIBUTE_POSITION, vec![[min; 3], [max; 3]]);
+        mesh
+    }
+
+    #[test]
+    fn calculate_bounds() {
+        let mut app_builder = App::build();
+        app_builder
+            .add_plugin(ReflectPlugin)
+            .add_plugin(CorePlugin)
+            .add_plugin(AssetPlugin)
+            .add_asset::<Mesh>()
+            .add_system(calculate_bounds_system);
+        let mut app = app_builder.app;
+        let (small, large) = {
+            let mut meshes = app.resources.get_mut::<Assets<Mesh>>().unwrap();
+            (meshes.add(mesh(-1.0, 1.0)), meshes.add(mesh(-2.0, 2.0)))
+        };
+        let entity = app.world.spawn((small.clone(),));
+        let not_culled = app.world.spawn((small, NoFrustumCulling));
+        let aabb = |app: &App| *app.world.get::<Aabb>(entity).unwrap();
+
+        app.update();
+        assert_eq!(
+            aabb(&app),
+            Aabb::from_min_max(Vec3::splat(-1.0), Vec3::splat(1.0))
+        );
+        assert!(app.world.get::<Aabb>(not_culled).is_err());
+
+        // the box follows changes to the mesh handle
+        *app.world.get_mut::<Handle<Mesh>>(entity).unwrap() = large.clone();
+        app.update();
+        assert_eq!(
+            aabb(&app),
+            Aabb::from_min_max(Vec3::splat(-2.0), Vec3::splat(2.0))
+        );
+
+        // and to the mesh itself, once its modified event is sent at the end of the frame
+        *app.resources
+            .get_mut::<Assets<Mesh>>()
+            .unwrap()
+            .get_mut(&large)
+            .unwrap() = mesh(-3.0, 3.0);
+        app.update();
+        app.update();
+        assert_eq!(
+            aabb(&app),
+            Aabb::from_min_max(Vec3::splat(-3.0), Vec3::splat(3.0))
+        );
+    }
+
+    #[test]
+    fn render_layers() {
+        let mut world = World::default();
+        let mut resources = Resources::default();
+        let mut schedule = Schedule::default();
+        let mut update_stage = SystemStage::parallel();
+        update_stage.add_system(visible_entities_system);
+        schedule.add_stage("update", update_stage);
+
+        let main_camera = world.spawn((
+            Camera::default(),
+            GlobalTransform::identity(),
+            VisibleEntities::default(),
+        ));
+        let gizmo_camera = world.spawn((
+            Camera::default(),
+            GlobalTransform::identity(),
+            VisibleEntities::default(),
+            RenderLayers::layer(1),
+        ));
+        let scene = world.spawn((Visible::default(),));
+        let gizmo = world.spawn((Visible::default(), RenderLayers::layer(1)));
+        let everywhere = world.spawn((Visible::default(), RenderLayers::all()));
+
+        schedule.initialize_and_run(&mut world, &mut resources);
+
+        let visible = |camera| {
+            let mut entities = world
+                .get::<VisibleEntities>(camera)
+                .unwrap()
+                .iter()
+                .map(|visible_entity| visible_entity.entity)
+                .collect::<Vec<_>>();
+            entities.sort();
+            entities
+        };
+        let mut expected = vec![scene, everywhere];
+        expected.sort();
+        assert_eq!(visible(main_camera), expected);
+        let mut expected = vec![gizmo, everywhere];
+        expected.sort();
+        assert_eq!(visible(gizmo_camera), expected);
+    }
+}
@@ -6,8 +6,10 @@ pub mod entity;
 pub mod mesh;
 pub mod pass;
 pub mod pipeline;
+pub mod primitives;
 pub mod render_graph;
 pub mod renderer;
+pub mod screenshot;
 pub mod shader;
 pub mod texture;
 
@@ -24,7 +26,7 @@ pub mod prelude {
         entity::*,
         mesh::{shape, Mesh},
         pass::ClearColor,
-        pipeline::RenderPipelines,
+        pipeline::{InstanceAttributes, Instanced, RenderPipelines},
         shader::Shader,
         texture::Texture,
     };
@@ -35,23 +37,29 @@ use base::Msaa;
 use bevy_app::prelude::*;
 use bevy_asset::AddAsset;
 use camera::{
-    ActiveCameras, Camera, OrthographicProjection, PerspectiveProjection, VisibleEntities,
+    ActiveCameras, Camera, NoFrustumCulling, OrthographicProjection, PerspectiveProjection,
+    RenderLayers, VisibleEntities,
 };
 use pipeline::{
-    IndexFormat, PipelineCompiler, PipelineDescriptor, PipelineSpecialization, PrimitiveTopology,
-    ShaderSpecialization,
+    IndexFormat, Instanced, PipelineCompiler, PipelineDescriptor, PipelineSpecialization,
+    PrimitiveTopology, ShaderSpecialization,
 };
 use render_graph::{
     base::{self, BaseRenderGraphBuilder, BaseRenderGraphConfig, MainPass},
     RenderGraph,
 };
 use renderer::{AssetRenderResourceBindings, RenderResourceBindings};
+use screenshot::{ScreenshotCaptured, ScreenshotFailed, Screenshots};
 use shader::ShaderLoader;
+#[cfg(feature = "dds")]
+use texture::DdsTextureLoader;
 #[cfg(feature = "hdr")]
 use texture::HdrTextureLoader;
+#[cfg(feature = "ktx2")]
+use texture::Ktx2TextureLoader;
+use texture::{CompressedTextureSupport, MipmapSettings, TextureResourceSystemState};
 #[cfg(feature = "png")]
-use texture::ImageTextureLoader;
-use texture::TextureResourceSystemState;
+use texture::{ImageTextureLoader, LayeredTextureLoader};
 
 /// The names of "render" App stages
 pub mod stage {
@@ -81,14 +89,28 @@ impl Default for RenderPlugin {
 
 impl Plugin for RenderPlugin {
     fn build(&self, app: &mut AppBuilder) {
+        // texture loaders read these when they are created
+        app.init_resource::<CompressedTextureSupport>();
+        if app.resources().get::<MipmapSettings>().is_none() {
+            app.init_resource::<MipmapSettings>();
+        }
         #[cfg(feature = "png")]
         {
             app.init_asset_loader::<ImageTextureLoader>();
+            app.init_asset_loader::<LayeredTextureLoader>();
         }
         #[cfg(feature = "hdr")]
         {
             app.init_asset_loader::<HdrTextureLoader>();
         }
+        #[cfg(feature = "ktx2")]
+        {
+            app.init_asset_loader::<Ktx2TextureLoader>();
+        }
+        #[cfg(feature = "dds")]
+        {
+            app.init_asset_loader::<DdsTextureLoader>();
+        }
 
         app.init_asset_loader::<ShaderLoader>();
 
@@ -121,11 +143,16 @@ impl Plugin for RenderPlugin {
         .register_type::<Draw>()
         .register_type::<Visible>()
         .register_type::<RenderPipelines>()
+        .register_type::<Instanced>()
         .register_type::<OrthographicProjection>()
         .register_type::<PerspectiveProjection>()
         .register_type::<MainPass>()
         .register_type::<VisibleEntities>()
+        .register_type::<NoFrustumCulling>()
+        .register_type::<RenderLayers>()
+        .register_type::<primitives::Aabb>()
         .register_type::<Color>()
+        .register_type::<ClearColor>()
         .register_type::<ShaderSpecialization>()
         .register_type::<PrimitiveTopology>()
         .register_type::<IndexFormat>()
@@ -136,6 +163,9 @@ impl Plugin for RenderPlugin {
         .init_resource::<TextureResourceSystemState>()
         .init_resource::<AssetRenderResourceBindings>()
         .init_resource::<ActiveCameras>()
+        .init_resource::<Screenshots>()
+        .add_event::<ScreenshotCaptured>()
+        .add_event::<ScreenshotFailed>()
         .add_system_to_stage(bevy_app::stage::PRE_UPDATE, draw::clear_draw_system)
         .add_system_to_stage(bevy_app::stage::POST_UPDATE, camera::active_cameras_system)
         .add_system_to_stage(
@@ -146,6 +176,10 @@ impl Plugin for RenderPlugin {
             bevy_app::stage::POST_UPDATE,
             camera::camera_system::<PerspectiveProjection>,
         )
+        .add_system_to_stage(
+            bevy_app::stage::POST_UPDATE,
+            camera::calculate_bounds_system,
+        )
         // registration order matters here. this must come after all camera_system::<T> systems
         .add_system_to_stage(
             bevy_app::stage::POST_UPDATE,
@@ -158,14 +192,27 @@ impl Plugin for RenderPlugin {
             stage::RENDER_GRAPH_SYSTEMS,
             render_graph::render_graph_schedule_executor_system,
         )
+        .add_system_to_stage(
+            stage::RENDER_GRAPH_SYSTEMS,
+            screenshot::screenshot_request_system,
+        )
         .add_system_to_stage(stage::DRAW, pipeline::draw_render_pipelines_system)
-        .add_system_to_stage(stage::POST_RENDER, shader::clear_shader_defs_system);
+        .add_system_to_stage(
+            stage::DRAW,
+            pipeline::draw_instanced_render_pipelines_system,
+        )
+        .add_system_to_stage(stage::POST_RENDER, shader::clear_shader_defs_system)
+        .add_system_to_stage(stage::POST_RENDER, screenshot::screenshot_system);
 
         if app.resources().get::<Msaa>().is_none() {
             app.init_resource::<Msaa>();
         }
 
         if let Some(ref config) = self.base_render_graph_config {
+            app.add_system_to_stage(
+                bevy_app::stage::POST_UPDATE,
+                base::texture_target_graph_system,
+            );
             let resources = app.resources();
             let mut render_graph = resources.get_mut::<RenderGraph>().unwrap();
             let msaa = resources.get::<Msaa>().unwrap();
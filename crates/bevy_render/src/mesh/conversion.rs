@@ -0,0 +1,275 @@
+use super::VertexAttributeValues;
+use crate::pipeline::VertexFormat;
+
+impl VertexAttributeValues {
+    /// Converts the values to `format`. Only conversions that don't lose information are supported: adding components
+    /// (filled with 0, and 1 for the fourth component, like GPUs do when reading vertices), normalized and 8 or 16 bit
+    /// integers to floats, and 8 or 16 bit integers to 32 bit integers of the same signedness.
+    pub fn convert(&self, format: VertexFormat) -> Option<VertexAttributeValues> {
+        let source_format = VertexFormat::from(self);
+        if source_format == format {
+            return Some(self.clone());
+        }
+        if !source_format.can_convert(format) {
+            return None;
+        }
+
+        match format {
+            VertexFormat::Float
+            | VertexFormat::Float2
+            | VertexFormat::Float3
+            | VertexFormat::Float4 => self
+                .float_components()
+                .map(|components| from_float_components(components, format)),
+            VertexFormat::Uint
+            | VertexFormat::Uint2
+            | VertexFormat::Uint3
+            | VertexFormat::Uint4 => self
+                .uint_components()
+                .map(|components| from_uint_components(components, format)),
+            VertexFormat::Int | VertexFormat::Int2 | VertexFormat::Int3 | VertexFormat::Int4 => {
+                self.int_components()
+                    .map(|components| from_int_components(components, format))
+            }
+            _ => None,
+        }
+    }
+
+    fn float_components(&self) -> Option<Vec<[f32; 4]>> {
+        const DEFAULTS: [f32; 4] = [0.0, 0.0, 0.0, 1.0];
+        Some(match self {
+            VertexAttributeValues::Float(values) => {
+                widen(values.iter().map(std::slice::from_ref), |x| x, DEFAULTS)
+            }
+            VertexAttributeValues::Float2(values) => widen(slices(values), |x| x, DEFAULTS),
+            VertexAttributeValues::Float3(values) => widen(slices(values), |x| x, DEFAULTS),
+            VertexAttributeValues::Float4(values) => widen(slices(values), |x| x, DEFAULTS),
+            VertexAttributeValues::Uchar2(values) => widen(slices(values), f32::from, DEFAULTS),
+            VertexAttributeValues::Uchar4(values) => widen(slices(values), f32::from, DEFAULTS),
+            VertexAttributeValues::Char2(values) => widen(slices(values), f32::from, DEFAULTS),
+            VertexAttributeValues::Char4(values) => widen(slices(values), f32::from, DEFAULTS),
+            VertexAttributeValues::Ushort2(values) => widen(slices(values), f32::from, DEFAULTS),
+            VertexAttributeValues::Ushort4(values) => widen(slices(values), f32::from, DEFAULTS),
+            VertexAttributeValues::Short2(values) => widen(slices(values), f32::from, DEFAULTS),
+            VertexAttributeValues::Short4(values) => widen(slices(values), f32::from, DEFAULTS),
+            VertexAttributeValues::Uchar2Norm(values) => {
+                widen(slices(values), |x| f32::from(x) / 255.0, DEFAULTS)
+            }
+            VertexAttributeValues::Uchar4Norm(values) => {
+                widen(slices(values), |x| f32::from(x) / 255.0, DEFAULTS)
+            }
+            VertexAttributeValues::Char2Norm(values) => widen(
+                slices(values),
+                |x| signed_norm(f32::from(x), 127.0),
+                DEFAULTS,
+            ),
+            VertexAttributeValues::Char4Norm(values) => widen(
+                slices(values),
+                |x| signed_norm(f32::from(x), 127.0),
+                DEFAULTS,
+            ),
+            VertexAttributeValues::Ushort2Norm(values) => {
+                widen(slices(values), |x| f32::from(x) / 65535.0, DEFAULTS)
+            }
+            VertexAttributeValues::Ushort4Norm(values) => {
+                widen(slices(values), |x| f32::from(x) / 65535.0, DEFAULTS)
+            }
+            VertexAttributeValues::Short2Norm(values) => widen(
+                slices(values),
+                |x| signed_norm(f32::from(x), 32767.0),
+                DEFAULTS,
+            ),
+            VertexAttributeValues::Short4Norm(values) => widen(
+                slices(values),
+                |x| signed_norm(f32::from(x), 32767.0),
+                DEFAULTS,
+            ),
+            // 32 bit integers don't fit into floats without losing precision
+            _ => return None,
+        })
+    }
+
+    fn uint_components(&self) -> Option<Vec<[u32; 4]>> {
+        const DEFAULTS: [u32; 4] = [0, 0, 0, 1];
+        Some(match self {
+            VertexAttributeValues::Uint(values) => {
+                widen(values.iter().map(std::slice::from_ref), |x| x, DEFAULTS)
+            }
+            VertexAttributeValues::Uint2(values) => widen(slices(values), |x| x, DEFAULTS),
+            VertexAttributeValues::Uint3(values) => widen(slices(values), |x| x, DEFAULTS),
+            VertexAttributeValues::Uint4(values) => widen(slices(values), |x| x, DEFAULTS),
+            VertexAttributeValues::Uchar2(values) => widen(slices(values), u32::from, DEFAULTS),
+            VertexAttributeValues::Uchar4(values) => widen(slices(values), u32::from, DEFAULTS),
+            VertexAttributeValues::Ushort2(values) => widen(slices(values), u32::from, DEFAULTS),
+            VertexAttributeValues::Ushort4(values) => widen(slices(values), u32::from, DEFAULTS),
+            _ => return None,
+        })
+    }
+
+    fn int_components(&self) -> Option<Vec<[i32; 4]>> {
+        const DEFAULTS: [i32; 4] = [0, 0, 0, 1];
+        Some(match self {
+            VertexAttributeValues::Int(values) => {
+                widen(values.iter().map(std::slice::from_ref), |x| x, DEFAULTS)
+            }
+            VertexAttributeValues::Int2(values) => widen(slices(values), |x| x, DEFAULTS),
+            VertexAttributeValues::Int3(values) => widen(slices(values), |x| x, DEFAULTS),
+            VertexAttributeValues::Int4(values) => widen(slices(values), |x| x, DEFAULTS),
+            VertexAttributeValues::Char2(values) => widen(slices(values), i32::from, DEFAULTS),
+            VertexAttributeValues::Char4(values) => widen(slices(values), i32::from, DEFAULTS),
+            VertexAttributeValues::Short2(values) => widen(slices(values), i32::from, DEFAULTS),
+            VertexAttributeValues::Short4(values) => widen(slices(values), i32::from, DEFAULTS),
+            _ => return None,
+        })
+    }
+}
+
+/// Like GPUs, maps both the most negative value and the one above it to -1.0
+fn signed_norm(value: f32, max: f32) -> f32 {
+    (value / max).max(-1.0)
+}
+
+fn slices<'a, T: 'a, V: AsRef<[T]>>(values: &'a [V]) -> impl Iterator<Item = &'a [T]> + 'a {
+    values.iter().map(|value| value.as_ref())
+}
+
+/// Converts each value's components and fills the components it doesn't have with `defaults`
+fn widen<'a, T: Copy + 'a, U: Copy>(
+    values: impl Iterator<Item = &'a [T]>,
+    convert: impl Fn(T) -> U,
+    defaults: [U; 4],
+) -> Vec<[U; 4]> {
+    values
+        .map(|value| {
+            let mut widened = defaults;
+            for (component, widened_component) in value.iter().zip(widened.iter_mut()) {
+                *widened_component = convert(*component);
+            }
+            widened
+        })
+        .collect()
+}
+
+fn from_float_components(components: Vec<[f32; 4]>, format: VertexFormat) -> VertexAttributeValues {
+    let components = components.into_iter();
+    match format {
+        VertexFormat::Float => VertexAttributeValues::Float(components.map(|c| c[0]).collect()),
+        VertexFormat::Float2 => {
+            VertexAttributeValues::Float2(components.map(|c| [c[0], c[1]]).collect())
+        }
+        VertexFormat::Float3 => {
+            VertexAttributeValues::Float3(components.map(|c| [c[0], c[1], c[2]]).collect())
+        }
+        VertexFormat::Float4 => VertexAttributeValues::Float4(components.collect()),
+        _ => unreachable!(),
+    }
+}
+
+fn from_uint_components(components: Vec<[u32; 4]>, format: VertexFormat) -> VertexAttributeValues {
+    let components = components.into_iter();
+    match format {
+        VertexFormat::Uint => VertexAttributeValues::Uint(components.map(|c| c[0]).collect()),
+        VertexFormat::Uint2 => {
+            VertexAttributeValues::Uint2(components.map(|c| [c[0], c[1]]).collect())
+        }
+        VertexFormat::Uint3 => {
+            VertexAttributeValues::Uint3(components.map(|c| [c[0], c[1], c[2]]).collect())
+        }
+        VertexFormat::Uint4 => VertexAttributeValues::Uint4(components.collect()),
+        _ => unreachable!(),
+    }
+}
+
+fn from_int_components(components: Vec<[i32; 4]>, format: VertexFormat) -> VertexAttributeValues {
+    let components = components.into_iter();
+    match format {
+        VertexFormat::Int => VertexAttributeValues::Int(components.map(|c| c[0]).collect()),
+        VertexFormat::Int2 => {
+            VertexAttributeValues::Int2(components.map(|c| [c[0], c[1]]).collect())
+        }
+        VertexFormat::Int3 => {
+            VertexAttributeValues::Int3(components.map(|c| [c[0], c[1], c[2]]).collect())
+        }
+        VertexFormat::Int4 => VertexAttributeValues::Int4(components.collect()),
+        _ => unreachable!(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn widening_conversions() {
+        let colors = VertexAttributeValues::Float3(vec![[0.5, 0.25, 1.0]]);
+        match colors.convert(VertexFormat::Float4) {
+            Some(VertexAttributeValues::Float4(values)) => {
+                assert_eq!(values, vec![[0.5, 0.25, 1.0, 1.0]])
+            }
+            values => panic!("unexpected conversion {:?}", values),
+        }
+
+        let normalized = VertexAttributeValues::Char2Norm(vec![[127, -128]]);
+        match normalized.convert(VertexFormat::Float2) {
+            Some(VertexAttributeValues::Float2(values)) => assert_eq!(values, vec![[1.0, -1.0]]),
+            values => panic!("unexpected conversion {:?}", values),
+        }
+
+        let indices = VertexAttributeValues::Ushort2(vec![[1, 65535]]);
+        match indices.convert(VertexFormat::Uint4) {
+            Some(VertexAttributeValues::Uint4(values)) => {
+                assert_eq!(values, vec![[1, 65535, 0, 1]])
+            }
+            values => panic!("unexpected conversion {:?}", values),
+        }
+    }
+
+    #[test]
+    fn lossy_conversions() {
+        let positions = VertexAttributeValues::Float3(vec![[1.0, 2.0, 3.0]]);
+        assert!(positions.convert(VertexFormat::Float2).is_none());
+        assert!(positions.convert(VertexFormat::Uchar4Norm).is_none());
+        assert!(VertexAttributeValues::Uint(vec![1])
+            .convert(VertexFormat::Float)
+            .is_none());
+        assert!(VertexAttributeValues::Short2(vec![[1, 2]])
+            .convert(VertexFormat::Uint2)
+            .is_none());
+    }
+
+    #[test]
+    fn conversions_match_format_conversions() {
+        let values = vec![
+            VertexAttributeValues::Float(vec![1.0]),
+            VertexAttributeValues::Float3(vec![[1.0; 3]]),
+            VertexAttributeValues::Uint2(vec![[1; 2]]),
+            VertexAttributeValues::Int3(vec![[1; 3]]),
+            VertexAttributeValues::Uchar4(vec![[1; 4]]),
+            VertexAttributeValues::Char2Norm(vec![[1; 2]]),
+            VertexAttributeValues::Ushort2(vec![[1; 2]]),
+            VertexAttributeValues::Short4(vec![[1; 4]]),
+        ];
+        let formats = [
+            VertexFormat::Float,
+            VertexFormat::Float2,
+            VertexFormat::Float4,
+            VertexFormat::Uint2,
+            VertexFormat::Uint4,
+            VertexFormat::Int3,
+            VertexFormat::Int4,
+            VertexFormat::Uchar4Norm,
+            VertexFormat::Half4,
+        ];
+        for values in values.iter() {
+            for format in formats.iter() {
+                assert_eq!(
+                    values.convert(*format).is_some(),
+                    VertexFormat::from(values).can_convert(*format),
+                    "{:?} to {:?}",
+                    values,
+                    format
+                );
+            }
+        }
+    }
+}
@@ -1,5 +1,6 @@
 use crate::{
     pipeline::{IndexFormat, PrimitiveTopology, RenderPipelines, VertexFormat},
+    primitives::Aabb,
     renderer::{BufferInfo, BufferUsage, RenderResourceContext, RenderResourceId},
 };
 use bevy_app::prelude::{EventReader, Events};
@@ -8,10 +9,12 @@ use bevy_core::AsBytes;
 use bevy_ecs::{Changed, Entity, Local, Mut, Query, QuerySet, Res, With};
 use bevy_math::*;
 use bevy_reflect::TypeUuid;
+use bevy_transform::components::Transform;
 use std::borrow::Cow;
+use thiserror::Error;
 
 use crate::pipeline::{InputStepMode, VertexAttributeDescriptor, VertexBufferDescriptor};
-use bevy_utils::{HashMap, HashSet};
+use bevy_utils::{tracing::error, HashMap, HashSet};
 
 pub const INDEX_BUFFER_ASSET_INDEX: u64 = 0;
 pub const VERTEX_ATTRIBUTE_BUFFER_ID: u64 = 10;
@@ -30,6 +33,22 @@ pub enum VertexAttributeValues {
     Float4(Vec<[f32; 4]>),
     Int4(Vec<[i32; 4]>),
     Uint4(Vec<[u32; 4]>),
+    Uchar2(Vec<[u8; 2]>),
+    Uchar4(Vec<[u8; 4]>),
+    Char2(Vec<[i8; 2]>),
+    Char4(Vec<[i8; 4]>),
+    Uchar2Norm(Vec<[u8; 2]>),
+    Uchar4Norm(Vec<[u8; 4]>),
+    Char2Norm(Vec<[i8; 2]>),
+    Char4Norm(Vec<[i8; 4]>),
+    Ushort2(Vec<[u16; 2]>),
+    Ushort4(Vec<[u16; 4]>),
+    Short2(Vec<[i16; 2]>),
+    Short4(Vec<[i16; 4]>),
+    Ushort2Norm(Vec<[u16; 2]>),
+    Ushort4Norm(Vec<[u16; 4]>),
+    Short2Norm(Vec<[i16; 2]>),
+    Short4Norm(Vec<[i16; 4]>),
 }
 
 impl VertexAttributeValues {
@@ -47,6 +66,22 @@ impl VertexAttributeValues {
             VertexAttributeValues::Float4(ref values) => values.len(),
             VertexAttributeValues::Int4(ref values) => values.len(),
             VertexAttributeValues::Uint4(ref values) => values.len(),
+            VertexAttributeValues::Uchar2(ref values) => values.len(),
+            VertexAttributeValues::Uchar4(ref values) => values.len(),
+            VertexAttributeValues::Char2(ref values) => values.len(),
+            VertexAttributeValues::Char4(ref values) => values.len(),
+            VertexAttributeValues::Uchar2Norm(ref values) => values.len(),
+            VertexAttributeValues::Uchar4Norm(ref values) => values.len(),
+            VertexAttributeValues::Char2Norm(ref values) => values.len(),
+            VertexAttributeValues::Char4Norm(ref values) => values.len(),
+            VertexAttributeValues::Ushort2(ref values) => values.len(),
+            VertexAttributeValues::Ushort4(ref values) => values.len(),
+            VertexAttributeValues::Short2(ref values) => values.len(),
+            VertexAttributeValues::Short4(ref values) => values.len(),
+            VertexAttributeValues::Ushort2Norm(ref values) => values.len(),
+            VertexAttributeValues::Ushort4Norm(ref values) => values.len(),
+            VertexAttributeValues::Short2Norm(ref values) => values.len(),
+            VertexAttributeValues::Short4Norm(ref values) => values.len(),
         }
     }
 
@@ -54,7 +89,7 @@ impl VertexAttributeValues {
         self.len() == 0
     }
 
-    // TODO: add vertex format as parameter here and perform type conversions
+    /// The raw bytes of the values. Use [VertexAttributeValues::convert] first to get them in another format.
     pub fn get_bytes(&self) -> &[u8] {
         match self {
             VertexAttributeValues::Float(values) => values.as_slice().as_bytes(),
@@ -69,8 +104,216 @@ impl VertexAttributeValues {
             VertexAttributeValues::Float4(values) => values.as_slice().as_bytes(),
             VertexAttributeValues::Int4(values) => values.as_slice().as_bytes(),
             VertexAttributeValues::Uint4(values) => values.as_slice().as_bytes(),
+            VertexAttributeValues::Uchar2(values) => values.as_slice().as_bytes(),
+            VertexAttributeValues::Uchar4(values) => values.as_slice().as_bytes(),
+            VertexAttributeValues::Char2(values) => values.as_slice().as_bytes(),
+            VertexAttributeValues::Char4(values) => values.as_slice().as_bytes(),
+            VertexAttributeValues::Uchar2Norm(values) => values.as_slice().as_bytes(),
+            VertexAttributeValues::Uchar4Norm(values) => values.as_slice().as_bytes(),
+            VertexAttributeValues::Char2Norm(values) => values.as_slice().as_bytes(),
+            VertexAttributeValues::Char4Norm(values) => values.as_slice().as_bytes(),
+            VertexAttributeValues::Ushort2(values) => values.as_slice().as_bytes(),
+            VertexAttributeValues::Ushort4(values) => values.as_slice().as_bytes(),
+            VertexAttributeValues::Short2(values) => values.as_slice().as_bytes(),
+            VertexAttributeValues::Short4(values) => values.as_slice().as_bytes(),
+            VertexAttributeValues::Ushort2Norm(values) => values.as_slice().as_bytes(),
+            VertexAttributeValues::Ushort4Norm(values) => values.as_slice().as_bytes(),
+            VertexAttributeValues::Short2Norm(values) => values.as_slice().as_bytes(),
+            VertexAttributeValues::Short4Norm(values) => values.as_slice().as_bytes(),
         }
     }
+
+    /// Returns the values at `indices`, in order
+    pub fn gather(&self, indices: &[usize]) -> VertexAttributeValues {
+        fn gather<T: Copy>(values: &[T], indices: &[usize]) -> Vec<T> {
+            indices.iter().map(|index| values[*index]).collect()
+        }
+
+        match self {
+            VertexAttributeValues::Float(values) => {
+                VertexAttributeValues::Float(gather(values, indices))
+            }
+            VertexAttributeValues::Int(values) => {
+                VertexAttributeValues::Int(gather(values, indices))
+            }
+            VertexAttributeValues::Uint(values) => {
+                VertexAttributeValues::Uint(gather(values, indices))
+            }
+            VertexAttributeValues::Float2(values) => {
+                VertexAttributeValues::Float2(gather(values, indices))
+            }
+            VertexAttributeValues::Int2(values) => {
+                VertexAttributeValues::Int2(gather(values, indices))
+            }
+            VertexAttributeValues::Uint2(values) => {
+                VertexAttributeValues::Uint2(gather(values, indices))
+            }
+            VertexAttributeValues::Float3(values) => {
+                VertexAttributeValues::Float3(gather(values, indices))
+            }
+            VertexAttributeValues::Int3(values) => {
+                VertexAttributeValues::Int3(gather(values, indices))
+            }
+            VertexAttributeValues::Uint3(values) => {
+                VertexAttributeValues::Uint3(gather(values, indices))
+            }
+            VertexAttributeValues::Float4(values) => {
+                VertexAttributeValues::Float4(gather(values, indices))
+            }
+            VertexAttributeValues::Int4(values) => {
+                VertexAttributeValues::Int4(gather(values, indices))
+            }
+            VertexAttributeValues::Uint4(values) => {
+                VertexAttributeValues::Uint4(gather(values, indices))
+            }
+            VertexAttributeValues::Uchar2(values) => {
+                VertexAttributeValues::Uchar2(gather(values, indices))
+            }
+            VertexAttributeValues::Uchar4(values) => {
+                VertexAttributeValues::Uchar4(gather(values, indices))
+            }
+            VertexAttributeValues::Char2(values) => {
+                VertexAttributeValues::Char2(gather(values, indices))
+            }
+            VertexAttributeValues::Char4(values) => {
+                VertexAttributeValues::Char4(gather(values, indices))
+            }
+            VertexAttributeValues::Uchar2Norm(values) => {
+                VertexAttributeValues::Uchar2Norm(gather(values, indices))
+            }
+            VertexAttributeValues::Uchar4Norm(values) => {
+                VertexAttributeValues::Uchar4Norm(gather(values, indices))
+            }
+            VertexAttributeValues::Char2Norm(values) => {
+                VertexAttributeValues::Char2Norm(gather(values, indices))
+            }
+            VertexAttributeValues::Char4Norm(values) => {
+                VertexAttributeValues::Char4Norm(gather(values, indices))
+            }
+            VertexAttributeValues::Ushort2(values) => {
+                VertexAttributeValues::Ushort2(gather(values, indices))
+            }
+            VertexAttributeValues::Ushort4(values) => {
+                VertexAttributeValues::Ushort4(gather(values, indices))
+            }
+            VertexAttributeValues::Short2(values) => {
+                VertexAttributeValues::Short2(gather(values, indices))
+            }
+            VertexAttributeValues::Short4(values) => {
+                VertexAttributeValues::Short4(gather(values, indices))
+            }
+            VertexAttributeValues::Ushort2Norm(values) => {
+                VertexAttributeValues::Ushort2Norm(gather(values, indices))
+            }
+            VertexAttributeValues::Ushort4Norm(values) => {
+                VertexAttributeValues::Ushort4Norm(gather(values, indices))
+            }
+            VertexAttributeValues::Short2Norm(values) => {
+                VertexAttributeValues::Short2Norm(gather(values, indices))
+            }
+            VertexAttributeValues::Short4Norm(values) => {
+                VertexAttributeValues::Short4Norm(gather(values, indices))
+            }
+        }
+    }
+
+    /// Appends the values of `other`. Returns false if `other` has a different format.
+    pub fn extend(&mut self, other: &VertexAttributeValues) -> bool {
+        match (self, other) {
+            (VertexAttributeValues::Float(values), VertexAttributeValues::Float(other)) => {
+                values.extend_from_slice(other)
+            }
+            (VertexAttributeValues::Int(values), VertexAttributeValues::Int(other)) => {
+                values.extend_from_slice(other)
+            }
+            (VertexAttributeValues::Uint(values), VertexAttributeValues::Uint(other)) => {
+                values.extend_from_slice(other)
+            }
+            (VertexAttributeValues::Float2(values), VertexAttributeValues::Float2(other)) => {
+                values.extend_from_slice(other)
+            }
+            (VertexAttributeValues::Int2(values), VertexAttributeValues::Int2(other)) => {
+                values.extend_from_slice(other)
+            }
+            (VertexAttributeValues::Uint2(values), VertexAttributeValues::Uint2(other)) => {
+                values.extend_from_slice(other)
+            }
+            (VertexAttributeValues::Float3(values), VertexAttributeValues::Float3(other)) => {
+                values.extend_from_slice(other)
+            }
+            (VertexAttributeValues::Int3(values), VertexAttributeValues::Int3(other)) => {
+                values.extend_from_slice(other)
+            }
+            (VertexAttributeValues::Uint3(values), VertexAttributeValues::Uint3(other)) => {
+                values.extend_from_slice(other)
+            }
+            (VertexAttributeValues::Float4(values), VertexAttributeValues::Float4(other)) => {
+                values.extend_from_slice(other)
+            }
+            (VertexAttributeValues::Int4(values), VertexAttributeValues::Int4(other)) => {
+                values.extend_from_slice(other)
+            }
+            (VertexAttributeValues::Uint4(values), VertexAttributeValues::Uint4(other)) => {
+                values.extend_from_slice(other)
+            }
+            (VertexAttributeValues::Uchar2(values), VertexAttributeValues::Uchar2(other)) => {
+                values.extend_from_slice(other)
+            }
+            (VertexAttributeValues::Uchar4(values), VertexAttributeValues::Uchar4(other)) => {
+                values.extend_from_slice(other)
+            }
+            (VertexAttributeValues::Char2(values), VertexAttributeValues::Char2(other)) => {
+                values.extend_from_slice(other)
+            }
+            (VertexAttributeValues::Char4(values), VertexAttributeValues::Char4(other)) => {
+                values.extend_from_slice(other)
+            }
+            (
+                VertexAttributeValues::Uchar2Norm(values),
+                VertexAttributeValues::Uchar2Norm(other),
+            ) => values.extend_from_slice(other),
+            (
+                VertexAttributeValues::Uchar4Norm(values),
+                VertexAttributeValues::Uchar4Norm(other),
+            ) => values.extend_from_slice(other),
+            (VertexAttributeValues::Char2Norm(values), VertexAttributeValues::Char2Norm(other)) => {
+                values.extend_from_slice(other)
+            }
+            (VertexAttributeValues::Char4Norm(values), VertexAttributeValues::Char4Norm(other)) => {
+                values.extend_from_slice(other)
+            }
+            (VertexAttributeValues::Ushort2(values), VertexAttributeValues::Ushort2(other)) => {
+                values.extend_from_slice(other)
+            }
+            (VertexAttributeValues::Ushort4(values), VertexAttributeValues::Ushort4(other)) => {
+                values.extend_from_slice(other)
+            }
+            (VertexAttributeValues::Short2(values), VertexAttributeValues::Short2(other)) => {
+                values.extend_from_slice(other)
+            }
+            (VertexAttributeValues::Short4(values), VertexAttributeValues::Short4(other)) => {
+                values.extend_from_slice(other)
+            }
+            (
+                VertexAttributeValues::Ushort2Norm(values),
+                VertexAttributeValues::Ushort2Norm(other),
+            ) => values.extend_from_slice(other),
+            (
+                VertexAttributeValues::Ushort4Norm(values),
+                VertexAttributeValues::Ushort4Norm(other),
+            ) => values.extend_from_slice(other),
+            (
+                VertexAttributeValues::Short2Norm(values),
+                VertexAttributeValues::Short2Norm(other),
+            ) => values.extend_from_slice(other),
+            (
+                VertexAttributeValues::Short4Norm(values),
+                VertexAttributeValues::Short4Norm(other),
+            ) => values.extend_from_slice(other),
+            _ => return false,
+        }
+        true
+    }
 }
 
 impl From<&VertexAttributeValues> for VertexFormat {
@@ -88,6 +331,22 @@ impl From<&VertexAttributeValues> for VertexFormat {
             VertexAttributeValues::Float4(_) => VertexFormat::Float4,
             VertexAttributeValues::Int4(_) => VertexFormat::Int4,
             VertexAttributeValues::Uint4(_) => VertexFormat::Uint4,
+            VertexAttributeValues::Uchar2(_) => VertexFormat::Uchar2,
+            VertexAttributeValues::Uchar4(_) => VertexFormat::Uchar4,
+            VertexAttributeValues::Char2(_) => VertexFormat::Char2,
+            VertexAttributeValues::Char4(_) => VertexFormat::Char4,
+            VertexAttributeValues::Uchar2Norm(_) => VertexFormat::Uchar2Norm,
+            VertexAttributeValues::Uchar4Norm(_) => VertexFormat::Uchar4Norm,
+            VertexAttributeValues::Char2Norm(_) => VertexFormat::Char2Norm,
+            VertexAttributeValues::Char4Norm(_) => VertexFormat::Char4Norm,
+            VertexAttributeValues::Ushort2(_) => VertexFormat::Ushort2,
+            VertexAttributeValues::Ushort4(_) => VertexFormat::Ushort4,
+            VertexAttributeValues::Short2(_) => VertexFormat::Short2,
+            VertexAttributeValues::Short4(_) => VertexFormat::Short4,
+            VertexAttributeValues::Ushort2Norm(_) => VertexFormat::Ushort2Norm,
+            VertexAttributeValues::Ushort4Norm(_) => VertexFormat::Ushort4Norm,
+            VertexAttributeValues::Short2Norm(_) => VertexFormat::Short2Norm,
+            VertexAttributeValues::Short4Norm(_) => VertexFormat::Short4Norm,
         }
     }
 }
@@ -164,12 +423,84 @@ impl From<Vec<[u32; 4]>> for VertexAttributeValues {
     }
 }
 
-#[derive(Debug)]
+impl From<Vec<[u8; 2]>> for VertexAttributeValues {
+    fn from(vec: Vec<[u8; 2]>) -> Self {
+        VertexAttributeValues::Uchar2(vec)
+    }
+}
+
+impl From<Vec<[u8; 4]>> for VertexAttributeValues {
+    fn from(vec: Vec<[u8; 4]>) -> Self {
+        VertexAttributeValues::Uchar4(vec)
+    }
+}
+
+impl From<Vec<[i8; 2]>> for VertexAttributeValues {
+    fn from(vec: Vec<[i8; 2]>) -> Self {
+        VertexAttributeValues::Char2(vec)
+    }
+}
+
+impl From<Vec<[i8; 4]>> for VertexAttributeValues {
+    fn from(vec: Vec<[i8; 4]>) -> Self {
+        VertexAttributeValues::Char4(vec)
+    }
+}
+
+impl From<Vec<[u16; 2]>> for VertexAttributeValues {
+    fn from(vec: Vec<[u16; 2]>) -> Self {
+        VertexAttributeValues::Ushort2(vec)
+    }
+}
+
+impl From<Vec<[u16; 4]>> for VertexAttributeValues {
+    fn from(vec: Vec<[u16; 4]>) -> Self {
+        VertexAttributeValues::Ushort4(vec)
+    }
+}
+
+impl From<Vec<[i16; 2]>> for VertexAttributeValues {
+    fn from(vec: Vec<[i16; 2]>) -> Self {
+        VertexAttributeValues::Short2(vec)
+    }
+}
+
+impl From<Vec<[i16; 4]>> for VertexAttributeValues {
+    fn from(vec: Vec<[i16; 4]>) -> Self {
+        VertexAttributeValues::Short4(vec)
+    }
+}
+
+#[derive(Clone, Debug)]
 pub enum Indices {
     U16(Vec<u16>),
     U32(Vec<u32>),
 }
 
+impl Indices {
+    pub fn len(&self) -> usize {
+        match self {
+            Indices::U16(indices) => indices.len(),
+            Indices::U32(indices) => indices.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        let (u16_indices, u32_indices): (&[u16], &[u32]) = match self {
+            Indices::U16(indices) => (indices.as_slice(), &[]),
+            Indices::U32(indices) => (&[], indices.as_slice()),
+        };
+        u16_indices
+            .iter()
+            .map(|index| *index as usize)
+            .chain(u32_indices.iter().map(|index| *index as usize))
+    }
+}
+
 impl From<&Indices> for IndexFormat {
     fn from(indices: &Indices) -> Self {
         match indices {
@@ -179,13 +510,52 @@ impl From<&Indices> for IndexFormat {
     }
 }
 
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum MeshError {
+    #[error("attribute {name} has {len} values, but other attributes have {expected}")]
+    AttributeLengthMismatch {
+        name: String,
+        len: usize,
+        expected: usize,
+    },
+    #[error("index {index} is out of range for a mesh with {vertex_count} vertices")]
+    IndexOutOfRange { index: usize, vertex_count: usize },
+    #[error("{count} indices or vertices don't form complete {topology:?} primitives")]
+    IncompletePrimitive {
+        topology: PrimitiveTopology,
+        count: usize,
+    },
+    #[error("the mesh has no {0} attribute")]
+    MissingAttribute(&'static str),
+    #[error("the {name} attribute should be {expected:?}, but it is {actual:?}")]
+    WrongAttributeFormat {
+        name: String,
+        expected: VertexFormat,
+        actual: VertexFormat,
+    },
+    #[error("this operation requires a TriangleList mesh, but the mesh is a {0:?}")]
+    UnsupportedTopology(PrimitiveTopology),
+    #[error("meshes with different topologies or attributes can't be merged")]
+    IncompatibleMeshes,
+    #[error("MikkTSpace couldn't generate tangents for the mesh")]
+    TangentGenerationFailed,
+    #[error("the {name} attribute can't be converted from {from:?} to {to:?} without losing information")]
+    UnsupportedConversion {
+        name: String,
+        from: VertexFormat,
+        to: VertexFormat,
+    },
+}
+
 // TODO: allow values to be unloaded after been submitting to the GPU to conserve memory
-#[derive(Debug, TypeUuid)]
+#[derive(Clone, Debug, TypeUuid)]
 #[uuid = "8ecbac0f-f545-4473-ad43-e1f4243af51e"]
 pub struct Mesh {
     primitive_topology: PrimitiveTopology,
     /// `bevy_utils::HashMap` with all defined vertex attributes (Positions, Normals, ...) for this mesh. Attribute name maps to attribute values.
     attributes: HashMap<Cow<'static, str>, VertexAttributeValues>,
+    /// Formats that attributes are converted to in the vertex buffer, if they differ from the attribute values' format
+    attribute_formats: HashMap<Cow<'static, str>, VertexFormat>,
     indices: Option<Indices>,
 }
 
@@ -193,11 +563,14 @@ impl Mesh {
     pub const ATTRIBUTE_NORMAL: &'static str = "Vertex_Normal";
     pub const ATTRIBUTE_POSITION: &'static str = "Vertex_Position";
     pub const ATTRIBUTE_UV_0: &'static str = "Vertex_Uv";
+    /// Tangents as in glTF: xyz is the tangent and w is the sign of the bitangent, which is `cross(normal, tangent) * w`
+    pub const ATTRIBUTE_TANGENT: &'static str = "Vertex_Tangent";
 
     pub fn new(primitive_topology: PrimitiveTopology) -> Self {
         Mesh {
             primitive_topology,
             attributes: Default::default(),
+            attribute_formats: Default::default(),
             indices: None,
         }
     }
@@ -219,6 +592,25 @@ impl Mesh {
         self.attributes.get(&name.into())
     }
 
+    /// Stores the attribute in the vertex buffer as `format` instead of the format of its values. The values are
+    /// converted when the vertex buffer is built, so the conversion has to be supported by
+    /// [VertexAttributeValues::convert].
+    pub fn set_attribute_format(
+        &mut self,
+        name: impl Into<Cow<'static, str>>,
+        format: VertexFormat,
+    ) {
+        self.attribute_formats.insert(name.into(), format);
+    }
+
+    /// The format the attribute has in the vertex buffer
+    pub fn attribute_format(&self, name: &str) -> Option<VertexFormat> {
+        self.attribute_formats
+            .get(name)
+            .cloned()
+            .or_else(|| self.attributes.get(name).map(VertexFormat::from))
+    }
+
     pub fn set_indices(&mut self, indices: Option<Indices>) {
         self.indices = indices;
     }
@@ -227,6 +619,319 @@ impl Mesh {
         self.indices.as_ref()
     }
 
+    /// Computes the bounding box of the mesh's positions. Returns `None` if the mesh has no `Float3` positions.
+    pub fn compute_aabb(&self) -> Option<Aabb> {
+        if let Some(VertexAttributeValues::Float3(positions)) =
+            self.attribute(Mesh::ATTRIBUTE_POSITION)
+        {
+            Aabb::from_points(positions.iter().map(|position| Vec3::from(*position)))
+        } else {
+            None
+        }
+    }
+
+    /// Checks that all attributes have the same number of values and can be converted to their vertex buffer formats,
+    /// that every index refers to a vertex, and that the vertices form complete primitives
+    pub fn validate(&self) -> Result<(), MeshError> {
+        let vertex_count = self.validate_attributes()?;
+        let element_count = match &self.indices {
+            Some(indices) => {
+                if let Some(index) = indices.iter().find(|index| *index >= vertex_count) {
+                    return Err(MeshError::IndexOutOfRange {
+                        index,
+                        vertex_count,
+                    });
+                }
+                indices.len()
+            }
+            None => vertex_count,
+        };
+
+        let complete = match self.primitive_topology {
+            PrimitiveTopology::PointList => true,
+            PrimitiveTopology::LineList => element_count % 2 == 0,
+            PrimitiveTopology::LineStrip => element_count != 1,
+            PrimitiveTopology::TriangleList => element_count % 3 == 0,
+            PrimitiveTopology::TriangleStrip => element_count == 0 || element_count >= 3,
+        };
+        if !complete {
+            return Err(MeshError::IncompletePrimitive {
+                topology: self.primitive_topology,
+                count: element_count,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Checks that all attributes have the same number of values and can be converted to their vertex buffer formats.
+    /// Returns the number of vertices.
+    fn validate_attributes(&self) -> Result<usize, MeshError> {
+        let mut vertex_count: Option<usize> = None;
+        for (name, values) in self.attributes.iter() {
+            if let Some(format) = self.attribute_formats.get(name) {
+                let values_format = VertexFormat::from(values);
+                if !values_format.can_convert(*format) {
+                    return Err(MeshError::UnsupportedConversion {
+                        name: name.to_string(),
+                        from: values_format,
+                        to: *format,
+                    });
+                }
+            }
+            match vertex_count {
+                Some(expected) if expected != values.len() => {
+                    return Err(MeshError::AttributeLengthMismatch {
+                        name: name.to_string(),
+                        len: values.len(),
+                        expected,
+                    })
+                }
+                _ => vertex_count = Some(values.len()),
+            }
+        }
+
+        Ok(vertex_count.unwrap_or(0))
+    }
+
+    fn float3_attribute(&self, name: &'static str) -> Result<&Vec<[f32; 3]>, MeshError> {
+        match self.attribute(name) {
+            Some(VertexAttributeValues::Float3(values)) => Ok(values),
+            Some(values) => Err(MeshError::WrongAttributeFormat {
+                name: name.to_string(),
+                expected: VertexFormat::Float3,
+                actual: values.into(),
+            }),
+            None => Err(MeshError::MissingAttribute(name)),
+        }
+    }
+
+    /// Returns the vertex indices of each triangle, three at a time
+    fn triangle_indices(&self) -> Result<Vec<usize>, MeshError> {
+        if self.primitive_topology != PrimitiveTopology::TriangleList {
+            return Err(MeshError::UnsupportedTopology(self.primitive_topology));
+        }
+        self.validate()?;
+        Ok(match &self.indices {
+            Some(indices) => indices.iter().collect(),
+            None => (0..self.count_vertices()).collect(),
+        })
+    }
+
+    /// Gives every index its own vertex and removes the indices, so no vertices are shared between primitives
+    pub fn duplicate_vertices(&mut self) {
+        let indices = match self.indices.take() {
+            Some(indices) => indices.iter().collect::<Vec<_>>(),
+            None => return,
+        };
+        for values in self.attributes.values_mut() {
+            *values = values.gather(&indices);
+        }
+    }
+
+    /// Sets each vertex normal to the normal of its triangle. Indexed meshes are un-indexed with
+    /// [Mesh::duplicate_vertices] first, because a shared vertex can't have a different normal for each triangle.
+    pub fn compute_flat_normals(&mut self) -> Result<(), MeshError> {
+        self.triangle_indices()?;
+        self.float3_attribute(Mesh::ATTRIBUTE_POSITION)?;
+        self.duplicate_vertices();
+        let positions = self.float3_attribute(Mesh::ATTRIBUTE_POSITION)?;
+        let normals = positions
+            .chunks_exact(3)
+            .flat_map(|triangle| {
+                // degenerate triangles don't have a normal
+                let normal: [f32; 3] =
+                    normalize_or_zero(triangle_normal(triangle[0], triangle[1], triangle[2]))
+                        .into();
+                vec![normal; 3]
+            })
+            .collect::<Vec<_>>();
+        self.set_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
+        Ok(())
+    }
+
+    /// Sets each vertex normal to the area weighted average of the normals of the triangles around it. Vertices with
+    /// the same position are treated as one vertex, so normals are also smoothed across uv seams.
+    pub fn compute_smooth_normals(&mut self) -> Result<(), MeshError> {
+        let indices = self.triangle_indices()?;
+        let positions = self.float3_attribute(Mesh::ATTRIBUTE_POSITION)?;
+
+        let mut position_normals = HashMap::<[u32; 3], Vec3>::default();
+        let position_key = |position: [f32; 3]| {
+            [
+                position[0].to_bits(),
+                position[1].to_bits(),
+                position[2].to_bits(),
+            ]
+        };
+        for triangle in indices.chunks_exact(3) {
+            // the cross product's length is twice the triangle's area, which weights the normals
+            let normal = triangle_normal(
+                positions[triangle[0]],
+                positions[triangle[1]],
+                positions[triangle[2]],
+            );
+            for index in triangle {
+                *position_normals
+                    .entry(position_key(positions[*index]))
+                    .or_insert_with(Vec3::zero) += normal;
+            }
+        }
+
+        let normals = positions
+            .iter()
+            .map(|position| {
+                let normal = position_normals
+                    .get(&position_key(*position))
+                    .copied()
+                    .unwrap_or_else(Vec3::zero);
+                normalize_or_zero(normal).into()
+            })
+            .collect::<Vec<[f32; 3]>>();
+        self.set_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
+        Ok(())
+    }
+
+    /// Computes [Mesh::ATTRIBUTE_TANGENT] from the positions, normals and uvs of the mesh with the MikkTSpace
+    /// algorithm, which is what most tools (and glTF) bake normal maps against.
+    ///
+    /// MikkTSpace computes a tangent for every corner of every triangle. Vertices that are shared by triangles whose
+    /// tangents differ, like vertices on a uv seam, get the tangent of one of those triangles, so split them first
+    /// (for example with [Mesh::duplicate_vertices]) for exact results.
+    pub fn compute_tangents(&mut self) -> Result<(), MeshError> {
+        let indices = self.triangle_indices()?;
+        let positions = self.float3_attribute(Mesh::ATTRIBUTE_POSITION)?;
+        let normals = self.float3_attribute(Mesh::ATTRIBUTE_NORMAL)?;
+        let uvs = match self.attribute(Mesh::ATTRIBUTE_UV_0) {
+            Some(VertexAttributeValues::Float2(uvs)) => uvs,
+            Some(values) => {
+                return Err(MeshError::WrongAttributeFormat {
+                    name: Mesh::ATTRIBUTE_UV_0.to_string(),
+                    expected: VertexFormat::Float2,
+                    actual: values.into(),
+                })
+            }
+            None => return Err(MeshError::MissingAttribute(Mesh::ATTRIBUTE_UV_0)),
+        };
+
+        let mut geometry = TangentGeometry {
+            indices: &indices,
+            positions,
+            normals,
+            uvs,
+            tangents: vec![[0.0; 4]; positions.len()],
+        };
+        // MikkTSpace fails on meshes without triangles, but those simply have no tangents
+        if !indices.is_empty() && !mikktspace::generate_tangents(&mut geometry) {
+            return Err(MeshError::TangentGenerationFailed);
+        }
+
+        let tangents = geometry.tangents;
+        self.set_attribute(Mesh::ATTRIBUTE_TANGENT, tangents);
+        Ok(())
+    }
+
+    /// Applies `transform` to the positions, normals and tangents of the mesh
+    pub fn transform_by(&mut self, transform: &Transform) {
+        let matrix = transform.compute_matrix();
+        if let Some(VertexAttributeValues::Float3(positions)) =
+            self.attributes.get_mut(Mesh::ATTRIBUTE_POSITION)
+        {
+            for position in positions.iter_mut() {
+                *position = matrix.transform_point3(Vec3::from(*position)).into();
+            }
+        }
+
+        // normals are transformed by the inverse transpose, which for a scale and a rotation is the rotation applied
+        // after the inverse scale. The inverse scale is multiplied by the absolute determinant, which doesn't change
+        // the normals' directions but keeps them finite for a zero scale, where they collapse to the flattened axis.
+        let scale = transform.scale;
+        let determinant = scale.x() * scale.y() * scale.z();
+        let normal_scale = Vec3::new(
+            scale.y() * scale.z(),
+            scale.x() * scale.z(),
+            scale.x() * scale.y(),
+        ) * determinant.signum();
+        if let Some(VertexAttributeValues::Float3(normals)) =
+            self.attributes.get_mut(Mesh::ATTRIBUTE_NORMAL)
+        {
+            for normal in normals.iter_mut() {
+                let scaled = Vec3::from(*normal) * normal_scale;
+                *normal = normalize_or_zero(transform.rotation * scaled).into();
+            }
+        }
+
+        if let Some(VertexAttributeValues::Float4(tangents)) =
+            self.attributes.get_mut(Mesh::ATTRIBUTE_TANGENT)
+        {
+            // a mirroring scale flips the bitangent
+            let handedness = determinant.signum();
+            for tangent in tangents.iter_mut() {
+                let direction = Vec3::new(tangent[0], tangent[1], tangent[2]) * scale;
+                let direction = normalize_or_zero(transform.rotation * direction);
+                *tangent = [
+                    direction.x(),
+                    direction.y(),
+                    direction.z(),
+                    tangent[3] * handedness,
+                ];
+            }
+        }
+    }
+
+    /// Appends the vertices and indices of `other` to this mesh. Both meshes need the same topology and the same
+    /// attributes with the same formats. If only one of them is indexed, the other one gets indices too.
+    pub fn merge(&mut self, other: &Mesh) -> Result<(), MeshError> {
+        self.validate()?;
+        other.validate()?;
+        if self.primitive_topology != other.primitive_topology
+            || self.attributes.len() != other.attributes.len()
+        {
+            return Err(MeshError::IncompatibleMeshes);
+        }
+        for (name, values) in self.attributes.iter() {
+            match other.attributes.get(name) {
+                Some(other_values)
+                    if VertexFormat::from(values) == VertexFormat::from(other_values) => {}
+                _ => return Err(MeshError::IncompatibleMeshes),
+            }
+        }
+
+        let vertex_count = self.count_vertices();
+        let other_vertex_count = other.count_vertices();
+        let indices = match (&self.indices, &other.indices) {
+            (None, None) => None,
+            (indices, other_indices) => {
+                let indices = indices
+                    .as_ref()
+                    .map(|indices| indices.iter().collect::<Vec<_>>())
+                    .unwrap_or_else(|| (0..vertex_count).collect());
+                let other_indices = other_indices
+                    .as_ref()
+                    .map(|indices| indices.iter().collect::<Vec<_>>())
+                    .unwrap_or_else(|| (0..other_vertex_count).collect());
+                let merged = indices
+                    .into_iter()
+                    .chain(other_indices.into_iter().map(|index| index + vertex_count));
+                let keep_u16 = matches!(
+                    (&self.indices, &other.indices),
+                    (Some(Indices::U16(_)), Some(Indices::U16(_)))
+                ) && vertex_count + other_vertex_count <= u16::MAX as usize + 1;
+                Some(if keep_u16 {
+                    Indices::U16(merged.map(|index| index as u16).collect())
+                } else {
+                    Indices::U32(merged.map(|index| index as u32).collect())
+                })
+            }
+        };
+
+        for (name, values) in self.attributes.iter_mut() {
+            values.extend(&other.attributes[name]);
+        }
+        self.indices = indices;
+        Ok(())
+    }
+
     pub fn get_index_buffer_bytes(&self) -> Option<Vec<u8>> {
         self.indices.as_ref().map(|indices| match &indices {
             Indices::U16(indices) => indices.as_slice().as_bytes().to_vec(),
@@ -237,8 +942,8 @@ impl Mesh {
     pub fn get_vertex_buffer_descriptor(&self) -> VertexBufferDescriptor {
         let mut attributes = Vec::new();
         let mut accumulated_offset = 0;
-        for (attribute_name, attribute_values) in self.attributes.iter() {
-            let vertex_format = VertexFormat::from(attribute_values);
+        for attribute_name in self.attributes.keys() {
+            let vertex_format = self.attribute_format(attribute_name).unwrap();
             attributes.push(VertexAttributeDescriptor {
                 name: attribute_name.clone(),
                 offset: accumulated_offset,
@@ -270,20 +975,30 @@ impl Mesh {
         vertex_count.unwrap_or(0)
     }
 
-    pub fn get_vertex_buffer_data(&self) -> Vec<u8> {
+    /// Interleaves the attributes into the bytes of a vertex buffer, converting them to their vertex buffer formats.
+    /// Fails if the attributes don't pass [Mesh::validate].
+    pub fn get_vertex_buffer_data(&self) -> Result<Vec<u8>, MeshError> {
+        let vertex_count = self.validate_attributes()?;
         let mut vertex_size = 0;
-        for attribute_values in self.attributes.values() {
-            let vertex_format = VertexFormat::from(attribute_values);
+        for attribute_name in self.attributes.keys() {
+            let vertex_format = self.attribute_format(attribute_name).unwrap();
             vertex_size += vertex_format.get_size() as usize;
         }
 
-        let vertex_count = self.count_vertices();
         let mut attributes_interleaved_buffer = vec![0; vertex_count * vertex_size];
         // bundle into interleaved buffers
         let mut attribute_offset = 0;
-        for attribute_values in self.attributes.values() {
-            let vertex_format = VertexFormat::from(attribute_values);
+        for (attribute_name, attribute_values) in self.attributes.iter() {
+            let vertex_format = self.attribute_format(attribute_name).unwrap();
             let attribute_size = vertex_format.get_size() as usize;
+            let converted_values;
+            let attribute_values = if vertex_format == VertexFormat::from(attribute_values) {
+                attribute_values
+            } else {
+                // the conversion was checked by validate_attributes
+                converted_values = attribute_values.convert(vertex_format).unwrap();
+                &converted_values
+            };
             let attributes_bytes = attribute_values.get_bytes();
             for (vertex_index, attribute_bytes) in
                 attributes_bytes.chunks_exact(attribute_size).enumerate()
@@ -296,7 +1011,65 @@ impl Mesh {
             attribute_offset += attribute_size;
         }
 
-        attributes_interleaved_buffer
+        Ok(attributes_interleaved_buffer)
+    }
+}
+
+/// The cross product of two edges of the triangle. Its length is twice the triangle's area.
+fn triangle_normal(a: [f32; 3], b: [f32; 3], c: [f32; 3]) -> Vec3 {
+    let (a, b, c) = (Vec3::from(a), Vec3::from(b), Vec3::from(c));
+    (b - a).cross(c - a)
+}
+
+/// Normalizes `vector`, or returns zero if it has no direction
+fn normalize_or_zero(vector: Vec3) -> Vec3 {
+    if vector.length_squared() > 0.0 {
+        vector.normalize()
+    } else {
+        Vec3::zero()
+    }
+}
+
+/// The triangles of a mesh as seen by MikkTSpace, which collects the tangent of each vertex
+struct TangentGeometry<'a> {
+    indices: &'a [usize],
+    positions: &'a [[f32; 3]],
+    normals: &'a [[f32; 3]],
+    uvs: &'a [[f32; 2]],
+    tangents: Vec<[f32; 4]>,
+}
+
+impl<'a> TangentGeometry<'a> {
+    fn vertex(&self, face: usize, vert: usize) -> usize {
+        self.indices[face * 3 + vert]
+    }
+}
+
+impl<'a> mikktspace::Geometry for TangentGeometry<'a> {
+    fn num_faces(&self) -> usize {
+        self.indices.len() / 3
+    }
+
+    fn num_vertices_of_face(&self, _face: usize) -> usize {
+        3
+    }
+
+    fn position(&self, face: usize, vert: usize) -> [f32; 3] {
+        self.positions[self.vertex(face, vert)]
+    }
+
+    fn normal(&self, face: usize, vert: usize) -> [f32; 3] {
+        self.normals[self.vertex(face, vert)]
+    }
+
+    fn tex_coord(&self, face: usize, vert: usize) -> [f32; 2] {
+        self.uvs[self.vertex(face, vert)]
+    }
+
+    // the w component is the sign of the bitangent, like Mesh::ATTRIBUTE_TANGENT expects
+    fn set_tangent_encoded(&mut self, tangent: [f32; 4], face: usize, vert: usize) {
+        let vertex = self.vertex(face, vert);
+        self.tangents[vertex] = tangent;
     }
 }
 
@@ -364,6 +1137,14 @@ pub fn mesh_resource_provider_system(
     // update changed mesh data
     for changed_mesh_handle in changed_meshes.iter() {
         if let Some(mesh) = meshes.get(changed_mesh_handle) {
+            let interleaved_buffer = match mesh.get_vertex_buffer_data() {
+                Ok(interleaved_buffer) => interleaved_buffer,
+                Err(err) => {
+                    error!("Mesh can't be uploaded: {}", err);
+                    continue;
+                }
+            };
+
             // TODO: check for individual buffer changes in non-interleaved mode
             let index_buffer = render_resource_context.create_buffer_with_data(
                 BufferInfo {
@@ -379,8 +1160,6 @@ pub fn mesh_resource_provider_system(
                 INDEX_BUFFER_ASSET_INDEX,
             );
 
-            let interleaved_buffer = mesh.get_vertex_buffer_data();
-
             render_resource_context.set_asset_resource(
                 changed_mesh_handle,
                 RenderResourceId::Buffer(render_resource_context.create_buffer_with_data(
@@ -454,3 +1233,235 @@ fn update_entity_mesh(
         render_pipelines.bindings.vertex_attribute_buffer = Some(vertex_attribute_buffer_resource);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn quad() -> Mesh {
+        let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
+        mesh.set_attribute(
+            Mesh::ATTRIBUTE_POSITION,
+            vec![
+                [0.0, 0.0, 0.0],
+                [1.0, 0.0, 0.0],
+                [1.0, 1.0, 0.0],
+                [0.0, 1.0, 0.0],
+            ],
+        );
+        mesh.set_attribute(
+            Mesh::ATTRIBUTE_UV_0,
+            vec![[0.0, 1.0], [1.0, 1.0], [1.0, 0.0], [0.0, 0.0]],
+        );
+        mesh.set_indices(Some(Indices::U16(vec![0, 1, 2, 0, 2, 3])));
+        mesh
+    }
+
+    #[test]
+    fn validate() {
+        let mut mesh = quad();
+        assert_eq!(mesh.validate(), Ok(()));
+
+        mesh.set_indices(Some(Indices::U16(vec![0, 1, 4])));
+        assert_eq!(
+            mesh.validate(),
+            Err(MeshError::IndexOutOfRange {
+                index: 4,
+                vertex_count: 4
+            })
+        );
+
+        mesh.set_indices(Some(Indices::U16(vec![0, 1])));
+        assert!(matches!(
+            mesh.validate(),
+            Err(MeshError::IncompletePrimitive { count: 2, .. })
+        ));
+
+        mesh.set_indices(None);
+        mesh.set_attribute(Mesh::ATTRIBUTE_NORMAL, vec![[0.0, 0.0, 1.0]]);
+        assert!(matches!(
+            mesh.validate(),
+            Err(MeshError::AttributeLengthMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn normals() {
+        let mut mesh = quad();
+        mesh.compute_smooth_normals().unwrap();
+        assert_eq!(mesh.count_vertices(), 4);
+        if let Some(VertexAttributeValues::Float3(normals)) = mesh.attribute(Mesh::ATTRIBUTE_NORMAL)
+        {
+            assert!(normals.iter().all(|normal| *normal == [0.0, 0.0, 1.0]));
+        } else {
+            panic!("expected normals");
+        }
+
+        mesh.compute_flat_normals().unwrap();
+        assert!(mesh.indices().is_none());
+        assert_eq!(mesh.count_vertices(), 6);
+        assert_eq!(
+            mesh.attribute(Mesh::ATTRIBUTE_UV_0).unwrap().len(),
+            6,
+            "all attributes are duplicated"
+        );
+
+        // a triangle whose corners are on one line has no normal
+        let mut degenerate = Mesh::new(PrimitiveTopology::TriangleList);
+        degenerate.set_attribute(
+            Mesh::ATTRIBUTE_POSITION,
+            vec![[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [2.0, 0.0, 0.0]],
+        );
+        degenerate.compute_flat_normals().unwrap();
+        if let Some(VertexAttributeValues::Float3(normals)) =
+            degenerate.attribute(Mesh::ATTRIBUTE_NORMAL)
+        {
+            assert_eq!(normals, &vec![[0.0, 0.0, 0.0]; 3]);
+        } else {
+            panic!("expected normals");
+        }
+
+        let mut lines = Mesh::new(PrimitiveTopology::LineList);
+        lines.set_attribute(Mesh::ATTRIBUTE_POSITION, vec![[0.0, 0.0, 0.0]; 2]);
+        assert_eq!(
+            lines.compute_flat_normals(),
+            Err(MeshError::UnsupportedTopology(PrimitiveTopology::LineList))
+        );
+    }
+
+    #[test]
+    fn tangents() {
+        let mut mesh = quad();
+        assert_eq!(
+            mesh.compute_tangents(),
+            Err(MeshError::MissingAttribute(Mesh::ATTRIBUTE_NORMAL))
+        );
+
+        mesh.compute_smooth_normals().unwrap();
+        mesh.compute_tangents().unwrap();
+        if let Some(VertexAttributeValues::Float4(tangents)) =
+            mesh.attribute(Mesh::ATTRIBUTE_TANGENT)
+        {
+            assert!(tangents
+                .iter()
+                .all(|tangent| *tangent == [1.0, 0.0, 0.0, -1.0]));
+        } else {
+            panic!("expected tangents");
+        }
+
+        // mirroring the uvs flips the tangent and the sign of the bitangent
+        mesh.set_attribute(
+            Mesh::ATTRIBUTE_UV_0,
+            vec![[1.0, 1.0], [0.0, 1.0], [0.0, 0.0], [1.0, 0.0]],
+        );
+        mesh.compute_tangents().unwrap();
+        if let Some(VertexAttributeValues::Float4(tangents)) =
+            mesh.attribute(Mesh::ATTRIBUTE_TANGENT)
+        {
+            assert!(tangents
+                .iter()
+                .all(|tangent| *tangent == [-1.0, 0.0, 0.0, 1.0]));
+        } else {
+            panic!("expected tangents");
+        }
+    }
+
+    #[test]
+    fn transform() {
+        let mut mesh = quad();
+        mesh.compute_smooth_normals().unwrap();
+        mesh.transform_by(&Transform {
+            translation: Vec3::new(0.0, 0.0, 1.0),
+            rotation: Quat::from_rotation_y(std::f32::consts::FRAC_PI_2),
+            scale: Vec3::new(2.0, 2.0, 2.0),
+        });
+
+        let aabb = mesh.compute_aabb().unwrap();
+        assert!((aabb.min() - Vec3::new(0.0, 0.0, -1.0)).length() < 1e-5);
+        assert!((aabb.max() - Vec3::new(0.0, 2.0, 1.0)).length() < 1e-5);
+        if let Some(VertexAttributeValues::Float3(normals)) = mesh.attribute(Mesh::ATTRIBUTE_NORMAL)
+        {
+            assert!((Vec3::from(normals[0]) - Vec3::unit_x()).length() < 1e-5);
+        } else {
+            panic!("expected normals");
+        }
+
+        // flattening the quad along its normal keeps the normals finite
+        let mut mesh = quad();
+        mesh.compute_smooth_normals().unwrap();
+        mesh.compute_tangents().unwrap();
+        mesh.transform_by(&Transform::from_scale(Vec3::new(2.0, 1.0, 0.0)));
+        if let Some(VertexAttributeValues::Float3(normals)) = mesh.attribute(Mesh::ATTRIBUTE_NORMAL)
+        {
+            assert!(normals.iter().all(|normal| *normal == [0.0, 0.0, 1.0]));
+        } else {
+            panic!("expected normals");
+        }
+        if let Some(VertexAttributeValues::Float4(tangents)) =
+            mesh.attribute(Mesh::ATTRIBUTE_TANGENT)
+        {
+            assert!(tangents
+                .iter()
+                .all(|tangent| tangent.iter().all(|value| value.is_finite())));
+        } else {
+            panic!("expected tangents");
+        }
+    }
+
+    #[test]
+    fn merge() {
+        let mut mesh = quad();
+        mesh.merge(&quad()).unwrap();
+        assert_eq!(mesh.count_vertices(), 8);
+        assert_eq!(
+            mesh.indices().unwrap().iter().collect::<Vec<_>>(),
+            vec![0, 1, 2, 0, 2, 3, 4, 5, 6, 4, 6, 7]
+        );
+        assert!(matches!(mesh.indices(), Some(Indices::U16(_))));
+
+        let mut other = quad();
+        other.compute_smooth_normals().unwrap();
+        assert_eq!(mesh.merge(&other), Err(MeshError::IncompatibleMeshes));
+    }
+
+    #[test]
+    fn attribute_formats() {
+        let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
+        mesh.set_attribute(Mesh::ATTRIBUTE_POSITION, vec![[1.0, 2.0, 3.0]]);
+        mesh.set_attribute(
+            "Vertex_Color",
+            VertexAttributeValues::Uchar4Norm(vec![[255; 4]]),
+        );
+        mesh.set_attribute_format("Vertex_Color", VertexFormat::Float4);
+        mesh.set_attribute_format(Mesh::ATTRIBUTE_POSITION, VertexFormat::Float4);
+        assert_eq!(mesh.validate(), Ok(()));
+
+        let descriptor = mesh.get_vertex_buffer_descriptor();
+        assert_eq!(descriptor.stride, 32);
+        assert!(descriptor
+            .attributes
+            .iter()
+            .all(|attribute| attribute.format == VertexFormat::Float4));
+        let data = mesh.get_vertex_buffer_data().unwrap();
+        let mut floats = data
+            .chunks_exact(4)
+            .map(|bytes| f32::from_ne_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+            .collect::<Vec<_>>();
+        floats.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        assert_eq!(floats, vec![1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 2.0, 3.0]);
+
+        mesh.set_attribute_format(Mesh::ATTRIBUTE_POSITION, VertexFormat::Float2);
+        assert_eq!(
+            mesh.validate(),
+            Err(MeshError::UnsupportedConversion {
+                name: Mesh::ATTRIBUTE_POSITION.to_string(),
+                from: VertexFormat::Float3,
+                to: VertexFormat::Float2,
+            })
+        );
+        assert!(matches!(
+            mesh.get_vertex_buffer_data(),
+            Err(MeshError::UnsupportedConversion { .. })
+        ));
+    }
+}
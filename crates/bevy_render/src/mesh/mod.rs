@@ -1,3 +1,4 @@
+mod conversion;
 #[allow(clippy::module_inception)]
 mod mesh;
 /// Generation for some primitive shape meshes.
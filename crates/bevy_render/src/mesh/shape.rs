@@ -324,3 +324,715 @@ impl From<Icosphere> for Mesh {
         mesh
     }
 }
+
+fn triangle_list_mesh(
+    positions: Vec<[f32; 3]>,
+    normals: Vec<[f32; 3]>,
+    uvs: Vec<[f32; 2]>,
+    indices: Vec<u32>,
+) -> Mesh {
+    let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
+    mesh.set_indices(Some(Indices::U32(indices)));
+    mesh.set_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+    mesh.set_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
+    mesh.set_attribute(Mesh::ATTRIBUTE_UV_0, uvs);
+    mesh
+}
+
+/// A square on the XZ plane, divided into a grid of quads.
+#[derive(Debug)]
+pub struct SubdividedPlane {
+    /// The total side length of the square.
+    pub size: f32,
+    /// The number of cuts along each side. Zero subdivisions is the same as a [Plane].
+    pub subdivisions: u32,
+}
+
+impl Default for SubdividedPlane {
+    fn default() -> Self {
+        SubdividedPlane {
+            size: 1.0,
+            subdivisions: 4,
+        }
+    }
+}
+
+impl From<SubdividedPlane> for Mesh {
+    fn from(plane: SubdividedPlane) -> Self {
+        let quads = plane.subdivisions + 1;
+        let row = quads + 1;
+        let extent = plane.size / 2.0;
+
+        let vertex_count = (row * row) as usize;
+        let mut positions = Vec::with_capacity(vertex_count);
+        let mut normals = Vec::with_capacity(vertex_count);
+        let mut uvs = Vec::with_capacity(vertex_count);
+        for z in 0..row {
+            for x in 0..row {
+                let u = x as f32 / quads as f32;
+                let v = z as f32 / quads as f32;
+                positions.push([-extent + u * plane.size, 0.0, -extent + v * plane.size]);
+                normals.push([0.0, 1.0, 0.0]);
+                uvs.push([u, 1.0 - v]);
+            }
+        }
+
+        let mut indices = Vec::with_capacity((quads * quads * 6) as usize);
+        for z in 0..quads {
+            for x in 0..quads {
+                let a = z * row + x;
+                let b = a + 1;
+                let c = a + row + 1;
+                let d = a + row;
+                indices.extend_from_slice(&[a, d, b, b, d, c]);
+            }
+        }
+
+        triangle_list_mesh(positions, normals, uvs, indices)
+    }
+}
+
+/// A sphere made of rings of quads, with uvs wrapping around it like a map projection.
+#[derive(Debug)]
+pub struct UVSphere {
+    /// The radius of the sphere.
+    pub radius: f32,
+    /// The number of vertical slices around the sphere.
+    pub sectors: usize,
+    /// The number of horizontal rings from pole to pole.
+    pub stacks: usize,
+}
+
+impl Default for UVSphere {
+    fn default() -> Self {
+        UVSphere {
+            radius: 1.0,
+            sectors: 36,
+            stacks: 18,
+        }
+    }
+}
+
+impl From<UVSphere> for Mesh {
+    fn from(sphere: UVSphere) -> Self {
+        let mut positions = Vec::with_capacity((sphere.stacks + 1) * (sphere.sectors + 1));
+        let mut normals = Vec::with_capacity(positions.capacity());
+        let mut uvs = Vec::with_capacity(positions.capacity());
+        for stack in 0..=sphere.stacks {
+            let v = stack as f32 / sphere.stacks as f32;
+            let inclination = v * std::f32::consts::PI;
+            for sector in 0..=sphere.sectors {
+                let u = sector as f32 / sphere.sectors as f32;
+                let azimuth = u * 2.0 * std::f32::consts::PI;
+                let normal = [
+                    inclination.sin() * azimuth.cos(),
+                    inclination.cos(),
+                    -inclination.sin() * azimuth.sin(),
+                ];
+                positions.push([
+                    normal[0] * sphere.radius,
+                    normal[1] * sphere.radius,
+                    normal[2] * sphere.radius,
+                ]);
+                normals.push(normal);
+                uvs.push([u, v]);
+            }
+        }
+
+        let indices = ring_indices(sphere.stacks, sphere.sectors);
+        triangle_list_mesh(positions, normals, uvs, indices)
+    }
+}
+
+/// Connects `rings` rows of `sectors + 1` vertices, from top to bottom. The first and last row are poles, so the
+/// degenerate triangles touching them are skipped.
+fn ring_indices(rings: usize, sectors: usize) -> Vec<u32> {
+    let row = sectors as u32 + 1;
+    let mut indices = Vec::with_capacity(rings.saturating_sub(1) * sectors * 6);
+    for ring in 0..rings as u32 {
+        for sector in 0..sectors as u32 {
+            let top = ring * row + sector;
+            let bottom = top + row;
+            if ring != rings as u32 - 1 {
+                indices.extend_from_slice(&[bottom, bottom + 1, top]);
+            }
+            if ring != 0 {
+                indices.extend_from_slice(&[bottom + 1, top + 1, top]);
+            }
+        }
+    }
+    indices
+}
+
+/// A cylinder with hemispheres on both ends, standing on the Y axis.
+#[derive(Debug)]
+pub struct Capsule {
+    /// The radius of the cylinder and the hemispheres.
+    pub radius: f32,
+    /// The height of the cylinder between the hemispheres.
+    pub depth: f32,
+    /// The number of rings in each hemisphere.
+    pub latitudes: usize,
+    /// The number of vertical slices around the capsule.
+    pub longitudes: usize,
+}
+
+impl Default for Capsule {
+    fn default() -> Self {
+        Capsule {
+            radius: 0.5,
+            depth: 1.0,
+            latitudes: 8,
+            longitudes: 32,
+        }
+    }
+}
+
+impl From<Capsule> for Mesh {
+    fn from(capsule: Capsule) -> Self {
+        let half_depth = capsule.depth / 2.0;
+        // uvs are spread by the length of the profile from pole to pole
+        let arc_length = std::f32::consts::PI * capsule.radius + capsule.depth;
+
+        let vertex_count = 2 * (capsule.latitudes + 1) * (capsule.longitudes + 1);
+        let mut positions = Vec::with_capacity(vertex_count);
+        let mut normals = Vec::with_capacity(vertex_count);
+        let mut uvs = Vec::with_capacity(vertex_count);
+        for hemisphere in 0..2 {
+            for latitude in 0..=capsule.latitudes {
+                let ring = hemisphere * capsule.latitudes + latitude;
+                let inclination =
+                    ring as f32 / (2 * capsule.latitudes) as f32 * std::f32::consts::PI;
+                let (offset, v) = if hemisphere == 0 {
+                    (half_depth, inclination * capsule.radius / arc_length)
+                } else {
+                    (
+                        -half_depth,
+                        (inclination * capsule.radius + capsule.depth) / arc_length,
+                    )
+                };
+                for longitude in 0..=capsule.longitudes {
+                    let u = longitude as f32 / capsule.longitudes as f32;
+                    let azimuth = u * 2.0 * std::f32::consts::PI;
+                    let normal = [
+                        inclination.sin() * azimuth.cos(),
+                        inclination.cos(),
+                        -inclination.sin() * azimuth.sin(),
+                    ];
+                    positions.push([
+                        normal[0] * capsule.radius,
+                        normal[1] * capsule.radius + offset,
+                        normal[2] * capsule.radius,
+                    ]);
+                    normals.push(normal);
+                    uvs.push([u, v]);
+                }
+            }
+        }
+
+        // the two equator rows are connected by the cylinder
+        let indices = ring_indices(2 * capsule.latitudes + 1, capsule.longitudes);
+        triangle_list_mesh(positions, normals, uvs, indices)
+    }
+}
+
+/// Adds a disc facing up or down at `y`, as a fan around a center vertex.
+#[allow(clippy::too_many_arguments)]
+fn add_cap(
+    y: f32,
+    up: bool,
+    radius: f32,
+    resolution: usize,
+    positions: &mut Vec<[f32; 3]>,
+    normals: &mut Vec<[f32; 3]>,
+    uvs: &mut Vec<[f32; 2]>,
+    indices: &mut Vec<u32>,
+) {
+    let center = positions.len() as u32;
+    let normal_y = if up { 1.0 } else { -1.0 };
+    positions.push([0.0, y, 0.0]);
+    normals.push([0.0, normal_y, 0.0]);
+    uvs.push([0.5, 0.5]);
+    for sector in 0..resolution {
+        let azimuth = sector as f32 / resolution as f32 * 2.0 * std::f32::consts::PI;
+        let (sin, cos) = azimuth.sin_cos();
+        positions.push([radius * cos, y, -radius * sin]);
+        normals.push([0.0, normal_y, 0.0]);
+        uvs.push([0.5 + 0.5 * cos, 0.5 - 0.5 * sin * normal_y]);
+    }
+
+    for sector in 0..resolution as u32 {
+        let current = center + 1 + sector;
+        let next = center + 1 + (sector + 1) % resolution as u32;
+        if up {
+            indices.extend_from_slice(&[center, current, next]);
+        } else {
+            indices.extend_from_slice(&[center, next, current]);
+        }
+    }
+}
+
+/// A cylinder standing on the Y axis, centered on the origin.
+#[derive(Debug)]
+pub struct Cylinder {
+    pub radius: f32,
+    pub height: f32,
+    /// The number of vertical slices around the cylinder.
+    pub resolution: usize,
+    /// The number of rings along the height of the cylinder.
+    pub segments: usize,
+}
+
+impl Default for Cylinder {
+    fn default() -> Self {
+        Cylinder {
+            radius: 0.5,
+            height: 1.0,
+            resolution: 16,
+            segments: 1,
+        }
+    }
+}
+
+impl From<Cylinder> for Mesh {
+    fn from(cylinder: Cylinder) -> Self {
+        let row = cylinder.resolution + 1;
+        let vertex_count = (cylinder.segments + 1) * row + 2 * (cylinder.resolution + 1);
+        let mut positions = Vec::with_capacity(vertex_count);
+        let mut normals = Vec::with_capacity(vertex_count);
+        let mut uvs = Vec::with_capacity(vertex_count);
+        let mut indices = Vec::with_capacity(6 * cylinder.resolution * (cylinder.segments + 1));
+
+        // rows from top to bottom
+        for segment in 0..=cylinder.segments {
+            let v = segment as f32 / cylinder.segments as f32;
+            let y = cylinder.height * (0.5 - v);
+            for sector in 0..=cylinder.resolution {
+                let u = sector as f32 / cylinder.resolution as f32;
+                let (sin, cos) = (u * 2.0 * std::f32::consts::PI).sin_cos();
+                positions.push([cylinder.radius * cos, y, -cylinder.radius * sin]);
+                normals.push([cos, 0.0, -sin]);
+                uvs.push([u, v]);
+            }
+        }
+
+        let row = row as u32;
+        for segment in 0..cylinder.segments as u32 {
+            for sector in 0..cylinder.resolution as u32 {
+                let top = segment * row + sector;
+                let bottom = top + row;
+                indices.extend_from_slice(&[bottom, bottom + 1, top, bottom + 1, top + 1, top]);
+            }
+        }
+
+        let half_height = cylinder.height / 2.0;
+        add_cap(
+            half_height,
+            true,
+            cylinder.radius,
+            cylinder.resolution,
+            &mut positions,
+            &mut normals,
+            &mut uvs,
+            &mut indices,
+        );
+        add_cap(
+            -half_height,
+            false,
+            cylinder.radius,
+            cylinder.resolution,
+            &mut positions,
+            &mut normals,
+            &mut uvs,
+            &mut indices,
+        );
+
+        triangle_list_mesh(positions, normals, uvs, indices)
+    }
+}
+
+/// A cone standing on the Y axis with its base at `-height / 2` and its tip at `height / 2`.
+#[derive(Debug)]
+pub struct Cone {
+    /// The radius of the base.
+    pub radius: f32,
+    pub height: f32,
+    /// The number of vertical slices around the cone.
+    pub resolution: usize,
+}
+
+impl Default for Cone {
+    fn default() -> Self {
+        Cone {
+            radius: 0.5,
+            height: 1.0,
+            resolution: 32,
+        }
+    }
+}
+
+impl From<Cone> for Mesh {
+    fn from(cone: Cone) -> Self {
+        let half_height = cone.height / 2.0;
+        let vertex_count = 3 * cone.resolution + 2;
+        let mut positions = Vec::with_capacity(vertex_count);
+        let mut normals = Vec::with_capacity(vertex_count);
+        let mut uvs = Vec::with_capacity(vertex_count);
+        let mut indices = Vec::with_capacity(6 * cone.resolution);
+
+        // the side normals are perpendicular to the slope from the base to the tip
+        let slope_length = (cone.height * cone.height + cone.radius * cone.radius).sqrt();
+        let normal_xz = cone.height / slope_length;
+        let normal_y = cone.radius / slope_length;
+        let side_normal = |azimuth: f32| {
+            let (sin, cos) = azimuth.sin_cos();
+            [normal_xz * cos, normal_y, -normal_xz * sin]
+        };
+
+        for sector in 0..=cone.resolution {
+            let u = sector as f32 / cone.resolution as f32;
+            let azimuth = u * 2.0 * std::f32::consts::PI;
+            let (sin, cos) = azimuth.sin_cos();
+            positions.push([cone.radius * cos, -half_height, -cone.radius * sin]);
+            normals.push(side_normal(azimuth));
+            uvs.push([u, 1.0]);
+        }
+
+        // every sector gets its own tip, so its normal can point in the direction of the sector
+        let tips = positions.len() as u32;
+        for sector in 0..cone.resolution {
+            let u = (sector as f32 + 0.5) / cone.resolution as f32;
+            positions.push([0.0, half_height, 0.0]);
+            normals.push(side_normal(u * 2.0 * std::f32::consts::PI));
+            uvs.push([u, 0.0]);
+        }
+
+        for sector in 0..cone.resolution as u32 {
+            indices.extend_from_slice(&[sector, sector + 1, tips + sector]);
+        }
+
+        add_cap(
+            -half_height,
+            false,
+            cone.radius,
+            cone.resolution,
+            &mut positions,
+            &mut normals,
+            &mut uvs,
+            &mut indices,
+        );
+
+        triangle_list_mesh(positions, normals, uvs, indices)
+    }
+}
+
+/// A ring shaped tube lying on the XZ plane.
+#[derive(Debug)]
+pub struct Torus {
+    /// The distance from the center of the torus to the center of the tube.
+    pub radius: f32,
+    /// The radius of the tube.
+    pub ring_radius: f32,
+    /// The number of slices around the center of the torus.
+    pub subdivisions_segments: usize,
+    /// The number of slices around the tube.
+    pub subdivisions_sides: usize,
+}
+
+impl Default for Torus {
+    fn default() -> Self {
+        Torus {
+            radius: 1.0,
+            ring_radius: 0.5,
+            subdivisions_segments: 32,
+            subdivisions_sides: 24,
+        }
+    }
+}
+
+impl From<Torus> for Mesh {
+    fn from(torus: Torus) -> Self {
+        let vertex_count = (torus.subdivisions_segments + 1) * (torus.subdivisions_sides + 1);
+        let mut positions = Vec::with_capacity(vertex_count);
+        let mut normals = Vec::with_capacity(vertex_count);
+        let mut uvs = Vec::with_capacity(vertex_count);
+        for segment in 0..=torus.subdivisions_segments {
+            let u = segment as f32 / torus.subdivisions_segments as f32;
+            let (segment_sin, segment_cos) = (u * 2.0 * std::f32::consts::PI).sin_cos();
+            for side in 0..=torus.subdivisions_sides {
+                let v = side as f32 / torus.subdivisions_sides as f32;
+                let (side_sin, side_cos) = (v * 2.0 * std::f32::consts::PI).sin_cos();
+                let normal = [side_cos * segment_cos, side_sin, -side_cos * segment_sin];
+                let distance = torus.radius + torus.ring_radius * side_cos;
+                positions.push([
+                    distance * segment_cos,
+                    torus.ring_radius * side_sin,
+                    -distance * segment_sin,
+                ]);
+                normals.push(normal);
+                uvs.push([u, v]);
+            }
+        }
+
+        let row = torus.subdivisions_sides as u32 + 1;
+        let mut indices =
+            Vec::with_capacity(6 * torus.subdivisions_segments * torus.subdivisions_sides);
+        for segment in 0..torus.subdivisions_segments as u32 {
+            for side in 0..torus.subdivisions_sides as u32 {
+                let a = segment * row + side;
+                let b = a + row;
+                indices.extend_from_slice(&[a, b, a + 1, b, b + 1, a + 1]);
+            }
+        }
+
+        triangle_list_mesh(positions, normals, uvs, indices)
+    }
+}
+
+/// A regular polygon on the XY plane, with a vertex pointing up.
+#[derive(Debug)]
+pub struct RegularPolygon {
+    /// The distance from the center to each vertex.
+    pub circumradius: f32,
+    /// The number of sides. Needs to be at least 3.
+    pub sides: usize,
+}
+
+impl RegularPolygon {
+    pub fn new(circumradius: f32, sides: usize) -> Self {
+        RegularPolygon {
+            circumradius,
+            sides,
+        }
+    }
+}
+
+impl Default for RegularPolygon {
+    fn default() -> Self {
+        RegularPolygon::new(0.5, 6)
+    }
+}
+
+impl From<RegularPolygon> for Mesh {
+    fn from(polygon: RegularPolygon) -> Self {
+        assert!(
+            polygon.sides >= 3,
+            "a polygon needs at least 3 sides, but it has {}",
+            polygon.sides
+        );
+
+        let mut positions = Vec::with_capacity(polygon.sides);
+        let mut normals = Vec::with_capacity(polygon.sides);
+        let mut uvs = Vec::with_capacity(polygon.sides);
+        for side in 0..polygon.sides {
+            let angle = std::f32::consts::FRAC_PI_2
+                + side as f32 / polygon.sides as f32 * 2.0 * std::f32::consts::PI;
+            let (sin, cos) = angle.sin_cos();
+            positions.push([polygon.circumradius * cos, polygon.circumradius * sin, 0.0]);
+            normals.push([0.0, 0.0, 1.0]);
+            uvs.push([0.5 + 0.5 * cos, 0.5 - 0.5 * sin]);
+        }
+
+        let mut indices = Vec::with_capacity((polygon.sides - 2) * 3);
+        for side in 1..polygon.sides as u32 - 1 {
+            indices.extend_from_slice(&[0, side, side + 1]);
+        }
+
+        triangle_list_mesh(positions, normals, uvs, indices)
+    }
+}
+
+/// A circle on the XY plane, approximated by a [RegularPolygon].
+#[derive(Debug)]
+pub struct Circle {
+    pub radius: f32,
+    /// The number of vertices on the edge of the circle.
+    pub vertices: usize,
+}
+
+impl Circle {
+    pub fn new(radius: f32) -> Self {
+        Circle {
+            radius,
+            vertices: 64,
+        }
+    }
+}
+
+impl Default for Circle {
+    fn default() -> Self {
+        Circle::new(0.5)
+    }
+}
+
+impl From<Circle> for Mesh {
+    fn from(circle: Circle) -> Self {
+        RegularPolygon::new(circle.radius, circle.vertices).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mesh::VertexAttributeValues;
+
+    fn sub(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+        [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+    }
+
+    fn dot(a: [f32; 3], b: [f32; 3]) -> f32 {
+        a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+    }
+
+    fn cross(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+        [
+            a[1] * b[2] - a[2] * b[1],
+            a[2] * b[0] - a[0] * b[2],
+            a[0] * b[1] - a[1] * b[0],
+        ]
+    }
+
+    fn float3(mesh: &Mesh, name: &'static str) -> Vec<[f32; 3]> {
+        match mesh.attribute(name) {
+            Some(VertexAttributeValues::Float3(values)) => values.clone(),
+            _ => panic!("expected a Float3 {} attribute", name),
+        }
+    }
+
+    /// Checks the vertex and index counts, that normals are unit length, that each triangle is wound counter
+    /// clockwise around its vertex normals, and, for convex shapes, that normals point away from `center`
+    fn check(mesh: Mesh, vertices: usize, indices: usize, center: Option<[f32; 3]>) {
+        assert_eq!(mesh.validate(), Ok(()));
+        let positions = float3(&mesh, Mesh::ATTRIBUTE_POSITION);
+        let normals = float3(&mesh, Mesh::ATTRIBUTE_NORMAL);
+        assert_eq!(positions.len(), vertices);
+        assert_eq!(
+            mesh.attribute(Mesh::ATTRIBUTE_UV_0).unwrap().len(),
+            vertices
+        );
+
+        let triangle_indices = mesh.indices().unwrap().iter().collect::<Vec<_>>();
+        assert_eq!(triangle_indices.len(), indices);
+
+        for normal in normals.iter() {
+            assert!((dot(*normal, *normal) - 1.0).abs() < 1e-4);
+        }
+
+        for triangle in triangle_indices.chunks_exact(3) {
+            let [a, b, c] = [
+                positions[triangle[0]],
+                positions[triangle[1]],
+                positions[triangle[2]],
+            ];
+            let face_normal = cross(sub(b, a), sub(c, a));
+            assert!(dot(face_normal, face_normal) > 0.0, "degenerate triangle");
+            for index in triangle {
+                assert!(
+                    dot(face_normal, normals[*index]) > 0.0,
+                    "triangle {:?} is wound against its normals",
+                    triangle
+                );
+            }
+        }
+
+        if let Some(center) = center {
+            for (position, normal) in positions.iter().zip(normals.iter()) {
+                assert!(dot(sub(*position, center), *normal) > 0.0);
+            }
+        }
+    }
+
+    #[test]
+    fn subdivided_plane() {
+        check(
+            SubdividedPlane {
+                size: 2.0,
+                subdivisions: 3,
+            }
+            .into(),
+            25,
+            96,
+            Some([0.0, -1.0, 0.0]),
+        );
+    }
+
+    #[test]
+    fn uv_sphere() {
+        check(
+            UVSphere {
+                radius: 2.0,
+                sectors: 8,
+                stacks: 6,
+            }
+            .into(),
+            63,
+            240,
+            Some([0.0; 3]),
+        );
+    }
+
+    #[test]
+    fn capsule() {
+        check(
+            Capsule {
+                latitudes: 4,
+                longitudes: 8,
+                ..Default::default()
+            }
+            .into(),
+            90,
+            384,
+            None,
+        );
+    }
+
+    #[test]
+    fn cylinder() {
+        check(
+            Cylinder {
+                resolution: 8,
+                segments: 2,
+                ..Default::default()
+            }
+            .into(),
+            45,
+            144,
+            Some([0.0; 3]),
+        );
+    }
+
+    #[test]
+    fn cone() {
+        check(
+            Cone {
+                resolution: 8,
+                ..Default::default()
+            }
+            .into(),
+            26,
+            48,
+            Some([0.0; 3]),
+        );
+    }
+
+    #[test]
+    fn torus() {
+        check(
+            Torus {
+                subdivisions_segments: 8,
+                subdivisions_sides: 6,
+                ..Default::default()
+            }
+            .into(),
+            63,
+            288,
+            None,
+        );
+    }
+
+    #[test]
+    fn polygons() {
+        check(RegularPolygon::new(1.0, 5).into(), 5, 9, None);
+        check(Circle::new(1.0).into(), 64, 186, None);
+    }
+}
@@ -1,5 +1,6 @@
 use super::Operations;
 use crate::{renderer::TextureId, Color};
+use bevy_reflect::{Reflect, ReflectResource};
 
 #[derive(Debug, Clone)]
 pub enum TextureAttachment {
@@ -18,7 +19,8 @@ impl TextureAttachment {
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Reflect)]
+#[reflect(Resource)]
 pub struct ClearColor(pub Color);
 
 impl Default for ClearColor {
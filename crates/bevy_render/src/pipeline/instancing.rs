@@ -0,0 +1,401 @@
+use super::{
+    update_pipeline_specializations, InputStepMode, PipelineDescriptor, RenderPipelines,
+    VertexAttributeDescriptor, VertexBufferDescriptor, VertexFormat,
+};
+use crate::{
+    camera::RenderLayers,
+    draw::{Draw, DrawContext, Visible},
+    mesh::{Indices, Mesh, VertexAttributeValues},
+    prelude::Msaa,
+    renderer::{BufferId, BufferInfo, BufferUsage, RenderResourceBindings},
+};
+use bevy_asset::{Assets, Handle, HandleUntyped};
+use bevy_core::AsBytes;
+use bevy_ecs::{Entity, Local, Query, Res, ResMut, With};
+use bevy_reflect::{Reflect, ReflectComponent};
+use bevy_transform::prelude::GlobalTransform;
+use bevy_utils::HashSet;
+use std::{any::TypeId, borrow::Cow};
+
+/// The shader def that instanced pipelines are compiled with
+pub const INSTANCING_SHADER_DEF: &str = "INSTANCING";
+
+/// The per-instance attributes that hold the columns of each instance's model matrix
+pub const INSTANCE_MODEL_ATTRIBUTES: [&str; 4] =
+    ["I_Model_0", "I_Model_1", "I_Model_2", "I_Model_3"];
+
+/// Draws an entity in a single instanced draw together with all other visible [Instanced] entities that share its mesh,
+/// pipelines, assets (like its material), [RenderLayers] and [InstanceAttributes] layout.
+///
+/// Pipelines of instanced entities are compiled with the `INSTANCING` shader def, and get each instance's model matrix
+/// in the [INSTANCE_MODEL_ATTRIBUTES] instead of the `Transform` uniform. The whole batch is drawn by one of its
+/// entities, so instanced entities are never frustum culled.
+///
+/// Of the built-in shaders, only the PBR forward shader of `StandardMaterial` reads the model matrix this way. Custom
+/// vertex shaders need to do the same when `INSTANCING` is defined, and pipelines whose shaders have no `I_`
+/// attributes are rejected when they're compiled for an instanced draw.
+#[derive(Debug, Default, Clone, Copy, Reflect)]
+#[reflect(Component)]
+pub struct Instanced;
+
+/// Extra per-instance vertex attributes of an [Instanced] entity. Shaders treat inputs with the `I_` prefix as
+/// per-instance attributes, so names need that prefix too.
+#[derive(Debug, Default, Clone)]
+pub struct InstanceAttributes {
+    attributes: Vec<(Cow<'static, str>, VertexAttributeValues)>,
+}
+
+impl InstanceAttributes {
+    pub fn set<T>(&mut self, name: impl Into<Cow<'static, str>>, value: T)
+    where
+        Vec<T>: Into<VertexAttributeValues>,
+    {
+        let name = name.into();
+        assert!(
+            name.starts_with("I_"),
+            "Instance attribute {} needs the I_ prefix.",
+            name
+        );
+        let value = vec![value].into();
+        match self
+            .attributes
+            .iter_mut()
+            .find(|(current_name, _)| *current_name == name)
+        {
+            Some((_, current_value)) => *current_value = value,
+            None => {
+                self.attributes.push((name, value));
+                self.attributes
+                    .sort_by(|(name_a, _), (name_b, _)| name_a.cmp(name_b));
+            }
+        }
+    }
+
+    pub fn with<T>(mut self, name: impl Into<Cow<'static, str>>, value: T) -> Self
+    where
+        Vec<T>: Into<VertexAttributeValues>,
+    {
+        self.set(name, value);
+        self
+    }
+
+    pub fn get(&self, name: &str) -> Option<&VertexAttributeValues> {
+        self.attributes
+            .iter()
+            .find(|(current_name, _)| current_name == name)
+            .map(|(_, value)| value)
+    }
+
+    fn layout(&self) -> Vec<(Cow<'static, str>, VertexFormat)> {
+        self.attributes
+            .iter()
+            .map(|(name, value)| (name.clone(), VertexFormat::from(value)))
+            .collect()
+    }
+}
+
+/// Instanced entities that are drawn together
+struct InstanceBatch {
+    mesh: Handle<Mesh>,
+    pipelines: Vec<Handle<PipelineDescriptor>>,
+    assets: HashSet<(HandleUntyped, TypeId)>,
+    render_layers: Option<RenderLayers>,
+    layout: Vec<(Cow<'static, str>, VertexFormat)>,
+    /// The entity whose [Draw] draws the batch
+    entity: Entity,
+    instance_data: Vec<u8>,
+    instance_count: u32,
+}
+
+impl InstanceBatch {
+    fn instance_buffer_descriptor(&self) -> VertexBufferDescriptor {
+        let model_attributes = INSTANCE_MODEL_ATTRIBUTES
+            .iter()
+            .map(|name| (Cow::Borrowed(*name), VertexFormat::Float4));
+        let mut attributes = Vec::new();
+        let mut offset = 0;
+        for (name, format) in model_attributes.chain(self.layout.iter().cloned()) {
+            attributes.push(VertexAttributeDescriptor {
+                name,
+                offset,
+                format,
+                shader_location: 0,
+            });
+            offset += format.get_size();
+        }
+
+        VertexBufferDescriptor {
+            name: "Instance".into(),
+            stride: offset,
+            step_mode: InputStepMode::Instance,
+            attributes,
+        }
+    }
+}
+
+/// Adds an entity to the batch it's drawn with, starting a new batch if no other entity can be drawn together with it
+fn add_instance(
+    batches: &mut Vec<InstanceBatch>,
+    entity: Entity,
+    render_pipelines: &RenderPipelines,
+    mesh: &Handle<Mesh>,
+    global_transform: &GlobalTransform,
+    render_layers: Option<&RenderLayers>,
+    attributes: Option<&InstanceAttributes>,
+) {
+    let pipelines = render_pipelines
+        .pipelines
+        .iter()
+        .map(|pipeline| pipeline.pipeline.clone_weak())
+        .collect::<Vec<_>>();
+    let assets = render_pipelines
+        .bindings
+        .iter_assets()
+        .cloned()
+        .collect::<HashSet<_>>();
+    let layout = attributes
+        .map(|attributes| attributes.layout())
+        .unwrap_or_default();
+    let render_layers = render_layers.cloned();
+
+    let batch_index = match batches.iter().position(|batch| {
+        batch.mesh == *mesh
+            && batch.pipelines == pipelines
+            && batch.assets == assets
+            && batch.render_layers == render_layers
+            && batch.layout == layout
+    }) {
+        Some(index) => index,
+        None => {
+            batches.push(InstanceBatch {
+                mesh: mesh.clone_weak(),
+                pipelines,
+                assets,
+                render_layers,
+                layout,
+                entity,
+                instance_data: Vec::new(),
+                instance_count: 0,
+            });
+            batches.len() - 1
+        }
+    };
+
+    let batch = &mut batches[batch_index];
+    batch
+        .instance_data
+        .extend_from_slice(global_transform.compute_matrix().to_cols_array().as_bytes());
+    if let Some(attributes) = attributes {
+        for (_, value) in attributes.attributes.iter() {
+            batch.instance_data.extend_from_slice(value.get_bytes());
+        }
+    }
+    batch.instance_count += 1;
+}
+
+#[derive(Default)]
+pub struct InstancingState {
+    instance_buffers: Vec<BufferId>,
+}
+
+pub fn draw_instanced_render_pipelines_system(
+    mut state: Local<InstancingState>,
+    mut draw_context: DrawContext,
+    mut render_resource_bindings: ResMut<RenderResourceBindings>,
+    msaa: Res<Msaa>,
+    meshes: Res<Assets<Mesh>>,
+    mut query: Query<
+        (
+            Entity,
+            &mut Draw,
+            &mut RenderPipelines,
+            &Handle<Mesh>,
+            &Visible,
+            &GlobalTransform,
+            Option<&RenderLayers>,
+            Option<&InstanceAttributes>,
+        ),
+        With<Instanced>,
+    >,
+) {
+    // instance buffers are rebuilt every frame, last frame's draws have already been submitted
+    for buffer in state.instance_buffers.drain(..) {
+        draw_context.render_resource_context.remove_buffer(buffer);
+    }
+
+    let mut batches = Vec::<InstanceBatch>::new();
+    for (
+        entity,
+        _draw,
+        render_pipelines,
+        mesh,
+        visible,
+        global_transform,
+        render_layers,
+        attributes,
+    ) in query.iter_mut()
+    {
+        if visible.is_visible {
+            add_instance(
+                &mut batches,
+                entity,
+                &render_pipelines,
+                mesh,
+                global_transform,
+                render_layers,
+                attributes,
+            );
+        }
+    }
+
+    for batch in batches {
+        // don't render if the mesh isn't loaded yet
+        let index_range = match meshes.get(&batch.mesh).and_then(|mesh| mesh.indices()) {
+            Some(Indices::U32(indices)) => 0..indices.len() as u32,
+            Some(Indices::U16(indices)) => 0..indices.len() as u32,
+            None => continue,
+        };
+
+        let instance_buffer = draw_context
+            .render_resource_context
+            .create_buffer_with_data(
+                BufferInfo {
+                    buffer_usage: BufferUsage::VERTEX,
+                    ..Default::default()
+                },
+                &batch.instance_data,
+            );
+        state.instance_buffers.push(instance_buffer);
+        let instance_buffer_descriptor = batch.instance_buffer_descriptor();
+
+        let (_, mut draw, mut render_pipelines, _, _, _, _, _) =
+            query.get_mut(batch.entity).unwrap();
+        let render_pipelines = &mut *render_pipelines;
+        update_pipeline_specializations(&draw_context, &msaa, render_pipelines);
+
+        for render_pipeline in render_pipelines.pipelines.iter_mut() {
+            let mut specialization = render_pipeline.specialization.clone();
+            specialization
+                .shader_specialization
+                .shader_defs
+                .insert(INSTANCING_SHADER_DEF.to_string());
+            specialization.instance_buffer_descriptor = Some(instance_buffer_descriptor.clone());
+
+            let render_resource_bindings = &mut [
+                &mut render_pipelines.bindings,
+                &mut render_resource_bindings,
+            ];
+            draw_context
+                .set_pipeline(&mut draw, &render_pipeline.pipeline, &specialization)
+                .unwrap();
+            draw_context
+                .set_bind_groups_from_bindings(&mut draw, render_resource_bindings)
+                .unwrap();
+            draw_context
+                .set_vertex_buffers_from_bindings(&mut draw, &[&render_pipelines.bindings])
+                .unwrap();
+            draw.set_vertex_buffer(1, instance_buffer, 0);
+            draw.draw_indexed(index_range.clone(), 0, 0..batch.instance_count);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::texture::Texture;
+    use bevy_asset::HandleId;
+    use bevy_math::Vec3;
+    use bevy_transform::prelude::Transform;
+
+    #[test]
+    fn batch_instances() {
+        let mesh = Handle::<Mesh>::weak(HandleId::random::<Mesh>());
+        let other_mesh = Handle::<Mesh>::weak(HandleId::random::<Mesh>());
+        let pipeline = Handle::<PipelineDescriptor>::weak(HandleId::random::<PipelineDescriptor>());
+        let render_pipelines = RenderPipelines::from_handles(&[pipeline]);
+        let mut textured_pipelines = render_pipelines.clone();
+        textured_pipelines.bindings.add_asset(
+            HandleUntyped::weak(HandleId::random::<Texture>()),
+            TypeId::of::<Texture>(),
+        );
+        let scaled = InstanceAttributes::default().with("I_Scale", 2.0f32);
+        let transform = GlobalTransform::from(Transform::from_translation(Vec3::unit_x()));
+
+        let mut batches = Vec::new();
+        let mut entities = (0..7).map(Entity::new);
+        let mut add = |render_pipelines: &RenderPipelines,
+                       mesh: &Handle<Mesh>,
+                       render_layers: Option<&RenderLayers>,
+                       attributes: Option<&InstanceAttributes>| {
+            add_instance(
+                &mut batches,
+                entities.next().unwrap(),
+                render_pipelines,
+                mesh,
+                &transform,
+                render_layers,
+                attributes,
+            );
+        };
+        add(&render_pipelines, &mesh, None, None);
+        add(&render_pipelines, &mesh, None, None);
+        add(&render_pipelines, &other_mesh, None, None);
+        add(&textured_pipelines, &mesh, None, None);
+        add(
+            &render_pipelines,
+            &mesh,
+            Some(&RenderLayers::layer(1)),
+            None,
+        );
+        add(&render_pipelines, &mesh, None, Some(&scaled));
+        add(&render_pipelines, &mesh, None, Some(&scaled));
+
+        let counts = batches
+            .iter()
+            .map(|batch| (batch.entity.id(), batch.instance_count))
+            .collect::<Vec<_>>();
+        assert_eq!(counts, vec![(0, 2), (2, 1), (3, 1), (4, 1), (5, 2)]);
+
+        // each instance has its model matrix, followed by its attributes
+        let matrix = transform.compute_matrix().to_cols_array();
+        assert_eq!(
+            batches[0].instance_data,
+            [matrix.as_bytes(), matrix.as_bytes()].concat()
+        );
+        let instance = [matrix.as_bytes(), 2.0f32.as_bytes()].concat();
+        assert_eq!(
+            batches[4].instance_data,
+            [&instance[..], &instance[..]].concat()
+        );
+        let descriptor = batches[4].instance_buffer_descriptor();
+        assert_eq!(descriptor.stride, 68);
+        assert_eq!(descriptor.attributes[4].name, "I_Scale");
+        assert_eq!(descriptor.attributes[4].offset, 64);
+    }
+
+    #[test]
+    fn instance_attributes() {
+        let attributes = InstanceAttributes::default()
+            .with("I_Scale", 2.0f32)
+            .with("I_Color", [1.0f32, 0.0, 0.0, 1.0])
+            .with("I_Scale", 3.0f32);
+        assert_eq!(
+            attributes.layout(),
+            vec![
+                (Cow::Borrowed("I_Color"), VertexFormat::Float4),
+                (Cow::Borrowed("I_Scale"), VertexFormat::Float),
+            ]
+        );
+        assert_eq!(
+            attributes.get("I_Scale").unwrap().get_bytes(),
+            3.0f32.as_bytes()
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn instance_attribute_prefix() {
+        InstanceAttributes::default().with("Color", [1.0f32; 4]);
+    }
+}
@@ -1,5 +1,6 @@
 mod bind_group;
 mod binding;
+mod instancing;
 #[allow(clippy::module_inception)]
 mod pipeline;
 mod pipeline_compiler;
@@ -11,6 +12,7 @@ mod vertex_format;
 
 pub use bind_group::*;
 pub use binding::*;
+pub use instancing::*;
 pub use pipeline::*;
 pub use pipeline_compiler::*;
 pub use pipeline_layout::*;
@@ -8,6 +8,7 @@ use super::{
 };
 use crate::{shader::ShaderStages, texture::TextureFormat};
 use bevy_reflect::TypeUuid;
+use bevy_utils::HashMap;
 
 #[derive(Clone, Debug, TypeUuid)]
 #[uuid = "ebfc1d11-a2a4-44cb-8f12-c49cc631146c"]
@@ -41,6 +42,10 @@ pub struct PipelineDescriptor {
     /// The implicit mask produced for alpha of zero is guaranteed to be zero, and for alpha of one
     /// is guaranteed to be all 1-s.
     pub alpha_to_coverage_enabled: bool,
+
+    /// Maps vertex shader input names to the names of the mesh attributes that feed them. Inputs that aren't in the
+    /// map are fed by the mesh attribute with the same name.
+    pub vertex_attribute_mapping: HashMap<String, String>,
 }
 
 impl PipelineDescriptor {
@@ -57,6 +62,7 @@ impl PipelineDescriptor {
             sample_count: 1,
             sample_mask: !0,
             alpha_to_coverage_enabled: false,
+            vertex_attribute_mapping: Default::default(),
         }
     }
 
@@ -69,6 +75,7 @@ impl PipelineDescriptor {
             sample_count: 1,
             sample_mask: !0,
             alpha_to_coverage_enabled: false,
+            vertex_attribute_mapping: Default::default(),
             rasterization_state: Some(RasterizationStateDescriptor {
                 front_face: FrontFace::Ccw,
                 cull_mode: CullMode::Back,
@@ -106,6 +113,16 @@ impl PipelineDescriptor {
         }
     }
 
+    /// Feeds the vertex shader input `shader_input` with the mesh attribute `mesh_attribute`
+    pub fn map_vertex_attribute(
+        &mut self,
+        shader_input: impl Into<String>,
+        mesh_attribute: impl Into<String>,
+    ) {
+        self.vertex_attribute_mapping
+            .insert(shader_input.into(), mesh_attribute.into());
+    }
+
     pub fn get_layout(&self) -> Option<&PipelineLayout> {
         self.layout.as_ref()
     }
@@ -17,6 +17,8 @@ pub struct PipelineSpecialization {
     pub dynamic_bindings: HashSet<String>,
     pub index_format: IndexFormat,
     pub vertex_buffer_descriptor: VertexBufferDescriptor,
+    /// The per-instance vertex buffer of an instanced draw. Provides the shader attributes with the `I_` prefix.
+    pub instance_buffer_descriptor: Option<VertexBufferDescriptor>,
     pub sample_count: u32,
 }
 
@@ -29,6 +31,7 @@ impl Default for PipelineSpecialization {
             primitive_topology: Default::default(),
             dynamic_bindings: Default::default(),
             vertex_buffer_descriptor: Default::default(),
+            instance_buffer_descriptor: None,
         }
     }
 }
@@ -211,17 +214,65 @@ impl PipelineCompiler {
             ..Default::default()
         };
 
-        for shader_vertex_attribute in pipeline_layout.vertex_buffer_descriptors.iter() {
-            let shader_vertex_attribute = shader_vertex_attribute
+        // the instance buffer descriptor that will be used for this pipeline, if the shader has instance attributes
+        let mut compiled_instance_buffer_descriptor = VertexBufferDescriptor {
+            step_mode: InputStepMode::Instance,
+            ..Default::default()
+        };
+
+        for shader_vertex_buffer in pipeline_layout.vertex_buffer_descriptors.iter() {
+            let shader_vertex_attribute = shader_vertex_buffer
                 .attributes
                 .get(0)
                 .expect("Reflected layout has no attributes.");
-
-            if let Some(target_vertex_attribute) = mesh_vertex_buffer_descriptor
+            let mesh_attribute_name = source_descriptor
+                .vertex_attribute_mapping
+                .get(&*shader_vertex_attribute.name)
+                .map(|name| name.as_str())
+                .unwrap_or(&*shader_vertex_attribute.name);
+
+            if shader_vertex_buffer.step_mode == InputStepMode::Instance {
+                let instance_buffer_descriptor =
+                    pipeline_specialization.instance_buffer_descriptor.as_ref();
+                if let Some(target_instance_attribute) =
+                    instance_buffer_descriptor.and_then(|descriptor| {
+                        descriptor
+                            .attributes
+                            .iter()
+                            .find(|x| x.name == shader_vertex_attribute.name)
+                    })
+                {
+                    let mut compiled_instance_attribute = target_instance_attribute.clone();
+                    compiled_instance_attribute.shader_location =
+                        shader_vertex_attribute.shader_location;
+                    compiled_instance_buffer_descriptor.stride =
+                        instance_buffer_descriptor.unwrap().stride;
+                    compiled_instance_buffer_descriptor
+                        .attributes
+                        .push(compiled_instance_attribute);
+                } else {
+                    panic!(
+                        "Instance attribute {} is required by shader, but not supplied. Either remove the attribute from the shader or draw the entity with the Instanced component and supply the attribute in its InstanceAttributes.",
+                        shader_vertex_attribute.name,
+                    );
+                }
+            } else if let Some(target_vertex_attribute) = mesh_vertex_buffer_descriptor
                 .attributes
                 .iter()
-                .find(|x| x.name == shader_vertex_attribute.name)
+                .find(|x| x.name == mesh_attribute_name)
             {
+                if !target_vertex_attribute
+                    .format
+                    .can_feed(shader_vertex_attribute.format)
+                {
+                    panic!(
+                        "Attribute {} has format {:?}, which can't be read by shader input {} of format {:?}. Either change the format of the shader input or convert the attribute with Mesh::set_attribute_format.",
+                        mesh_attribute_name,
+                        target_vertex_attribute.format,
+                        shader_vertex_attribute.name,
+                        shader_vertex_attribute.format,
+                    );
+                }
                 // copy shader location from reflected layout
                 let mut compiled_vertex_attribute = target_vertex_attribute.clone();
                 compiled_vertex_attribute.shader_location = shader_vertex_attribute.shader_location;
@@ -232,14 +283,25 @@ impl PipelineCompiler {
                 panic!(
                     "Attribute {} is required by shader, but not supplied by mesh. Either remove the attribute from the shader or supply the attribute ({}) to the mesh.",
                     shader_vertex_attribute.name,
-                    shader_vertex_attribute.name,
+                    mesh_attribute_name,
                 );
             }
         }
 
-        //TODO: add other buffers (like instancing) here
+        // shaders that don't read instance attributes would draw every instance with the same transform
+        if pipeline_specialization.instance_buffer_descriptor.is_some()
+            && compiled_instance_buffer_descriptor.attributes.is_empty()
+        {
+            panic!(
+                "An instance buffer was supplied, but the shader has no instance attributes. Only shaders that read the I_Model attributes when INSTANCING is defined, like the PBR forward shader, can draw Instanced entities."
+            );
+        }
+
         let mut vertex_buffer_descriptors = Vec::<VertexBufferDescriptor>::default();
         vertex_buffer_descriptors.push(compiled_vertex_buffer_descriptor);
+        if !compiled_instance_buffer_descriptor.attributes.is_empty() {
+            vertex_buffer_descriptors.push(compiled_instance_buffer_descriptor);
+        }
 
         pipeline_layout.vertex_buffer_descriptors = vertex_buffer_descriptors;
         specialized_descriptor.sample_count = pipeline_specialization.sample_count;
@@ -356,3 +418,236 @@ impl PipelineCompiler {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        pipeline::{ShaderStages, VertexAttributeDescriptor, VertexFormat},
+        renderer::HeadlessRenderResourceContext,
+        shader::ShaderStage,
+    };
+    use bevy_app::App;
+    use bevy_asset::{AddAsset, AssetPlugin};
+    use bevy_core::CorePlugin;
+    use bevy_reflect::ReflectPlugin;
+
+    fn attribute(
+        name: &'static str,
+        offset: u64,
+        format: VertexFormat,
+    ) -> VertexAttributeDescriptor {
+        VertexAttributeDescriptor {
+            name: name.into(),
+            offset,
+            format,
+            shader_location: 0,
+        }
+    }
+
+    /// Compiles a pipeline with the given vertex shader, with its `Vertex_Color` input read from `Vertex_Tint`
+    fn compile_shader(
+        source: &str,
+        specialization: &PipelineSpecialization,
+    ) -> Vec<VertexBufferDescriptor> {
+        let mut app_builder = App::build();
+        app_builder
+            .add_plugin(ReflectPlugin)
+            .add_plugin(CorePlugin)
+            .add_plugin(AssetPlugin)
+            .add_asset::<Shader>()
+            .add_asset::<PipelineDescriptor>();
+        let app = app_builder.app;
+        let mut shaders = app.resources.get_mut::<Assets<Shader>>().unwrap();
+        let mut pipelines = app
+            .resources
+            .get_mut::<Assets<PipelineDescriptor>>()
+            .unwrap();
+
+        let vertex = Shader::from_glsl(ShaderStage::Vertex, source)
+            .get_spirv_shader(None)
+            .unwrap();
+        let mut descriptor = PipelineDescriptor::new(ShaderStages::new(shaders.add(vertex)));
+        descriptor.map_vertex_attribute("Vertex_Color", "Vertex_Tint");
+        let pipeline = pipelines.add(descriptor);
+
+        let mut compiler = PipelineCompiler::default();
+        let compiled = compiler.compile_pipeline(
+            &HeadlessRenderResourceContext::default(),
+            &mut pipelines,
+            &mut shaders,
+            &pipeline,
+            specialization,
+        );
+        pipelines
+            .get(compiled)
+            .unwrap()
+            .get_layout()
+            .unwrap()
+            .vertex_buffer_descriptors
+            .clone()
+    }
+
+    /// Compiles a pipeline with `Vertex_Position` and `Vertex_Color` inputs, with the color read from `Vertex_Tint`,
+    /// for a mesh with the given attributes
+    fn compile(attributes: Vec<VertexAttributeDescriptor>) -> VertexBufferDescriptor {
+        let specialization = PipelineSpecialization {
+            vertex_buffer_descriptor: VertexBufferDescriptor {
+                name: "Vertex".into(),
+                stride: 28,
+                step_mode: InputStepMode::Vertex,
+                attributes,
+            },
+            ..Default::default()
+        };
+        let mut descriptors = compile_shader(
+            r#"
+            #version 450
+            layout(location = 0) in vec3 Vertex_Position;
+            layout(location = 1) in vec4 Vertex_Color;
+
+            void main() {
+                gl_Position = vec4(Vertex_Position, 1.0) + Vertex_Color;
+            }
+        "#,
+            &specialization,
+        );
+        assert_eq!(descriptors.len(), 1);
+        descriptors.remove(0)
+    }
+
+    /// Compiles a pipeline with a `Vertex_Position` input and the given instance attributes
+    fn compile_instanced(
+        source: &str,
+        instance_attributes: Vec<VertexAttributeDescriptor>,
+    ) -> Vec<VertexBufferDescriptor> {
+        let specialization = PipelineSpecialization {
+            vertex_buffer_descriptor: VertexBufferDescriptor {
+                name: "Vertex".into(),
+                stride: 12,
+                step_mode: InputStepMode::Vertex,
+                attributes: vec![attribute("Vertex_Position", 0, VertexFormat::Float3)],
+            },
+            instance_buffer_descriptor: Some(VertexBufferDescriptor {
+                name: "Instance".into(),
+                stride: 68,
+                step_mode: InputStepMode::Instance,
+                attributes: instance_attributes,
+            }),
+            ..Default::default()
+        };
+        compile_shader(source, &specialization)
+    }
+
+    const INSTANCED_SHADER: &str = r#"
+        #version 450
+        layout(location = 0) in vec3 Vertex_Position;
+        layout(location = 1) in vec4 I_Model_0;
+        layout(location = 2) in float I_Scale;
+
+        void main() {
+            gl_Position = vec4(Vertex_Position * I_Scale, 1.0) + I_Model_0;
+        }
+    "#;
+
+    #[test]
+    fn remap_vertex_attributes() {
+        let descriptor = compile(vec![
+            attribute("Vertex_Position", 0, VertexFormat::Float3),
+            attribute("Vertex_Tint", 12, VertexFormat::Float4),
+        ]);
+        assert_eq!(descriptor.stride, 28);
+        let locations = descriptor
+            .attributes
+            .iter()
+            .map(|attribute| {
+                (
+                    &*attribute.name,
+                    attribute.offset,
+                    attribute.shader_location,
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            locations,
+            vec![("Vertex_Position", 0, 0), ("Vertex_Tint", 12, 1)]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "can't be read by shader input Vertex_Color")]
+    fn reject_unreadable_vertex_attributes() {
+        compile(vec![
+            attribute("Vertex_Position", 0, VertexFormat::Float3),
+            attribute("Vertex_Tint", 12, VertexFormat::Uint4),
+        ]);
+    }
+
+    #[test]
+    #[should_panic(expected = "supply the attribute (Vertex_Tint) to the mesh")]
+    fn reject_missing_mapped_attributes() {
+        compile(vec![
+            attribute("Vertex_Position", 0, VertexFormat::Float3),
+            attribute("Vertex_Color", 12, VertexFormat::Float4),
+        ]);
+    }
+
+    #[test]
+    fn compile_instance_attributes() {
+        let descriptors = compile_instanced(
+            INSTANCED_SHADER,
+            vec![
+                attribute("I_Model_0", 0, VertexFormat::Float4),
+                attribute("I_Model_1", 16, VertexFormat::Float4),
+                attribute("I_Model_2", 32, VertexFormat::Float4),
+                attribute("I_Model_3", 48, VertexFormat::Float4),
+                attribute("I_Scale", 64, VertexFormat::Float),
+            ],
+        );
+        assert_eq!(descriptors.len(), 2);
+        assert_eq!(descriptors[0].step_mode, InputStepMode::Vertex);
+        assert_eq!(descriptors[0].attributes.len(), 1);
+
+        // only the attributes the shader reads are bound, at the stride of the whole instance buffer
+        let instance = &descriptors[1];
+        assert_eq!(instance.step_mode, InputStepMode::Instance);
+        assert_eq!(instance.stride, 68);
+        let locations = instance
+            .attributes
+            .iter()
+            .map(|attribute| {
+                (
+                    &*attribute.name,
+                    attribute.offset,
+                    attribute.shader_location,
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(locations, vec![("I_Model_0", 0, 1), ("I_Scale", 64, 2)]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Instance attribute I_Scale is required by shader")]
+    fn reject_missing_instance_attributes() {
+        compile_instanced(
+            INSTANCED_SHADER,
+            vec![attribute("I_Model_0", 0, VertexFormat::Float4)],
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "the shader has no instance attributes")]
+    fn reject_shaders_without_instance_attributes() {
+        compile_instanced(
+            r#"
+            #version 450
+            layout(location = 0) in vec3 Vertex_Position;
+
+            void main() {
+                gl_Position = vec4(Vertex_Position, 1.0);
+            }
+        "#,
+            vec![attribute("I_Model_0", 0, VertexFormat::Float4)],
+        );
+    }
+}
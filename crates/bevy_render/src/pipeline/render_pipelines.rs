@@ -1,4 +1,4 @@
-use super::{PipelineDescriptor, PipelineSpecialization};
+use super::{Instanced, PipelineDescriptor, PipelineSpecialization};
 use crate::{
     draw::{Draw, DrawContext},
     mesh::{Indices, Mesh},
@@ -6,7 +6,7 @@ use crate::{
     renderer::RenderResourceBindings,
 };
 use bevy_asset::{Assets, Handle};
-use bevy_ecs::{Query, Res, ResMut};
+use bevy_ecs::{Query, Res, ResMut, Without};
 use bevy_reflect::{Reflect, ReflectComponent};
 use bevy_utils::HashSet;
 
@@ -77,12 +77,50 @@ impl Default for RenderPipelines {
     }
 }
 
+/// Syncs the sample count and dynamic bindings of each pipeline's specialization with the current state
+pub(crate) fn update_pipeline_specializations(
+    draw_context: &DrawContext,
+    msaa: &Msaa,
+    render_pipelines: &mut RenderPipelines,
+) {
+    for pipeline in render_pipelines.pipelines.iter_mut() {
+        pipeline.specialization.sample_count = msaa.samples;
+        if pipeline.dynamic_bindings_generation
+            != render_pipelines.bindings.dynamic_bindings_generation()
+        {
+            pipeline.specialization.dynamic_bindings = render_pipelines
+                .bindings
+                .iter_dynamic_bindings()
+                .map(|name| name.to_string())
+                .collect::<HashSet<String>>();
+            pipeline.dynamic_bindings_generation =
+                render_pipelines.bindings.dynamic_bindings_generation();
+            for (handle, _) in render_pipelines.bindings.iter_assets() {
+                if let Some(bindings) = draw_context
+                    .asset_render_resource_bindings
+                    .get_untyped(handle)
+                {
+                    for binding in bindings.iter_dynamic_bindings() {
+                        pipeline
+                            .specialization
+                            .dynamic_bindings
+                            .insert(binding.to_string());
+                    }
+                }
+            }
+        }
+    }
+}
+
 pub fn draw_render_pipelines_system(
     mut draw_context: DrawContext,
     mut render_resource_bindings: ResMut<RenderResourceBindings>,
     msaa: Res<Msaa>,
     meshes: Res<Assets<Mesh>>,
-    mut query: Query<(&mut Draw, &mut RenderPipelines, &Handle<Mesh>, &Visible)>,
+    mut query: Query<
+        (&mut Draw, &mut RenderPipelines, &Handle<Mesh>, &Visible),
+        Without<Instanced>,
+    >,
 ) {
     for (mut draw, mut render_pipelines, mesh_handle, visible) in query.iter_mut() {
         if !visible.is_visible {
@@ -103,33 +141,7 @@ pub fn draw_render_pipelines_system(
         };
 
         let render_pipelines = &mut *render_pipelines;
-        for pipeline in render_pipelines.pipelines.iter_mut() {
-            pipeline.specialization.sample_count = msaa.samples;
-            if pipeline.dynamic_bindings_generation
-                != render_pipelines.bindings.dynamic_bindings_generation()
-            {
-                pipeline.specialization.dynamic_bindings = render_pipelines
-                    .bindings
-                    .iter_dynamic_bindings()
-                    .map(|name| name.to_string())
-                    .collect::<HashSet<String>>();
-                pipeline.dynamic_bindings_generation =
-                    render_pipelines.bindings.dynamic_bindings_generation();
-                for (handle, _) in render_pipelines.bindings.iter_assets() {
-                    if let Some(bindings) = draw_context
-                        .asset_render_resource_bindings
-                        .get_untyped(handle)
-                    {
-                        for binding in bindings.iter_dynamic_bindings() {
-                            pipeline
-                                .specialization
-                                .dynamic_bindings
-                                .insert(binding.to_string());
-                        }
-                    }
-                }
-            }
-        }
+        update_pipeline_specializations(&draw_context, &msaa, render_pipelines);
 
         for render_pipeline in render_pipelines.pipelines.iter_mut() {
             let render_resource_bindings = &mut [
@@ -70,6 +70,120 @@ impl VertexFormat {
             VertexFormat::Int4 => 4 * 4,
         }
     }
+
+    pub fn component_count(&self) -> u32 {
+        match *self {
+            VertexFormat::Float | VertexFormat::Uint | VertexFormat::Int => 1,
+            VertexFormat::Float3 | VertexFormat::Uint3 | VertexFormat::Int3 => 3,
+            VertexFormat::Uchar4
+            | VertexFormat::Char4
+            | VertexFormat::Uchar4Norm
+            | VertexFormat::Char4Norm
+            | VertexFormat::Ushort4
+            | VertexFormat::Short4
+            | VertexFormat::Ushort4Norm
+            | VertexFormat::Short4Norm
+            | VertexFormat::Half4
+            | VertexFormat::Float4
+            | VertexFormat::Uint4
+            | VertexFormat::Int4 => 4,
+            _ => 2,
+        }
+    }
+
+    /// The type shaders read the components of this format as. Normalized and half formats are read as floats.
+    pub fn kind(&self) -> VertexFormatKind {
+        match *self {
+            VertexFormat::Uchar2
+            | VertexFormat::Uchar4
+            | VertexFormat::Ushort2
+            | VertexFormat::Ushort4
+            | VertexFormat::Uint
+            | VertexFormat::Uint2
+            | VertexFormat::Uint3
+            | VertexFormat::Uint4 => VertexFormatKind::Uint,
+            VertexFormat::Char2
+            | VertexFormat::Char4
+            | VertexFormat::Short2
+            | VertexFormat::Short4
+            | VertexFormat::Int
+            | VertexFormat::Int2
+            | VertexFormat::Int3
+            | VertexFormat::Int4 => VertexFormatKind::Int,
+            _ => VertexFormatKind::Float,
+        }
+    }
+
+    /// Whether a vertex buffer attribute with this format can feed a shader input of `shader_format`. GPUs fill
+    /// components the attribute doesn't have with 0 (and 1 for the fourth component) and ignore extra ones, so only
+    /// the kinds need to match.
+    pub fn can_feed(&self, shader_format: VertexFormat) -> bool {
+        self.kind() == shader_format.kind()
+    }
+
+    /// Whether [VertexAttributeValues::convert](crate::mesh::VertexAttributeValues::convert) can convert values of
+    /// this format to `format` without losing information
+    pub fn can_convert(&self, format: VertexFormat) -> bool {
+        if *self == format {
+            return true;
+        }
+        if format.component_count() < self.component_count() {
+            return false;
+        }
+
+        match format {
+            VertexFormat::Float
+            | VertexFormat::Float2
+            | VertexFormat::Float3
+            | VertexFormat::Float4 => !matches!(
+                self,
+                // 32 bit integers don't fit into floats without losing precision
+                VertexFormat::Half2
+                    | VertexFormat::Half4
+                    | VertexFormat::Uint
+                    | VertexFormat::Uint2
+                    | VertexFormat::Uint3
+                    | VertexFormat::Uint4
+                    | VertexFormat::Int
+                    | VertexFormat::Int2
+                    | VertexFormat::Int3
+                    | VertexFormat::Int4
+            ),
+            VertexFormat::Uint
+            | VertexFormat::Uint2
+            | VertexFormat::Uint3
+            | VertexFormat::Uint4 => matches!(
+                self,
+                VertexFormat::Uchar2
+                    | VertexFormat::Uchar4
+                    | VertexFormat::Ushort2
+                    | VertexFormat::Ushort4
+                    | VertexFormat::Uint
+                    | VertexFormat::Uint2
+                    | VertexFormat::Uint3
+            ),
+            VertexFormat::Int | VertexFormat::Int2 | VertexFormat::Int3 | VertexFormat::Int4 => {
+                matches!(
+                    self,
+                    VertexFormat::Char2
+                        | VertexFormat::Char4
+                        | VertexFormat::Short2
+                        | VertexFormat::Short4
+                        | VertexFormat::Int
+                        | VertexFormat::Int2
+                        | VertexFormat::Int3
+                )
+            }
+            _ => false,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
+pub enum VertexFormatKind {
+    Float,
+    Uint,
+    Int,
 }
 
 pub trait AsVertexFormats {
@@ -134,3 +248,38 @@ impl AsVertexFormats for [f32; 4] {
         &[VertexFormat::Float4]
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_kinds() {
+        assert_eq!(VertexFormat::Uchar4Norm.kind(), VertexFormatKind::Float);
+        assert_eq!(VertexFormat::Half2.kind(), VertexFormatKind::Float);
+        assert_eq!(VertexFormat::Ushort2.kind(), VertexFormatKind::Uint);
+        assert_eq!(VertexFormat::Char4.kind(), VertexFormatKind::Int);
+
+        assert!(VertexFormat::Float3.can_feed(VertexFormat::Float4));
+        assert!(VertexFormat::Uchar4Norm.can_feed(VertexFormat::Float4));
+        assert!(VertexFormat::Ushort4.can_feed(VertexFormat::Uint4));
+        assert!(!VertexFormat::Uint4.can_feed(VertexFormat::Float4));
+        assert!(!VertexFormat::Short2.can_feed(VertexFormat::Uint2));
+    }
+
+    #[test]
+    fn format_conversions() {
+        assert!(VertexFormat::Float3.can_convert(VertexFormat::Float3));
+        assert!(VertexFormat::Float3.can_convert(VertexFormat::Float4));
+        assert!(VertexFormat::Short2Norm.can_convert(VertexFormat::Float2));
+        assert!(VertexFormat::Uchar4.can_convert(VertexFormat::Uint4));
+        assert!(VertexFormat::Char2.can_convert(VertexFormat::Int3));
+
+        assert!(!VertexFormat::Float3.can_convert(VertexFormat::Float2));
+        assert!(!VertexFormat::Float.can_convert(VertexFormat::Uchar4Norm));
+        assert!(!VertexFormat::Uint.can_convert(VertexFormat::Float));
+        assert!(!VertexFormat::Half4.can_convert(VertexFormat::Float4));
+        assert!(!VertexFormat::Short2.can_convert(VertexFormat::Uint2));
+        assert!(!VertexFormat::Uchar2Norm.can_convert(VertexFormat::Uint2));
+    }
+}
@@ -0,0 +1,202 @@
+use bevy_math::{Mat4, Vec3, Vec4};
+use bevy_reflect::{Reflect, ReflectComponent};
+
+/// An axis-aligned bounding box in the local space of an entity. Entities with a `Handle<Mesh>` get their [Aabb]
+/// from the mesh's positions automatically, which is used to skip entities outside of a camera's [Frustum].
+/// Sprites and UI nodes are sized in their shaders instead of by their quad mesh, so their bundles opt out with
+/// [NoFrustumCulling](crate::camera::NoFrustumCulling).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct Aabb {
+    pub center: Vec3,
+    pub half_extents: Vec3,
+}
+
+impl Aabb {
+    pub fn from_min_max(min: Vec3, max: Vec3) -> Self {
+        Aabb {
+            center: (max + min) * 0.5,
+            half_extents: (max - min) * 0.5,
+        }
+    }
+
+    /// Returns the smallest box containing all `points`, or `None` if there are no points
+    pub fn from_points(points: impl IntoIterator<Item = Vec3>) -> Option<Self> {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+        let (min, max) = points.fold((first, first), |(min, max), point| {
+            (min.min(point), max.max(point))
+        });
+        Some(Aabb::from_min_max(min, max))
+    }
+
+    pub fn min(&self) -> Vec3 {
+        self.center - self.half_extents
+    }
+
+    pub fn max(&self) -> Vec3 {
+        self.center + self.half_extents
+    }
+
+    pub fn bounding_sphere(&self) -> Sphere {
+        Sphere {
+            center: self.center,
+            radius: self.half_extents.length(),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Sphere {
+    pub center: Vec3,
+    pub radius: f32,
+}
+
+/// A plane containing the points `p` with `normal.dot(p) + d == 0`. Points on the side the normal points to have a
+/// positive signed distance.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Plane {
+    pub normal: Vec3,
+    pub d: f32,
+}
+
+impl Plane {
+    /// Creates the plane `x * p.x + y * p.y + z * p.z + w == 0` with a normalized normal. Returns `None` if `x`, `y`
+    /// and `z` are all zero, as there is no such plane.
+    pub fn from_coefficients(coefficients: Vec4) -> Option<Self> {
+        let normal = coefficients.truncate();
+        let length = normal.length();
+        if !length.is_normal() {
+            return None;
+        }
+        Some(Plane {
+            normal: normal / length,
+            d: coefficients.w / length,
+        })
+    }
+
+    pub fn signed_distance(&self, point: Vec3) -> f32 {
+        self.normal.dot(point) + self.d
+    }
+}
+
+/// The volume visible to a camera, bounded by the left, right, bottom, top, near and far planes. All plane normals
+/// point inwards.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Frustum {
+    pub planes: [Plane; 6],
+}
+
+impl Frustum {
+    /// Extracts the planes of a view projection matrix with a depth range of 0 to 1. Returns `None` for degenerate
+    /// matrices, like the zero projection matrix of a [Camera](crate::camera::Camera) that has no projection yet.
+    pub fn from_view_projection(view_projection: &Mat4) -> Option<Self> {
+        let m = view_projection;
+        let row = |index: usize| {
+            let column = |axis: &Vec4| match index {
+                0 => axis.x,
+                1 => axis.y,
+                2 => axis.z,
+                _ => axis.w,
+            };
+            Vec4::new(
+                column(&m.x_axis),
+                column(&m.y_axis),
+                column(&m.z_axis),
+                column(&m.w_axis),
+            )
+        };
+        let (row_x, row_y, row_z, row_w) = (row(0), row(1), row(2), row(3));
+        Some(Frustum {
+            planes: [
+                Plane::from_coefficients(row_w + row_x)?,
+                Plane::from_coefficients(row_w - row_x)?,
+                Plane::from_coefficients(row_w + row_y)?,
+                Plane::from_coefficients(row_w - row_y)?,
+                Plane::from_coefficients(row_z)?,
+                Plane::from_coefficients(row_w - row_z)?,
+            ],
+        })
+    }
+
+    /// Returns false if the sphere is completely outside of the frustum
+    pub fn intersects_sphere(&self, sphere: &Sphere) -> bool {
+        self.planes
+            .iter()
+            .all(|plane| plane.signed_distance(sphere.center) >= -sphere.radius)
+    }
+
+    /// Returns false if `aabb`, transformed by `model`, is completely outside of the frustum. This is conservative:
+    /// boxes close to a frustum corner may be reported as intersecting.
+    pub fn intersects_obb(&self, aabb: &Aabb, model: &Mat4) -> bool {
+        let center = (*model * aabb.center.extend(1.0)).truncate();
+        let axes = [
+            model.x_axis.truncate() * aabb.half_extents.x,
+            model.y_axis.truncate() * aabb.half_extents.y,
+            model.z_axis.truncate() * aabb.half_extents.z,
+        ];
+        self.planes.iter().all(|plane| {
+            let radius = axes
+                .iter()
+                .map(|axis| plane.normal.dot(*axis).abs())
+                .sum::<f32>();
+            plane.signed_distance(center) >= -radius
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frustum_culling() {
+        let projection = Mat4::perspective_rh(std::f32::consts::FRAC_PI_2, 1.0, 1.0, 100.0);
+        // the camera looks down -z from the origin
+        let frustum = Frustum::from_view_projection(&projection).unwrap();
+        let aabb = Aabb::from_min_max(Vec3::splat(-1.0), Vec3::splat(1.0));
+
+        let in_front = Mat4::from_translation(Vec3::new(0.0, 0.0, -10.0));
+        let behind = Mat4::from_translation(Vec3::new(0.0, 0.0, 10.0));
+        let beyond_far = Mat4::from_translation(Vec3::new(0.0, 0.0, -110.0));
+        let left = Mat4::from_translation(Vec3::new(-20.0, 0.0, -10.0));
+        let touching_left = Mat4::from_translation(Vec3::new(-10.5, 0.0, -10.0));
+        assert!(frustum.intersects_obb(&aabb, &in_front));
+        assert!(!frustum.intersects_obb(&aabb, &behind));
+        assert!(!frustum.intersects_obb(&aabb, &beyond_far));
+        assert!(!frustum.intersects_obb(&aabb, &left));
+        assert!(frustum.intersects_obb(&aabb, &touching_left));
+
+        let sphere = aabb.bounding_sphere();
+        assert!(frustum.intersects_sphere(&Sphere {
+            center: Vec3::new(0.0, 0.0, -10.0),
+            ..sphere
+        }));
+        assert!(!frustum.intersects_sphere(&Sphere {
+            center: Vec3::new(0.0, 0.0, 10.0),
+            ..sphere
+        }));
+    }
+
+    #[test]
+    fn aabb_from_points() {
+        let aabb = Aabb::from_points(vec![
+            Vec3::new(1.0, -2.0, 0.0),
+            Vec3::new(-1.0, 4.0, 2.0),
+            Vec3::new(0.0, 0.0, 1.0),
+        ])
+        .unwrap();
+        assert_eq!(aabb.min(), Vec3::new(-1.0, -2.0, 0.0));
+        assert_eq!(aabb.max(), Vec3::new(1.0, 4.0, 2.0));
+        assert!(Aabb::from_points(Vec::new()).is_none());
+    }
+
+    #[test]
+    fn degenerate_planes() {
+        assert!(Plane::from_coefficients(Vec4::new(0.0, 0.0, 0.0, 1.0)).is_none());
+        assert!(Frustum::from_view_projection(&Mat4::zero()).is_none());
+        let plane = Plane::from_coefficients(Vec4::new(0.0, 2.0, 0.0, 4.0)).unwrap();
+        assert_eq!(plane.normal, Vec3::unit_y());
+        assert_eq!(plane.signed_distance(Vec3::zero()), 2.0);
+    }
+}
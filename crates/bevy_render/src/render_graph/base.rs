@@ -1,16 +1,23 @@
 use super::{
-    CameraNode, PassNode, RenderGraph, SharedBuffersNode, TextureCopyNode, WindowSwapChainNode,
-    WindowTextureNode,
+    CameraNode, PassNode, RenderGraph, ScreenshotNode, SharedBuffersNode, TextureCopyNode,
+    TextureTargetAttachmentNode, TextureTargetNode, WindowSwapChainNode, WindowTextureNode,
 };
 use crate::{
+    camera::{ActiveCameras, Camera, RenderTarget},
     pass::{
         LoadOp, Operations, PassDescriptor, RenderPassColorAttachmentDescriptor,
         RenderPassDepthStencilAttachmentDescriptor, TextureAttachment,
     },
-    texture::{Extent3d, TextureDescriptor, TextureDimension, TextureFormat, TextureUsage},
+    texture::{
+        Extent3d, Texture, TextureDescriptor, TextureDimension, TextureFormat, TextureUsage,
+        TextureViewDimension,
+    },
     Color,
 };
+use bevy_asset::Handle;
+use bevy_ecs::{Entity, Local, Query, Res, ResMut};
 use bevy_reflect::{Reflect, ReflectComponent};
+use bevy_utils::{tracing::warn, HashMap, HashSet};
 use bevy_window::WindowId;
 
 /// A component that indicates that an entity should be drawn in the "main pass"
@@ -71,6 +78,29 @@ pub mod node {
     pub const MAIN_SAMPLED_COLOR_ATTACHMENT: &str = "main_pass_sampled_color_attachment";
     pub const MAIN_PASS: &str = "main_pass";
     pub const SHARED_BUFFERS: &str = "shared_buffers";
+    pub const PRIMARY_SCREENSHOT: &str = "primary_screenshot";
+
+    /// The names of the nodes added for a camera that renders to a texture
+    pub fn texture_target_nodes(camera_name: &str) -> TextureTargetNodes {
+        TextureTargetNodes {
+            camera: format!("{}_camera", camera_name),
+            target: format!("{}_target", camera_name),
+            depth_texture: format!("{}_depth_texture", camera_name),
+            sampled_color_attachment: format!("{}_sampled_color_attachment", camera_name),
+            pass: format!("{}_pass", camera_name),
+            screenshot: format!("{}_screenshot", camera_name),
+        }
+    }
+
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct TextureTargetNodes {
+        pub camera: String,
+        pub target: String,
+        pub depth_texture: String,
+        pub sampled_color_attachment: String,
+        pub pass: String,
+        pub screenshot: String,
+    }
 }
 
 pub mod camera {
@@ -95,6 +125,21 @@ impl Default for BaseRenderGraphConfig {
 /// set of nodes. It can be customized using `BaseRenderGraphConfig`.
 pub trait BaseRenderGraphBuilder {
     fn add_base_graph(&mut self, config: &BaseRenderGraphConfig, msaa: &Msaa) -> &mut Self;
+
+    /// Adds the nodes that draw [MainPass] entities seen by the camera named `camera_name` into `texture`. The pass
+    /// runs before the main pass, so the texture can be sampled by materials in the same frame.
+    /// [texture_target_graph_system] calls this for every camera with a [RenderTarget::Texture].
+    fn add_texture_target_camera(
+        &mut self,
+        camera_name: &str,
+        texture: Handle<Texture>,
+        msaa: &Msaa,
+    ) -> &mut Self;
+
+    /// Removes the nodes added by [BaseRenderGraphBuilder::add_texture_target_camera], except for the camera node.
+    /// The camera node's system can't be removed, so the node is kept and reused if a camera with the same name
+    /// renders to a texture again.
+    fn remove_texture_target_camera(&mut self, camera_name: &str) -> &mut Self;
 }
 
 impl BaseRenderGraphBuilder for RenderGraph {
@@ -123,6 +168,7 @@ impl BaseRenderGraphBuilder for RenderGraph {
                         mip_level_count: 1,
                         sample_count: msaa.samples,
                         dimension: TextureDimension::D2,
+                        view_dimension: TextureViewDimension::D2,
                         format: TextureFormat::Depth32Float, // PERF: vulkan docs recommend using 24 bit depth for better performance
                         usage: TextureUsage::OUTPUT_ATTACHMENT,
                     },
@@ -184,6 +230,23 @@ impl BaseRenderGraphBuilder for RenderGraph {
             WindowSwapChainNode::new(WindowId::primary()),
         );
 
+        // passes that draw to the primary window should run before this node, so that screenshots include them
+        self.add_node(
+            node::PRIMARY_SCREENSHOT,
+            ScreenshotNode::new(RenderTarget::Window(WindowId::primary())),
+        );
+        self.add_slot_edge(
+            node::PRIMARY_SWAP_CHAIN,
+            WindowSwapChainNode::OUT_TEXTURE,
+            node::PRIMARY_SCREENSHOT,
+            ScreenshotNode::IN_TEXTURE,
+        )
+        .unwrap();
+        if config.add_main_pass {
+            self.add_node_edge(node::MAIN_PASS, node::PRIMARY_SCREENSHOT)
+                .unwrap();
+        }
+
         if config.connect_main_pass_to_swapchain {
             self.add_slot_edge(
                 node::PRIMARY_SWAP_CHAIN,
@@ -212,6 +275,7 @@ impl BaseRenderGraphBuilder for RenderGraph {
                         mip_level_count: 1,
                         sample_count: msaa.samples,
                         dimension: TextureDimension::D2,
+                        view_dimension: TextureViewDimension::D2,
                         format: TextureFormat::default(),
                         usage: TextureUsage::OUTPUT_ATTACHMENT,
                     },
@@ -239,4 +303,329 @@ impl BaseRenderGraphBuilder for RenderGraph {
 
         self
     }
+
+    fn add_texture_target_camera(
+        &mut self,
+        camera_name: &str,
+        texture: Handle<Texture>,
+        msaa: &Msaa,
+    ) -> &mut Self {
+        let nodes = node::texture_target_nodes(camera_name);
+        if self.get_node_id(nodes.camera.clone()).is_err() {
+            self.add_system_node(
+                nodes.camera.clone(),
+                CameraNode::new(camera_name.to_string()),
+            );
+        }
+        self.add_node(
+            nodes.target.clone(),
+            TextureTargetNode::new(texture.clone()),
+        );
+        self.add_node(
+            nodes.depth_texture.clone(),
+            TextureTargetAttachmentNode::new(
+                texture.clone(),
+                TextureDescriptor {
+                    sample_count: msaa.samples,
+                    format: TextureFormat::Depth32Float,
+                    usage: TextureUsage::OUTPUT_ATTACHMENT,
+                    ..Default::default()
+                },
+            ),
+        );
+
+        let mut pass_node = PassNode::<&MainPass>::new(PassDescriptor {
+            color_attachments: vec![msaa.color_attachment_descriptor(
+                TextureAttachment::Input("color_attachment".to_string()),
+                TextureAttachment::Input("color_resolve_target".to_string()),
+                Operations {
+                    load: LoadOp::Clear(Color::rgb(0.1, 0.1, 0.1)),
+                    store: true,
+                },
+            )],
+            depth_stencil_attachment: Some(RenderPassDepthStencilAttachmentDescriptor {
+                attachment: TextureAttachment::Input("depth".to_string()),
+                depth_ops: Some(Operations {
+                    load: LoadOp::Clear(1.0),
+                    store: true,
+                }),
+                stencil_ops: None,
+            }),
+            sample_count: msaa.samples,
+        });
+        pass_node.use_default_clear_color(0);
+        pass_node.add_camera(camera_name);
+        self.add_node(nodes.pass.clone(), pass_node);
+
+        self.add_node_edge(node::TEXTURE_COPY, nodes.pass.clone())
+            .unwrap();
+        self.add_node_edge(node::SHARED_BUFFERS, nodes.pass.clone())
+            .unwrap();
+        self.add_node_edge(nodes.camera.clone(), nodes.pass.clone())
+            .unwrap();
+        if self.get_node_id(node::MAIN_PASS).is_ok() {
+            self.add_node_edge(nodes.pass.clone(), node::MAIN_PASS)
+                .unwrap();
+        }
+
+        self.add_slot_edge(
+            nodes.depth_texture.clone(),
+            TextureTargetAttachmentNode::OUT_TEXTURE,
+            nodes.pass.clone(),
+            "depth",
+        )
+        .unwrap();
+
+        if msaa.samples > 1 {
+            self.add_node(
+                nodes.sampled_color_attachment.clone(),
+                TextureTargetAttachmentNode::new(
+                    texture.clone(),
+                    TextureDescriptor {
+                        sample_count: msaa.samples,
+                        format: TextureFormat::default(),
+                        usage: TextureUsage::OUTPUT_ATTACHMENT,
+                        ..Default::default()
+                    },
+                ),
+            );
+            self.add_slot_edge(
+                nodes.sampled_color_attachment.clone(),
+                TextureTargetAttachmentNode::OUT_TEXTURE,
+                nodes.pass.clone(),
+                "color_attachment",
+            )
+            .unwrap();
+        }
+
+        self.add_node(
+            nodes.screenshot.clone(),
+            ScreenshotNode::new(RenderTarget::Texture(texture)),
+        );
+        self.add_slot_edge(
+            nodes.target.clone(),
+            TextureTargetNode::OUT_TEXTURE,
+            nodes.screenshot.clone(),
+            ScreenshotNode::IN_TEXTURE,
+        )
+        .unwrap();
+        self.add_node_edge(nodes.pass.clone(), nodes.screenshot)
+            .unwrap();
+
+        self.add_slot_edge(
+            nodes.target,
+            TextureTargetNode::OUT_TEXTURE,
+            nodes.pass,
+            if msaa.samples > 1 {
+                "color_resolve_target"
+            } else {
+                "color_attachment"
+            },
+        )
+        .unwrap();
+
+        self
+    }
+
+    fn remove_texture_target_camera(&mut self, camera_name: &str) -> &mut Self {
+        let nodes = node::texture_target_nodes(camera_name);
+        for name in [
+            nodes.target,
+            nodes.depth_texture,
+            nodes.sampled_color_attachment,
+            nodes.pass,
+            nodes.screenshot,
+        ]
+        .iter()
+        {
+            // the sampled color attachment only exists with msaa
+            let _ = self.remove_node(name.clone());
+        }
+        self
+    }
+}
+
+#[derive(Default)]
+pub struct TextureTargetGraphState {
+    targets: HashMap<String, Handle<Texture>>,
+    unnamed_cameras: HashSet<Entity>,
+}
+
+/// Adds render graph nodes for cameras that render to a texture, points them at the new texture when a camera's
+/// target changes and removes them when the camera is despawned or stops rendering to a texture. Cameras without a
+/// name are skipped with a warning.
+pub fn texture_target_graph_system(
+    mut state: Local<TextureTargetGraphState>,
+    msaa: Res<Msaa>,
+    mut render_graph: ResMut<RenderGraph>,
+    mut active_cameras: ResMut<ActiveCameras>,
+    query: Query<(Entity, &Camera)>,
+) {
+    let state = &mut *state;
+    let mut camera_names = HashSet::default();
+    let mut texture_target_names = HashSet::default();
+    let mut unnamed_cameras = HashSet::default();
+    for (entity, camera) in query.iter() {
+        if let Some(name) = camera.name.as_ref() {
+            camera_names.insert(name.clone());
+        }
+        let (name, texture) = match (&camera.name, &camera.target) {
+            (Some(name), RenderTarget::Texture(texture)) => (name, texture),
+            (None, RenderTarget::Texture(_)) => {
+                if !state.unnamed_cameras.contains(&entity) {
+                    warn!(
+                        "Camera {:?} renders to a texture, but has no name. Give it a unique name to add it to the render graph.",
+                        entity
+                    );
+                }
+                unnamed_cameras.insert(entity);
+                continue;
+            }
+            _ => continue,
+        };
+        texture_target_names.insert(name.clone());
+
+        match state.targets.get_mut(name) {
+            Some(current_texture) if *current_texture == *texture => {}
+            Some(current_texture) => {
+                let nodes = node::texture_target_nodes(name);
+                render_graph
+                    .get_node_mut::<TextureTargetNode>(nodes.target)
+                    .unwrap()
+                    .texture = texture.clone();
+                render_graph
+                    .get_node_mut::<TextureTargetAttachmentNode>(nodes.depth_texture)
+                    .unwrap()
+                    .texture = texture.clone();
+                if let Ok(node) = render_graph
+                    .get_node_mut::<TextureTargetAttachmentNode>(nodes.sampled_color_attachment)
+                {
+                    node.texture = texture.clone();
+                }
+                render_graph
+                    .get_node_mut::<ScreenshotNode>(nodes.screenshot)
+                    .unwrap()
+                    .target = RenderTarget::Texture(texture.clone());
+                *current_texture = texture.clone();
+            }
+            None => {
+                render_graph.add_texture_target_camera(name, texture.clone(), &msaa);
+                active_cameras.add(name);
+                state.targets.insert(name.clone(), texture.clone());
+            }
+        }
+    }
+    state.unnamed_cameras = unnamed_cameras;
+
+    let removed_targets = state
+        .targets
+        .keys()
+        .filter(|name| !texture_target_names.contains(*name))
+        .cloned()
+        .collect::<Vec<_>>();
+    for name in removed_targets {
+        render_graph.remove_texture_target_camera(&name);
+        // a camera that now renders to a window may still be active under the same name
+        if !camera_names.contains(&name) {
+            active_cameras.remove(&name);
+        }
+        state.targets.remove(&name);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_asset::HandleId;
+    use bevy_ecs::{Resources, Schedule, SystemStage, World};
+
+    fn texture_camera(name: Option<&str>, texture: &Handle<Texture>) -> (Camera,) {
+        (Camera {
+            name: name.map(|name| name.to_string()),
+            target: RenderTarget::Texture(texture.clone()),
+            ..Default::default()
+        },)
+    }
+
+    #[test]
+    fn texture_target_cameras() {
+        let mut world = World::default();
+        let mut resources = Resources::default();
+        let msaa = Msaa::default();
+        let mut render_graph = RenderGraph::default();
+        render_graph.add_base_graph(&BaseRenderGraphConfig::default(), &msaa);
+        let base_node_count = render_graph.iter_nodes().count();
+        resources.insert(render_graph);
+        resources.insert(msaa);
+        resources.insert(ActiveCameras::default());
+        let mut schedule = Schedule::default();
+        let mut update_stage = SystemStage::parallel();
+        update_stage.add_system(texture_target_graph_system);
+        schedule.add_stage("update", update_stage);
+
+        let mirror_texture = Handle::<Texture>::weak(HandleId::from("mirror.png"));
+        let monitor_texture = Handle::<Texture>::weak(HandleId::from("monitor.png"));
+        let mirror = world.spawn(texture_camera(Some("Mirror"), &mirror_texture));
+        // unnamed cameras can't be added to the graph
+        world.spawn(texture_camera(None, &monitor_texture));
+        schedule.initialize_and_run(&mut world, &mut resources);
+        {
+            let render_graph = resources.get::<RenderGraph>().unwrap();
+            // camera, target, depth texture, pass and screenshot nodes
+            assert_eq!(render_graph.iter_nodes().count(), base_node_count + 5);
+            assert_eq!(
+                render_graph
+                    .get_node::<TextureTargetNode>("Mirror_target")
+                    .unwrap()
+                    .texture,
+                mirror_texture
+            );
+            let main_pass_inputs = render_graph
+                .iter_node_inputs(node::MAIN_PASS)
+                .unwrap()
+                .map(|(_edge, node)| node.name.clone().unwrap())
+                .collect::<Vec<_>>();
+            assert!(main_pass_inputs.contains(&"Mirror_pass".into()));
+            assert!(resources
+                .get::<ActiveCameras>()
+                .unwrap()
+                .cameras
+                .contains_key("Mirror"));
+        }
+
+        world.get_mut::<Camera>(mirror).unwrap().target =
+            RenderTarget::Texture(monitor_texture.clone());
+        schedule.initialize_and_run(&mut world, &mut resources);
+        {
+            let render_graph = resources.get::<RenderGraph>().unwrap();
+            assert_eq!(render_graph.iter_nodes().count(), base_node_count + 5);
+            assert_eq!(
+                render_graph
+                    .get_node::<TextureTargetNode>("Mirror_target")
+                    .unwrap()
+                    .texture,
+                monitor_texture
+            );
+        }
+
+        // the camera node is kept for cameras with the same name
+        world.despawn(mirror).unwrap();
+        schedule.initialize_and_run(&mut world, &mut resources);
+        {
+            let render_graph = resources.get::<RenderGraph>().unwrap();
+            assert_eq!(render_graph.iter_nodes().count(), base_node_count + 1);
+            assert!(render_graph.get_node_id("Mirror_pass").is_err());
+            assert!(render_graph.get_node_id("Mirror_camera").is_ok());
+            assert!(!resources
+                .get::<ActiveCameras>()
+                .unwrap()
+                .cameras
+                .contains_key("Mirror"));
+        }
+
+        world.spawn(texture_camera(Some("Mirror"), &mirror_texture));
+        schedule.initialize_and_run(&mut world, &mut resources);
+        let render_graph = resources.get::<RenderGraph>().unwrap();
+        assert_eq!(render_graph.iter_nodes().count(), base_node_count + 5);
+    }
 }
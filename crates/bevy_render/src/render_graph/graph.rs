@@ -46,6 +46,37 @@ impl RenderGraph {
         self.add_node(name, node)
     }
 
+    /// Removes the node and its edges. The systems of a [SystemNode] keep running, as they can't be removed from the
+    /// graph's schedule.
+    pub fn remove_node(&mut self, label: impl Into<NodeLabel>) -> Result<(), RenderGraphError> {
+        let label = label.into();
+        let id = self.get_node_id(&label)?;
+        let node_state = self
+            .nodes
+            .remove(&id)
+            .ok_or(RenderGraphError::InvalidNode(label))?;
+        if let Some(name) = node_state.name.as_ref() {
+            self.node_names.remove(name);
+        }
+        for edge in node_state.edges.input_edges.iter() {
+            if let Some(output_node) = self.nodes.get_mut(&edge.get_output_node()) {
+                output_node
+                    .edges
+                    .output_edges
+                    .retain(|output_edge| output_edge != edge);
+            }
+        }
+        for edge in node_state.edges.output_edges.iter() {
+            if let Some(input_node) = self.nodes.get_mut(&edge.get_input_node()) {
+                input_node
+                    .edges
+                    .input_edges
+                    .retain(|input_edge| input_edge != edge);
+            }
+        }
+        Ok(())
+    }
+
     pub fn get_node_state(
         &self,
         label: impl Into<NodeLabel>,
@@ -403,6 +434,33 @@ mod tests {
         assert!(output_nodes("D", &graph).is_empty(), "D has no outputs");
     }
 
+    #[test]
+    pub fn test_remove_node() {
+        let mut graph = RenderGraph::default();
+        graph.add_node("A", TestNode::new(0, 1));
+        let b_id = graph.add_node("B", TestNode::new(1, 1));
+        graph.add_node("C", TestNode::new(1, 0));
+        graph.add_slot_edge("A", 0, "B", 0).unwrap();
+        graph.add_slot_edge("B", 0, "C", 0).unwrap();
+
+        graph.remove_node("B").unwrap();
+        assert_eq!(
+            graph.get_node_id("B"),
+            Err(RenderGraphError::InvalidNode("B".into()))
+        );
+        assert!(graph.get_node_state(b_id).is_err());
+        assert_eq!(graph.iter_node_outputs("A").unwrap().count(), 0);
+        assert_eq!(graph.iter_node_inputs("C").unwrap().count(), 0);
+        assert_eq!(
+            graph.remove_node("B"),
+            Err(RenderGraphError::InvalidNode("B".into()))
+        );
+
+        // the name can be used again
+        graph.add_node("B", TestNode::new(1, 0));
+        graph.add_slot_edge("A", 0, "B", 0).unwrap();
+    }
+
     #[test]
     pub fn test_get_node_typed() {
         struct MyNode {
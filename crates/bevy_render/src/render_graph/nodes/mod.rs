@@ -1,15 +1,19 @@
 mod camera_node;
 mod pass_node;
 mod render_resources_node;
+mod screenshot_node;
 mod shared_buffers_node;
 mod texture_copy_node;
+mod texture_target_node;
 mod window_swapchain_node;
 mod window_texture_node;
 
 pub use camera_node::*;
 pub use pass_node::*;
 pub use render_resources_node::*;
+pub use screenshot_node::*;
 pub use shared_buffers_node::*;
 pub use texture_copy_node::*;
+pub use texture_target_node::*;
 pub use window_swapchain_node::*;
 pub use window_texture_node::*;
@@ -0,0 +1,125 @@
+use crate::{
+    camera::RenderTarget,
+    render_graph::{Node, ResourceSlotInfo, ResourceSlots},
+    renderer::{BufferInfo, BufferUsage, RenderContext, RenderResourceType},
+    screenshot::{PendingScreenshot, Screenshots},
+    texture::{Extent3d, Texture, TextureFormat},
+};
+use bevy_asset::Assets;
+use bevy_ecs::{Resources, World};
+use bevy_utils::tracing::warn;
+use bevy_window::Windows;
+use std::borrow::Cow;
+
+/// Copies its input texture to a buffer when a screenshot of `target` has been requested in [Screenshots]. The input
+/// should be the texture the target is rendered to, and the node should run after every pass that draws to it.
+pub struct ScreenshotNode {
+    pub target: RenderTarget,
+}
+
+impl ScreenshotNode {
+    pub const IN_TEXTURE: &'static str = "texture";
+
+    pub fn new(target: RenderTarget) -> Self {
+        ScreenshotNode { target }
+    }
+}
+
+impl Node for ScreenshotNode {
+    fn input(&self) -> &[ResourceSlotInfo] {
+        static INPUT: &[ResourceSlotInfo] = &[ResourceSlotInfo {
+            name: Cow::Borrowed(ScreenshotNode::IN_TEXTURE),
+            resource_type: RenderResourceType::Texture,
+        }];
+        INPUT
+    }
+
+    fn update(
+        &mut self,
+        _world: &World,
+        resources: &Resources,
+        render_context: &mut dyn RenderContext,
+        input: &ResourceSlots,
+        _output: &mut ResourceSlots,
+    ) {
+        const SOURCE_TEXTURE: usize = 0;
+        let mut screenshots = resources.get_mut::<Screenshots>().unwrap();
+        if !screenshots.is_requested(&self.target) {
+            return;
+        }
+
+        let source_texture = match input.get(SOURCE_TEXTURE) {
+            Some(resource) => resource.get_texture().unwrap(),
+            None => return,
+        };
+
+        let (size, format, capture_texture) = match self.target {
+            RenderTarget::Window(window_id) => {
+                // the swap chain node renders to a capture texture instead of the swap chain if a screenshot was
+                // requested before it ran
+                match screenshots.take_window_capture(window_id) {
+                    Some(capture_texture) if capture_texture == source_texture => {}
+                    capture_texture => {
+                        if let Some(capture_texture) = capture_texture {
+                            render_context.resources().remove_texture(capture_texture);
+                        }
+                        warn!(
+                            "Window {:?} was not rendered to a capture texture.",
+                            window_id
+                        );
+                        return;
+                    }
+                }
+                let windows = resources.get::<Windows>().unwrap();
+                let window = windows.get(window_id).unwrap();
+                (
+                    Extent3d::new(window.physical_width(), window.physical_height(), 1),
+                    TextureFormat::default(),
+                    Some(source_texture),
+                )
+            }
+            RenderTarget::Texture(ref handle) => {
+                let textures = resources.get::<Assets<Texture>>().unwrap();
+                let texture = match textures.get(handle) {
+                    Some(texture) => texture,
+                    None => return,
+                };
+                (
+                    Extent3d::new(texture.size.width, texture.size.height, 1),
+                    texture.format,
+                    None,
+                )
+            }
+        };
+
+        let format_size = format.pixel_size();
+        let aligned_width = render_context
+            .resources()
+            .get_aligned_texture_size(size.width as usize);
+        let bytes_per_row = aligned_width * format_size;
+        let buffer = render_context.resources().create_buffer(BufferInfo {
+            size: bytes_per_row * size.height as usize,
+            buffer_usage: BufferUsage::COPY_DST | BufferUsage::MAP_READ,
+            ..Default::default()
+        });
+        render_context.copy_texture_to_buffer(
+            source_texture,
+            [0, 0, 0],
+            0,
+            buffer,
+            0,
+            bytes_per_row as u32,
+            size,
+        );
+
+        let requests = screenshots.take_requests(&self.target);
+        screenshots.add_pending(PendingScreenshot {
+            requests,
+            buffer,
+            bytes_per_row,
+            size,
+            format,
+            capture_texture,
+        });
+    }
+}
@@ -1,7 +1,7 @@
 use crate::{
     render_graph::{Node, ResourceSlots},
-    renderer::{BufferInfo, BufferUsage, RenderContext},
-    texture::{Texture, TextureDescriptor, TEXTURE_ASSET_INDEX},
+    renderer::{BufferInfo, BufferUsage, RenderContext, TextureId},
+    texture::{Extent3d, Texture, TEXTURE_ASSET_INDEX},
 };
 use bevy_app::prelude::{EventReader, Events};
 use bevy_asset::{AssetEvent, Assets};
@@ -33,50 +33,18 @@ impl Node for TextureCopyNode {
                             continue;
                         }
 
-                        let texture_descriptor: TextureDescriptor = texture.into();
-                        let width = texture.size.width as usize;
-                        let aligned_width =
-                            render_context.resources().get_aligned_texture_size(width);
-                        let format_size = texture.format.pixel_size();
-                        let mut aligned_data = vec![
-                            0;
-                            format_size
-                                * aligned_width
-                                * texture.size.height as usize
-                                * texture.size.depth as usize
-                        ];
-                        texture
-                            .data
-                            .chunks_exact(format_size * width)
-                            .enumerate()
-                            .for_each(|(index, row)| {
-                                let offset = index * aligned_width * format_size;
-                                aligned_data[offset..(offset + width * format_size)]
-                                    .copy_from_slice(row);
-                            });
-                        let texture_buffer = render_context.resources().create_buffer_with_data(
-                            BufferInfo {
-                                buffer_usage: BufferUsage::COPY_SRC,
-                                ..Default::default()
-                            },
-                            &aligned_data,
-                        );
-
-                        let texture_resource = render_context
+                        let texture_resource = match render_context
                             .resources()
                             .get_asset_resource(handle, TEXTURE_ASSET_INDEX)
-                            .unwrap();
+                            .and_then(|resource| resource.get_texture())
+                        {
+                            Some(texture_resource) => texture_resource,
+                            None => continue,
+                        };
 
-                        render_context.copy_buffer_to_texture(
-                            texture_buffer,
-                            0,
-                            (format_size * aligned_width) as u32,
-                            texture_resource.get_texture().unwrap(),
-                            [0, 0, 0],
-                            0,
-                            texture_descriptor.size,
-                        );
-                        render_context.resources().remove_buffer(texture_buffer);
+                        for level in 0..texture.mip_level_count() {
+                            copy_mip_level(render_context, texture, texture_resource, level);
+                        }
 
                         copied_textures.insert(&handle.id);
                     }
@@ -86,3 +54,56 @@ impl Node for TextureCopyNode {
         }
     }
 }
+
+/// Copies one mip level of `texture` to the gpu, padding each row of texels (or of blocks, for compressed formats) to
+/// the renderer's row alignment
+fn copy_mip_level(
+    render_context: &mut dyn RenderContext,
+    texture: &Texture,
+    texture_resource: TextureId,
+    level: u32,
+) {
+    let data = match texture.mip_level_data(level) {
+        Some(data) => data,
+        None => return,
+    };
+    let size = texture.mip_level_size(level);
+    let (block_width, block_height) = texture.format.block_dimensions();
+    // compressed levels are copied in whole blocks, even when they are smaller than one block
+    let physical_size = Extent3d::new(
+        (size.width + block_width - 1) / block_width * block_width,
+        (size.height + block_height - 1) / block_height * block_height,
+        size.depth,
+    );
+    let row_size = (physical_size.width / block_width) as usize * texture.format.pixel_size();
+    let aligned_row_size = render_context
+        .resources()
+        .get_aligned_texture_size(row_size);
+    let row_count = (physical_size.height / block_height * physical_size.depth) as usize;
+
+    let mut aligned_data = vec![0; aligned_row_size * row_count];
+    data.chunks_exact(row_size)
+        .enumerate()
+        .for_each(|(index, row)| {
+            let offset = index * aligned_row_size;
+            aligned_data[offset..(offset + row_size)].copy_from_slice(row);
+        });
+    let texture_buffer = render_context.resources().create_buffer_with_data(
+        BufferInfo {
+            buffer_usage: BufferUsage::COPY_SRC,
+            ..Default::default()
+        },
+        &aligned_data,
+    );
+
+    render_context.copy_buffer_to_texture(
+        texture_buffer,
+        0,
+        aligned_row_size as u32,
+        texture_resource,
+        [0, 0, 0],
+        level,
+        physical_size,
+    );
+    render_context.resources().remove_buffer(texture_buffer);
+}
@@ -0,0 +1,119 @@
+use crate::{
+    render_graph::{Node, ResourceSlotInfo, ResourceSlots},
+    renderer::{RenderContext, RenderResourceId, RenderResourceType, TextureId},
+    texture::{Extent3d, Texture, TextureDescriptor, TextureUsage, TEXTURE_ASSET_INDEX},
+};
+use bevy_asset::{Assets, Handle};
+use bevy_ecs::{Resources, World};
+use std::borrow::Cow;
+
+/// Outputs the gpu texture of a [Texture] asset so it can be used as a render pass attachment. Until the asset is
+/// loaded a 1x1 placeholder texture is used instead.
+pub struct TextureTargetNode {
+    pub texture: Handle<Texture>,
+    placeholder_texture: Option<TextureId>,
+}
+
+impl TextureTargetNode {
+    pub const OUT_TEXTURE: &'static str = "texture";
+
+    pub fn new(texture: Handle<Texture>) -> Self {
+        TextureTargetNode {
+            texture,
+            placeholder_texture: None,
+        }
+    }
+}
+
+impl Node for TextureTargetNode {
+    fn output(&self) -> &[ResourceSlotInfo] {
+        static OUTPUT: &[ResourceSlotInfo] = &[ResourceSlotInfo {
+            name: Cow::Borrowed(TextureTargetNode::OUT_TEXTURE),
+            resource_type: RenderResourceType::Texture,
+        }];
+        OUTPUT
+    }
+
+    fn update(
+        &mut self,
+        _world: &World,
+        _resources: &Resources,
+        render_context: &mut dyn RenderContext,
+        _input: &ResourceSlots,
+        output: &mut ResourceSlots,
+    ) {
+        const TARGET_TEXTURE: usize = 0;
+        let render_resource_context = render_context.resources_mut();
+        let texture =
+            match render_resource_context.get_asset_resource(&self.texture, TEXTURE_ASSET_INDEX) {
+                Some(RenderResourceId::Texture(texture)) => texture,
+                _ => *self.placeholder_texture.get_or_insert_with(|| {
+                    render_resource_context.create_texture(TextureDescriptor {
+                        usage: TextureUsage::OUTPUT_ATTACHMENT,
+                        ..Default::default()
+                    })
+                }),
+            };
+
+        output.set(TARGET_TEXTURE, RenderResourceId::Texture(texture));
+    }
+}
+
+/// Creates a texture with the same width and height as a [Texture] asset, like the depth texture of a camera that
+/// renders to that asset. The texture is recreated whenever the asset is resized.
+pub struct TextureTargetAttachmentNode {
+    pub texture: Handle<Texture>,
+    descriptor: TextureDescriptor,
+}
+
+impl TextureTargetAttachmentNode {
+    pub const OUT_TEXTURE: &'static str = "texture";
+
+    pub fn new(texture: Handle<Texture>, descriptor: TextureDescriptor) -> Self {
+        TextureTargetAttachmentNode {
+            texture,
+            descriptor,
+        }
+    }
+}
+
+impl Node for TextureTargetAttachmentNode {
+    fn output(&self) -> &[ResourceSlotInfo] {
+        static OUTPUT: &[ResourceSlotInfo] = &[ResourceSlotInfo {
+            name: Cow::Borrowed(TextureTargetAttachmentNode::OUT_TEXTURE),
+            resource_type: RenderResourceType::Texture,
+        }];
+        OUTPUT
+    }
+
+    fn update(
+        &mut self,
+        _world: &World,
+        resources: &Resources,
+        render_context: &mut dyn RenderContext,
+        _input: &ResourceSlots,
+        output: &mut ResourceSlots,
+    ) {
+        const ATTACHMENT_TEXTURE: usize = 0;
+        let textures = resources.get::<Assets<Texture>>().unwrap();
+        let size = textures
+            .get(&self.texture)
+            .map(|texture| Extent3d::new(texture.size.width, texture.size.height, 1))
+            .unwrap_or_else(|| Extent3d::new(1, 1, 1));
+
+        let old_texture = output.get(ATTACHMENT_TEXTURE);
+        if old_texture.is_none() || self.descriptor.size != size {
+            let render_resource_context = render_context.resources_mut();
+            if let Some(RenderResourceId::Texture(old_texture)) = old_texture {
+                render_resource_context.remove_texture(old_texture);
+            }
+
+            self.descriptor.size = size;
+            let texture_resource = render_resource_context.create_texture(self.descriptor);
+            output.set(
+                ATTACHMENT_TEXTURE,
+                RenderResourceId::Texture(texture_resource),
+            );
+        }
+    }
+}
@@ -1,6 +1,9 @@
 use crate::{
+    camera::RenderTarget,
     render_graph::{Node, ResourceSlotInfo, ResourceSlots},
     renderer::{RenderContext, RenderResourceId, RenderResourceType},
+    screenshot::Screenshots,
+    texture::{Extent3d, TextureDescriptor, TextureFormat, TextureUsage},
 };
 use bevy_app::prelude::{EventReader, Events};
 use bevy_ecs::{Resources, World};
@@ -47,9 +50,12 @@ impl Node for WindowSwapChainNode {
         let window_resized_events = resources.get::<Events<WindowResized>>().unwrap();
         let windows = resources.get::<Windows>().unwrap();
 
-        let window = windows
-            .get(self.window_id)
-            .expect("Received window resized event for non-existent window.");
+        // there is nothing to render to if the window doesn't exist, like when running headless
+        let window = if let Some(window) = windows.get(self.window_id) {
+            window
+        } else {
+            return;
+        };
 
         let render_resource_context = render_context.resources_mut();
 
@@ -66,6 +72,24 @@ impl Node for WindowSwapChainNode {
             render_resource_context.create_swap_chain(window);
         }
 
+        // swap chain textures can't be copied, so frames that are captured are rendered to a texture instead and
+        // aren't presented. the ScreenshotNode of the window frees the texture once it has been copied.
+        let mut screenshots = resources.get_mut::<Screenshots>().unwrap();
+        if let Some(stale_texture) = screenshots.take_window_capture(self.window_id) {
+            render_resource_context.remove_texture(stale_texture);
+        }
+        if screenshots.is_requested(&RenderTarget::Window(self.window_id)) {
+            let capture_texture = render_resource_context.create_texture(TextureDescriptor {
+                size: Extent3d::new(window.physical_width(), window.physical_height(), 1),
+                usage: TextureUsage::OUTPUT_ATTACHMENT | TextureUsage::COPY_SRC,
+                format: TextureFormat::default(),
+                ..Default::default()
+            });
+            screenshots.set_window_capture(self.window_id, capture_texture);
+            output.set(WINDOW_TEXTURE, RenderResourceId::Texture(capture_texture));
+            return;
+        }
+
         let swap_chain_texture = render_resource_context.next_swap_chain_texture(&window);
         output.set(
             WINDOW_TEXTURE,
@@ -50,9 +50,11 @@ impl Node for WindowTextureNode {
         let window_resized_events = resources.get::<Events<WindowResized>>().unwrap();
         let windows = resources.get::<Windows>().unwrap();
 
-        let window = windows
-            .get(self.window_id)
-            .expect("Received window resized event for non-existent window.");
+        let window = if let Some(window) = windows.get(self.window_id) {
+            window
+        } else {
+            return;
+        };
 
         if self
             .window_created_event_reader
@@ -27,6 +27,20 @@ pub trait RenderContext {
         destination_mip_level: u32,
         size: Extent3d,
     );
+    /// Copies a texture to a buffer, e.g. to read it back for a screenshot. The default implementation does nothing,
+    /// for backends that can't read textures back.
+    #[allow(clippy::too_many_arguments)]
+    fn copy_texture_to_buffer(
+        &mut self,
+        _source_texture: TextureId,
+        _source_origin: [u32; 3],
+        _source_mip_level: u32,
+        _destination_buffer: BufferId,
+        _destination_offset: u64,
+        _destination_bytes_per_row: u32,
+        _size: Extent3d,
+    ) {
+    }
     fn begin_pass(
         &mut self,
         pass_descriptor: &PassDescriptor,
@@ -25,6 +25,17 @@ pub trait RenderResourceContext: Downcast + Send + Sync + 'static {
         write: &mut dyn FnMut(&mut [u8], &dyn RenderResourceContext),
     );
     fn map_buffer(&self, id: BufferId);
+    /// Maps a buffer so that the cpu can read it with [RenderResourceContext::read_mapped_buffer]. Backends that
+    /// can't read buffers back can keep the default implementations of both functions, which do nothing. Screenshots
+    /// then fail with [ScreenshotError::ReadbackUnsupported](crate::screenshot::ScreenshotError::ReadbackUnsupported).
+    fn map_buffer_for_read(&self, _id: BufferId) {}
+    fn read_mapped_buffer(
+        &self,
+        _id: BufferId,
+        _range: Range<u64>,
+        _read: &mut dyn FnMut(&[u8], &dyn RenderResourceContext),
+    ) {
+    }
     fn unmap_buffer(&self, id: BufferId);
     fn create_buffer_with_data(&self, buffer_info: BufferInfo, data: &[u8]) -> BufferId;
     fn create_shader_module(&self, shader_handle: &Handle<Shader>, shaders: &Assets<Shader>);
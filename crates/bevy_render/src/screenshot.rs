@@ -0,0 +1,454 @@
+use crate::{
+    camera::RenderTarget,
+    render_graph::{RenderGraph, ScreenshotNode},
+    renderer::{BufferId, RenderResourceContext, TextureId},
+    texture::{Extent3d, Texture, TextureDimension, TextureFormat},
+};
+use bevy_app::prelude::Events;
+use bevy_asset::Assets;
+use bevy_ecs::{Res, ResMut};
+use bevy_utils::{tracing::error, HashMap};
+use bevy_window::{WindowId, Windows};
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ScreenshotId(usize);
+
+/// Sent once the pixels of a requested screenshot have been read back from the gpu
+#[derive(Debug, Clone)]
+pub struct ScreenshotCaptured {
+    pub id: ScreenshotId,
+    pub target: RenderTarget,
+    pub texture: Texture,
+}
+
+/// Sent instead of [ScreenshotCaptured] when a requested screenshot can't be taken
+#[derive(Debug)]
+pub struct ScreenshotFailed {
+    pub id: ScreenshotId,
+    pub target: RenderTarget,
+    pub error: ScreenshotError,
+}
+
+#[derive(Error, Debug)]
+pub enum ScreenshotError {
+    #[error("the target doesn't exist or no ScreenshotNode in the render graph captures it")]
+    UnsupportedTarget,
+    #[error("the render backend can't read textures back")]
+    ReadbackUnsupported,
+    #[error("textures with the format {0:?} can't be saved as png")]
+    UnsupportedFormat(TextureFormat),
+    #[error("failed to save the image: {0}")]
+    Image(#[from] image::ImageError),
+}
+
+#[derive(Debug)]
+pub(crate) struct ScreenshotRequest {
+    pub id: ScreenshotId,
+    pub target: RenderTarget,
+    pub path: Option<PathBuf>,
+}
+
+/// A texture that has been copied to `buffer`, waiting to be read once the frame has been submitted
+#[derive(Debug)]
+pub(crate) struct PendingScreenshot {
+    pub requests: Vec<ScreenshotRequest>,
+    pub buffer: BufferId,
+    pub bytes_per_row: usize,
+    pub size: Extent3d,
+    pub format: TextureFormat,
+    pub capture_texture: Option<TextureId>,
+}
+
+/// Requests screenshots of windows and render target textures. Each request is captured when its target is rendered
+/// in the next frame, and is sent as a [ScreenshotCaptured] event in the [POST_RENDER](crate::stage::POST_RENDER)
+/// stage of that frame.
+///
+/// Only targets with a [ScreenshotNode] in the render graph can be captured. The base render graph adds one for the
+/// primary window and for every camera that renders to a texture. Requests for other targets, or for windows and
+/// textures that don't exist, are dropped with a [ScreenshotFailed] event before rendering.
+///
+/// The swap chain textures of windows can't be copied, so a window renders the frame it is captured in into an
+/// offscreen texture instead, and skips presenting that frame. Render target textures need
+/// [TextureUsage::COPY_SRC](crate::texture::TextureUsage::COPY_SRC), which [Texture::new_render_target] sets.
+#[derive(Debug, Default)]
+pub struct Screenshots {
+    next_id: usize,
+    requests: Vec<ScreenshotRequest>,
+    window_captures: HashMap<WindowId, TextureId>,
+    pending: Vec<PendingScreenshot>,
+}
+
+impl Screenshots {
+    pub fn take(&mut self, target: RenderTarget) -> ScreenshotId {
+        self.add_request(target, None)
+    }
+
+    /// Takes a screenshot and saves it as a png at `path`
+    pub fn take_and_save(
+        &mut self,
+        target: RenderTarget,
+        path: impl Into<PathBuf>,
+    ) -> ScreenshotId {
+        self.add_request(target, Some(path.into()))
+    }
+
+    fn add_request(&mut self, target: RenderTarget, path: Option<PathBuf>) -> ScreenshotId {
+        let id = ScreenshotId(self.next_id);
+        self.next_id += 1;
+        self.requests.push(ScreenshotRequest { id, target, path });
+        id
+    }
+
+    pub fn is_requested(&self, target: &RenderTarget) -> bool {
+        self.requests
+            .iter()
+            .any(|request| request.target == *target)
+    }
+
+    pub(crate) fn take_requests(&mut self, target: &RenderTarget) -> Vec<ScreenshotRequest> {
+        let (taken, requests) = self
+            .requests
+            .drain(..)
+            .partition(|request| request.target == *target);
+        self.requests = requests;
+        taken
+    }
+
+    pub(crate) fn set_window_capture(&mut self, window_id: WindowId, texture: TextureId) {
+        self.window_captures.insert(window_id, texture);
+    }
+
+    pub(crate) fn take_window_capture(&mut self, window_id: WindowId) -> Option<TextureId> {
+        self.window_captures.remove(&window_id)
+    }
+
+    pub(crate) fn add_pending(&mut self, pending: PendingScreenshot) {
+        self.pending.push(pending);
+    }
+}
+
+/// Drops the screenshot requests that can't be captured this frame with a [ScreenshotFailed] event, so that they don't
+/// stay pending forever
+pub fn screenshot_request_system(
+    mut screenshots: ResMut<Screenshots>,
+    mut failed_events: ResMut<Events<ScreenshotFailed>>,
+    render_graph: Res<RenderGraph>,
+    windows: Res<Windows>,
+    textures: Res<Assets<Texture>>,
+) {
+    let is_supported = |target: &RenderTarget| {
+        let exists = match target {
+            RenderTarget::Window(window_id) => windows.get(*window_id).is_some(),
+            RenderTarget::Texture(handle) => textures.get(handle).is_some(),
+        };
+        exists
+            && render_graph.iter_nodes().any(|node_state| {
+                node_state
+                    .node
+                    .downcast_ref::<ScreenshotNode>()
+                    .map_or(false, |node| node.target == *target)
+            })
+    };
+
+    let (requests, unsupported): (Vec<_>, Vec<_>) = screenshots
+        .requests
+        .drain(..)
+        .partition(|request| is_supported(&request.target));
+    screenshots.requests = requests;
+    for request in unsupported {
+        failed_events.send(ScreenshotFailed {
+            id: request.id,
+            target: request.target,
+            error: ScreenshotError::UnsupportedTarget,
+        });
+    }
+}
+
+/// Reads back the screenshots copied during this frame, saves them and sends [ScreenshotCaptured] events
+pub fn screenshot_system(
+    mut screenshots: ResMut<Screenshots>,
+    mut screenshot_events: ResMut<Events<ScreenshotCaptured>>,
+    mut failed_events: ResMut<Events<ScreenshotFailed>>,
+    render_resource_context: Res<Box<dyn RenderResourceContext>>,
+) {
+    let render_resource_context = &**render_resource_context;
+    for pending in screenshots.pending.drain(..) {
+        let row_size = pending.size.width as usize * pending.format.pixel_size();
+        let mut data = Vec::with_capacity(row_size * pending.size.height as usize);
+        render_resource_context.map_buffer_for_read(pending.buffer);
+        render_resource_context.read_mapped_buffer(
+            pending.buffer,
+            0..(pending.bytes_per_row * pending.size.height as usize) as u64,
+            &mut |bytes, _| {
+                for row in bytes.chunks_exact(pending.bytes_per_row) {
+                    data.extend_from_slice(&row[..row_size]);
+                }
+            },
+        );
+        render_resource_context.unmap_buffer(pending.buffer);
+        render_resource_context.remove_buffer(pending.buffer);
+        if let Some(capture_texture) = pending.capture_texture {
+            render_resource_context.remove_texture(capture_texture);
+        }
+
+        // backends that can't read buffers back don't call the read function
+        if data.len() != row_size * pending.size.height as usize {
+            for request in pending.requests {
+                failed_events.send(ScreenshotFailed {
+                    id: request.id,
+                    target: request.target,
+                    error: ScreenshotError::ReadbackUnsupported,
+                });
+            }
+            continue;
+        }
+
+        let texture = Texture::new(pending.size, TextureDimension::D2, data, pending.format);
+        for request in pending.requests {
+            if let Some(path) = request.path {
+                if let Err(err) = save_png(&texture, &path) {
+                    error!("Failed to save screenshot to {}: {}", path.display(), err);
+                }
+            }
+
+            screenshot_events.send(ScreenshotCaptured {
+                id: request.id,
+                target: request.target,
+                texture: texture.clone(),
+            });
+        }
+    }
+}
+
+/// Saves a texture with 8 bit rgba or bgra pixels as a png. Requires the "png" feature.
+pub fn save_png(texture: &Texture, path: &Path) -> Result<(), ScreenshotError> {
+    let data = to_rgba8(texture)?;
+    image::save_buffer_with_format(
+        path,
+        &data,
+        texture.size.width,
+        texture.size.height,
+        image::ColorType::Rgba8,
+        image::ImageFormat::Png,
+    )?;
+    Ok(())
+}
+
+fn to_rgba8(texture: &Texture) -> Result<Vec<u8>, ScreenshotError> {
+    match texture.format {
+        TextureFormat::Rgba8Unorm | TextureFormat::Rgba8UnormSrgb => Ok(texture.data.clone()),
+        TextureFormat::Bgra8Unorm | TextureFormat::Bgra8UnormSrgb => {
+            let mut data = texture.data.clone();
+            for pixel in data.chunks_exact_mut(4) {
+                pixel.swap(0, 2);
+            }
+            Ok(data)
+        }
+        format => Err(ScreenshotError::UnsupportedFormat(format)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        pass::{PassDescriptor, RenderPass},
+        render_graph::{Node, ResourceSlots, WindowSwapChainNode},
+        renderer::{HeadlessRenderResourceContext, RenderContext, RenderResourceBindings},
+    };
+    use bevy_app::App;
+    use bevy_asset::{AddAsset, AssetPlugin, Handle};
+    use bevy_core::CorePlugin;
+    use bevy_ecs::{Resources, Schedule, SystemStage, World};
+    use bevy_reflect::ReflectPlugin;
+    use bevy_window::{Window, WindowCreated, WindowDescriptor, WindowResized};
+
+    /// Records nothing, like a backend that runs without a gpu
+    struct HeadlessRenderContext(HeadlessRenderResourceContext);
+
+    impl RenderContext for HeadlessRenderContext {
+        fn resources(&self) -> &dyn RenderResourceContext {
+            &self.0
+        }
+
+        fn resources_mut(&mut self) -> &mut dyn RenderResourceContext {
+            &mut self.0
+        }
+
+        fn copy_buffer_to_buffer(&mut self, _: BufferId, _: u64, _: BufferId, _: u64, _: u64) {}
+
+        fn copy_buffer_to_texture(
+            &mut self,
+            _: BufferId,
+            _: u64,
+            _: u32,
+            _: TextureId,
+            _: [u32; 3],
+            _: u32,
+            _: Extent3d,
+        ) {
+        }
+
+        fn begin_pass(
+            &mut self,
+            _: &PassDescriptor,
+            _: &RenderResourceBindings,
+            _: &mut dyn Fn(&mut dyn RenderPass),
+        ) {
+        }
+    }
+
+    fn primary_window(width: u32, height: u32) -> Windows {
+        let mut windows = Windows::default();
+        windows.add(Window::new(
+            WindowId::primary(),
+            &WindowDescriptor {
+                width,
+                height,
+                ..Default::default()
+            },
+        ));
+        windows
+    }
+
+    #[test]
+    fn bgra_to_rgba() {
+        let texture = Texture::new(
+            Extent3d::new(2, 1, 1),
+            TextureDimension::D2,
+            vec![1, 2, 3, 4, 5, 6, 7, 8],
+            TextureFormat::Bgra8UnormSrgb,
+        );
+        assert_eq!(to_rgba8(&texture).unwrap(), vec![3, 2, 1, 4, 7, 6, 5, 8]);
+
+        let texture = Texture::new(
+            Extent3d::new(1, 1, 1),
+            TextureDimension::D2,
+            vec![0; 8],
+            TextureFormat::Rg32Float,
+        );
+        assert!(to_rgba8(&texture).is_err());
+    }
+
+    #[test]
+    fn screenshot_requests() {
+        let mut screenshots = Screenshots::default();
+        let window = RenderTarget::Window(WindowId::primary());
+        let first = screenshots.take(window.clone());
+        let second = screenshots.take_and_save(RenderTarget::default(), "screenshot.png");
+        assert_ne!(first, second);
+        assert!(screenshots.is_requested(&window));
+
+        let requests = screenshots.take_requests(&window);
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[1].path, Some(PathBuf::from("screenshot.png")));
+        assert!(!screenshots.is_requested(&window));
+    }
+
+    #[test]
+    fn capture_window() {
+        let mut world = World::default();
+        let mut resources = Resources::default();
+        resources.insert(primary_window(4, 2));
+        resources.insert(Events::<WindowCreated>::default());
+        resources.insert(Events::<WindowResized>::default());
+        resources.insert(Events::<ScreenshotCaptured>::default());
+        resources.insert(Events::<ScreenshotFailed>::default());
+        let target = RenderTarget::Window(WindowId::primary());
+        let mut screenshots = Screenshots::default();
+        let id = screenshots.take(target.clone());
+        resources.insert(screenshots);
+
+        let mut render_context = HeadlessRenderContext(HeadlessRenderResourceContext::default());
+        let mut swap_chain_node = WindowSwapChainNode::new(WindowId::primary());
+        let mut swap_chain_output = ResourceSlots::from(swap_chain_node.output());
+        swap_chain_node.update(
+            &world,
+            &resources,
+            &mut render_context,
+            &ResourceSlots::default(),
+            &mut swap_chain_output,
+        );
+        let mut screenshot_node = ScreenshotNode::new(target.clone());
+        screenshot_node.update(
+            &world,
+            &resources,
+            &mut render_context,
+            &swap_chain_output,
+            &mut ResourceSlots::default(),
+        );
+        assert!(!resources
+            .get::<Screenshots>()
+            .unwrap()
+            .is_requested(&target));
+
+        resources.insert::<Box<dyn RenderResourceContext>>(Box::new(render_context.0));
+        let mut schedule = Schedule::default();
+        let mut update_stage = SystemStage::parallel();
+        update_stage.add_system(screenshot_system);
+        schedule.add_stage("update", update_stage);
+        schedule.initialize_and_run(&mut world, &mut resources);
+
+        let captured = resources.get::<Events<ScreenshotCaptured>>().unwrap();
+        let captured = captured.get_reader().iter(&captured).collect::<Vec<_>>();
+        assert_eq!(captured.len(), 1);
+        assert_eq!(captured[0].id, id);
+        assert_eq!(captured[0].target, target);
+        assert_eq!(captured[0].texture.size, Extent3d::new(4, 2, 1));
+        assert_eq!(captured[0].texture.data.len(), 4 * 2 * 4);
+        assert!(screenshots_are_done(&resources));
+    }
+
+    fn screenshots_are_done(resources: &Resources) -> bool {
+        let screenshots = resources.get::<Screenshots>().unwrap();
+        screenshots.requests.is_empty()
+            && screenshots.pending.is_empty()
+            && screenshots.window_captures.is_empty()
+    }
+
+    #[test]
+    fn unsupported_targets_fail() {
+        let mut app_builder = App::build();
+        app_builder
+            .add_plugin(ReflectPlugin)
+            .add_plugin(CorePlugin)
+            .add_plugin(AssetPlugin)
+            .add_asset::<Texture>()
+            .add_resource(primary_window(4, 2))
+            .init_resource::<RenderGraph>()
+            .init_resource::<Screenshots>()
+            .add_event::<ScreenshotFailed>()
+            .add_system(screenshot_request_system);
+        let mut app = app_builder.app;
+
+        let primary = RenderTarget::Window(WindowId::primary());
+        app.resources
+            .get_mut::<RenderGraph>()
+            .unwrap()
+            .add_node("screenshot", ScreenshotNode::new(primary.clone()));
+        let (captured, missing_window, missing_texture) = {
+            let mut screenshots = app.resources.get_mut::<Screenshots>().unwrap();
+            (
+                screenshots.take(primary.clone()),
+                screenshots.take(RenderTarget::Window(WindowId::new())),
+                screenshots.take(RenderTarget::Texture(Handle::<Texture>::default())),
+            )
+        };
+        app.update();
+
+        let failed = app.resources.get::<Events<ScreenshotFailed>>().unwrap();
+        let failed = failed.get_reader().iter(&failed).collect::<Vec<_>>();
+        assert_eq!(
+            failed.iter().map(|event| event.id).collect::<Vec<_>>(),
+            vec![missing_window, missing_texture]
+        );
+        assert!(failed
+            .iter()
+            .all(|event| matches!(event.error, ScreenshotError::UnsupportedTarget)));
+        let screenshots = app.resources.get::<Screenshots>().unwrap();
+        assert_eq!(screenshots.requests.len(), 1);
+        assert_eq!(screenshots.requests[0].id, captured);
+    }
+}
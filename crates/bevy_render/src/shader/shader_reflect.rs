@@ -103,12 +103,18 @@ fn reflect_bind_group(
 }
 
 fn reflect_dimension(type_description: &ReflectTypeDescription) -> TextureViewDimension {
-    match type_description.traits.image.dim {
-        ReflectDimension::Type1d => TextureViewDimension::D1,
-        ReflectDimension::Type2d => TextureViewDimension::D2,
-        ReflectDimension::Type3d => TextureViewDimension::D3,
-        ReflectDimension::Cube => TextureViewDimension::Cube,
-        dimension => panic!("Unsupported image dimension: {:?}.", dimension),
+    let arrayed = type_description.traits.image.arrayed > 0;
+    match (type_description.traits.image.dim, arrayed) {
+        (ReflectDimension::Type1d, false) => TextureViewDimension::D1,
+        (ReflectDimension::Type2d, false) => TextureViewDimension::D2,
+        (ReflectDimension::Type2d, true) => TextureViewDimension::D2Array,
+        (ReflectDimension::Type3d, false) => TextureViewDimension::D3,
+        (ReflectDimension::Cube, false) => TextureViewDimension::Cube,
+        (ReflectDimension::Cube, true) => TextureViewDimension::CubeArray,
+        (dimension, arrayed) => panic!(
+            "Unsupported image dimension: {:?} (arrayed: {}).",
+            dimension, arrayed
+        ),
     }
 }
 
@@ -142,6 +148,11 @@ fn reflect_binding(
         ),
         // TODO: detect comparison "true" case: https://github.com/gpuweb/gpuweb/issues/552
         ReflectDescriptorType::Sampler => (&binding.name, BindType::Sampler { comparison: false }),
+        // wgpu has no combined image samplers, so textures and samplers are always bound separately
+        ReflectDescriptorType::CombinedImageSampler => panic!(
+            "Combined image sampler {:?} is not supported. Declare a separate texture (like `textureCube` or `texture2DArray`) and `sampler`, and combine them where they are sampled (like `samplerCube(texture, sampler)`).",
+            binding.name
+        ),
         _ => panic!("Unsupported bind type {:?}.", binding.descriptor_type),
     };
 
@@ -398,4 +409,67 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    fn test_reflect_texture_dimensions() {
+        let fragment_shader = Shader::from_glsl(
+            ShaderStage::Fragment,
+            r#"
+            #version 450
+            layout(location = 0) out vec4 o_Target;
+            layout(set = 0, binding = 0) uniform texture2DArray Splat;
+            layout(set = 0, binding = 1) uniform textureCube Skybox;
+            layout(set = 0, binding = 2) uniform texture3D Volume;
+            layout(set = 0, binding = 3) uniform sampler Sampler;
+
+            void main() {
+                o_Target = texture(sampler2DArray(Splat, Sampler), vec3(0.0))
+                    + texture(samplerCube(Skybox, Sampler), vec3(1.0))
+                    + texture(sampler3D(Volume, Sampler), vec3(0.0));
+            }
+        "#,
+        )
+        .get_spirv_shader(None)
+        .unwrap();
+
+        let layout = fragment_shader.reflect_layout(true).unwrap();
+        let mut dimensions = layout.bind_groups[0]
+            .bindings
+            .iter()
+            .filter_map(|binding| match binding.bind_type {
+                BindType::SampledTexture { dimension, .. } => Some((binding.index, dimension)),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        dimensions.sort();
+        assert_eq!(
+            dimensions,
+            vec![
+                (0, TextureViewDimension::D2Array),
+                (1, TextureViewDimension::Cube),
+                (2, TextureViewDimension::D3),
+            ]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Combined image sampler \"Skybox\" is not supported.")]
+    fn test_reject_combined_image_samplers() {
+        let fragment_shader = Shader::from_glsl(
+            ShaderStage::Fragment,
+            r#"
+            #version 450
+            layout(location = 0) out vec4 o_Target;
+            layout(set = 0, binding = 0) uniform samplerCube Skybox;
+
+            void main() {
+                o_Target = texture(Skybox, vec3(1.0));
+            }
+        "#,
+        )
+        .get_spirv_shader(None)
+        .unwrap();
+
+        fragment_shader.reflect_layout(true).unwrap();
+    }
 }
@@ -0,0 +1,289 @@
+use super::{Extent3d, Texture, TextureFormat};
+
+/// Decompresses every mip level of a BC1 to BC5 texture, for GPUs that don't support BC compression. Returns `false`
+/// and leaves the texture as it is for other formats, including BC6H and BC7.
+pub(crate) fn decompress_bc(texture: &mut Texture) -> bool {
+    let format = match decompressed_format(texture.format) {
+        Some(format) => format,
+        None => return false,
+    };
+
+    let mut levels = (0..texture.mip_level_count())
+        .map(|level| {
+            decompress_level(
+                texture.mip_level_data(level).unwrap(),
+                texture.format,
+                texture.mip_level_size(level),
+                format,
+            )
+        })
+        .collect::<Vec<_>>()
+        .into_iter();
+    texture.data = levels.next().unwrap();
+    texture.mipmaps = levels.collect();
+    texture.format = format;
+    true
+}
+
+fn decompressed_format(format: TextureFormat) -> Option<TextureFormat> {
+    match format {
+        TextureFormat::Bc1RgbaUnorm | TextureFormat::Bc2RgbaUnorm | TextureFormat::Bc3RgbaUnorm => {
+            Some(TextureFormat::Rgba8Unorm)
+        }
+        TextureFormat::Bc1RgbaUnormSrgb
+        | TextureFormat::Bc2RgbaUnormSrgb
+        | TextureFormat::Bc3RgbaUnormSrgb => Some(TextureFormat::Rgba8UnormSrgb),
+        TextureFormat::Bc4RUnorm => Some(TextureFormat::R8Unorm),
+        TextureFormat::Bc4RSnorm => Some(TextureFormat::R8Snorm),
+        TextureFormat::Bc5RgUnorm => Some(TextureFormat::Rg8Unorm),
+        TextureFormat::Bc5RgSnorm => Some(TextureFormat::Rg8Snorm),
+        _ => None,
+    }
+}
+
+/// Decodes the blocks of one mip level, dropping the texels of edge blocks that are outside of the level
+fn decompress_level(
+    data: &[u8],
+    format: TextureFormat,
+    size: Extent3d,
+    decompressed_format: TextureFormat,
+) -> Vec<u8> {
+    let (width, height) = (size.width as usize, size.height as usize);
+    let blocks_wide = (width + 3) / 4;
+    let blocks_high = (height + 3) / 4;
+    let channels = decompressed_format.pixel_size();
+    let mut decompressed = vec![0; width * height * size.depth as usize * channels];
+    for (index, block) in data.chunks_exact(format.pixel_size()).enumerate() {
+        let slice = index / (blocks_wide * blocks_high);
+        let block_x = index % blocks_wide * 4;
+        let block_y = index / blocks_wide % blocks_high * 4;
+        for (texel, value) in decode_block(format, block).iter().enumerate() {
+            let (x, y) = (block_x + texel % 4, block_y + texel / 4);
+            if x < width && y < height {
+                let offset = ((slice * height + y) * width + x) * channels;
+                decompressed[offset..offset + channels].copy_from_slice(&value[..channels]);
+            }
+        }
+    }
+    decompressed
+}
+
+/// Decodes the 4x4 texels of a block, in rows from top to bottom. Formats with fewer than four channels only fill
+/// the first ones.
+fn decode_block(format: TextureFormat, block: &[u8]) -> [[u8; 4]; 16] {
+    match format {
+        TextureFormat::Bc1RgbaUnorm | TextureFormat::Bc1RgbaUnormSrgb => {
+            decode_color_block(block, true)
+        }
+        TextureFormat::Bc2RgbaUnorm | TextureFormat::Bc2RgbaUnormSrgb => {
+            let mut texels = decode_color_block(&block[8..], false);
+            for (texel, value) in texels.iter_mut().enumerate() {
+                let alpha = block[texel / 2] >> (texel % 2 * 4) & 0xf;
+                value[3] = alpha * 17;
+            }
+            texels
+        }
+        TextureFormat::Bc3RgbaUnorm | TextureFormat::Bc3RgbaUnormSrgb => {
+            let mut texels = decode_color_block(&block[8..], false);
+            let alpha = decode_interpolated_block(&block[..8], false);
+            for (value, alpha) in texels.iter_mut().zip(alpha.iter()) {
+                value[3] = *alpha;
+            }
+            texels
+        }
+        TextureFormat::Bc4RUnorm | TextureFormat::Bc4RSnorm => {
+            let red = decode_interpolated_block(block, format == TextureFormat::Bc4RSnorm);
+            let mut texels = [[0; 4]; 16];
+            for (value, red) in texels.iter_mut().zip(red.iter()) {
+                value[0] = *red;
+            }
+            texels
+        }
+        TextureFormat::Bc5RgUnorm | TextureFormat::Bc5RgSnorm => {
+            let signed = format == TextureFormat::Bc5RgSnorm;
+            let red = decode_interpolated_block(&block[..8], signed);
+            let green = decode_interpolated_block(&block[8..], signed);
+            let mut texels = [[0; 4]; 16];
+            for (texel, value) in texels.iter_mut().enumerate() {
+                value[0] = red[texel];
+                value[1] = green[texel];
+            }
+            texels
+        }
+        format => unreachable!("{:?} can't be decompressed", format),
+    }
+}
+
+/// Decodes the 8 byte color blocks of BC1, BC2 and BC3. Only BC1 blocks can have transparent texels.
+fn decode_color_block(block: &[u8], allow_transparent: bool) -> [[u8; 4]; 16] {
+    let color0 = u16::from_le_bytes([block[0], block[1]]);
+    let color1 = u16::from_le_bytes([block[2], block[3]]);
+    let (endpoint0, endpoint1) = (rgb565_to_rgb(color0), rgb565_to_rgb(color1));
+    let mix = |weight: i32, total: i32| {
+        let mut color = [0, 0, 0, 255];
+        for (channel, value) in color.iter_mut().take(3).enumerate() {
+            *value = interpolate(endpoint0[channel], endpoint1[channel], weight, total) as u8;
+        }
+        color
+    };
+    let palette = if color0 > color1 || !allow_transparent {
+        [mix(0, 1), mix(1, 1), mix(1, 3), mix(2, 3)]
+    } else {
+        [mix(0, 1), mix(1, 1), mix(1, 2), [0; 4]]
+    };
+
+    let indices = u32::from_le_bytes([block[4], block[5], block[6], block[7]]);
+    let mut texels = [[0; 4]; 16];
+    for (texel, value) in texels.iter_mut().enumerate() {
+        *value = palette[(indices >> (texel * 2)) as usize & 3];
+    }
+    texels
+}
+
+/// Decodes the 8 byte blocks of BC4 and BC5 channels and BC3 alpha. Signed values are returned as their two's
+/// complement bytes.
+fn decode_interpolated_block(block: &[u8], signed: bool) -> [u8; 16] {
+    let endpoint = |byte: u8| {
+        if signed {
+            // -128 and -127 both map to -1.0
+            (byte as i8 as i32).max(-127)
+        } else {
+            byte as i32
+        }
+    };
+    let (min, max) = if signed { (-127, 127) } else { (0, 255) };
+    let (endpoint0, endpoint1) = (endpoint(block[0]), endpoint(block[1]));
+    let mut palette = [endpoint0, endpoint1, 0, 0, 0, 0, 0, 0];
+    if endpoint0 > endpoint1 {
+        for weight in 1..7 {
+            palette[weight as usize + 1] = interpolate(endpoint0, endpoint1, weight, 7);
+        }
+    } else {
+        for weight in 1..5 {
+            palette[weight as usize + 1] = interpolate(endpoint0, endpoint1, weight, 5);
+        }
+        palette[6] = min;
+        palette[7] = max;
+    }
+
+    let indices = block[2..8]
+        .iter()
+        .rev()
+        .fold(0u64, |indices, byte| indices << 8 | *byte as u64);
+    let mut values = [0; 16];
+    for (texel, value) in values.iter_mut().enumerate() {
+        *value = palette[(indices >> (texel * 3)) as usize & 7] as u8;
+    }
+    values
+}
+
+fn rgb565_to_rgb(color: u16) -> [i32; 3] {
+    let red = (color >> 11 & 0x1f) as i32;
+    let green = (color >> 5 & 0x3f) as i32;
+    let blue = (color & 0x1f) as i32;
+    [
+        red << 3 | red >> 2,
+        green << 2 | green >> 4,
+        blue << 3 | blue >> 2,
+    ]
+}
+
+/// Blends `weight / total` of the way from `from` to `to`, rounding to the nearest integer
+fn interpolate(from: i32, to: i32, weight: i32, total: i32) -> i32 {
+    ((total - weight) * from + weight * to + total / 2).div_euclid(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::texture::TextureDimension;
+
+    #[test]
+    fn decompress_bc1() {
+        // pure red and pure blue endpoints, with the texels of each row using the four palette colors in turn
+        let block = [0x00, 0xf8, 0x1f, 0x00, 0xe4, 0xe4, 0xe4, 0xe4];
+        let mut texture = Texture::new(
+            Extent3d::new(4, 4, 1),
+            TextureDimension::D2,
+            block.to_vec(),
+            TextureFormat::Bc1RgbaUnorm,
+        );
+        assert!(decompress_bc(&mut texture));
+        assert_eq!(texture.format, TextureFormat::Rgba8Unorm);
+        assert_eq!(
+            texture.data[..16],
+            [255, 0, 0, 255, 0, 0, 255, 255, 170, 0, 85, 255, 85, 0, 170, 255]
+        );
+
+        // swapping the endpoints switches to three colors and transparent black. Texels outside of a 2x1 level are
+        // dropped.
+        let block = [0x1f, 0x00, 0x00, 0xf8, 0xe4, 0xe4, 0xe4, 0xe4];
+        let mut texture = Texture::new(
+            Extent3d::new(2, 1, 1),
+            TextureDimension::D2,
+            block.to_vec(),
+            TextureFormat::Bc1RgbaUnormSrgb,
+        );
+        texture.mipmaps.push(block.to_vec());
+        assert!(decompress_bc(&mut texture));
+        assert_eq!(texture.format, TextureFormat::Rgba8UnormSrgb);
+        assert_eq!(texture.data, vec![0, 0, 255, 255, 255, 0, 0, 255]);
+        assert_eq!(texture.mipmaps, vec![vec![0, 0, 255, 255]]);
+    }
+
+    #[test]
+    fn decompress_alpha_and_channels() {
+        // explicit 4 bit alpha, increasing from 0 to 15 across the block, over white
+        let mut block = vec![0x10, 0x32, 0x54, 0x76, 0x98, 0xba, 0xdc, 0xfe];
+        block.extend_from_slice(&[0xff, 0xff, 0xff, 0xff, 0, 0, 0, 0]);
+        let mut texture = Texture::new(
+            Extent3d::new(4, 4, 1),
+            TextureDimension::D2,
+            block,
+            TextureFormat::Bc2RgbaUnorm,
+        );
+        assert!(decompress_bc(&mut texture));
+        let alpha = texture
+            .data
+            .chunks_exact(4)
+            .map(|texel| texel[3])
+            .collect::<Vec<_>>();
+        assert_eq!(alpha, (0..16).map(|value| value * 17).collect::<Vec<u8>>());
+
+        // interpolated values from 70 to 0, with the index of each texel being the texel's column
+        let mut red = vec![70, 0];
+        red.extend_from_slice(&[0x88, 0x86, 0x68, 0x88, 0x86, 0x68]);
+        let mut texture = Texture::new(
+            Extent3d::new(4, 4, 1),
+            TextureDimension::D2,
+            red.clone(),
+            TextureFormat::Bc4RUnorm,
+        );
+        assert!(decompress_bc(&mut texture));
+        assert_eq!(texture.format, TextureFormat::R8Unorm);
+        assert_eq!(texture.data[..4], [70, 0, 60, 50]);
+
+        // signed endpoints 10 and -128, which is clamped to -127
+        let mut green = vec![10, 0x80];
+        green.extend_from_slice(&[0; 6]);
+        green[2] = 0x01;
+        let mut texture = Texture::new(
+            Extent3d::new(4, 4, 1),
+            TextureDimension::D2,
+            [red, green].concat(),
+            TextureFormat::Bc5RgSnorm,
+        );
+        assert!(decompress_bc(&mut texture));
+        assert_eq!(texture.format, TextureFormat::Rg8Snorm);
+        assert_eq!(texture.data[..4], [70, (-127i8) as u8, 0, 10]);
+
+        let mut texture = Texture::new(
+            Extent3d::new(4, 4, 1),
+            TextureDimension::D2,
+            vec![0; 16],
+            TextureFormat::Bc7RgbaUnorm,
+        );
+        assert!(!decompress_bc(&mut texture));
+        assert_eq!(texture.format, TextureFormat::Bc7RgbaUnorm);
+    }
+}
@@ -0,0 +1,392 @@
+use super::{
+    block_compression::decompress_bc, CompressedTextureSupport, Extent3d, FilterMode,
+    MipmapSettings, Texture, TextureDimension, TextureFormat, TextureViewDimension,
+};
+use anyhow::Result;
+use bevy_asset::{AssetLoader, LoadContext, LoadedAsset};
+use bevy_ecs::{FromResources, Resources};
+use bevy_utils::BoxedFuture;
+use thiserror::Error;
+
+const DDS_MAGIC: &[u8; 4] = b"DDS ";
+/// The size of the magic number and the header, after which the data or the DX10 header starts
+const HEADER_END: usize = 128;
+const DX10_HEADER_SIZE: usize = 20;
+
+const DDPF_ALPHAPIXELS: u32 = 0x1;
+const DDPF_FOURCC: u32 = 0x4;
+const DDPF_RGB: u32 = 0x40;
+const DDPF_LUMINANCE: u32 = 0x20000;
+const DDSCAPS2_CUBEMAP: u32 = 0x200;
+const DDSCAPS2_VOLUME: u32 = 0x200000;
+const D3D10_RESOURCE_DIMENSION_TEXTURE1D: u32 = 2;
+const D3D10_RESOURCE_DIMENSION_TEXTURE3D: u32 = 4;
+const D3D10_RESOURCE_MISC_TEXTURECUBE: u32 = 0x4;
+
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum DdsError {
+    #[error("the file isn't a DDS file")]
+    InvalidMagic,
+    #[error("the file ends before the end of its data")]
+    UnexpectedEnd,
+    #[error("the FourCC {0:?} isn't supported")]
+    UnsupportedFourCc(String),
+    #[error(
+        "uncompressed pixel formats other than 8 bit luminance and 32 bit RGBA aren't supported"
+    )]
+    UnsupportedPixelFormat,
+    #[error("ASTC compressed textures aren't supported (DXGI format {0})")]
+    UnsupportedCompression(u32),
+    #[error("the DXGI format {0} isn't supported")]
+    UnsupportedDxgiFormat(u32),
+    #[error("the GPU doesn't support the {0:?} format, and it can't be decompressed")]
+    FormatNotSupportedByGpu(TextureFormat),
+}
+
+/// Loads DDS textures, including array textures, cube maps, 3D textures and their mip levels. Compressed data is
+/// passed to the GPU as-is, or decompressed if the GPU doesn't support it, see [CompressedTextureSupport]. Files
+/// without mip levels get generated ones if [MipmapSettings] enables them.
+#[derive(Clone)]
+pub struct DdsTextureLoader {
+    compressed_texture_support: CompressedTextureSupport,
+    mipmap_settings: MipmapSettings,
+}
+
+impl FromResources for DdsTextureLoader {
+    fn from_resources(resources: &Resources) -> Self {
+        DdsTextureLoader {
+            compressed_texture_support: resources
+                .get::<CompressedTextureSupport>()
+                .map(|support| support.clone())
+                .unwrap_or_default(),
+            mipmap_settings: resources
+                .get::<MipmapSettings>()
+                .map(|settings| *settings)
+                .unwrap_or_default(),
+        }
+    }
+}
+
+impl AssetLoader for DdsTextureLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let mut texture = dds_to_texture(bytes, &self.compressed_texture_support)?;
+            self.mipmap_settings.apply(&mut texture);
+            load_context.set_default_asset(LoadedAsset::new(texture));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["dds"]
+    }
+}
+
+/// Reads a DDS file. BC1 to BC5 compressed files are decompressed if the GPU doesn't support them.
+pub(crate) fn dds_to_texture(
+    bytes: &[u8],
+    compressed_texture_support: &CompressedTextureSupport,
+) -> Result<Texture, DdsError> {
+    if bytes.get(..DDS_MAGIC.len()) != Some(&DDS_MAGIC[..]) {
+        return Err(DdsError::InvalidMagic);
+    }
+    let height = read_u32(bytes, 12)?;
+    let width = read_u32(bytes, 16)?;
+    let depth = read_u32(bytes, 24)?;
+    let level_count = read_u32(bytes, 28)?.max(1);
+    let pixel_format_flags = read_u32(bytes, 80)?;
+    let four_cc = bytes.get(84..88).ok_or(DdsError::UnexpectedEnd)?;
+    let caps2 = read_u32(bytes, 112)?;
+
+    let mut data_offset = HEADER_END;
+    let mut is_cube = caps2 & DDSCAPS2_CUBEMAP != 0;
+    let mut is_volume = caps2 & DDSCAPS2_VOLUME != 0;
+    let mut is_1d = false;
+    let mut array_size = 1;
+    let format = if pixel_format_flags & DDPF_FOURCC != 0 {
+        match four_cc {
+            b"DXT1" => TextureFormat::Bc1RgbaUnorm,
+            b"DXT3" => TextureFormat::Bc2RgbaUnorm,
+            b"DXT5" => TextureFormat::Bc3RgbaUnorm,
+            b"ATI1" | b"BC4U" => TextureFormat::Bc4RUnorm,
+            b"BC4S" => TextureFormat::Bc4RSnorm,
+            b"ATI2" | b"BC5U" => TextureFormat::Bc5RgUnorm,
+            b"BC5S" => TextureFormat::Bc5RgSnorm,
+            // D3DFMT values
+            [113, 0, 0, 0] => TextureFormat::Rgba16Float,
+            [114, 0, 0, 0] => TextureFormat::R32Float,
+            [115, 0, 0, 0] => TextureFormat::Rg32Float,
+            [116, 0, 0, 0] => TextureFormat::Rgba32Float,
+            b"DX10" => {
+                data_offset += DX10_HEADER_SIZE;
+                let resource_dimension = read_u32(bytes, HEADER_END + 4)?;
+                is_1d = resource_dimension == D3D10_RESOURCE_DIMENSION_TEXTURE1D;
+                is_volume = resource_dimension == D3D10_RESOURCE_DIMENSION_TEXTURE3D;
+                is_cube = read_u32(bytes, HEADER_END + 8)? & D3D10_RESOURCE_MISC_TEXTURECUBE != 0;
+                array_size = read_u32(bytes, HEADER_END + 12)?.max(1);
+                dxgi_format_to_texture_format(read_u32(bytes, HEADER_END)?)?
+            }
+            four_cc => {
+                return Err(DdsError::UnsupportedFourCc(
+                    String::from_utf8_lossy(four_cc).into_owned(),
+                ))
+            }
+        }
+    } else {
+        let bit_count = read_u32(bytes, 88)?;
+        let red_mask = read_u32(bytes, 92)?;
+        let blue_mask = read_u32(bytes, 100)?;
+        match (pixel_format_flags & !DDPF_ALPHAPIXELS, bit_count) {
+            (DDPF_LUMINANCE, 8) => TextureFormat::R8Unorm,
+            (DDPF_RGB, 32) if red_mask == 0xff && blue_mask == 0xff0000 => {
+                TextureFormat::Rgba8Unorm
+            }
+            (DDPF_RGB, 32) if red_mask == 0xff0000 && blue_mask == 0xff => {
+                TextureFormat::Bgra8Unorm
+            }
+            _ => return Err(DdsError::UnsupportedPixelFormat),
+        }
+    };
+    let layers = array_size * if is_cube { 6 } else { 1 };
+    let (dimension, view_dimension, size) = if is_volume {
+        (
+            TextureDimension::D3,
+            TextureViewDimension::D3,
+            Extent3d::new(width, height, depth.max(1)),
+        )
+    } else if is_1d {
+        (
+            TextureDimension::D1,
+            TextureViewDimension::D1,
+            Extent3d::new(width, 1, 1),
+        )
+    } else {
+        let view_dimension = match (is_cube, array_size) {
+            (true, 1) => TextureViewDimension::Cube,
+            (true, _) => TextureViewDimension::CubeArray,
+            (false, 1) => TextureViewDimension::D2,
+            (false, _) => TextureViewDimension::D2Array,
+        };
+        (
+            TextureDimension::D2,
+            view_dimension,
+            Extent3d::new(width, height, layers),
+        )
+    };
+
+    let mut texture = Texture {
+        size,
+        dimension,
+        view_dimension,
+        format,
+        ..Default::default()
+    };
+    // DDS files store each layer's mip chain in turn, while textures store each level's layers in turn
+    let mut levels = vec![Vec::new(); level_count as usize];
+    let mut offset = data_offset;
+    for _ in 0..layers {
+        for (level, data) in levels.iter_mut().enumerate() {
+            let level_size = texture.mip_level_size(level as u32);
+            let image_size = format.image_size(Extent3d {
+                depth: level_size.depth / layers,
+                ..level_size
+            });
+            data.extend_from_slice(
+                bytes
+                    .get(offset..offset + image_size)
+                    .ok_or(DdsError::UnexpectedEnd)?,
+            );
+            offset += image_size;
+        }
+    }
+    let mut levels = levels.into_iter();
+    texture.data = levels.next().unwrap_or_default();
+    texture.mipmaps = levels.collect();
+    if level_count > 1 {
+        texture.sampler.mipmap_filter = FilterMode::Linear;
+    }
+
+    if !compressed_texture_support.supports(format) && !decompress_bc(&mut texture) {
+        return Err(DdsError::FormatNotSupportedByGpu(format));
+    }
+    Ok(texture)
+}
+
+fn dxgi_format_to_texture_format(dxgi_format: u32) -> Result<TextureFormat, DdsError> {
+    Ok(match dxgi_format {
+        2 => TextureFormat::Rgba32Float,
+        10 => TextureFormat::Rgba16Float,
+        16 => TextureFormat::Rg32Float,
+        28 => TextureFormat::Rgba8Unorm,
+        29 => TextureFormat::Rgba8UnormSrgb,
+        41 => TextureFormat::R32Float,
+        49 => TextureFormat::Rg8Unorm,
+        61 => TextureFormat::R8Unorm,
+        71 => TextureFormat::Bc1RgbaUnorm,
+        72 => TextureFormat::Bc1RgbaUnormSrgb,
+        74 => TextureFormat::Bc2RgbaUnorm,
+        75 => TextureFormat::Bc2RgbaUnormSrgb,
+        77 => TextureFormat::Bc3RgbaUnorm,
+        78 => TextureFormat::Bc3RgbaUnormSrgb,
+        80 => TextureFormat::Bc4RUnorm,
+        81 => TextureFormat::Bc4RSnorm,
+        83 => TextureFormat::Bc5RgUnorm,
+        84 => TextureFormat::Bc5RgSnorm,
+        87 => TextureFormat::Bgra8Unorm,
+        91 => TextureFormat::Bgra8UnormSrgb,
+        95 => TextureFormat::Bc6hRgbUfloat,
+        96 => TextureFormat::Bc6hRgbSfloat,
+        98 => TextureFormat::Bc7RgbaUnorm,
+        99 => TextureFormat::Bc7RgbaUnormSrgb,
+        // ASTC
+        133..=187 => return Err(DdsError::UnsupportedCompression(dxgi_format)),
+        _ => return Err(DdsError::UnsupportedDxgiFormat(dxgi_format)),
+    })
+}
+
+fn read_u32(bytes: &[u8], offset: usize) -> Result<u32, DdsError> {
+    let mut value = [0; 4];
+    value.copy_from_slice(
+        bytes
+            .get(offset..offset + 4)
+            .ok_or(DdsError::UnexpectedEnd)?,
+    );
+    Ok(u32::from_le_bytes(value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dds_file(
+        size: Extent3d,
+        level_count: u32,
+        four_cc: &[u8; 4],
+        caps2: u32,
+        dx10_header: Option<[u32; 5]>,
+        data: &[u8],
+    ) -> Vec<u8> {
+        let mut header = [0u32; 31];
+        header[0] = 124;
+        header[2] = size.height;
+        header[3] = size.width;
+        header[5] = size.depth;
+        header[6] = level_count;
+        header[18] = 32;
+        header[19] = DDPF_FOURCC;
+        header[20] = u32::from_le_bytes(*four_cc);
+        header[27] = caps2;
+
+        let mut bytes = DDS_MAGIC.to_vec();
+        for value in header.iter().chain(dx10_header.iter().flatten()) {
+            bytes.extend_from_slice(&value.to_le_bytes());
+        }
+        bytes.extend_from_slice(data);
+        bytes
+    }
+
+    #[test]
+    fn load_dds() {
+        let support = CompressedTextureSupport::default();
+
+        // a 4x4 BC1 cube map with 3 mip levels. Each face's levels are 8 bytes: one block each.
+        let data = (0..6 * 3)
+            .flat_map(|image| vec![image as u8; 8])
+            .collect::<Vec<_>>();
+        let cube = dds_to_texture(
+            &dds_file(
+                Extent3d::new(4, 4, 0),
+                3,
+                b"DXT1",
+                DDSCAPS2_CUBEMAP,
+                None,
+                &data,
+            ),
+            &support,
+        )
+        .unwrap();
+        assert_eq!(cube.size, Extent3d::new(4, 4, 6));
+        assert_eq!(cube.view_dimension, TextureViewDimension::Cube);
+        assert_eq!(cube.format, TextureFormat::Bc1RgbaUnorm);
+        assert_eq!(cube.mip_level_count(), 3);
+        assert_eq!(cube.sampler.mipmap_filter, FilterMode::Linear);
+        // the levels are gathered from each face
+        assert_eq!(cube.data[..16], [[0; 8], [3; 8]].concat()[..]);
+        assert_eq!(cube.mipmaps[0][..16], [[1; 8], [4; 8]].concat()[..]);
+
+        // a DX10 array of two 2x2 sRGB layers, without mip levels
+        let array = dds_to_texture(
+            &dds_file(
+                Extent3d::new(2, 2, 0),
+                0,
+                b"DX10",
+                0,
+                Some([29, 3, 0, 2, 0]),
+                &[255; 32],
+            ),
+            &support,
+        )
+        .unwrap();
+        assert_eq!(array.size, Extent3d::new(2, 2, 2));
+        assert_eq!(array.view_dimension, TextureViewDimension::D2Array);
+        assert_eq!(array.format, TextureFormat::Rgba8UnormSrgb);
+        // mip levels are only generated by the loader, if MipmapSettings enables them
+        assert_eq!(array.mip_level_count(), 1);
+
+        let file = dds_file(Extent3d::new(4, 4, 0), 1, b"DXT5", 0, None, &[0; 16]);
+        assert!(dds_to_texture(&file, &support).is_ok());
+        assert_eq!(
+            dds_to_texture(&file[..file.len() - 1], &support).err(),
+            Some(DdsError::UnexpectedEnd)
+        );
+        // without BC support, BC1 to BC5 are decompressed and BC6H and BC7 are errors
+        support.set_bc(false);
+        let decompressed = dds_to_texture(&file, &support).unwrap();
+        assert_eq!(decompressed.format, TextureFormat::Rgba8Unorm);
+        assert_eq!(decompressed.data.len(), 4 * 4 * 4);
+        assert_eq!(
+            dds_to_texture(
+                &dds_file(
+                    Extent3d::new(4, 4, 0),
+                    1,
+                    b"DX10",
+                    0,
+                    Some([98, 3, 0, 1, 0]),
+                    &[0; 16]
+                ),
+                &support
+            )
+            .err(),
+            Some(DdsError::FormatNotSupportedByGpu(
+                TextureFormat::Bc7RgbaUnorm
+            ))
+        );
+        assert_eq!(
+            dds_to_texture(
+                &dds_file(
+                    Extent3d::new(4, 4, 0),
+                    1,
+                    b"DX10",
+                    0,
+                    Some([134, 3, 0, 1, 0]),
+                    &[]
+                ),
+                &support
+            )
+            .err(),
+            Some(DdsError::UnsupportedCompression(134))
+        );
+        assert_eq!(
+            dds_to_texture(
+                &dds_file(Extent3d::new(4, 4, 0), 1, b"ETC2", 0, None, &[]),
+                &support
+            )
+            .err(),
+            Some(DdsError::UnsupportedFourCc("ETC2".to_string()))
+        );
+    }
+}
@@ -1,11 +1,25 @@
-use super::{Extent3d, Texture, TextureDimension, TextureFormat};
+use super::{Extent3d, MipmapSettings, Texture, TextureDimension, TextureFormat};
 use anyhow::Result;
 use bevy_asset::{AssetLoader, LoadContext, LoadedAsset};
+use bevy_ecs::{FromResources, Resources};
 use bevy_utils::BoxedFuture;
 
-/// Loads HDR textures as Texture assets
-#[derive(Clone, Default)]
-pub struct HdrTextureLoader;
+/// Loads HDR textures as Texture assets. Mip levels are generated if [MipmapSettings] enables them.
+#[derive(Clone)]
+pub struct HdrTextureLoader {
+    mipmap_settings: MipmapSettings,
+}
+
+impl FromResources for HdrTextureLoader {
+    fn from_resources(resources: &Resources) -> Self {
+        HdrTextureLoader {
+            mipmap_settings: resources
+                .get::<MipmapSettings>()
+                .map(|settings| *settings)
+                .unwrap_or_default(),
+        }
+    }
+}
 
 impl AssetLoader for HdrTextureLoader {
     fn load<'a>(
@@ -14,34 +28,8 @@ impl AssetLoader for HdrTextureLoader {
         load_context: &'a mut LoadContext,
     ) -> BoxedFuture<'a, Result<()>> {
         Box::pin(async move {
-            let format = TextureFormat::Rgba32Float;
-            debug_assert_eq!(
-                format.pixel_size(),
-                4 * 4,
-                "Format should have 32bit x 4 size"
-            );
-
-            let decoder = image::hdr::HdrDecoder::new(bytes)?;
-            let info = decoder.metadata();
-            let rgb_data = decoder.read_image_hdr()?;
-            let mut rgba_data = Vec::with_capacity(rgb_data.len() * format.pixel_size());
-
-            for rgb in rgb_data {
-                let alpha = 1.0f32;
-
-                rgba_data.extend_from_slice(&rgb.0[0].to_ne_bytes());
-                rgba_data.extend_from_slice(&rgb.0[1].to_ne_bytes());
-                rgba_data.extend_from_slice(&rgb.0[2].to_ne_bytes());
-                rgba_data.extend_from_slice(&alpha.to_ne_bytes());
-            }
-
-            let texture = Texture::new(
-                Extent3d::new(info.width, info.height, 1),
-                TextureDimension::D2,
-                rgba_data,
-                format,
-            );
-
+            let mut texture = hdr_to_texture(bytes)?;
+            self.mipmap_settings.apply(&mut texture);
             load_context.set_default_asset(LoadedAsset::new(texture));
             Ok(())
         })
@@ -51,3 +39,34 @@ impl AssetLoader for HdrTextureLoader {
         &["hdr"]
     }
 }
+
+/// Decodes an HDR image to an [TextureFormat::Rgba32Float] texture
+pub(crate) fn hdr_to_texture(bytes: &[u8]) -> Result<Texture> {
+    let format = TextureFormat::Rgba32Float;
+    debug_assert_eq!(
+        format.pixel_size(),
+        4 * 4,
+        "Format should have 32bit x 4 size"
+    );
+
+    let decoder = image::hdr::HdrDecoder::new(bytes)?;
+    let info = decoder.metadata();
+    let rgb_data = decoder.read_image_hdr()?;
+    let mut rgba_data = Vec::with_capacity(rgb_data.len() * format.pixel_size());
+
+    for rgb in rgb_data {
+        let alpha = 1.0f32;
+
+        rgba_data.extend_from_slice(&rgb.0[0].to_ne_bytes());
+        rgba_data.extend_from_slice(&rgb.0[1].to_ne_bytes());
+        rgba_data.extend_from_slice(&rgb.0[2].to_ne_bytes());
+        rgba_data.extend_from_slice(&alpha.to_ne_bytes());
+    }
+
+    Ok(Texture::new(
+        Extent3d::new(info.width, info.height, 1),
+        TextureDimension::D2,
+        rgba_data,
+        format,
+    ))
+}
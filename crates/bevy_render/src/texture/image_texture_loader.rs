@@ -1,15 +1,29 @@
-use super::{Extent3d, Texture, TextureDimension, TextureFormat};
+use super::{Extent3d, MipmapSettings, Texture, TextureDimension, TextureFormat};
 use anyhow::Result;
 use bevy_asset::{AssetLoader, LoadContext, LoadedAsset};
+use bevy_ecs::{FromResources, Resources};
 use bevy_utils::BoxedFuture;
 
 /// Loader for images that can be read by the `image` crate.
 ///
-/// Reads only PNG images for now.
-#[derive(Clone, Default)]
-pub struct ImageTextureLoader;
+/// Reads only PNG images for now. Mip levels are generated if [MipmapSettings] enables them.
+#[derive(Clone)]
+pub struct ImageTextureLoader {
+    mipmap_settings: MipmapSettings,
+}
+
+impl FromResources for ImageTextureLoader {
+    fn from_resources(resources: &Resources) -> Self {
+        ImageTextureLoader {
+            mipmap_settings: resources
+                .get::<MipmapSettings>()
+                .map(|settings| *settings)
+                .unwrap_or_default(),
+        }
+    }
+}
 
-const FILE_EXTENSIONS: &[&str] = &["png", "dds", "tga", "jpg", "jpeg"];
+const FILE_EXTENSIONS: &[&str] = &["png", "tga", "jpg", "jpeg"];
 
 impl AssetLoader for ImageTextureLoader {
     fn load<'a>(
@@ -18,8 +32,6 @@ impl AssetLoader for ImageTextureLoader {
         load_context: &'a mut LoadContext,
     ) -> BoxedFuture<'a, Result<()>> {
         Box::pin(async move {
-            use bevy_core::AsBytes;
-
             // Find the image type we expect. A file with the extension "png" should
             // probably load as a PNG.
 
@@ -42,118 +54,8 @@ impl AssetLoader for ImageTextureLoader {
             // cases.
 
             let dyn_img = image::load_from_memory_with_format(bytes, img_format)?;
-
-            let width;
-            let height;
-
-            let data: Vec<u8>;
-            let format: TextureFormat;
-
-            match dyn_img {
-                image::DynamicImage::ImageLuma8(i) => {
-                    width = i.width();
-                    height = i.height();
-                    format = TextureFormat::R8Unorm;
-
-                    data = i.into_raw();
-                }
-                image::DynamicImage::ImageLumaA8(i) => {
-                    width = i.width();
-                    height = i.height();
-                    format = TextureFormat::Rg8Unorm;
-
-                    data = i.into_raw();
-                }
-                image::DynamicImage::ImageRgb8(i) => {
-                    let i = image::DynamicImage::ImageRgb8(i).into_rgba8();
-                    width = i.width();
-                    height = i.height();
-                    format = TextureFormat::Rgba8UnormSrgb;
-
-                    data = i.into_raw();
-                }
-                image::DynamicImage::ImageRgba8(i) => {
-                    width = i.width();
-                    height = i.height();
-                    format = TextureFormat::Rgba8UnormSrgb;
-
-                    data = i.into_raw();
-                }
-                image::DynamicImage::ImageBgr8(i) => {
-                    let i = image::DynamicImage::ImageBgr8(i).into_bgra8();
-
-                    width = i.width();
-                    height = i.height();
-                    format = TextureFormat::Bgra8UnormSrgb;
-
-                    data = i.into_raw();
-                }
-                image::DynamicImage::ImageBgra8(i) => {
-                    width = i.width();
-                    height = i.height();
-                    format = TextureFormat::Bgra8UnormSrgb;
-
-                    data = i.into_raw();
-                }
-                image::DynamicImage::ImageLuma16(i) => {
-                    width = i.width();
-                    height = i.height();
-                    format = TextureFormat::R16Uint;
-
-                    let raw_data = i.into_raw();
-
-                    data = raw_data.as_slice().as_bytes().to_owned();
-                }
-                image::DynamicImage::ImageLumaA16(i) => {
-                    width = i.width();
-                    height = i.height();
-                    format = TextureFormat::Rg16Uint;
-
-                    let raw_data = i.into_raw();
-
-                    data = raw_data.as_slice().as_bytes().to_owned();
-                }
-
-                image::DynamicImage::ImageRgb16(image) => {
-                    width = image.width();
-                    height = image.height();
-                    format = TextureFormat::Rgba16Uint;
-
-                    let mut local_data =
-                        Vec::with_capacity(width as usize * height as usize * format.pixel_size());
-
-                    for pixel in image.into_raw().chunks_exact(3) {
-                        // TODO unsafe_get in release builds?
-                        let r = pixel[0];
-                        let g = pixel[1];
-                        let b = pixel[2];
-                        let a = u16::max_value();
-
-                        local_data.extend_from_slice(&r.to_ne_bytes());
-                        local_data.extend_from_slice(&g.to_ne_bytes());
-                        local_data.extend_from_slice(&b.to_ne_bytes());
-                        local_data.extend_from_slice(&a.to_ne_bytes());
-                    }
-
-                    data = local_data;
-                }
-                image::DynamicImage::ImageRgba16(i) => {
-                    width = i.width();
-                    height = i.height();
-                    format = TextureFormat::Rgba16Uint;
-
-                    let raw_data = i.into_raw();
-
-                    data = raw_data.as_slice().as_bytes().to_owned();
-                }
-            }
-
-            let texture = Texture::new(
-                Extent3d::new(width, height, 1),
-                TextureDimension::D2,
-                data,
-                format,
-            );
+            let mut texture = image_to_texture(dyn_img);
+            self.mipmap_settings.apply(&mut texture);
             load_context.set_default_asset(LoadedAsset::new(texture));
             Ok(())
         })
@@ -163,3 +65,120 @@ impl AssetLoader for ImageTextureLoader {
         FILE_EXTENSIONS
     }
 }
+
+/// Converts a decoded image to a 2D [Texture], adding an alpha channel to RGB images
+pub(crate) fn image_to_texture(dyn_img: image::DynamicImage) -> Texture {
+    use bevy_core::AsBytes;
+
+    let width;
+    let height;
+
+    let data: Vec<u8>;
+    let format: TextureFormat;
+
+    match dyn_img {
+        image::DynamicImage::ImageLuma8(i) => {
+            width = i.width();
+            height = i.height();
+            format = TextureFormat::R8Unorm;
+
+            data = i.into_raw();
+        }
+        image::DynamicImage::ImageLumaA8(i) => {
+            width = i.width();
+            height = i.height();
+            format = TextureFormat::Rg8Unorm;
+
+            data = i.into_raw();
+        }
+        image::DynamicImage::ImageRgb8(i) => {
+            let i = image::DynamicImage::ImageRgb8(i).into_rgba8();
+            width = i.width();
+            height = i.height();
+            format = TextureFormat::Rgba8UnormSrgb;
+
+            data = i.into_raw();
+        }
+        image::DynamicImage::ImageRgba8(i) => {
+            width = i.width();
+            height = i.height();
+            format = TextureFormat::Rgba8UnormSrgb;
+
+            data = i.into_raw();
+        }
+        image::DynamicImage::ImageBgr8(i) => {
+            let i = image::DynamicImage::ImageBgr8(i).into_bgra8();
+
+            width = i.width();
+            height = i.height();
+            format = TextureFormat::Bgra8UnormSrgb;
+
+            data = i.into_raw();
+        }
+        image::DynamicImage::ImageBgra8(i) => {
+            width = i.width();
+            height = i.height();
+            format = TextureFormat::Bgra8UnormSrgb;
+
+            data = i.into_raw();
+        }
+        image::DynamicImage::ImageLuma16(i) => {
+            width = i.width();
+            height = i.height();
+            format = TextureFormat::R16Uint;
+
+            let raw_data = i.into_raw();
+
+            data = raw_data.as_slice().as_bytes().to_owned();
+        }
+        image::DynamicImage::ImageLumaA16(i) => {
+            width = i.width();
+            height = i.height();
+            format = TextureFormat::Rg16Uint;
+
+            let raw_data = i.into_raw();
+
+            data = raw_data.as_slice().as_bytes().to_owned();
+        }
+
+        image::DynamicImage::ImageRgb16(image) => {
+            width = image.width();
+            height = image.height();
+            format = TextureFormat::Rgba16Uint;
+
+            let mut local_data =
+                Vec::with_capacity(width as usize * height as usize * format.pixel_size());
+
+            for pixel in image.into_raw().chunks_exact(3) {
+                // TODO unsafe_get in release builds?
+                let r = pixel[0];
+                let g = pixel[1];
+                let b = pixel[2];
+                let a = u16::max_value();
+
+                local_data.extend_from_slice(&r.to_ne_bytes());
+                local_data.extend_from_slice(&g.to_ne_bytes());
+                local_data.extend_from_slice(&b.to_ne_bytes());
+                local_data.extend_from_slice(&a.to_ne_bytes());
+            }
+
+            data = local_data;
+        }
+        image::DynamicImage::ImageRgba16(i) => {
+            width = i.width();
+            height = i.height();
+            format = TextureFormat::Rgba16Uint;
+
+            let raw_data = i.into_raw();
+
+            data = raw_data.as_slice().as_bytes().to_owned();
+        }
+    }
+
+    Texture::new(
+        Extent3d::new(width, height, 1),
+        TextureDimension::D2,
+        data,
+        format,
+    )
+}
@@ -0,0 +1,338 @@
+use super::{
+    block_compression::decompress_bc, CompressedTextureSupport, Extent3d, FilterMode,
+    MipmapSettings, Texture, TextureDimension, TextureFormat, TextureViewDimension,
+};
+use anyhow::Result;
+use bevy_asset::{AssetLoader, LoadContext, LoadedAsset};
+use bevy_ecs::{FromResources, Resources};
+use bevy_utils::BoxedFuture;
+use std::convert::TryFrom;
+use thiserror::Error;
+
+const KTX2_IDENTIFIER: [u8; 12] = [
+    0xAB, b'K', b'T', b'X', b' ', b'2', b'0', 0xBB, b'\r', b'\n', 0x1A, b'\n',
+];
+/// The size of the identifier, the header and the index, after which the level index starts
+const LEVEL_INDEX_OFFSET: usize = 80;
+
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum Ktx2Error {
+    #[error("the file isn't a KTX2 file")]
+    InvalidIdentifier,
+    #[error("the file ends before the end of its data")]
+    UnexpectedEnd,
+    #[error("supercompressed KTX2 files aren't supported")]
+    Supercompressed,
+    #[error("textures have 1 face, or 6 for cube maps, not {0}")]
+    InvalidFaceCount(u32),
+    #[error("ETC2 and ASTC compressed textures aren't supported (Vulkan format {0})")]
+    UnsupportedCompression(u32),
+    #[error("the Vulkan format {0} isn't supported")]
+    UnsupportedFormat(u32),
+    #[error("the GPU doesn't support the {0:?} format, and it can't be decompressed")]
+    FormatNotSupportedByGpu(TextureFormat),
+    #[error("mip level {level} has {len} bytes, {expected} bytes were expected")]
+    InvalidLevelLength {
+        level: u32,
+        len: usize,
+        expected: usize,
+    },
+}
+
+/// Loads KTX2 textures, including array textures, cube maps, 3D textures and their mip levels. Compressed data is
+/// passed to the GPU as-is, or decompressed if the GPU doesn't support it, see [CompressedTextureSupport]. Files
+/// without mip levels get generated ones if [MipmapSettings] enables them.
+#[derive(Clone)]
+pub struct Ktx2TextureLoader {
+    compressed_texture_support: CompressedTextureSupport,
+    mipmap_settings: MipmapSettings,
+}
+
+impl FromResources for Ktx2TextureLoader {
+    fn from_resources(resources: &Resources) -> Self {
+        Ktx2TextureLoader {
+            compressed_texture_support: resources
+                .get::<CompressedTextureSupport>()
+                .map(|support| support.clone())
+                .unwrap_or_default(),
+            mipmap_settings: resources
+                .get::<MipmapSettings>()
+                .map(|settings| *settings)
+                .unwrap_or_default(),
+        }
+    }
+}
+
+impl AssetLoader for Ktx2TextureLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let mut texture = ktx2_to_texture(bytes, &self.compressed_texture_support)?;
+            self.mipmap_settings.apply(&mut texture);
+            load_context.set_default_asset(LoadedAsset::new(texture));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["ktx2"]
+    }
+}
+
+/// Reads a KTX2 file. BC1 to BC5 compressed files are decompressed if the GPU doesn't support them.
+pub(crate) fn ktx2_to_texture(
+    bytes: &[u8],
+    compressed_texture_support: &CompressedTextureSupport,
+) -> Result<Texture, Ktx2Error> {
+    if bytes.get(..KTX2_IDENTIFIER.len()) != Some(&KTX2_IDENTIFIER[..]) {
+        return Err(Ktx2Error::InvalidIdentifier);
+    }
+    let header = |index: usize| read_u32(bytes, KTX2_IDENTIFIER.len() + index * 4);
+    let vk_format = header(0)?;
+    let width = header(2)?;
+    let height = header(3)?;
+    let depth = header(4)?;
+    let layer_count = header(5)?;
+    let face_count = header(6)?;
+    let level_count = header(7)?;
+    if header(8)? != 0 {
+        return Err(Ktx2Error::Supercompressed);
+    }
+    if face_count != 1 && face_count != 6 {
+        return Err(Ktx2Error::InvalidFaceCount(face_count));
+    }
+
+    let format = vk_format_to_texture_format(vk_format)?;
+
+    // a 0 height or depth means the texture has fewer dimensions, and a 0 layer count that it isn't an array
+    let layers = layer_count.max(1) * face_count;
+    let (dimension, view_dimension, size) = if depth > 0 {
+        (
+            TextureDimension::D3,
+            TextureViewDimension::D3,
+            Extent3d::new(width, height, depth),
+        )
+    } else if height == 0 {
+        (
+            TextureDimension::D1,
+            TextureViewDimension::D1,
+            Extent3d::new(width, 1, 1),
+        )
+    } else {
+        let view_dimension = match (face_count, layer_count) {
+            (6, 0) => TextureViewDimension::Cube,
+            (6, _) => TextureViewDimension::CubeArray,
+            (_, 0) => TextureViewDimension::D2,
+            _ => TextureViewDimension::D2Array,
+        };
+        (
+            TextureDimension::D2,
+            view_dimension,
+            Extent3d::new(width, height, layers),
+        )
+    };
+
+    let mut texture = Texture {
+        size,
+        dimension,
+        view_dimension,
+        format,
+        ..Default::default()
+    };
+    // each level holds every layer, face and depth slice, which is the order textures store them in
+    for level in 0..level_count.max(1) {
+        let level_index = LEVEL_INDEX_OFFSET + level as usize * 24;
+        let offset = read_usize(bytes, level_index)?;
+        let len = read_usize(bytes, level_index + 8)?;
+        let expected = format.image_size(texture.mip_level_size(level));
+        if len != expected {
+            return Err(Ktx2Error::InvalidLevelLength {
+                level,
+                len,
+                expected,
+            });
+        }
+        let end = offset.checked_add(len).ok_or(Ktx2Error::UnexpectedEnd)?;
+        let data = bytes
+            .get(offset..end)
+            .ok_or(Ktx2Error::UnexpectedEnd)?
+            .to_vec();
+        if level == 0 {
+            texture.data = data;
+        } else {
+            texture.mipmaps.push(data);
+        }
+    }
+
+    if level_count > 1 {
+        texture.sampler.mipmap_filter = FilterMode::Linear;
+    }
+
+    if !compressed_texture_support.supports(format) && !decompress_bc(&mut texture) {
+        return Err(Ktx2Error::FormatNotSupportedByGpu(format));
+    }
+    Ok(texture)
+}
+
+fn vk_format_to_texture_format(vk_format: u32) -> Result<TextureFormat, Ktx2Error> {
+    Ok(match vk_format {
+        9 => TextureFormat::R8Unorm,
+        16 => TextureFormat::Rg8Unorm,
+        37 => TextureFormat::Rgba8Unorm,
+        43 => TextureFormat::Rgba8UnormSrgb,
+        44 => TextureFormat::Bgra8Unorm,
+        50 => TextureFormat::Bgra8UnormSrgb,
+        97 => TextureFormat::Rgba16Float,
+        100 => TextureFormat::R32Float,
+        103 => TextureFormat::Rg32Float,
+        109 => TextureFormat::Rgba32Float,
+        // the RGB and RGBA variants of BC1 share a block layout
+        131 | 133 => TextureFormat::Bc1RgbaUnorm,
+        132 | 134 => TextureFormat::Bc1RgbaUnormSrgb,
+        135 => TextureFormat::Bc2RgbaUnorm,
+        136 => TextureFormat::Bc2RgbaUnormSrgb,
+        137 => TextureFormat::Bc3RgbaUnorm,
+        138 => TextureFormat::Bc3RgbaUnormSrgb,
+        139 => TextureFormat::Bc4RUnorm,
+        140 => TextureFormat::Bc4RSnorm,
+        141 => TextureFormat::Bc5RgUnorm,
+        142 => TextureFormat::Bc5RgSnorm,
+        143 => TextureFormat::Bc6hRgbUfloat,
+        144 => TextureFormat::Bc6hRgbSfloat,
+        145 => TextureFormat::Bc7RgbaUnorm,
+        146 => TextureFormat::Bc7RgbaUnormSrgb,
+        // ETC2, EAC and ASTC
+        147..=184 => return Err(Ktx2Error::UnsupportedCompression(vk_format)),
+        _ => return Err(Ktx2Error::UnsupportedFormat(vk_format)),
+    })
+}
+
+fn read_u32(bytes: &[u8], offset: usize) -> Result<u32, Ktx2Error> {
+    let mut value = [0; 4];
+    value.copy_from_slice(
+        bytes
+            .get(offset..offset + 4)
+            .ok_or(Ktx2Error::UnexpectedEnd)?,
+    );
+    Ok(u32::from_le_bytes(value))
+}
+
+/// Reads a u64 offset or length, which can't point past the end of the file if it doesn't fit in a usize
+fn read_usize(bytes: &[u8], offset: usize) -> Result<usize, Ktx2Error> {
+    let mut value = [0; 8];
+    value.copy_from_slice(
+        bytes
+            .get(offset..offset + 8)
+            .ok_or(Ktx2Error::UnexpectedEnd)?,
+    );
+    usize::try_from(u64::from_le_bytes(value)).map_err(|_| Ktx2Error::UnexpectedEnd)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Builds a KTX2 file without a data format descriptor, which the loader doesn't read
+    fn ktx2_file(header: [u32; 9], levels: &[Vec<u8>]) -> Vec<u8> {
+        let mut bytes = KTX2_IDENTIFIER.to_vec();
+        for value in header.iter() {
+            bytes.extend_from_slice(&value.to_le_bytes());
+        }
+        bytes.extend_from_slice(&[0; 32]);
+        let mut offset = LEVEL_INDEX_OFFSET + levels.len() * 24;
+        for level in levels.iter() {
+            bytes.extend_from_slice(&(offset as u64).to_le_bytes());
+            bytes.extend_from_slice(&(level.len() as u64).to_le_bytes());
+            bytes.extend_from_slice(&(level.len() as u64).to_le_bytes());
+            offset += level.len();
+        }
+        for level in levels.iter() {
+            bytes.extend_from_slice(level);
+        }
+        bytes
+    }
+
+    #[test]
+    fn load_ktx2() {
+        let support = CompressedTextureSupport::default();
+
+        // a 2x2 cube map with two mip levels
+        let levels = vec![(0..96).collect::<Vec<u8>>(), (0..24).collect()];
+        let cube =
+            ktx2_to_texture(&ktx2_file([43, 1, 2, 2, 0, 0, 6, 2, 0], &levels), &support).unwrap();
+        assert_eq!(cube.size, Extent3d::new(2, 2, 6));
+        assert_eq!(cube.view_dimension, TextureViewDimension::Cube);
+        assert_eq!(cube.format, TextureFormat::Rgba8UnormSrgb);
+        assert_eq!(cube.data, levels[0]);
+        assert_eq!(cube.mipmaps, vec![levels[1].clone()]);
+        assert_eq!(cube.sampler.mipmap_filter, FilterMode::Linear);
+
+        // BC1 levels are made of 8 byte blocks, even when the level is smaller than a block
+        let levels = vec![vec![1; 32], vec![2; 8], vec![3; 8], vec![4; 8]];
+        let bc1 =
+            ktx2_to_texture(&ktx2_file([131, 1, 8, 8, 0, 0, 1, 4, 0], &levels), &support).unwrap();
+        assert_eq!(bc1.format, TextureFormat::Bc1RgbaUnorm);
+        assert_eq!(bc1.mip_level_count(), 4);
+
+        // a level count of 0 means the file has one level, mip levels are only generated by the loader
+        let single = ktx2_to_texture(
+            &ktx2_file([9, 1, 4, 4, 0, 0, 1, 0, 0], &[vec![0; 16]]),
+            &support,
+        )
+        .unwrap();
+        assert_eq!(single.mip_level_count(), 1);
+
+        // without BC support, BC1 to BC5 are decompressed and BC6H and BC7 are errors
+        support.set_bc(false);
+        let decompressed =
+            ktx2_to_texture(&ktx2_file([131, 1, 8, 8, 0, 0, 1, 4, 0], &levels), &support).unwrap();
+        assert_eq!(decompressed.format, TextureFormat::Rgba8Unorm);
+        assert_eq!(decompressed.mip_level_count(), 4);
+        assert_eq!(decompressed.mipmaps[2].len(), 4);
+        assert_eq!(
+            ktx2_to_texture(
+                &ktx2_file([145, 1, 4, 4, 0, 0, 1, 1, 0], &[vec![0; 16]]),
+                &support
+            )
+            .err(),
+            Some(Ktx2Error::FormatNotSupportedByGpu(
+                TextureFormat::Bc7RgbaUnorm
+            ))
+        );
+        assert_eq!(
+            ktx2_to_texture(&ktx2_file([147, 1, 8, 8, 0, 0, 1, 1, 0], &levels), &support).err(),
+            Some(Ktx2Error::UnsupportedCompression(147))
+        );
+        assert_eq!(
+            ktx2_to_texture(
+                &ktx2_file([43, 1, 2, 2, 0, 0, 1, 1, 0], &[vec![0; 4]]),
+                &support
+            )
+            .err(),
+            Some(Ktx2Error::InvalidLevelLength {
+                level: 0,
+                len: 4,
+                expected: 16
+            })
+        );
+        assert_eq!(
+            ktx2_to_texture(&[0; 12], &support).err(),
+            Some(Ktx2Error::InvalidIdentifier)
+        );
+        assert_eq!(
+            ktx2_to_texture(&ktx2_file([43, 1, 2, 2, 0, 0, 0, 1, 0], &[]), &support).err(),
+            Some(Ktx2Error::InvalidFaceCount(0))
+        );
+
+        // a level that ends past the end of the address space
+        let mut file = ktx2_file([43, 1, 1, 1, 0, 0, 1, 1, 0], &[vec![0; 4]]);
+        file[LEVEL_INDEX_OFFSET..LEVEL_INDEX_OFFSET + 8].copy_from_slice(&u64::MAX.to_le_bytes());
+        assert_eq!(
+            ktx2_to_texture(&file, &support).err(),
+            Some(Ktx2Error::UnexpectedEnd)
+        );
+    }
+}
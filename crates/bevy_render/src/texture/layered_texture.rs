@@ -0,0 +1,397 @@
+use super::{Extent3d, Texture, TextureDimension, TextureViewDimension};
+use std::f32::consts::PI;
+use thiserror::Error;
+
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum LayeredTextureError {
+    #[error("expected {expected} images, but got {actual}")]
+    WrongImageCount { expected: usize, actual: usize },
+    #[error("all images need to be 2D images with the same size and format")]
+    MismatchedImages,
+    #[error("cube map faces need to be square, but they are {width}x{height}")]
+    NonSquareFaces { width: u32, height: u32 },
+    #[error(
+        "a {width}x{height} image isn't a horizontal (4x3) or vertical (3x4) cross of square faces"
+    )]
+    InvalidCrossLayout { width: u32, height: u32 },
+    #[error("a {height} pixel high image can't be split into {layers} layers")]
+    InvalidLayerCount { height: u32, layers: u32 },
+    #[error("equirectangular panoramas need to be non-empty 2D images")]
+    InvalidPanorama,
+    #[error("cube map faces need to be at least 1x1")]
+    EmptyFaces,
+}
+
+/// The order of cube map faces in the layers of a cube map texture
+pub const CUBE_FACES: [CubeFace; 6] = [
+    CubeFace::PositiveX,
+    CubeFace::NegativeX,
+    CubeFace::PositiveY,
+    CubeFace::NegativeY,
+    CubeFace::PositiveZ,
+    CubeFace::NegativeZ,
+];
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum CubeFace {
+    PositiveX,
+    NegativeX,
+    PositiveY,
+    NegativeY,
+    PositiveZ,
+    NegativeZ,
+}
+
+impl CubeFace {
+    /// The direction a cube map is sampled with to get the texel at `(u, v)` of this face, where `u` goes right and
+    /// `v` goes down, both from -1.0 to 1.0
+    pub fn direction(self, u: f32, v: f32) -> [f32; 3] {
+        match self {
+            CubeFace::PositiveX => [1.0, -v, -u],
+            CubeFace::NegativeX => [-1.0, -v, u],
+            CubeFace::PositiveY => [u, 1.0, v],
+            CubeFace::NegativeY => [u, -1.0, -v],
+            CubeFace::PositiveZ => [u, -v, 1.0],
+            CubeFace::NegativeZ => [-u, -v, -1.0],
+        }
+    }
+}
+
+impl Texture {
+    /// Creates a 2D array texture with one layer per image, for use with the `texture2DArray` shader uniform type
+    pub fn new_2d_array(images: &[Texture]) -> Result<Texture, LayeredTextureError> {
+        let mut texture = stack_images(images)?;
+        texture.view_dimension = TextureViewDimension::D2Array;
+        Ok(texture)
+    }
+
+    /// Creates a cube map from six square images, in the order of [CUBE_FACES]. Cube maps are sampled with a direction
+    /// through the `textureCube` shader uniform type.
+    pub fn new_cube(faces: &[Texture]) -> Result<Texture, LayeredTextureError> {
+        if faces.len() != CUBE_FACES.len() {
+            return Err(LayeredTextureError::WrongImageCount {
+                expected: CUBE_FACES.len(),
+                actual: faces.len(),
+            });
+        }
+        let mut texture = stack_images(faces)?;
+        if texture.size.width != texture.size.height {
+            return Err(LayeredTextureError::NonSquareFaces {
+                width: texture.size.width,
+                height: texture.size.height,
+            });
+        }
+        texture.view_dimension = TextureViewDimension::Cube;
+        Ok(texture)
+    }
+
+    /// Creates a 3D texture with one depth slice per image, for use with the `texture3D` shader uniform type
+    pub fn new_3d(slices: &[Texture]) -> Result<Texture, LayeredTextureError> {
+        let mut texture = stack_images(slices)?;
+        texture.dimension = TextureDimension::D3;
+        texture.view_dimension = TextureViewDimension::D3;
+        Ok(texture)
+    }
+
+    /// Creates a cube map from a 2D texture that has the faces laid out as a horizontal cross:
+    ///
+    /// ```text
+    ///       +Y
+    ///   -X  +Z  +X  -Z
+    ///       -Y
+    /// ```
+    ///
+    /// or as a vertical cross, where -Z is upside down:
+    ///
+    /// ```text
+    ///       +Y
+    ///   -X  +Z  +X
+    ///       -Y
+    ///       -Z
+    /// ```
+    pub fn cube_from_cross(&self) -> Result<Texture, LayeredTextureError> {
+        let (width, height) = (self.size.width, self.size.height);
+        let invalid_layout = LayeredTextureError::InvalidCrossLayout { width, height };
+        if self.dimension != TextureDimension::D2 || self.size.depth != 1 {
+            return Err(invalid_layout);
+        }
+        let (face_size, vertical) = if width * 3 == height * 4 && width % 4 == 0 {
+            (width / 4, false)
+        } else if width * 4 == height * 3 && width % 3 == 0 {
+            (width / 3, true)
+        } else {
+            return Err(invalid_layout);
+        };
+        if face_size == 0 {
+            return Err(invalid_layout);
+        }
+
+        let pixel_size = self.format.pixel_size();
+        let face_size = face_size as usize;
+        let mut data = Vec::with_capacity(face_size * face_size * 6 * pixel_size);
+        for face in CUBE_FACES.iter() {
+            // the face's position in the cross, in faces, and whether it is upside down
+            let (column, row, flipped) = match (face, vertical) {
+                (CubeFace::PositiveX, _) => (2, 1, false),
+                (CubeFace::NegativeX, _) => (0, 1, false),
+                (CubeFace::PositiveY, _) => (1, 0, false),
+                (CubeFace::NegativeY, _) => (1, 2, false),
+                (CubeFace::PositiveZ, _) => (1, 1, false),
+                (CubeFace::NegativeZ, false) => (3, 1, false),
+                (CubeFace::NegativeZ, true) => (1, 3, true),
+            };
+            for y in 0..face_size {
+                for x in 0..face_size {
+                    let (x, y) = if flipped {
+                        (face_size - 1 - x, face_size - 1 - y)
+                    } else {
+                        (x, y)
+                    };
+                    let offset = ((row * face_size + y) * width as usize + column * face_size + x)
+                        * pixel_size;
+                    data.extend_from_slice(&self.data[offset..offset + pixel_size]);
+                }
+            }
+        }
+
+        Ok(self.cube_with_data(face_size as u32, data))
+    }
+
+    /// Projects a 2D texture with an equirectangular panorama onto a cube map with `face_size` x `face_size` faces.
+    /// The center of the panorama faces -Z. Texels are copied from the nearest panorama pixel, so `face_size` should
+    /// be about a quarter of the panorama's width to avoid aliasing.
+    pub fn cube_from_equirectangular(
+        &self,
+        face_size: u32,
+    ) -> Result<Texture, LayeredTextureError> {
+        if self.dimension != TextureDimension::D2
+            || self.size.depth != 1
+            || self.size.width == 0
+            || self.size.height == 0
+        {
+            return Err(LayeredTextureError::InvalidPanorama);
+        }
+        if face_size == 0 {
+            return Err(LayeredTextureError::EmptyFaces);
+        }
+
+        let pixel_size = self.format.pixel_size();
+        let (width, height) = (self.size.width as usize, self.size.height as usize);
+        let size = face_size as usize;
+        let mut data = Vec::with_capacity(size * size * 6 * pixel_size);
+        for face in CUBE_FACES.iter() {
+            for y in 0..size {
+                for x in 0..size {
+                    let u = (x as f32 + 0.5) / size as f32 * 2.0 - 1.0;
+                    let v = (y as f32 + 0.5) / size as f32 * 2.0 - 1.0;
+                    let [dx, dy, dz] = face.direction(u, v);
+                    let longitude = dx.atan2(-dz);
+                    let latitude = (dy / (dx * dx + dy * dy + dz * dz).sqrt()).asin();
+                    let column = ((longitude / (2.0 * PI) + 0.5) * width as f32) as usize;
+                    let row = ((0.5 - latitude / PI) * height as f32) as usize;
+                    let offset = (row.min(height - 1) * width + column.min(width - 1)) * pixel_size;
+                    data.extend_from_slice(&self.data[offset..offset + pixel_size]);
+                }
+            }
+        }
+
+        Ok(self.cube_with_data(face_size, data))
+    }
+
+    /// Splits a 2D texture with `layers` images stacked vertically into a 2D array texture, like
+    /// [Texture::reinterpret_stacked_2d_as_array], but returns an error instead of panicking if the texture can't be
+    /// split into that many layers
+    pub fn stacked_2d_array(mut self, layers: u32) -> Result<Texture, LayeredTextureError> {
+        if self.dimension != TextureDimension::D2 || self.size.depth != 1 {
+            return Err(LayeredTextureError::MismatchedImages);
+        }
+        if layers == 0 || self.size.height % layers != 0 {
+            return Err(LayeredTextureError::InvalidLayerCount {
+                height: self.size.height,
+                layers,
+            });
+        }
+        self.reinterpret_stacked_2d_as_array(layers);
+        Ok(self)
+    }
+
+    fn cube_with_data(&self, face_size: u32, data: Vec<u8>) -> Texture {
+        Texture {
+            data,
+            size: Extent3d::new(face_size, face_size, CUBE_FACES.len() as u32),
+            dimension: TextureDimension::D2,
+            view_dimension: TextureViewDimension::Cube,
+            mipmaps: Vec::new(),
+            ..self.clone()
+        }
+    }
+}
+
+/// Stacks 2D images of the same size and format into the layers of one texture
+fn stack_images(images: &[Texture]) -> Result<Texture, LayeredTextureError> {
+    let first = images.first().ok_or(LayeredTextureError::WrongImageCount {
+        expected: 1,
+        actual: 0,
+    })?;
+    let mut data = Vec::with_capacity(first.data.len() * images.len());
+    for image in images.iter() {
+        if image.dimension != TextureDimension::D2
+            || image.size != first.size
+            || image.size.depth != 1
+            || image.format != first.format
+        {
+            return Err(LayeredTextureError::MismatchedImages);
+        }
+        data.extend_from_slice(&image.data);
+    }
+
+    Ok(Texture {
+        data,
+        size: Extent3d::new(first.size.width, first.size.height, images.len() as u32),
+        mipmaps: Vec::new(),
+        ..first.clone()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::texture::TextureFormat;
+
+    fn image(width: u32, height: u32, value: impl Fn(u32, u32) -> u8) -> Texture {
+        let mut data = Vec::new();
+        for y in 0..height {
+            for x in 0..width {
+                data.push(value(x, y));
+            }
+        }
+        Texture::new(
+            Extent3d::new(width, height, 1),
+            TextureDimension::D2,
+            data,
+            TextureFormat::R8Unorm,
+        )
+    }
+
+    #[test]
+    fn layered_textures() {
+        let layers = (0..3).map(|i| image(2, 2, |_, _| i)).collect::<Vec<_>>();
+        let array = Texture::new_2d_array(&layers).unwrap();
+        assert_eq!(array.size, Extent3d::new(2, 2, 3));
+        assert_eq!(array.view_dimension, TextureViewDimension::D2Array);
+        assert_eq!(array.data, vec![0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2]);
+
+        let volume = Texture::new_3d(&layers).unwrap();
+        assert_eq!(volume.dimension, TextureDimension::D3);
+        assert_eq!(volume.view_dimension, TextureViewDimension::D3);
+
+        assert_eq!(
+            Texture::new_cube(&layers).err(),
+            Some(LayeredTextureError::WrongImageCount {
+                expected: 6,
+                actual: 3
+            })
+        );
+        let faces = (0..6).map(|i| image(2, 1, |_, _| i)).collect::<Vec<_>>();
+        assert_eq!(
+            Texture::new_cube(&faces).err(),
+            Some(LayeredTextureError::NonSquareFaces {
+                width: 2,
+                height: 1
+            })
+        );
+        let mismatched = vec![image(2, 2, |_, _| 0), image(1, 1, |_, _| 0)];
+        assert_eq!(
+            Texture::new_2d_array(&mismatched).err(),
+            Some(LayeredTextureError::MismatchedImages)
+        );
+    }
+
+    #[test]
+    fn cube_from_cross() {
+        // each texel holds the column and row of its face in the cross
+        let horizontal = image(8, 6, |x, y| (x / 2 + 10 * (y / 2)) as u8);
+        let cube = horizontal.cube_from_cross().unwrap();
+        assert_eq!(cube.size, Extent3d::new(2, 2, 6));
+        assert_eq!(cube.view_dimension, TextureViewDimension::Cube);
+        let faces = cube
+            .data
+            .chunks_exact(4)
+            .map(|face| face[0])
+            .collect::<Vec<_>>();
+        assert_eq!(faces, vec![12, 10, 1, 21, 11, 13]);
+
+        // -Z is upside down at the bottom of a vertical cross
+        let vertical = image(3, 4, |x, y| (x + 10 * y) as u8);
+        let cube = vertical.cube_from_cross().unwrap();
+        assert_eq!(cube.size, Extent3d::new(1, 1, 6));
+        assert_eq!(cube.data, vec![12, 10, 1, 21, 11, 31]);
+        let vertical = image(6, 8, |x, y| (x % 2 + 2 * (y % 2) + 10 * (y / 2)) as u8);
+        let negative_z = &vertical.cube_from_cross().unwrap().data[20..24];
+        assert_eq!(negative_z, &[33, 32, 31, 30]);
+
+        assert_eq!(
+            image(4, 4, |_, _| 0).cube_from_cross().err(),
+            Some(LayeredTextureError::InvalidCrossLayout {
+                width: 4,
+                height: 4
+            })
+        );
+    }
+
+    #[test]
+    fn cube_from_equirectangular() {
+        // the panorama's columns are the longitude, from -180 to 180 degrees, and its rows the latitude
+        let panorama = image(8, 4, |x, y| (x + 10 * y) as u8);
+        let cube = panorama.cube_from_equirectangular(2).unwrap();
+        assert_eq!(cube.size, Extent3d::new(2, 2, 6));
+        let face_row =
+            |face: usize, row: usize| &cube.data[face * 4 + row * 2..face * 4 + row * 2 + 2];
+        // -Z is in the center of the panorama, +Z at its edges, +X a quarter to the right of the center. Cube map faces
+        // are seen from outside the cube, so they are mirrored.
+        assert_eq!(face_row(5, 0), &[14, 13]);
+        assert_eq!(face_row(0, 0), &[16, 15]);
+        assert_eq!(face_row(1, 0), &[12, 11]);
+        assert_eq!(face_row(4, 0), &[10, 17]);
+        // the poles are at the top and bottom rows
+        assert!(cube.data[8..12].iter().all(|texel| texel / 10 == 0));
+        assert!(cube.data[12..16].iter().all(|texel| texel / 10 == 3));
+
+        assert_eq!(
+            panorama.cube_from_equirectangular(0).err(),
+            Some(LayeredTextureError::EmptyFaces)
+        );
+        assert_eq!(
+            image(0, 0, |_, _| 0).cube_from_equirectangular(2).err(),
+            Some(LayeredTextureError::InvalidPanorama)
+        );
+        let volume = Texture::new_3d(&[image(2, 2, |_, _| 0), image(2, 2, |_, _| 0)]).unwrap();
+        assert_eq!(
+            volume.cube_from_equirectangular(2).err(),
+            Some(LayeredTextureError::InvalidPanorama)
+        );
+    }
+
+    #[test]
+    fn stacked_2d_array() {
+        let stacked = image(2, 6, |_, y| (y / 2) as u8);
+        let array = stacked.clone().stacked_2d_array(3).unwrap();
+        assert_eq!(array.size, Extent3d::new(2, 2, 3));
+        assert_eq!(array.view_dimension, TextureViewDimension::D2Array);
+
+        assert_eq!(
+            stacked.clone().stacked_2d_array(4).err(),
+            Some(LayeredTextureError::InvalidLayerCount {
+                height: 6,
+                layers: 4
+            })
+        );
+        assert_eq!(
+            stacked.stacked_2d_array(0).err(),
+            Some(LayeredTextureError::InvalidLayerCount {
+                height: 6,
+                layers: 0
+            })
+        );
+    }
+}
@@ -0,0 +1,230 @@
+use super::{image_texture_loader::image_to_texture, MipmapSettings, Texture};
+use anyhow::Result;
+use bevy_asset::{AssetLoader, LoadContext, LoadedAsset};
+use bevy_ecs::{FromResources, Resources};
+use bevy_utils::BoxedFuture;
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+
+/// Describes a texture made of one or more images. `.layers` files contain one of these in the RON format, with image
+/// paths relative to the `.layers` file, like:
+///
+/// ```text
+/// CubeFaces(["right.png", "left.png", "top.png", "bottom.png", "back.png", "front.png"])
+/// ```
+#[derive(Debug, Clone, Deserialize)]
+pub enum LayeredTextureDescriptor {
+    /// A 2D array texture with one layer per image
+    Array(Vec<String>),
+    /// A 2D array texture from one image with `layers` images stacked vertically
+    StackedArray { image: String, layers: u32 },
+    /// A cube map from six images, in the order of [CUBE_FACES](super::CUBE_FACES)
+    CubeFaces(Vec<String>),
+    /// A cube map from one image with the faces laid out as a cross, see [Texture::cube_from_cross]
+    CubeCross(String),
+    /// A cube map projected from an equirectangular panorama, see [Texture::cube_from_equirectangular]
+    CubeEquirectangular { image: String, face_size: u32 },
+    /// A 3D texture with one depth slice per image
+    Volume(Vec<String>),
+}
+
+/// Loads array textures, cube maps and 3D textures from `.layers` files. See [LayeredTextureDescriptor]. Mip levels
+/// are generated if [MipmapSettings] enables them.
+#[derive(Clone)]
+pub struct LayeredTextureLoader {
+    mipmap_settings: MipmapSettings,
+}
+
+impl FromResources for LayeredTextureLoader {
+    fn from_resources(resources: &Resources) -> Self {
+        LayeredTextureLoader {
+            mipmap_settings: resources
+                .get::<MipmapSettings>()
+                .map(|settings| *settings)
+                .unwrap_or_default(),
+        }
+    }
+}
+
+impl AssetLoader for LayeredTextureLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let descriptor: LayeredTextureDescriptor = ron::de::from_bytes(bytes)?;
+            let mut texture = match descriptor {
+                LayeredTextureDescriptor::Array(images) => {
+                    Texture::new_2d_array(&load_images(load_context, &images).await?)?
+                }
+                LayeredTextureDescriptor::StackedArray { image, layers } => {
+                    load_image(load_context, &image)
+                        .await?
+                        .stacked_2d_array(layers)?
+                }
+                LayeredTextureDescriptor::CubeFaces(images) => {
+                    Texture::new_cube(&load_images(load_context, &images).await?)?
+                }
+                LayeredTextureDescriptor::CubeCross(image) => {
+                    load_image(load_context, &image).await?.cube_from_cross()?
+                }
+                LayeredTextureDescriptor::CubeEquirectangular { image, face_size } => {
+                    load_image(load_context, &image)
+                        .await?
+                        .cube_from_equirectangular(face_size)?
+                }
+                LayeredTextureDescriptor::Volume(images) => {
+                    Texture::new_3d(&load_images(load_context, &images).await?)?
+                }
+            };
+            self.mipmap_settings.apply(&mut texture);
+            load_context.set_default_asset(LoadedAsset::new(texture));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["layers"]
+    }
+}
+
+async fn load_images(load_context: &LoadContext<'_>, paths: &[String]) -> Result<Vec<Texture>> {
+    let mut images = Vec::with_capacity(paths.len());
+    for path in paths.iter() {
+        images.push(load_image(load_context, path).await?);
+    }
+    Ok(images)
+}
+
+async fn load_image(load_context: &LoadContext<'_>, path: &str) -> Result<Texture> {
+    let path = image_path(load_context.path(), path);
+    let bytes = load_context.read_asset_bytes(&path).await?;
+    let extension = path
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .unwrap_or_default();
+
+    #[cfg(feature = "hdr")]
+    {
+        if extension == "hdr" {
+            return super::hdr_texture_loader::hdr_to_texture(&bytes);
+        }
+    }
+
+    let format = image::ImageFormat::from_extension(extension).ok_or_else(|| {
+        anyhow::anyhow!(
+            "Unsupported image format {:?} for file {}.",
+            extension,
+            path.display()
+        )
+    })?;
+    Ok(image_to_texture(image::load_from_memory_with_format(
+        &bytes, format,
+    )?))
+}
+
+/// Image paths are relative to the `.layers` file
+fn image_path(layers_path: &Path, image_path: &str) -> PathBuf {
+    layers_path
+        .parent()
+        .map(|parent| parent.join(image_path))
+        .unwrap_or_else(|| PathBuf::from(image_path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::texture::{Extent3d, TextureViewDimension};
+    use bevy_app::App;
+    use bevy_asset::{
+        AddAsset, AssetPlugin, AssetServer, Assets, Handle, LoadState, MemoryAssetIo,
+        DEFAULT_ASSET_SOURCE,
+    };
+    use bevy_core::CorePlugin;
+    use bevy_reflect::ReflectPlugin;
+    use std::sync::Arc;
+
+    #[test]
+    fn parse_descriptors() {
+        let descriptor: LayeredTextureDescriptor =
+            ron::de::from_str(r#"StackedArray(image: "splat.png", layers: 4)"#).unwrap();
+        assert!(matches!(
+            descriptor,
+            LayeredTextureDescriptor::StackedArray { ref image, layers: 4 } if image == "splat.png"
+        ));
+
+        let descriptor: LayeredTextureDescriptor =
+            ron::de::from_str(r#"CubeEquirectangular(image: "sky.hdr", face_size: 256)"#).unwrap();
+        assert!(matches!(
+            descriptor,
+            LayeredTextureDescriptor::CubeEquirectangular { ref image, face_size: 256 }
+                if image == "sky.hdr"
+        ));
+
+        let descriptor: LayeredTextureDescriptor = ron::de::from_str(
+            r#"CubeFaces(["px.png", "nx.png", "py.png", "ny.png", "pz.png", "nz.png"])"#,
+        )
+        .unwrap();
+        assert!(matches!(
+            descriptor,
+            LayeredTextureDescriptor::CubeFaces(ref images) if images.len() == 6
+        ));
+
+        assert!(ron::de::from_str::<LayeredTextureDescriptor>(r#"Stacked("splat.png")"#).is_err());
+    }
+
+    fn png(width: u32, height: u32, value: u8) -> Vec<u8> {
+        let image = image::DynamicImage::ImageLuma8(image::ImageBuffer::from_pixel(
+            width,
+            height,
+            image::Luma([value]),
+        ));
+        let mut bytes = Vec::new();
+        image
+            .write_to(&mut bytes, image::ImageOutputFormat::Png)
+            .unwrap();
+        bytes
+    }
+
+    #[test]
+    fn load_layered_textures() {
+        let mut app_builder = App::build();
+        app_builder
+            .add_plugin(ReflectPlugin)
+            .add_plugin(CorePlugin)
+            .add_plugin(AssetPlugin)
+            .add_asset::<Texture>()
+            .init_asset_loader::<LayeredTextureLoader>();
+        let mut app = app_builder.app;
+
+        let asset_io = Arc::new(MemoryAssetIo::default());
+        asset_io.insert("terrain/grass.png", png(2, 2, 10));
+        asset_io.insert("terrain/rock.png", png(2, 2, 20));
+        asset_io.insert(
+            "terrain/splat.layers",
+            r#"Array(["grass.png", "rock.png"])"#,
+        );
+        let asset_server = (*app.resources.get::<AssetServer>().unwrap()).clone();
+        asset_server
+            .asset_sources()
+            .mount_arc(DEFAULT_ASSET_SOURCE, asset_io);
+
+        // images are read relative to the .layers file
+        let handle: Handle<Texture> = asset_server.load("terrain/splat.layers");
+        for _ in 0..1000 {
+            app.update();
+            if asset_server.get_load_state(&handle) == LoadState::Loaded {
+                break;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(1));
+        }
+
+        let textures = app.resources.get::<Assets<Texture>>().unwrap();
+        let splat = textures.get(&handle).unwrap();
+        assert_eq!(splat.size, Extent3d::new(2, 2, 2));
+        assert_eq!(splat.view_dimension, TextureViewDimension::D2Array);
+        assert_eq!(splat.data, vec![10, 10, 10, 10, 20, 20, 20, 20]);
+        assert_eq!(splat.mip_level_count(), 1);
+    }
+}
@@ -0,0 +1,381 @@
+use super::{Extent3d, FilterMode, Texture, TextureDimension, TextureFormat};
+use thiserror::Error;
+
+/// The filter that [Texture::generate_mipmaps] downsamples with
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum MipmapFilter {
+    /// Averages each 2x2 block of texels. Fast, but slightly blurry.
+    Box,
+    /// A Kaiser windowed sinc filter. Keeps mip levels sharper than [MipmapFilter::Box], but is slower.
+    Kaiser,
+}
+
+impl Default for MipmapFilter {
+    fn default() -> Self {
+        MipmapFilter::Box
+    }
+}
+
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum MipmapError {
+    #[error("mipmaps can't be generated for textures with the {0:?} format")]
+    UnsupportedFormat(TextureFormat),
+    #[error("mipmaps can only be generated for 2D textures")]
+    UnsupportedDimension(TextureDimension),
+}
+
+/// The half width of the Kaiser filter, in texels of the smaller mip level
+const KAISER_RADIUS: f32 = 2.0;
+/// The shape parameter of the Kaiser window. Higher values blur more, but ring less.
+const KAISER_BETA: f32 = 4.0;
+
+impl Texture {
+    /// Replaces the mip levels after the first with a full mip chain downsampled from the first. Each layer of array
+    /// textures and cube maps is downsampled separately. The color channels of sRGB textures are averaged in linear
+    /// space.
+    pub fn generate_mipmaps(&mut self, filter: MipmapFilter) -> Result<(), MipmapError> {
+        if self.dimension != TextureDimension::D2 {
+            return Err(MipmapError::UnsupportedDimension(self.dimension));
+        }
+        let channels = texel_channels(self.format)?;
+
+        let level_count = 32 - self.size.width.max(self.size.height).leading_zeros();
+        let mut layers = self
+            .data
+            .chunks_exact(
+                self.size.width as usize * self.size.height as usize * self.format.pixel_size(),
+            )
+            .map(|layer| decode(layer, self.format, channels))
+            .collect::<Vec<_>>();
+
+        self.mipmaps.clear();
+        for level in 1..level_count {
+            let source_size = self.mip_level_size(level - 1);
+            let size = self.mip_level_size(level);
+            let mut data = Vec::with_capacity(self.format.image_size(size));
+            for layer in layers.iter_mut() {
+                *layer = downsample(layer, channels, source_size, size, filter);
+                encode(layer, self.format, channels, &mut data);
+            }
+            debug_assert_eq!(data.len(), self.format.image_size(size));
+            self.mipmaps.push(data);
+        }
+
+        Ok(())
+    }
+}
+
+/// Configures the mip levels texture loaders generate for images that don't have any. Mip levels keep textures that
+/// are drawn smaller than their size from flickering, but they take up a third more memory and take a while to
+/// generate, so loaders don't generate them by default. Loaders read this when they are created, so insert it before
+/// adding the [RenderPlugin](crate::RenderPlugin).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MipmapSettings {
+    /// The filter that mip levels are generated with, or `None` to not generate any
+    pub generate: Option<MipmapFilter>,
+}
+
+impl MipmapSettings {
+    /// Generates mip levels for a texture that only has one and blends between them when sampling. Textures with
+    /// formats mipmaps can't be generated for are left as they are.
+    pub(crate) fn apply(&self, texture: &mut Texture) {
+        if let Some(filter) = self.generate {
+            if texture.mip_level_count() == 1
+                && texture.generate_mipmaps(filter).is_ok()
+                && texture.mip_level_count() > 1
+            {
+                texture.sampler.mipmap_filter = FilterMode::Linear;
+            }
+        }
+    }
+}
+
+/// The number of channels of the formats mipmaps can be generated for
+fn texel_channels(format: TextureFormat) -> Result<usize, MipmapError> {
+    match format {
+        TextureFormat::R8Unorm | TextureFormat::R32Float => Ok(1),
+        TextureFormat::Rg8Unorm | TextureFormat::Rg32Float => Ok(2),
+        TextureFormat::Rgba8Unorm
+        | TextureFormat::Rgba8UnormSrgb
+        | TextureFormat::Bgra8Unorm
+        | TextureFormat::Bgra8UnormSrgb
+        | TextureFormat::Rgba32Float => Ok(4),
+        format => Err(MipmapError::UnsupportedFormat(format)),
+    }
+}
+
+fn is_float(format: TextureFormat) -> bool {
+    matches!(
+        format,
+        TextureFormat::R32Float | TextureFormat::Rg32Float | TextureFormat::Rgba32Float
+    )
+}
+
+/// Whether the channel holds a color, which is stored in sRGB space by sRGB formats. Alpha is always linear.
+fn is_srgb_channel(format: TextureFormat, channels: usize, channel: usize) -> bool {
+    format.is_srgb() && !(channels == 4 && channel == 3)
+}
+
+fn srgb_to_linear(value: f32) -> f32 {
+    if value <= 0.04045 {
+        value / 12.92
+    } else {
+        ((value + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn linear_to_srgb(value: f32) -> f32 {
+    if value <= 0.003_130_8 {
+        value * 12.92
+    } else {
+        1.055 * value.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+/// Converts the texels of one layer to linear floats
+fn decode(data: &[u8], format: TextureFormat, channels: usize) -> Vec<f32> {
+    if is_float(format) {
+        data.chunks_exact(4)
+            .map(|bytes| f32::from_ne_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+            .collect()
+    } else {
+        data.iter()
+            .enumerate()
+            .map(|(index, value)| {
+                let value = *value as f32 / 255.0;
+                if is_srgb_channel(format, channels, index % channels) {
+                    srgb_to_linear(value)
+                } else {
+                    value
+                }
+            })
+            .collect()
+    }
+}
+
+fn encode(texels: &[f32], format: TextureFormat, channels: usize, data: &mut Vec<u8>) {
+    if is_float(format) {
+        for value in texels.iter() {
+            data.extend_from_slice(&value.to_ne_bytes());
+        }
+    } else {
+        for (index, value) in texels.iter().enumerate() {
+            let value = value.max(0.0).min(1.0);
+            let value = if is_srgb_channel(format, channels, index % channels) {
+                linear_to_srgb(value)
+            } else {
+                value
+            };
+            data.push((value * 255.0).round() as u8);
+        }
+    }
+}
+
+fn downsample(
+    texels: &[f32],
+    channels: usize,
+    source_size: Extent3d,
+    size: Extent3d,
+    filter: MipmapFilter,
+) -> Vec<f32> {
+    // the filter is separable, so rows and columns are downsampled one after the other
+    let (source_width, source_height) = (source_size.width as usize, source_size.height as usize);
+    let (width, height) = (size.width as usize, size.height as usize);
+    let column_weights = filter_weights(source_width, width, filter);
+    let row_weights = filter_weights(source_height, height, filter);
+
+    let mut horizontal = vec![0.0; width * source_height * channels];
+    for y in 0..source_height {
+        for (x, weights) in column_weights.iter().enumerate() {
+            for (source_x, weight) in weights.iter() {
+                for channel in 0..channels {
+                    horizontal[(y * width + x) * channels + channel] +=
+                        weight * texels[(y * source_width + source_x) * channels + channel];
+                }
+            }
+        }
+    }
+
+    let mut result = vec![0.0; width * height * channels];
+    for (y, weights) in row_weights.iter().enumerate() {
+        for x in 0..width {
+            for (source_y, weight) in weights.iter() {
+                for channel in 0..channels {
+                    result[(y * width + x) * channels + channel] +=
+                        weight * horizontal[(source_y * width + x) * channels + channel];
+                }
+            }
+        }
+    }
+    result
+}
+
+/// For each texel of a row (or column) with `size` texels, the source texels it is filtered from and their weights
+fn filter_weights(source_size: usize, size: usize, filter: MipmapFilter) -> Vec<Vec<(usize, f32)>> {
+    // dimensions that are already 1 texel wide don't shrink
+    if source_size == size {
+        return (0..size).map(|index| vec![(index, 1.0)]).collect();
+    }
+
+    let scale = source_size as f32 / size as f32;
+    (0..size)
+        .map(|index| {
+            // the texel's center, in source texels
+            let center = (index as f32 + 0.5) * scale;
+            let mut weights = match filter {
+                MipmapFilter::Box => {
+                    let start = index as f32 * scale;
+                    let end = start + scale;
+                    (start.floor() as usize..(end.ceil() as usize).min(source_size))
+                        .map(|source| {
+                            let overlap =
+                                (end.min(source as f32 + 1.0) - start.max(source as f32)).max(0.0);
+                            (source, overlap)
+                        })
+                        .collect::<Vec<_>>()
+                }
+                MipmapFilter::Kaiser => {
+                    let radius = KAISER_RADIUS * scale;
+                    let first = (center - radius).floor() as isize;
+                    let last = (center + radius).ceil() as isize;
+                    (first..=last)
+                        .filter_map(|source| {
+                            let distance = (source as f32 + 0.5 - center) / scale;
+                            if distance.abs() >= KAISER_RADIUS {
+                                return None;
+                            }
+                            let weight = sinc(distance) * kaiser(distance / KAISER_RADIUS);
+                            // texels outside of the image repeat the edge texels
+                            let source = source.max(0).min(source_size as isize - 1) as usize;
+                            Some((source, weight))
+                        })
+                        .collect::<Vec<_>>()
+                }
+            };
+            let total: f32 = weights.iter().map(|(_, weight)| weight).sum();
+            for (_, weight) in weights.iter_mut() {
+                *weight /= total;
+            }
+            weights
+        })
+        .collect()
+}
+
+fn sinc(x: f32) -> f32 {
+    if x == 0.0 {
+        1.0
+    } else {
+        let x = x * std::f32::consts::PI;
+        x.sin() / x
+    }
+}
+
+/// The Kaiser window at `x`, which goes from -1.0 to 1.0
+fn kaiser(x: f32) -> f32 {
+    bessel_i0(KAISER_BETA * (1.0 - x * x).max(0.0).sqrt()) / bessel_i0(KAISER_BETA)
+}
+
+/// The zeroth order modified Bessel function of the first kind
+fn bessel_i0(x: f32) -> f32 {
+    let mut sum = 1.0;
+    let mut term = 1.0;
+    let half_x_squared = x * x / 4.0;
+    for k in 1..20 {
+        term *= half_x_squared / (k * k) as f32;
+        sum += term;
+    }
+    sum
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mip_chain() {
+        let mut texture = Texture::new(
+            Extent3d::new(4, 2, 2),
+            TextureDimension::D2,
+            vec![
+                0, 0, 255, 255, //
+                0, 0, 255, 255, //
+                10, 20, 30, 40, //
+                50, 60, 70, 80, //
+            ],
+            TextureFormat::R8Unorm,
+        );
+        texture.generate_mipmaps(MipmapFilter::Box).unwrap();
+        assert_eq!(texture.mip_level_count(), 3);
+        assert_eq!(texture.mip_level_size(1), Extent3d::new(2, 1, 2));
+        assert_eq!(texture.mip_level_size(2), Extent3d::new(1, 1, 2));
+        // each layer is downsampled separately
+        assert_eq!(texture.mip_level_data(1), Some(&[0, 255, 35, 55][..]));
+        assert_eq!(texture.mip_level_data(2), Some(&[128, 45][..]));
+        assert_eq!(texture.mip_level_data(3), None);
+
+        texture.generate_mipmaps(MipmapFilter::Kaiser).unwrap();
+        assert_eq!(texture.mip_level_count(), 3);
+        let flat = Texture::new(
+            Extent3d::new(8, 8, 1),
+            TextureDimension::D2,
+            vec![100; 64],
+            TextureFormat::R8Unorm,
+        );
+        for filter in [MipmapFilter::Box, MipmapFilter::Kaiser].iter() {
+            let mut flat = flat.clone();
+            flat.generate_mipmaps(*filter).unwrap();
+            assert!(flat.mipmaps.iter().flatten().all(|value| *value == 100));
+        }
+    }
+
+    #[test]
+    fn srgb_mipmaps() {
+        // black and white average to 50% linear light, which is brighter than 50% in sRGB. Alpha stays linear.
+        let mut texture = Texture::new(
+            Extent3d::new(2, 1, 1),
+            TextureDimension::D2,
+            vec![0, 0, 0, 0, 255, 255, 255, 255],
+            TextureFormat::Rgba8UnormSrgb,
+        );
+        texture.generate_mipmaps(MipmapFilter::Box).unwrap();
+        assert_eq!(texture.mipmaps, vec![vec![188, 188, 188, 128]]);
+
+        texture.format = TextureFormat::Rgba8Unorm;
+        texture.generate_mipmaps(MipmapFilter::Box).unwrap();
+        assert_eq!(texture.mipmaps, vec![vec![128, 128, 128, 128]]);
+
+        texture.format = TextureFormat::Rgba16Uint;
+        assert_eq!(
+            texture.generate_mipmaps(MipmapFilter::Box),
+            Err(MipmapError::UnsupportedFormat(TextureFormat::Rgba16Uint))
+        );
+    }
+
+    #[test]
+    fn mipmap_settings() {
+        let texture = Texture::new(
+            Extent3d::new(4, 4, 1),
+            TextureDimension::D2,
+            vec![0; 16],
+            TextureFormat::R8Unorm,
+        );
+        let mut unchanged = texture.clone();
+        MipmapSettings::default().apply(&mut unchanged);
+        assert_eq!(unchanged.mip_level_count(), 1);
+        assert_eq!(unchanged.sampler.mipmap_filter, FilterMode::Nearest);
+
+        let settings = MipmapSettings {
+            generate: Some(MipmapFilter::Box),
+        };
+        let mut generated = texture.clone();
+        settings.apply(&mut generated);
+        assert_eq!(generated.mip_level_count(), 3);
+        assert_eq!(generated.sampler.mipmap_filter, FilterMode::Linear);
+
+        // existing mip levels are kept
+        let mut loaded = texture;
+        loaded.mipmaps.push(vec![1; 4]);
+        settings.apply(&mut loaded);
+        assert_eq!(loaded.mipmaps, vec![vec![1; 4]]);
+    }
+}
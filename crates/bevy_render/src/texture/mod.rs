@@ -1,17 +1,35 @@
+#[cfg(any(feature = "dds", feature = "ktx2"))]
+mod block_compression;
+#[cfg(feature = "dds")]
+mod dds_texture_loader;
 #[cfg(feature = "hdr")]
 mod hdr_texture_loader;
 #[cfg(feature = "png")]
 mod image_texture_loader;
+#[cfg(feature = "ktx2")]
+mod ktx2_texture_loader;
+mod layered_texture;
+#[cfg(feature = "png")]
+mod layered_texture_loader;
+mod mipmap;
 mod sampler_descriptor;
 #[allow(clippy::module_inception)]
 mod texture;
 mod texture_descriptor;
 mod texture_dimension;
 
+#[cfg(feature = "dds")]
+pub use dds_texture_loader::*;
 #[cfg(feature = "hdr")]
 pub use hdr_texture_loader::*;
 #[cfg(feature = "png")]
 pub use image_texture_loader::*;
+#[cfg(feature = "ktx2")]
+pub use ktx2_texture_loader::*;
+pub use layered_texture::*;
+#[cfg(feature = "png")]
+pub use layered_texture_loader::*;
+pub use mipmap::*;
 pub use sampler_descriptor::*;
 pub use texture::*;
 pub use texture_descriptor::*;
@@ -1,4 +1,7 @@
-use super::{Extent3d, SamplerDescriptor, TextureDescriptor, TextureDimension, TextureFormat};
+use super::{
+    Extent3d, SamplerDescriptor, TextureDescriptor, TextureDimension, TextureFormat, TextureUsage,
+    TextureViewDimension,
+};
 use crate::renderer::{
     RenderResource, RenderResourceContext, RenderResourceId, RenderResourceType,
 };
@@ -7,6 +10,10 @@ use bevy_asset::{AssetEvent, Assets, Handle};
 use bevy_ecs::{Res, ResMut};
 use bevy_reflect::TypeUuid;
 use bevy_utils::HashSet;
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
 
 pub const TEXTURE_ASSET_INDEX: u64 = 0;
 pub const SAMPLER_ASSET_INDEX: u64 = 1;
@@ -14,17 +21,27 @@ pub const SAMPLER_ASSET_INDEX: u64 = 1;
 #[derive(Debug, Clone, TypeUuid)]
 #[uuid = "6ea26da6-6cf8-4ea2-9986-1d7bf6c17d6f"]
 pub struct Texture {
+    /// The data of the first mip level. The other levels are downsampled from it, so replace it with
+    /// [Texture::set_data], or clear `mipmaps` when changing it.
     pub data: Vec<u8>,
+    /// The data of the mip levels after the first, from largest to smallest. See [Texture::generate_mipmaps].
+    pub mipmaps: Vec<Vec<u8>>,
     pub size: Extent3d,
     pub format: TextureFormat,
     pub dimension: TextureDimension,
+    /// The dimension shaders see the texture as. Array textures and cube maps are [TextureDimension::D2] textures
+    /// with one layer per `size.depth`.
+    pub view_dimension: TextureViewDimension,
     pub sampler: SamplerDescriptor,
+    /// How the gpu texture can be used. Textures that cameras render to need [TextureUsage::OUTPUT_ATTACHMENT].
+    pub usage: TextureUsage,
 }
 
 impl Default for Texture {
     fn default() -> Self {
         Texture {
             data: Default::default(),
+            mipmaps: Default::default(),
             size: Extent3d {
                 width: 1,
                 height: 1,
@@ -32,7 +49,9 @@ impl Default for Texture {
             },
             format: TextureFormat::Rgba8UnormSrgb,
             dimension: TextureDimension::D2,
+            view_dimension: TextureViewDimension::D2,
             sampler: Default::default(),
+            usage: TextureUsage::SAMPLED | TextureUsage::COPY_DST,
         }
     }
 }
@@ -45,7 +64,7 @@ impl Texture {
         format: TextureFormat,
     ) -> Self {
         debug_assert_eq!(
-            size.volume() * format.pixel_size(),
+            format.image_size(size),
             data.len(),
             "Pixel data, size and format have to match",
         );
@@ -53,6 +72,7 @@ impl Texture {
             data,
             size,
             dimension,
+            view_dimension: default_view_dimension(dimension),
             format,
             ..Default::default()
         }
@@ -67,6 +87,7 @@ impl Texture {
         let mut value = Texture {
             format,
             dimension,
+            view_dimension: default_view_dimension(dimension),
             ..Default::default()
         };
         value.resize(size);
@@ -87,17 +108,36 @@ impl Texture {
         value
     }
 
+    /// Creates a texture that a camera can render to, that materials can sample and that can be captured with
+    /// [Screenshots](crate::screenshot::Screenshots). It uses the default
+    /// [TextureFormat] so that it is compatible with the built in pipelines.
+    pub fn new_render_target(size: Extent3d) -> Self {
+        let mut value = Texture {
+            format: TextureFormat::default(),
+            usage: TextureUsage::OUTPUT_ATTACHMENT
+                | TextureUsage::SAMPLED
+                | TextureUsage::COPY_DST
+                | TextureUsage::COPY_SRC,
+            ..Default::default()
+        };
+        value.resize(size);
+        value
+    }
+
     pub fn aspect_2d(&self) -> f32 {
         self.size.height as f32 / self.size.width as f32
     }
 
+    /// Resizes the texture, keeping the data of the first mip level and removing the others
     pub fn resize(&mut self, size: Extent3d) {
         self.size = size;
         self.data
             .resize(size.volume() * self.format.pixel_size(), 0);
+        self.mipmaps.clear();
     }
 
-    /// Changes the `size`, asserting that the total number of data elements (pixels) remains the same.
+    /// Changes the `size`, asserting that the total number of data elements (pixels) remains the same. Mip levels
+    /// after the first are removed.
     pub fn reinterpret_size(&mut self, new_size: Extent3d) {
         assert!(
             new_size.volume() == self.size.volume(),
@@ -107,6 +147,54 @@ impl Texture {
         );
 
         self.size = new_size;
+        self.mipmaps.clear();
+    }
+
+    /// Replaces the data of the first mip level and removes the other levels, which were made from the old data
+    pub fn set_data(&mut self, data: Vec<u8>) {
+        self.data = data;
+        self.mipmaps.clear();
+    }
+
+    /// The number of mip levels, including the first. A level whose data doesn't match its size, like after `data`
+    /// was replaced with an image of another size, is ignored along with the levels after it.
+    pub fn mip_level_count(&self) -> u32 {
+        let valid_mipmaps = self
+            .mipmaps
+            .iter()
+            .enumerate()
+            .take_while(|(index, data)| {
+                data.len()
+                    == self
+                        .format
+                        .image_size(self.mip_level_size(*index as u32 + 1))
+            })
+            .count();
+        1 + valid_mipmaps as u32
+    }
+
+    /// The size of a mip level. Each level halves the width and height, and for [TextureDimension::D3] textures the
+    /// depth, down to 1.
+    pub fn mip_level_size(&self, level: u32) -> Extent3d {
+        Extent3d {
+            width: (self.size.width >> level).max(1),
+            height: (self.size.height >> level).max(1),
+            depth: if self.dimension == TextureDimension::D3 {
+                (self.size.depth >> level).max(1)
+            } else {
+                self.size.depth
+            },
+        }
+    }
+
+    pub fn mip_level_data(&self, level: u32) -> Option<&[u8]> {
+        match level {
+            0 => Some(&self.data),
+            level => self
+                .mipmaps
+                .get(level as usize - 1)
+                .map(|data| data.as_slice()),
+        }
     }
 
     /// Takes a 2D texture containing vertically stacked images of the same size, and reinterprets it as a 2D array texture,
@@ -123,6 +211,7 @@ impl Texture {
             height: self.size.height / layers,
             depth: layers,
         });
+        self.view_dimension = TextureViewDimension::D2Array;
     }
 
     pub fn texture_resource_system(
@@ -191,6 +280,44 @@ impl Texture {
     }
 }
 
+fn default_view_dimension(dimension: TextureDimension) -> TextureViewDimension {
+    match dimension {
+        TextureDimension::D1 => TextureViewDimension::D1,
+        TextureDimension::D2 => TextureViewDimension::D2,
+        TextureDimension::D3 => TextureViewDimension::D3,
+    }
+}
+
+/// Whether the GPU supports compressed texture formats. The renderer sets this when it creates its device, and
+/// texture loaders check it to pass compressed data to the GPU as-is, or to decompress it. Without a renderer, all
+/// formats are considered supported.
+#[derive(Debug, Clone)]
+pub struct CompressedTextureSupport {
+    bc: Arc<AtomicBool>,
+}
+
+impl Default for CompressedTextureSupport {
+    fn default() -> Self {
+        CompressedTextureSupport {
+            bc: Arc::new(AtomicBool::new(true)),
+        }
+    }
+}
+
+impl CompressedTextureSupport {
+    pub fn set_bc(&self, supported: bool) {
+        self.bc.store(supported, Ordering::Relaxed);
+    }
+
+    pub fn bc(&self) -> bool {
+        self.bc.load(Ordering::Relaxed)
+    }
+
+    pub fn supports(&self, format: TextureFormat) -> bool {
+        !format.is_compressed() || self.bc()
+    }
+}
+
 #[derive(Default)]
 pub struct TextureResourceSystemState {
     event_reader: EventReader<AssetEvent<Texture>>,
@@ -1,4 +1,6 @@
-use super::{Extent3d, Texture, TextureDimension, TextureFormat, TextureUsage};
+use super::{
+    Extent3d, Texture, TextureDimension, TextureFormat, TextureUsage, TextureViewDimension,
+};
 
 /// Describes a texture
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
@@ -7,6 +9,9 @@ pub struct TextureDescriptor {
     pub mip_level_count: u32,
     pub sample_count: u32,
     pub dimension: TextureDimension,
+    /// The dimension shaders see the texture as, like [TextureViewDimension::Cube] for a [TextureDimension::D2]
+    /// texture with six layers
+    pub view_dimension: TextureViewDimension,
     pub format: TextureFormat,
     pub usage: TextureUsage,
 }
@@ -15,11 +20,12 @@ impl From<&Texture> for TextureDescriptor {
     fn from(texture: &Texture) -> Self {
         TextureDescriptor {
             size: texture.size,
-            mip_level_count: 1,
+            mip_level_count: texture.mip_level_count(),
             sample_count: 1,
             dimension: texture.dimension,
+            view_dimension: texture.view_dimension,
             format: texture.format,
-            usage: TextureUsage::SAMPLED | TextureUsage::COPY_DST,
+            usage: texture.usage,
         }
     }
 }
@@ -35,6 +41,7 @@ impl Default for TextureDescriptor {
             mip_level_count: 1,
             sample_count: 1,
             dimension: TextureDimension::D2,
+            view_dimension: TextureViewDimension::D2,
             format: TextureFormat::Rgba8UnormSrgb,
             usage: TextureUsage::SAMPLED | TextureUsage::COPY_DST,
         }
@@ -117,6 +117,22 @@ pub enum TextureFormat {
     Depth32Float = 35,
     Depth24Plus = 36,
     Depth24PlusStencil8 = 37,
+
+    // Compressed formats, made of 4x4 blocks. Using them requires GPU support, see `CompressedTextureSupport`.
+    Bc1RgbaUnorm = 38,
+    Bc1RgbaUnormSrgb = 39,
+    Bc2RgbaUnorm = 40,
+    Bc2RgbaUnormSrgb = 41,
+    Bc3RgbaUnorm = 42,
+    Bc3RgbaUnormSrgb = 43,
+    Bc4RUnorm = 44,
+    Bc4RSnorm = 45,
+    Bc5RgUnorm = 46,
+    Bc5RgSnorm = 47,
+    Bc6hRgbUfloat = 48,
+    Bc6hRgbSfloat = 49,
+    Bc7RgbaUnorm = 50,
+    Bc7RgbaUnormSrgb = 51,
 }
 
 impl TextureFormat {
@@ -167,6 +183,22 @@ impl TextureFormat {
             TextureFormat::Rg11b10Float => 4,
             TextureFormat::Depth24Plus => 3, // FIXME is this correct?
             TextureFormat::Depth24PlusStencil8 => 4,
+
+            // compressed formats don't have pixels, this is the size of a block
+            TextureFormat::Bc1RgbaUnorm
+            | TextureFormat::Bc1RgbaUnormSrgb
+            | TextureFormat::Bc4RUnorm
+            | TextureFormat::Bc4RSnorm => 8,
+            TextureFormat::Bc2RgbaUnorm
+            | TextureFormat::Bc2RgbaUnormSrgb
+            | TextureFormat::Bc3RgbaUnorm
+            | TextureFormat::Bc3RgbaUnormSrgb
+            | TextureFormat::Bc5RgUnorm
+            | TextureFormat::Bc5RgSnorm
+            | TextureFormat::Bc6hRgbUfloat
+            | TextureFormat::Bc6hRgbSfloat
+            | TextureFormat::Bc7RgbaUnorm
+            | TextureFormat::Bc7RgbaUnormSrgb => 16,
         };
 
         let components = match self {
@@ -212,6 +244,22 @@ impl TextureFormat {
             | TextureFormat::Depth32Float
             | TextureFormat::Depth24Plus
             | TextureFormat::Depth24PlusStencil8 => 1,
+
+            // compressed formats, the type size is the size of a whole block
+            TextureFormat::Bc1RgbaUnorm
+            | TextureFormat::Bc1RgbaUnormSrgb
+            | TextureFormat::Bc2RgbaUnorm
+            | TextureFormat::Bc2RgbaUnormSrgb
+            | TextureFormat::Bc3RgbaUnorm
+            | TextureFormat::Bc3RgbaUnormSrgb
+            | TextureFormat::Bc4RUnorm
+            | TextureFormat::Bc4RSnorm
+            | TextureFormat::Bc5RgUnorm
+            | TextureFormat::Bc5RgSnorm
+            | TextureFormat::Bc6hRgbUfloat
+            | TextureFormat::Bc6hRgbSfloat
+            | TextureFormat::Bc7RgbaUnorm
+            | TextureFormat::Bc7RgbaUnormSrgb => 1,
         };
 
         PixelInfo {
@@ -224,6 +272,55 @@ impl TextureFormat {
         let info = self.pixel_info();
         info.type_size * info.num_components
     }
+
+    pub fn is_compressed(&self) -> bool {
+        matches!(
+            self,
+            TextureFormat::Bc1RgbaUnorm
+                | TextureFormat::Bc1RgbaUnormSrgb
+                | TextureFormat::Bc2RgbaUnorm
+                | TextureFormat::Bc2RgbaUnormSrgb
+                | TextureFormat::Bc3RgbaUnorm
+                | TextureFormat::Bc3RgbaUnormSrgb
+                | TextureFormat::Bc4RUnorm
+                | TextureFormat::Bc4RSnorm
+                | TextureFormat::Bc5RgUnorm
+                | TextureFormat::Bc5RgSnorm
+                | TextureFormat::Bc6hRgbUfloat
+                | TextureFormat::Bc6hRgbSfloat
+                | TextureFormat::Bc7RgbaUnorm
+                | TextureFormat::Bc7RgbaUnormSrgb
+        )
+    }
+
+    pub fn is_srgb(&self) -> bool {
+        matches!(
+            self,
+            TextureFormat::Rgba8UnormSrgb
+                | TextureFormat::Bgra8UnormSrgb
+                | TextureFormat::Bc1RgbaUnormSrgb
+                | TextureFormat::Bc2RgbaUnormSrgb
+                | TextureFormat::Bc3RgbaUnormSrgb
+                | TextureFormat::Bc7RgbaUnormSrgb
+        )
+    }
+
+    /// The width and height of the blocks the format stores texels in. Uncompressed formats have 1x1 blocks.
+    pub fn block_dimensions(&self) -> (u32, u32) {
+        if self.is_compressed() {
+            (4, 4)
+        } else {
+            (1, 1)
+        }
+    }
+
+    /// The number of bytes a `width` x `height` x `depth` image takes up in this format
+    pub fn image_size(&self, size: Extent3d) -> usize {
+        let (block_width, block_height) = self.block_dimensions();
+        let blocks_wide = (size.width + block_width - 1) / block_width;
+        let blocks_high = (size.height + block_height - 1) / block_height;
+        blocks_wide as usize * blocks_high as usize * size.depth as usize * self.pixel_size()
+    }
 }
 
 impl Default for TextureFormat {
@@ -0,0 +1,302 @@
+use super::{tag, zigzag_decode, BinaryError, MAGIC, VERSION};
+use serde::de::{self, DeserializeSeed, IntoDeserializer, Visitor};
+use std::convert::TryFrom;
+
+/// Reads serde values from the binary scene format
+pub struct BinaryDeserializer<'de> {
+    input: &'de [u8],
+    strings: Vec<String>,
+}
+
+impl<'de> BinaryDeserializer<'de> {
+    /// Validates the header and reads the string table of `bytes`
+    pub fn from_bytes(bytes: &'de [u8]) -> Result<Self, BinaryError> {
+        if bytes.len() < MAGIC.len() + 1
+            || &bytes[..MAGIC.len()] != MAGIC
+            || bytes[MAGIC.len()] != VERSION
+        {
+            return Err(BinaryError::InvalidHeader);
+        }
+
+        let mut deserializer = BinaryDeserializer {
+            input: &bytes[MAGIC.len() + 1..],
+            strings: Vec::new(),
+        };
+        let count = deserializer.read_varint()?;
+        for _ in 0..count {
+            let len = deserializer.read_uint::<usize>()?;
+            let bytes = deserializer.read_bytes(len)?;
+            let string = String::from_utf8(bytes.to_vec()).map_err(|_| BinaryError::InvalidUtf8)?;
+            deserializer.strings.push(string);
+        }
+
+        Ok(deserializer)
+    }
+
+    /// Fails if there is data left after the deserialized value
+    pub fn end(&self) -> Result<(), BinaryError> {
+        if self.input.is_empty() {
+            Ok(())
+        } else {
+            Err(BinaryError::TrailingBytes(self.input.len()))
+        }
+    }
+
+    fn read_byte(&mut self) -> Result<u8, BinaryError> {
+        let (byte, rest) = self.input.split_first().ok_or(BinaryError::UnexpectedEnd)?;
+        self.input = rest;
+        Ok(*byte)
+    }
+
+    fn read_bytes(&mut self, len: usize) -> Result<&'de [u8], BinaryError> {
+        if self.input.len() < len {
+            return Err(BinaryError::UnexpectedEnd);
+        }
+        let (bytes, rest) = self.input.split_at(len);
+        self.input = rest;
+        Ok(bytes)
+    }
+
+    fn read_varint(&mut self) -> Result<u64, BinaryError> {
+        let mut value = 0;
+        let mut shift = 0;
+        loop {
+            if shift >= 64 {
+                return Err(BinaryError::InvalidVarint);
+            }
+            let byte = self.read_byte()?;
+            value |= ((byte & 0x7f) as u64) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+            shift += 7;
+        }
+    }
+
+    fn read_uint<T: TryFrom<u64>>(&mut self) -> Result<T, BinaryError> {
+        let value = self.read_varint()?;
+        T::try_from(value).map_err(|_| BinaryError::IntegerOutOfRange(i128::from(value)))
+    }
+
+    fn read_int<T: TryFrom<i64>>(&mut self) -> Result<T, BinaryError> {
+        let value = zigzag_decode(self.read_varint()?);
+        T::try_from(value).map_err(|_| BinaryError::IntegerOutOfRange(i128::from(value)))
+    }
+
+    fn read_string(&mut self) -> Result<&str, BinaryError> {
+        let index = self.read_varint()?;
+        let strings = &self.strings;
+        usize::try_from(index)
+            .ok()
+            .and_then(|index| strings.get(index))
+            .map(|string| string.as_str())
+            .ok_or(BinaryError::InvalidStringIndex(index))
+    }
+}
+
+impl<'de> de::Deserializer<'de> for &mut BinaryDeserializer<'de> {
+    type Error = BinaryError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, BinaryError> {
+        let value_tag = self.read_byte()?;
+        match value_tag {
+            tag::UNIT => visitor.visit_unit(),
+            tag::FALSE => visitor.visit_bool(false),
+            tag::TRUE => visitor.visit_bool(true),
+            tag::I8 => visitor.visit_i8(self.read_byte()? as i8),
+            tag::I16 => visitor.visit_i16(self.read_int()?),
+            tag::I32 => visitor.visit_i32(self.read_int()?),
+            tag::I64 => visitor.visit_i64(self.read_int()?),
+            tag::U8 => visitor.visit_u8(self.read_byte()?),
+            tag::U16 => visitor.visit_u16(self.read_uint()?),
+            tag::U32 => visitor.visit_u32(self.read_uint()?),
+            tag::U64 => visitor.visit_u64(self.read_varint()?),
+            tag::F32 => {
+                let mut bytes = [0; 4];
+                bytes.copy_from_slice(self.read_bytes(4)?);
+                visitor.visit_f32(f32::from_le_bytes(bytes))
+            }
+            tag::F64 => {
+                let mut bytes = [0; 8];
+                bytes.copy_from_slice(self.read_bytes(8)?);
+                visitor.visit_f64(f64::from_le_bytes(bytes))
+            }
+            tag::CHAR => {
+                let value = self.read_uint()?;
+                let value = std::char::from_u32(value).ok_or(BinaryError::InvalidChar(value))?;
+                visitor.visit_char(value)
+            }
+            tag::STR => visitor.visit_str(self.read_string()?),
+            tag::BYTES => {
+                let len = self.read_uint::<usize>()?;
+                visitor.visit_borrowed_bytes(self.read_bytes(len)?)
+            }
+            tag::NONE => visitor.visit_none(),
+            tag::SOME => visitor.visit_some(self),
+            tag::SEQ => {
+                let remaining = self.read_uint::<usize>()?;
+                visitor.visit_seq(Access {
+                    de: self,
+                    remaining,
+                })
+            }
+            tag::MAP => {
+                let remaining = self.read_uint::<usize>()?;
+                visitor.visit_map(Access {
+                    de: self,
+                    remaining,
+                })
+            }
+            tag::UNIT_VARIANT | tag::NEWTYPE_VARIANT | tag::TUPLE_VARIANT | tag::STRUCT_VARIANT => {
+                visitor.visit_enum(Enum {
+                    de: self,
+                    variant_tag: value_tag,
+                })
+            }
+            _ => Err(BinaryError::InvalidTag(value_tag)),
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, BinaryError> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct seq tuple
+        tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+/// The elements of a sequence or the entries of a map
+struct Access<'a, 'de> {
+    de: &'a mut BinaryDeserializer<'de>,
+    remaining: usize,
+}
+
+impl<'de, 'a> de::SeqAccess<'de> for Access<'a, 'de> {
+    type Error = BinaryError;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, BinaryError> {
+        if self.remaining == 0 {
+            return Ok(None);
+        }
+        self.remaining -= 1;
+        seed.deserialize(&mut *self.de).map(Some)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.remaining)
+    }
+}
+
+impl<'de, 'a> de::MapAccess<'de> for Access<'a, 'de> {
+    type Error = BinaryError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, BinaryError> {
+        if self.remaining == 0 {
+            return Ok(None);
+        }
+        self.remaining -= 1;
+        seed.deserialize(&mut *self.de).map(Some)
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> Result<V::Value, BinaryError> {
+        seed.deserialize(&mut *self.de)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.remaining)
+    }
+}
+
+struct Enum<'a, 'de> {
+    de: &'a mut BinaryDeserializer<'de>,
+    variant_tag: u8,
+}
+
+impl<'de, 'a> de::EnumAccess<'de> for Enum<'a, 'de> {
+    type Error = BinaryError;
+    type Variant = Self;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(
+        self,
+        seed: V,
+    ) -> Result<(V::Value, Self), BinaryError> {
+        let name = self.de.read_string()?;
+        let value = seed.deserialize(name.into_deserializer())?;
+        Ok((value, self))
+    }
+}
+
+impl<'de, 'a> de::VariantAccess<'de> for Enum<'a, 'de> {
+    type Error = BinaryError;
+
+    fn unit_variant(self) -> Result<(), BinaryError> {
+        match self.variant_tag {
+            tag::UNIT_VARIANT => Ok(()),
+            _ => Err(de::Error::custom("expected a unit variant")),
+        }
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(
+        self,
+        seed: T,
+    ) -> Result<T::Value, BinaryError> {
+        match self.variant_tag {
+            tag::NEWTYPE_VARIANT => seed.deserialize(self.de),
+            _ => Err(de::Error::custom("expected a newtype variant")),
+        }
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, BinaryError> {
+        match self.variant_tag {
+            tag::TUPLE_VARIANT => {
+                let remaining = self.de.read_uint::<usize>()?;
+                visitor.visit_seq(Access {
+                    de: self.de,
+                    remaining,
+                })
+            }
+            _ => Err(de::Error::custom("expected a tuple variant")),
+        }
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, BinaryError> {
+        match self.variant_tag {
+            tag::STRUCT_VARIANT => {
+                let remaining = self.de.read_uint::<usize>()?;
+                visitor.visit_map(Access {
+                    de: self.de,
+                    remaining,
+                })
+            }
+            _ => Err(de::Error::custom("expected a struct variant")),
+        }
+    }
+}
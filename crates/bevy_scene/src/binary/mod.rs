@@ -0,0 +1,274 @@
+//! A compact binary encoding for scenes and other serde values.
+//!
+//! The format is self-describing like RON, so anything that can be read from a `.scn` file (including
+//! [ReflectDeserializer](bevy_reflect::serde::ReflectDeserializer) values) can be read from the binary encoding.
+//! Every string, most importantly the type names and field names written by reflection, is stored once in a
+//! string table at the start of the file and referenced by index afterwards.
+//!
+//! Layout: the magic bytes `BSCN`, a version byte, the string table (a varint count followed by varint length
+//! prefixed UTF-8 strings) and finally the tagged value.
+
+mod de;
+mod ser;
+
+pub use de::*;
+pub use ser::*;
+
+use serde::{de::DeserializeSeed, Deserialize, Serialize};
+use thiserror::Error;
+
+pub const MAGIC: &[u8; 4] = b"BSCN";
+pub const VERSION: u8 = 1;
+
+pub(crate) mod tag {
+    pub const UNIT: u8 = 0;
+    pub const FALSE: u8 = 1;
+    pub const TRUE: u8 = 2;
+    pub const I8: u8 = 3;
+    pub const I16: u8 = 4;
+    pub const I32: u8 = 5;
+    pub const I64: u8 = 6;
+    pub const U8: u8 = 7;
+    pub const U16: u8 = 8;
+    pub const U32: u8 = 9;
+    pub const U64: u8 = 10;
+    pub const F32: u8 = 11;
+    pub const F64: u8 = 12;
+    pub const CHAR: u8 = 13;
+    pub const STR: u8 = 14;
+    pub const BYTES: u8 = 15;
+    pub const NONE: u8 = 16;
+    pub const SOME: u8 = 17;
+    pub const SEQ: u8 = 18;
+    pub const MAP: u8 = 19;
+    pub const UNIT_VARIANT: u8 = 20;
+    pub const NEWTYPE_VARIANT: u8 = 21;
+    pub const TUPLE_VARIANT: u8 = 22;
+    pub const STRUCT_VARIANT: u8 = 23;
+}
+
+#[derive(Error, Debug)]
+pub enum BinaryError {
+    #[error("{0}")]
+    Message(String),
+    #[error("data is not a binary scene or was written by an unsupported version")]
+    InvalidHeader,
+    #[error("unexpected end of data")]
+    UnexpectedEnd,
+    #[error("invalid value tag {0}")]
+    InvalidTag(u8),
+    #[error("string index {0} is not in the string table")]
+    InvalidStringIndex(u64),
+    #[error("string table contains invalid UTF-8")]
+    InvalidUtf8,
+    #[error("invalid char {0}")]
+    InvalidChar(u32),
+    #[error("integer {0} is out of range for its type")]
+    IntegerOutOfRange(i128),
+    #[error("varint is too long")]
+    InvalidVarint,
+    #[error("sequences and maps must have a known length")]
+    UnknownLength,
+    #[error("data has {0} trailing bytes")]
+    TrailingBytes(usize),
+}
+
+impl serde::ser::Error for BinaryError {
+    fn custom<T: std::fmt::Display>(msg: T) -> Self {
+        BinaryError::Message(msg.to_string())
+    }
+}
+
+impl serde::de::Error for BinaryError {
+    fn custom<T: std::fmt::Display>(msg: T) -> Self {
+        BinaryError::Message(msg.to_string())
+    }
+}
+
+pub fn to_bytes<T: Serialize + ?Sized>(value: &T) -> Result<Vec<u8>, BinaryError> {
+    let mut serializer = BinarySerializer::default();
+    value.serialize(&mut serializer)?;
+    Ok(serializer.finish())
+}
+
+pub fn from_bytes<'de, T: Deserialize<'de>>(bytes: &'de [u8]) -> Result<T, BinaryError> {
+    from_bytes_seed(std::marker::PhantomData, bytes)
+}
+
+pub fn from_bytes_seed<'de, T: DeserializeSeed<'de>>(
+    seed: T,
+    bytes: &'de [u8],
+) -> Result<T::Value, BinaryError> {
+    let mut deserializer = BinaryDeserializer::from_bytes(bytes)?;
+    let value = seed.deserialize(&mut deserializer)?;
+    deserializer.end()?;
+    Ok(value)
+}
+
+pub(crate) fn write_varint(output: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        output.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    output.push(value as u8);
+}
+
+fn zigzag_encode(value: i64) -> u64 {
+    ((value << 1) ^ (value >> 63)) as u64
+}
+
+fn zigzag_decode(value: u64) -> i64 {
+    (value >> 1) as i64 ^ -((value & 1) as i64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DynamicScene;
+    use bevy_ecs::World;
+    use bevy_reflect::{
+        serde::{ReflectDeserializer, ReflectSerializer},
+        Reflect, ReflectComponent, TypeRegistry, TypeRegistryArc,
+    };
+    use bevy_utils::HashMap;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    enum Shape {
+        Empty,
+        Circle(f32),
+        Rect(f32, f32),
+        Polygon { points: Vec<(i32, i32)> },
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Level {
+        name: String,
+        id: u64,
+        offset: i16,
+        visible: bool,
+        tag: Option<char>,
+        shapes: Vec<Shape>,
+        scores: HashMap<String, f64>,
+    }
+
+    #[test]
+    fn serde_round_trip() {
+        let mut scores = HashMap::default();
+        scores.insert("first".to_string(), 1.5);
+        let level = Level {
+            name: "level".to_string(),
+            id: u64::MAX,
+            offset: -300,
+            visible: true,
+            tag: Some('x'),
+            shapes: vec![
+                Shape::Empty,
+                Shape::Circle(2.0),
+                Shape::Rect(1.0, 3.0),
+                Shape::Polygon {
+                    points: vec![(0, 0), (-1, 5)],
+                },
+            ],
+            scores,
+        };
+
+        let bytes = to_bytes(&level).unwrap();
+        assert_eq!(&bytes[..4], MAGIC);
+        assert_eq!(from_bytes::<Level>(&bytes).unwrap(), level);
+
+        assert!(matches!(
+            from_bytes::<Level>(&bytes[..bytes.len() - 1]),
+            Err(BinaryError::UnexpectedEnd)
+        ));
+        assert!(matches!(
+            from_bytes::<Level>(b"RON("),
+            Err(BinaryError::InvalidHeader)
+        ));
+    }
+
+    #[test]
+    fn integers_out_of_range() {
+        let mut bytes = MAGIC.to_vec();
+        bytes.push(VERSION);
+        // empty string table
+        bytes.push(0);
+        bytes.push(tag::U16);
+        write_varint(&mut bytes, 70000);
+        assert!(matches!(
+            from_bytes::<u16>(&bytes),
+            Err(BinaryError::IntegerOutOfRange(70000))
+        ));
+
+        let i32_bytes = to_bytes(&i32::MIN).unwrap();
+        assert_eq!(from_bytes::<i32>(&i32_bytes).unwrap(), i32::MIN);
+        let mut bytes = i32_bytes;
+        bytes[MAGIC.len() + 2] = tag::I16;
+        assert!(matches!(
+            from_bytes::<i16>(&bytes),
+            Err(BinaryError::IntegerOutOfRange(value)) if value == i32::MIN as i128
+        ));
+    }
+
+    #[derive(Reflect)]
+    struct Foo {
+        a: u32,
+        b: Vec<String>,
+        c: (u16, i8),
+    }
+
+    #[test]
+    fn reflect_round_trip() {
+        let mut registry = TypeRegistry::default();
+        registry.register::<u32>();
+        registry.register::<i8>();
+        registry.register::<u16>();
+        registry.register::<String>();
+        registry.register::<Foo>();
+
+        let foo = Foo {
+            a: 1,
+            b: vec!["x".to_string(), "y".to_string()],
+            c: (2, -3),
+        };
+        let serializer = ReflectSerializer::new(&foo, &registry);
+        let bytes = to_bytes(&serializer).unwrap();
+
+        // type and field names are only stored once
+        let ron = ron::to_string(&serializer).unwrap();
+        assert!(bytes.len() < ron.len());
+
+        let value = from_bytes_seed(ReflectDeserializer::new(&registry), &bytes).unwrap();
+        assert!(foo.reflect_partial_eq(&*value).unwrap());
+    }
+
+    #[derive(Reflect, Default)]
+    #[reflect(Component)]
+    struct Health {
+        value: u32,
+    }
+
+    #[test]
+    fn convert_scenes() {
+        let registry = TypeRegistryArc::default();
+        {
+            let mut registry = registry.write();
+            registry.register::<u32>();
+            registry.register::<Health>();
+        }
+        let mut world = World::default();
+        world.spawn((Health { value: 5 },));
+        world.spawn((Health { value: 7 },));
+        let scene = DynamicScene::from_world(&world, &registry);
+        let ron = scene.serialize_ron(&registry).unwrap();
+        let binary = scene.serialize_binary(&registry).unwrap();
+        assert!(binary.len() < ron.len());
+
+        // binary to RON
+        let from_binary = DynamicScene::deserialize_binary(&binary, &registry.read()).unwrap();
+        assert_eq!(from_binary.serialize_ron(&registry).unwrap(), ron);
+
+        // RON to binary
+        let from_ron = DynamicScene::deserialize_ron(ron.as_bytes(), &registry.read()).unwrap();
+        assert_eq!(from_ron.serialize_binary(&registry).unwrap(), binary);
+    }
+}
@@ -0,0 +1,361 @@
+use super::{tag, write_varint, zigzag_encode, BinaryError, MAGIC, VERSION};
+use bevy_utils::HashMap;
+use serde::{ser, Serialize};
+
+/// Writes serde values in the binary scene format. Call [BinarySerializer::finish] once the value has been
+/// serialized to get the encoded bytes.
+#[derive(Default)]
+pub struct BinarySerializer {
+    output: Vec<u8>,
+    strings: Vec<String>,
+    string_indices: HashMap<String, u64>,
+}
+
+impl BinarySerializer {
+    /// Returns the header and string table followed by everything that has been serialized
+    pub fn finish(self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(self.output.len() + 5);
+        bytes.extend_from_slice(MAGIC);
+        bytes.push(VERSION);
+        write_varint(&mut bytes, self.strings.len() as u64);
+        for string in self.strings.iter() {
+            write_varint(&mut bytes, string.len() as u64);
+            bytes.extend_from_slice(string.as_bytes());
+        }
+        bytes.extend_from_slice(&self.output);
+        bytes
+    }
+
+    fn write_tag(&mut self, tag: u8) {
+        self.output.push(tag);
+    }
+
+    fn write_varint(&mut self, value: u64) {
+        write_varint(&mut self.output, value);
+    }
+
+    fn write_len(&mut self, len: Option<usize>) -> Result<(), BinaryError> {
+        let len = len.ok_or(BinaryError::UnknownLength)?;
+        self.write_varint(len as u64);
+        Ok(())
+    }
+
+    /// Writes the index of `value` in the string table, adding it to the table if necessary
+    fn write_string_index(&mut self, value: &str) {
+        let index = match self.string_indices.get(value) {
+            Some(index) => *index,
+            None => {
+                let index = self.strings.len() as u64;
+                self.strings.push(value.to_string());
+                self.string_indices.insert(value.to_string(), index);
+                index
+            }
+        };
+        self.write_varint(index);
+    }
+}
+
+impl ser::Serializer for &mut BinarySerializer {
+    type Ok = ();
+    type Error = BinaryError;
+    type SerializeSeq = Self;
+    type SerializeTuple = Self;
+    type SerializeTupleStruct = Self;
+    type SerializeTupleVariant = Self;
+    type SerializeMap = Self;
+    type SerializeStruct = Self;
+    type SerializeStructVariant = Self;
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+
+    fn serialize_bool(self, v: bool) -> Result<(), BinaryError> {
+        self.write_tag(if v { tag::TRUE } else { tag::FALSE });
+        Ok(())
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<(), BinaryError> {
+        self.write_tag(tag::I8);
+        self.output.push(v as u8);
+        Ok(())
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<(), BinaryError> {
+        self.write_tag(tag::I16);
+        self.write_varint(zigzag_encode(v as i64));
+        Ok(())
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<(), BinaryError> {
+        self.write_tag(tag::I32);
+        self.write_varint(zigzag_encode(v as i64));
+        Ok(())
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<(), BinaryError> {
+        self.write_tag(tag::I64);
+        self.write_varint(zigzag_encode(v));
+        Ok(())
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<(), BinaryError> {
+        self.write_tag(tag::U8);
+        self.output.push(v);
+        Ok(())
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<(), BinaryError> {
+        self.write_tag(tag::U16);
+        self.write_varint(v as u64);
+        Ok(())
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<(), BinaryError> {
+        self.write_tag(tag::U32);
+        self.write_varint(v as u64);
+        Ok(())
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<(), BinaryError> {
+        self.write_tag(tag::U64);
+        self.write_varint(v);
+        Ok(())
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<(), BinaryError> {
+        self.write_tag(tag::F32);
+        self.output.extend_from_slice(&v.to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<(), BinaryError> {
+        self.write_tag(tag::F64);
+        self.output.extend_from_slice(&v.to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_char(self, v: char) -> Result<(), BinaryError> {
+        self.write_tag(tag::CHAR);
+        self.write_varint(v as u64);
+        Ok(())
+    }
+
+    fn serialize_str(self, v: &str) -> Result<(), BinaryError> {
+        self.write_tag(tag::STR);
+        self.write_string_index(v);
+        Ok(())
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<(), BinaryError> {
+        self.write_tag(tag::BYTES);
+        self.write_varint(v.len() as u64);
+        self.output.extend_from_slice(v);
+        Ok(())
+    }
+
+    fn serialize_none(self) -> Result<(), BinaryError> {
+        self.write_tag(tag::NONE);
+        Ok(())
+    }
+
+    fn serialize_some<T: ?Sized + Serialize>(self, value: &T) -> Result<(), BinaryError> {
+        self.write_tag(tag::SOME);
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<(), BinaryError> {
+        self.write_tag(tag::UNIT);
+        Ok(())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<(), BinaryError> {
+        self.serialize_unit()
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<(), BinaryError> {
+        self.write_tag(tag::UNIT_VARIANT);
+        self.write_string_index(variant);
+        Ok(())
+    }
+
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<(), BinaryError> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<(), BinaryError> {
+        self.write_tag(tag::NEWTYPE_VARIANT);
+        self.write_string_index(variant);
+        value.serialize(self)
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<Self, BinaryError> {
+        self.write_tag(tag::SEQ);
+        self.write_len(len)?;
+        Ok(self)
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<Self, BinaryError> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(self, _name: &'static str, len: usize) -> Result<Self, BinaryError> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self, BinaryError> {
+        self.write_tag(tag::TUPLE_VARIANT);
+        self.write_string_index(variant);
+        self.write_varint(len as u64);
+        Ok(self)
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<Self, BinaryError> {
+        self.write_tag(tag::MAP);
+        self.write_len(len)?;
+        Ok(self)
+    }
+
+    fn serialize_struct(self, _name: &'static str, len: usize) -> Result<Self, BinaryError> {
+        self.serialize_map(Some(len))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self, BinaryError> {
+        self.write_tag(tag::STRUCT_VARIANT);
+        self.write_string_index(variant);
+        self.write_varint(len as u64);
+        Ok(self)
+    }
+}
+
+impl ser::SerializeSeq for &mut BinarySerializer {
+    type Ok = ();
+    type Error = BinaryError;
+
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), BinaryError> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), BinaryError> {
+        Ok(())
+    }
+}
+
+impl ser::SerializeTuple for &mut BinarySerializer {
+    type Ok = ();
+    type Error = BinaryError;
+
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), BinaryError> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), BinaryError> {
+        Ok(())
+    }
+}
+
+impl ser::SerializeTupleStruct for &mut BinarySerializer {
+    type Ok = ();
+    type Error = BinaryError;
+
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), BinaryError> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), BinaryError> {
+        Ok(())
+    }
+}
+
+impl ser::SerializeTupleVariant for &mut BinarySerializer {
+    type Ok = ();
+    type Error = BinaryError;
+
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), BinaryError> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), BinaryError> {
+        Ok(())
+    }
+}
+
+impl ser::SerializeMap for &mut BinarySerializer {
+    type Ok = ();
+    type Error = BinaryError;
+
+    fn serialize_key<T: ?Sized + Serialize>(&mut self, key: &T) -> Result<(), BinaryError> {
+        key.serialize(&mut **self)
+    }
+
+    fn serialize_value<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), BinaryError> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), BinaryError> {
+        Ok(())
+    }
+}
+
+impl ser::SerializeStruct for &mut BinarySerializer {
+    type Ok = ();
+    type Error = BinaryError;
+
+    fn serialize_field<T: ?Sized + Serialize>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), BinaryError> {
+        ser::Serializer::serialize_str(&mut **self, key)?;
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), BinaryError> {
+        Ok(())
+    }
+}
+
+impl ser::SerializeStructVariant for &mut BinarySerializer {
+    type Ok = ();
+    type Error = BinaryError;
+
+    fn serialize_field<T: ?Sized + Serialize>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), BinaryError> {
+        ser::Serializer::serialize_str(&mut **self, key)?;
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), BinaryError> {
+        Ok(())
+    }
+}
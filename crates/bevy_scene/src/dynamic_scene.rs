@@ -1,20 +1,67 @@
-use crate::{serde::SceneSerializer, Scene};
+use crate::{
+    binary::{self, BinaryError},
+    scene_spawner::{check_prefabs, spawn_dynamic_scene, InstanceInfo},
+    serde::{SceneDeserializer, SceneSerializer},
+    Scene, SceneFilter, SceneSpawnError,
+};
 use anyhow::Result;
-use bevy_ecs::{EntityMap, Resources, World};
-use bevy_reflect::{Reflect, ReflectComponent, ReflectMapEntities, TypeRegistryArc, TypeUuid};
-use serde::Serialize;
+use bevy_asset::{Assets, HandleId, HandleUntyped};
+use bevy_ecs::{Resources, World};
+use bevy_reflect::{
+    Reflect, ReflectComponent, ReflectMapEntities, ReflectResource, TypeRegistry, TypeRegistryArc,
+    TypeUuid,
+};
+use bevy_transform::prelude::{Parent, PreviousParent};
+use serde::{de::DeserializeSeed, Serialize};
+use std::any::TypeId;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
 pub enum DynamicSceneToWorldError {
     #[error("scene contains an unregistered component")]
     UnregisteredComponent { type_name: String },
+    #[error("scene contains an unregistered resource")]
+    UnregisteredResource { type_name: String },
+    #[error("scene references the prefab `{path}`, which is not loaded")]
+    NonExistentPrefab { path: String },
+    #[error("scene patches entity {entity} of the prefab `{path}`, which does not exist")]
+    NonExistentPrefabEntity { path: String, entity: u32 },
+    #[error("the prefab `{path}` references itself, directly or through another prefab")]
+    PrefabCycle { path: String },
+}
+
+impl From<SceneSpawnError> for DynamicSceneToWorldError {
+    fn from(error: SceneSpawnError) -> Self {
+        match error {
+            SceneSpawnError::UnregisteredComponent { type_name }
+            | SceneSpawnError::UnregisteredType { type_name } => {
+                DynamicSceneToWorldError::UnregisteredComponent { type_name }
+            }
+            SceneSpawnError::UnregisteredResource { type_name } => {
+                DynamicSceneToWorldError::UnregisteredResource { type_name }
+            }
+            SceneSpawnError::NonExistentPrefab { path } => {
+                DynamicSceneToWorldError::NonExistentPrefab { path }
+            }
+            SceneSpawnError::NonExistentPrefabEntity { path, entity } => {
+                DynamicSceneToWorldError::NonExistentPrefabEntity { path, entity }
+            }
+            SceneSpawnError::PrefabCycle { path } => DynamicSceneToWorldError::PrefabCycle { path },
+            // scenes are written directly, without looking up a handle
+            SceneSpawnError::NonExistentScene { .. }
+            | SceneSpawnError::NonExistentRealScene { .. } => {
+                unreachable!()
+            }
+        }
+    }
 }
 
 #[derive(Default, TypeUuid)]
 #[uuid = "749479b1-fb8c-4ff8-a775-623aa76014f5"]
 pub struct DynamicScene {
+    pub resources: Vec<Box<dyn Reflect>>,
     pub entities: Vec<Entity>,
+    pub prefabs: Vec<ScenePrefab>,
 }
 
 pub struct Entity {
@@ -22,79 +69,180 @@ pub struct Entity {
     pub components: Vec<Box<dyn Reflect>>,
 }
 
+/// An instance of another scene asset that is spawned along with the scene referencing it
+pub struct ScenePrefab {
+    /// The asset path of the referenced scene
+    pub path: String,
+    /// A handle to the referenced [DynamicScene]. It is untyped because a scene can't hold a typed handle to its own
+    /// asset type.
+    pub handle: HandleUntyped,
+    /// Components that are applied to the entities of the referenced scene with [Reflect::apply]. Components that
+    /// an entity doesn't have yet are added to it.
+    pub patches: Vec<Entity>,
+}
+
+impl ScenePrefab {
+    /// Creates a prefab with a weak handle to the scene at `path`. The [SceneLoader](crate::SceneLoader) replaces
+    /// it with a strong handle, which loads the referenced scene.
+    pub fn new(path: String, patches: Vec<Entity>) -> Self {
+        ScenePrefab {
+            handle: HandleUntyped::weak(HandleId::from(path.as_str())),
+            path,
+            patches,
+        }
+    }
+}
+
 impl DynamicScene {
     pub fn from_scene(scene: &Scene, type_registry: &TypeRegistryArc) -> Self {
         Self::from_world(&scene.world, type_registry)
     }
 
     pub fn from_world(world: &World, type_registry: &TypeRegistryArc) -> Self {
+        Self::from_world_filtered(world, &SceneFilter::default(), type_registry)
+    }
+
+    /// Creates a scene from the entities and components of `world` that are selected by `filter`
+    pub fn from_world_filtered(
+        world: &World,
+        filter: &SceneFilter,
+        type_registry: &TypeRegistryArc,
+    ) -> Self {
         let mut scene = DynamicScene::default();
         let type_registry = type_registry.read();
+        let captured_entities = filter.captured_entities(world);
         for archetype in world.archetypes() {
-            let mut entities = Vec::new();
             for (index, entity) in archetype.iter_entities().enumerate() {
-                if index == entities.len() {
-                    entities.push(Entity {
-                        entity: entity.id(),
-                        components: Vec::new(),
-                    })
+                let mut detached_root = false;
+                if let Some(captured_entities) = captured_entities.as_ref() {
+                    if !captured_entities.contains(entity) {
+                        continue;
+                    }
+                    detached_root = world
+                        .get::<Parent>(*entity)
+                        .map_or(false, |parent| !captured_entities.contains(&parent.0));
                 }
+
+                let mut scene_entity = Entity {
+                    entity: entity.id(),
+                    components: Vec::new(),
+                };
                 for type_info in archetype.types() {
+                    if !filter.allows_component(type_info.id()) {
+                        continue;
+                    }
+                    // the parent of a root isn't part of the scene, so it can't be mapped when the scene is spawned
+                    if detached_root
+                        && (type_info.id() == TypeId::of::<Parent>()
+                            || type_info.id() == TypeId::of::<PreviousParent>())
+                    {
+                        continue;
+                    }
                     if let Some(registration) = type_registry.get(type_info.id()) {
                         if let Some(reflect_component) = registration.data::<ReflectComponent>() {
                             // SAFE: the index comes directly from a currently live component
                             unsafe {
                                 let component =
                                     reflect_component.reflect_component(&archetype, index);
-                                entities[index].components.push(component.clone_value());
+                                scene_entity.components.push(component.clone_value());
                             }
                         }
                     }
                 }
-            }
 
-            scene.entities.extend(entities.drain(..));
+                scene.entities.push(scene_entity);
+            }
         }
 
         scene
     }
 
+    /// Creates a scene from the entities of `world` and every resource whose type is registered with
+    /// [ReflectResource] (ex: `#[reflect(Resource)]`)
+    pub fn from_world_with_resources(
+        world: &World,
+        resources: &Resources,
+        type_registry: &TypeRegistryArc,
+    ) -> Self {
+        let mut scene = Self::from_world(world, type_registry);
+        scene.capture_resources(resources, type_registry);
+        scene
+    }
+
+    /// Adds every resource whose type is registered with [ReflectResource] to the scene
+    pub fn capture_resources(&mut self, resources: &Resources, type_registry: &TypeRegistryArc) {
+        let type_registry = type_registry.read();
+        for registration in type_registry.iter() {
+            if let Some(reflect_resource) = registration.data::<ReflectResource>() {
+                if let Some(resource) = reflect_resource.clone_resource(resources) {
+                    self.resources.push(resource);
+                }
+            }
+        }
+    }
+
+    /// Inserts the scene's resources, or patches them if they already exist, and spawns the scene's entities
+    pub fn write_to_world_with_resources(
+        &self,
+        world: &mut World,
+        resources: &mut Resources,
+    ) -> Result<(), DynamicSceneToWorldError> {
+        let mut scene_resources = Vec::new();
+        self.write_entities(world, resources, Some(&mut scene_resources))?;
+
+        let type_registry = (*resources.get::<TypeRegistryArc>().unwrap()).clone();
+        let type_registry = type_registry.read();
+        for resource in scene_resources {
+            let reflect_resource = type_registry
+                .get_with_name(resource.type_name())
+                .and_then(|registration| registration.data::<ReflectResource>())
+                .ok_or_else(|| DynamicSceneToWorldError::UnregisteredResource {
+                    type_name: resource.type_name().to_string(),
+                })?;
+            reflect_resource.insert_resource(resources, &*resource);
+        }
+        Ok(())
+    }
+
+    /// Spawns the scene's entities. Use [DynamicScene::write_to_world_with_resources] to also insert its resources.
     pub fn write_to_world(
         &self,
         world: &mut World,
         resources: &Resources,
+    ) -> Result<(), DynamicSceneToWorldError> {
+        self.write_entities(world, resources, None)
+    }
+
+    fn write_entities(
+        &self,
+        world: &mut World,
+        resources: &Resources,
+        scene_resources: Option<&mut Vec<Box<dyn Reflect>>>,
     ) -> Result<(), DynamicSceneToWorldError> {
         let type_registry = resources.get::<TypeRegistryArc>().unwrap();
         let type_registry = type_registry.read();
-        let mut entity_map = EntityMap::default();
-        for scene_entity in self.entities.iter() {
-            let new_entity = world.reserve_entity();
-            entity_map.insert(bevy_ecs::Entity::new(scene_entity.entity), new_entity);
-            for component in scene_entity.components.iter() {
-                let registration = type_registry
-                    .get_with_name(component.type_name())
-                    .ok_or_else(|| DynamicSceneToWorldError::UnregisteredComponent {
-                        type_name: component.type_name().to_string(),
-                    })?;
-                let reflect_component =
-                    registration.data::<ReflectComponent>().ok_or_else(|| {
-                        DynamicSceneToWorldError::UnregisteredComponent {
-                            type_name: component.type_name().to_string(),
-                        }
-                    })?;
-                if world.has_component_type(new_entity, registration.type_id()) {
-                    reflect_component.apply_component(world, new_entity, &**component);
-                } else {
-                    reflect_component.add_component(world, resources, new_entity, &**component);
-                }
-            }
-        }
+        let scenes = resources.get::<Assets<DynamicScene>>();
+        let scenes = scenes.as_deref();
+        check_prefabs(scenes, self, &mut Vec::new())?;
+        let mut instance_info = InstanceInfo::new();
+        spawn_dynamic_scene(
+            world,
+            resources,
+            &type_registry,
+            scenes,
+            self,
+            &mut instance_info,
+            scene_resources,
+        )?;
 
-        for registration in type_registry.iter() {
-            if let Some(map_entities_reflect) = registration.data::<ReflectMapEntities>() {
-                map_entities_reflect
-                    .map_entities(world, &entity_map)
-                    .unwrap();
+        // every scene and prefab has its own entity ids
+        for entity_map in instance_info.entity_maps() {
+            for registration in type_registry.iter() {
+                if let Some(map_entities_reflect) = registration.data::<ReflectMapEntities>() {
+                    map_entities_reflect
+                        .map_entities(world, entity_map)
+                        .unwrap();
+                }
             }
         }
 
@@ -106,6 +254,29 @@ impl DynamicScene {
         serialize_ron(SceneSerializer::new(self, registry))
     }
 
+    /// Serializes the scene in the compact [binary] format, which is read by the `.scnb` loader
+    pub fn serialize_binary(&self, registry: &TypeRegistryArc) -> Result<Vec<u8>, BinaryError> {
+        binary::to_bytes(&SceneSerializer::new(self, registry))
+    }
+
+    pub fn deserialize_binary(
+        bytes: &[u8],
+        type_registry: &TypeRegistry,
+    ) -> Result<DynamicScene, BinaryError> {
+        binary::from_bytes_seed(SceneDeserializer { type_registry }, bytes)
+    }
+
+    /// Reads a scene written by [DynamicScene::serialize_ron]. Together with the binary functions this converts
+    /// between the two formats, e.g. to diff binary scenes as text.
+    pub fn deserialize_ron(
+        bytes: &[u8],
+        type_registry: &TypeRegistry,
+    ) -> Result<DynamicScene, ron::Error> {
+        let mut deserializer = ron::de::Deserializer::from_bytes(bytes)?;
+        SceneDeserializer { type_registry }.deserialize(&mut deserializer)
+    }
+
+    /// Creates a [Scene] from the scene's entities. [Scene]s don't hold resources, so the scene's resources are ignored.
     pub fn get_scene(&self, resources: &Resources) -> Result<Scene, DynamicSceneToWorldError> {
         let mut world = World::default();
         self.write_to_world(&mut world, resources)?;
@@ -1,6 +1,8 @@
+pub mod binary;
 mod command;
 mod dynamic_scene;
 mod scene;
+mod scene_filter;
 mod scene_loader;
 mod scene_spawner;
 pub mod serde;
@@ -9,12 +11,14 @@ use bevy_ecs::SystemStage;
 pub use command::*;
 pub use dynamic_scene::*;
 pub use scene::*;
+pub use scene_filter::*;
 pub use scene_loader::*;
 pub use scene_spawner::*;
 
 pub mod prelude {
     pub use crate::{
-        DynamicScene, Scene, SceneSpawner, SpawnSceneAsChildCommands, SpawnSceneCommands,
+        DynamicScene, Scene, SceneFilter, SceneInstanceReady, SceneSpawner,
+        SpawnSceneAsChildCommands, SpawnSceneCommands,
     };
 }
 
@@ -31,7 +35,9 @@ impl Plugin for ScenePlugin {
         app.add_asset::<DynamicScene>()
             .add_asset::<Scene>()
             .init_asset_loader::<SceneLoader>()
+            .init_asset_loader::<BinarySceneLoader>()
             .init_resource::<SceneSpawner>()
+            .add_event::<SceneInstanceReady>()
             .add_stage_after(stage::EVENT, SCENE_STAGE, SystemStage::parallel())
             .add_system_to_stage(SCENE_STAGE, scene_spawner_system);
     }
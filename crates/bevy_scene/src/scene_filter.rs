@@ -0,0 +1,172 @@
+use bevy_ecs::{Component, Entity, World};
+use bevy_transform::prelude::Children;
+use bevy_utils::HashSet;
+use std::any::TypeId;
+
+/// Selects the entities and components that [DynamicScene::from_world_filtered](crate::DynamicScene::from_world_filtered)
+/// captures. The default filter captures everything.
+#[derive(Debug, Clone, Default)]
+pub struct SceneFilter {
+    allowed: HashSet<TypeId>,
+    denied: HashSet<TypeId>,
+    roots: Option<Vec<Entity>>,
+}
+
+impl SceneFilter {
+    /// Only captures components of the allowed types. Can be called multiple times to allow more types.
+    pub fn allow<T: Component>(mut self) -> Self {
+        self.allowed.insert(TypeId::of::<T>());
+        self
+    }
+
+    /// Never captures components of type `T`, even if they are allowed
+    pub fn deny<T: Component>(mut self) -> Self {
+        self.denied.insert(TypeId::of::<T>());
+        self
+    }
+
+    /// Only captures `entity`, its descendants and the entities of other roots. A root's [Parent](bevy_transform::prelude::Parent)
+    /// is not captured if the parent itself isn't captured.
+    pub fn with_root(mut self, entity: Entity) -> Self {
+        self.roots.get_or_insert_with(Vec::new).push(entity);
+        self
+    }
+
+    pub fn allows_component(&self, type_id: TypeId) -> bool {
+        (self.allowed.is_empty() || self.allowed.contains(&type_id))
+            && !self.denied.contains(&type_id)
+    }
+
+    /// Returns the entities selected by the filter's roots, or `None` if all entities are captured
+    pub fn captured_entities(&self, world: &World) -> Option<HashSet<Entity>> {
+        let roots = self.roots.as_ref()?;
+        let mut captured = HashSet::default();
+        let mut to_visit = roots.clone();
+        while let Some(entity) = to_visit.pop() {
+            if captured.insert(entity) {
+                if let Ok(children) = world.get::<Children>(entity) {
+                    to_visit.extend(children.iter().cloned());
+                }
+            }
+        }
+
+        Some(captured)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DynamicScene;
+    use bevy_reflect::{Reflect, ReflectComponent, TypeRegistryArc};
+    use bevy_transform::prelude::Parent;
+
+    #[derive(Reflect, Default)]
+    #[reflect(Component)]
+    struct Health {
+        value: u32,
+    }
+
+    #[derive(Reflect, Default)]
+    #[reflect(Component)]
+    struct Armor {
+        value: u32,
+    }
+
+    fn registry() -> TypeRegistryArc {
+        let registry = TypeRegistryArc::default();
+        {
+            let mut registry = registry.write();
+            registry.register::<u32>();
+            registry.register::<Health>();
+            registry.register::<Armor>();
+            registry.register::<Parent>();
+        }
+        registry
+    }
+
+    /// Describes each captured entity as its id followed by the short type names of its captured components
+    fn captured(world: &World, filter: &SceneFilter) -> Vec<String> {
+        let scene = DynamicScene::from_world_filtered(world, filter, &registry());
+        let mut entities = scene
+            .entities
+            .iter()
+            .map(|entity| {
+                let mut components = entity
+                    .components
+                    .iter()
+                    .map(|component| component.type_name().rsplit("::").next().unwrap())
+                    .collect::<Vec<_>>();
+                components.sort_unstable();
+                (entity.entity, components.join(", "))
+            })
+            .collect::<Vec<_>>();
+        entities.sort_unstable();
+        entities
+            .into_iter()
+            .map(|(entity, components)| format!("{}: {}", entity, components))
+            .collect()
+    }
+
+    #[test]
+    fn filter_components() {
+        let mut world = World::default();
+        let entity = world.spawn((Health { value: 1 }, Armor { value: 2 }));
+
+        assert_eq!(
+            captured(&world, &SceneFilter::default()),
+            vec![format!("{}: Armor, Health", entity.id())]
+        );
+        assert_eq!(
+            captured(&world, &SceneFilter::default().allow::<Health>()),
+            vec![format!("{}: Health", entity.id())]
+        );
+        assert_eq!(
+            captured(&world, &SceneFilter::default().deny::<Health>()),
+            vec![format!("{}: Armor", entity.id())]
+        );
+        // denying wins over allowing
+        let filter = SceneFilter::default()
+            .allow::<Health>()
+            .allow::<Armor>()
+            .deny::<Armor>();
+        assert_eq!(
+            captured(&world, &filter),
+            vec![format!("{}: Health", entity.id())]
+        );
+    }
+
+    #[test]
+    fn filter_hierarchies() {
+        let mut world = World::default();
+        let root = world.spawn((Health { value: 1 },));
+        let child = world.spawn((Health { value: 2 }, Parent(root)));
+        let grandchild = world.spawn((Health { value: 3 }, Parent(child)));
+        let other = world.spawn((Health { value: 4 },));
+        world.insert_one(root, Children::with(&[child])).unwrap();
+        world
+            .insert_one(child, Children::with(&[grandchild]))
+            .unwrap();
+
+        // the parent of the captured root is left out, as it isn't part of the scene
+        let filter = SceneFilter::default().with_root(child);
+        assert_eq!(
+            captured(&world, &filter),
+            vec![
+                format!("{}: Health", child.id()),
+                format!("{}: Health, Parent", grandchild.id()),
+            ]
+        );
+
+        let filter = SceneFilter::default().with_root(root).with_root(other);
+        assert_eq!(
+            captured(&world, &filter),
+            vec![
+                format!("{}: Health", root.id()),
+                format!("{}: Health, Parent", child.id()),
+                format!("{}: Health, Parent", grandchild.id()),
+                format!("{}: Health", other.id()),
+            ]
+        );
+    }
+}
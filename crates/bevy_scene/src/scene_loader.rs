@@ -1,6 +1,6 @@
-use crate::serde::SceneDeserializer;
+use crate::{serde::SceneDeserializer, DynamicScene};
 use anyhow::Result;
-use bevy_asset::{AssetLoader, LoadContext, LoadedAsset};
+use bevy_asset::{AssetLoader, AssetPath, LoadContext, LoadedAsset};
 use bevy_ecs::{FromResources, Resources};
 use bevy_reflect::TypeRegistryArc;
 use bevy_utils::BoxedFuture;
@@ -32,7 +32,7 @@ impl AssetLoader for SceneLoader {
                 type_registry: &*self.type_registry.read(),
             };
             let scene = scene_deserializer.deserialize(&mut deserializer)?;
-            load_context.set_default_asset(LoadedAsset::new(scene));
+            set_scene_asset(scene, load_context);
             Ok(())
         })
     }
@@ -41,3 +41,108 @@ impl AssetLoader for SceneLoader {
         &["scn"]
     }
 }
+
+/// Loads scenes in the [binary](crate::binary) format
+#[derive(Debug)]
+pub struct BinarySceneLoader {
+    type_registry: TypeRegistryArc,
+}
+
+impl FromResources for BinarySceneLoader {
+    fn from_resources(resources: &Resources) -> Self {
+        let type_registry = resources.get::<TypeRegistryArc>().unwrap();
+        BinarySceneLoader {
+            type_registry: (&*type_registry).clone(),
+        }
+    }
+}
+
+impl AssetLoader for BinarySceneLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let scene = DynamicScene::deserialize_binary(bytes, &*self.type_registry.read())?;
+            set_scene_asset(scene, load_context);
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["scnb"]
+    }
+}
+
+fn set_scene_asset(mut scene: DynamicScene, load_context: &mut LoadContext) {
+    // the referenced scenes are loaded as dependencies and kept alive by the prefabs' strong handles
+    let mut dependencies = Vec::new();
+    for prefab in scene.prefabs.iter_mut() {
+        prefab.handle = load_context
+            .get_handle::<_, DynamicScene>(prefab.handle.id)
+            .clone_untyped();
+        dependencies.push(AssetPath::from(prefab.path.as_str()).to_owned());
+    }
+    load_context.set_default_asset(LoadedAsset::new(scene).with_dependencies(dependencies));
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{DynamicScene, ScenePlugin};
+    use bevy_app::App;
+    use bevy_asset::{
+        AssetPlugin, AssetServer, Assets, Handle, LoadState, MemoryAssetIo, DEFAULT_ASSET_SOURCE,
+    };
+    use bevy_ecs::World;
+    use bevy_reflect::{
+        Reflect, ReflectComponent, ReflectPlugin, RegisterTypeBuilder, TypeRegistryArc,
+    };
+    use bevy_tasks::{IoTaskPool, TaskPool};
+    use std::sync::Arc;
+
+    #[derive(Reflect, Default)]
+    #[reflect(Component)]
+    struct Health {
+        value: u32,
+    }
+
+    #[test]
+    fn load_binary_scenes() {
+        let mut app_builder = App::build();
+        app_builder
+            .add_resource(IoTaskPool(TaskPool::new()))
+            .add_plugin(ReflectPlugin)
+            .add_plugin(AssetPlugin)
+            .add_plugin(ScenePlugin)
+            .register_type::<Health>();
+        let mut app = app_builder.app;
+
+        let registry = (*app.resources.get::<TypeRegistryArc>().unwrap()).clone();
+        let mut world = World::default();
+        world.spawn((Health { value: 5 },));
+        let scene = DynamicScene::from_world(&world, &registry);
+        let asset_io = Arc::new(MemoryAssetIo::default());
+        asset_io.insert("level.scnb", scene.serialize_binary(&registry).unwrap());
+        let asset_server = (*app.resources.get::<AssetServer>().unwrap()).clone();
+        asset_server
+            .asset_sources()
+            .mount_arc(DEFAULT_ASSET_SOURCE, asset_io);
+
+        let handle: Handle<DynamicScene> = asset_server.load("level.scnb");
+        for _ in 0..1000 {
+            app.update();
+            if asset_server.get_load_state(&handle) == LoadState::Loaded {
+                break;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(1));
+        }
+
+        let scenes = app.resources.get::<Assets<DynamicScene>>().unwrap();
+        let loaded = scenes.get(&handle).unwrap();
+        assert_eq!(
+            loaded.serialize_ron(&registry).unwrap(),
+            scene.serialize_ron(&registry).unwrap()
+        );
+    }
+}
@@ -1,107 +1,263 @@
 use crate::{DynamicScene, Scene};
 use bevy_app::prelude::*;
-use bevy_asset::{AssetEvent, Assets, Handle};
+use bevy_asset::{AssetEvent, Assets, Handle, HandleId};
 use bevy_ecs::{Entity, EntityMap, Resources, World};
-use bevy_reflect::{ReflectComponent, ReflectMapEntities, TypeRegistryArc};
+use bevy_reflect::{
+    Reflect, ReflectComponent, ReflectMapEntities, ReflectResource, TypeRegistry, TypeRegistryArc,
+};
 use bevy_transform::prelude::Parent;
 use bevy_utils::HashMap;
 use thiserror::Error;
 use uuid::Uuid;
 
 #[derive(Debug)]
-struct InstanceInfo {
+pub(crate) struct InstanceInfo {
     entity_map: EntityMap,
+    roots: Vec<Entity>,
+    /// The entity the instance was spawned as a child of
+    parent: Option<Entity>,
+    /// The instances of the scene's prefabs, in the order of [DynamicScene::prefabs]
+    prefabs: Vec<InstanceInfo>,
 }
 
+impl InstanceInfo {
+    pub(crate) fn new() -> Self {
+        InstanceInfo {
+            entity_map: EntityMap::default(),
+            roots: Vec::new(),
+            parent: None,
+            prefabs: Vec::new(),
+        }
+    }
+
+    /// Returns the entities of the instance, including the entities of its prefabs
+    fn entities(&self) -> Vec<Entity> {
+        let mut entities = self.entity_map.values().collect::<Vec<_>>();
+        for prefab in self.prefabs.iter() {
+            entities.extend(prefab.entities());
+        }
+        entities
+    }
+
+    /// Returns the entity maps of the instance and of its prefabs, which each have their own scene entity ids
+    pub(crate) fn entity_maps(&self) -> Vec<&EntityMap> {
+        let mut entity_maps = vec![&self.entity_map];
+        for prefab in self.prefabs.iter() {
+            entity_maps.extend(prefab.entity_maps());
+        }
+        entity_maps
+    }
+}
+
+/// Identifies a scene instance spawned by the [SceneSpawner]
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
-struct InstanceId(Uuid);
+pub struct InstanceId(Uuid);
 
 impl InstanceId {
-    pub fn new() -> Self {
+    pub(crate) fn new() -> Self {
         InstanceId(Uuid::new_v4())
     }
 }
 
+/// Sent once all entities of a scene instance have been spawned (and parented, for instances spawned as a child)
+#[derive(Debug, Clone)]
+pub struct SceneInstanceReady {
+    pub instance_id: InstanceId,
+}
+
+/// The scene an instance was spawned from
+enum InstanceScene {
+    Dynamic(Handle<DynamicScene>),
+    Scene(Handle<Scene>),
+}
+
 #[derive(Default)]
 pub struct SceneSpawner {
     spawned_scenes: HashMap<Handle<Scene>, Vec<InstanceId>>,
     spawned_dynamic_scenes: HashMap<Handle<DynamicScene>, Vec<InstanceId>>,
     spawned_instances: HashMap<InstanceId, InstanceInfo>,
     scene_asset_event_reader: EventReader<AssetEvent<DynamicScene>>,
-    dynamic_scenes_to_spawn: Vec<Handle<DynamicScene>>,
+    dynamic_scenes_to_spawn: Vec<(Handle<DynamicScene>, InstanceId)>,
     scenes_to_spawn: Vec<(Handle<Scene>, InstanceId)>,
     scenes_to_despawn: Vec<Handle<DynamicScene>>,
+    instances_to_despawn: Vec<InstanceId>,
+    instances_to_respawn: Vec<InstanceId>,
     scenes_with_parent: Vec<(InstanceId, Entity)>,
+    resources_to_insert: Vec<Box<dyn Reflect>>,
+    instances_ready: Vec<InstanceId>,
 }
 
 #[derive(Error, Debug)]
 pub enum SceneSpawnError {
     #[error("scene contains the unregistered component `{type_name}`. consider adding `#[reflect(Component)]` to your type")]
     UnregisteredComponent { type_name: String },
+    #[error("scene contains the unregistered resource `{type_name}`. consider adding `#[reflect(Resource)]` to your type")]
+    UnregisteredResource { type_name: String },
     #[error("scene contains the unregistered type `{type_name}`. consider registering the type using `app.register_type::<T>()`")]
     UnregisteredType { type_name: String },
     #[error("scene does not exist")]
     NonExistentScene { handle: Handle<DynamicScene> },
     #[error("scene does not exist")]
     NonExistentRealScene { handle: Handle<Scene> },
+    #[error("scene references the prefab `{path}`, which is not loaded")]
+    NonExistentPrefab { path: String },
+    #[error("scene patches entity {entity} of the prefab `{path}`, which does not exist")]
+    NonExistentPrefabEntity { path: String, entity: u32 },
+    #[error("the prefab `{path}` references itself, directly or through another prefab")]
+    PrefabCycle { path: String },
 }
 
 impl SceneSpawner {
-    pub fn spawn_dynamic(&mut self, scene_handle: Handle<DynamicScene>) {
-        self.dynamic_scenes_to_spawn.push(scene_handle);
+    pub fn spawn_dynamic(&mut self, scene_handle: Handle<DynamicScene>) -> InstanceId {
+        let instance_id = InstanceId::new();
+        self.dynamic_scenes_to_spawn
+            .push((scene_handle, instance_id));
+        instance_id
     }
 
-    pub fn spawn(&mut self, scene_handle: Handle<Scene>) {
+    pub fn spawn(&mut self, scene_handle: Handle<Scene>) -> InstanceId {
         let instance_id = InstanceId::new();
         self.scenes_to_spawn.push((scene_handle, instance_id));
+        instance_id
     }
 
-    pub fn spawn_as_child(&mut self, scene_handle: Handle<Scene>, parent: Entity) {
+    pub fn spawn_as_child(&mut self, scene_handle: Handle<Scene>, parent: Entity) -> InstanceId {
         let instance_id = InstanceId::new();
         self.scenes_to_spawn.push((scene_handle, instance_id));
         self.scenes_with_parent.push((instance_id, parent));
+        instance_id
     }
 
+    /// Despawns all instances of the scene
     pub fn despawn(&mut self, scene_handle: Handle<DynamicScene>) {
         self.scenes_to_despawn.push(scene_handle);
     }
 
+    /// Despawns the entities of the instance. Instances that haven't been spawned yet won't be spawned anymore.
+    pub fn despawn_instance(&mut self, instance_id: InstanceId) {
+        self.instances_to_despawn.push(instance_id);
+    }
+
+    /// Despawns the entities of the instance and spawns its scene again with the same [InstanceId], as a child of
+    /// the same parent. A [SceneInstanceReady] event is sent once the new entities are spawned. Instances that
+    /// haven't been spawned yet are left as they are.
+    pub fn respawn_instance(&mut self, instance_id: InstanceId) {
+        self.instances_to_respawn.push(instance_id);
+    }
+
     pub fn despawn_sync(
         &mut self,
         world: &mut World,
         scene_handle: Handle<DynamicScene>,
     ) -> Result<(), SceneSpawnError> {
-        if let Some(instance_ids) = self.spawned_dynamic_scenes.get(&scene_handle) {
+        if let Some(instance_ids) = self.spawned_dynamic_scenes.remove(&scene_handle) {
             for instance_id in instance_ids {
-                if let Some(instance) = self.spawned_instances.get(&instance_id) {
-                    for entity in instance.entity_map.values() {
-                        let _ = world.despawn(entity); // Ignore the result, despawn only cares if it exists.
-                    }
-                }
+                self.despawn_instance_sync(world, instance_id);
             }
-
-            self.spawned_dynamic_scenes.remove(&scene_handle);
         }
         Ok(())
     }
 
+    /// Despawns the entities of the instance. Instances that haven't been spawned yet won't be spawned anymore.
+    pub fn despawn_instance_sync(&mut self, world: &mut World, instance_id: InstanceId) {
+        if let Some(instance) = self.spawned_instances.remove(&instance_id) {
+            for entity in instance.entities() {
+                let _ = world.despawn(entity); // Ignore the result, despawn only cares if it exists.
+            }
+        }
+
+        for instance_ids in self.spawned_scenes.values_mut() {
+            instance_ids.retain(|id| *id != instance_id);
+        }
+        for instance_ids in self.spawned_dynamic_scenes.values_mut() {
+            instance_ids.retain(|id| *id != instance_id);
+        }
+        self.dynamic_scenes_to_spawn
+            .retain(|(_, id)| *id != instance_id);
+        self.scenes_to_spawn.retain(|(_, id)| *id != instance_id);
+        self.scenes_with_parent.retain(|(id, _)| *id != instance_id);
+        self.instances_ready.retain(|id| *id != instance_id);
+    }
+
+    /// Despawns the entities of the instance and spawns its scene again with the same [InstanceId]. Instances that
+    /// haven't been spawned yet are left as they are.
+    pub fn respawn_instance_sync(
+        &mut self,
+        world: &mut World,
+        resources: &Resources,
+        instance_id: InstanceId,
+    ) -> Result<(), SceneSpawnError> {
+        match self.despawn_for_respawn(world, instance_id) {
+            Some(InstanceScene::Dynamic(handle)) => {
+                self.spawn_dynamic_sync_internal(world, resources, &handle, instance_id)
+            }
+            Some(InstanceScene::Scene(handle)) => {
+                self.spawn_sync_internal(world, resources, handle, instance_id)
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Despawns the entities of a spawned instance and returns the scene it was spawned from. The instance is
+    /// parented again once it is spawned.
+    fn despawn_for_respawn(
+        &mut self,
+        world: &mut World,
+        instance_id: InstanceId,
+    ) -> Option<InstanceScene> {
+        let parent = self.spawned_instances.get(&instance_id)?.parent;
+        let scene = self
+            .spawned_dynamic_scenes
+            .iter()
+            .find(|(_, instance_ids)| instance_ids.contains(&instance_id))
+            .map(|(handle, _)| InstanceScene::Dynamic(handle.clone()))
+            .or_else(|| {
+                self.spawned_scenes
+                    .iter()
+                    .find(|(_, instance_ids)| instance_ids.contains(&instance_id))
+                    .map(|(handle, _)| InstanceScene::Scene(handle.clone()))
+            })?;
+        self.despawn_instance_sync(world, instance_id);
+        if let Some(parent) = parent {
+            self.scenes_with_parent.push((instance_id, parent));
+        }
+        Some(scene)
+    }
+
     pub fn spawn_dynamic_sync(
         &mut self,
         world: &mut World,
         resources: &Resources,
         scene_handle: &Handle<DynamicScene>,
-    ) -> Result<(), SceneSpawnError> {
+    ) -> Result<InstanceId, SceneSpawnError> {
         let instance_id = InstanceId::new();
-        let mut instance_info = InstanceInfo {
-            entity_map: EntityMap::default(),
-        };
-        Self::spawn_dynamic_internal(world, resources, scene_handle, &mut instance_info)?;
+        self.spawn_dynamic_sync_internal(world, resources, scene_handle, instance_id)?;
+        Ok(instance_id)
+    }
+
+    fn spawn_dynamic_sync_internal(
+        &mut self,
+        world: &mut World,
+        resources: &Resources,
+        scene_handle: &Handle<DynamicScene>,
+        instance_id: InstanceId,
+    ) -> Result<(), SceneSpawnError> {
+        let mut instance_info = InstanceInfo::new();
+        Self::spawn_dynamic_internal(
+            world,
+            resources,
+            scene_handle,
+            &mut instance_info,
+            &mut self.resources_to_insert,
+        )?;
+        instance_info.roots = find_roots(world, &instance_info);
         self.spawned_instances.insert(instance_id, instance_info);
         let spawned = self
             .spawned_dynamic_scenes
             .entry(scene_handle.clone())
             .or_insert_with(Vec::new);
         spawned.push(instance_id);
+        self.instances_ready.push(instance_id);
         Ok(())
     }
 
@@ -110,6 +266,7 @@ impl SceneSpawner {
         resources: &Resources,
         scene_handle: &Handle<DynamicScene>,
         instance_info: &mut InstanceInfo,
+        resources_to_insert: &mut Vec<Box<dyn Reflect>>,
     ) -> Result<(), SceneSpawnError> {
         let type_registry = resources.get::<TypeRegistryArc>().unwrap();
         let type_registry = type_registry.read();
@@ -119,32 +276,35 @@ impl SceneSpawner {
             .ok_or_else(|| SceneSpawnError::NonExistentScene {
                 handle: scene_handle.clone_weak(),
             })?;
+        // nothing is spawned until the scene's prefabs are loaded, so that spawning can be retried
+        check_prefabs(Some(&scenes), scene, &mut vec![scene_handle.id])?;
+        let mut scene_resources = Vec::new();
+        spawn_dynamic_scene(
+            world,
+            resources,
+            &type_registry,
+            Some(&scenes),
+            scene,
+            instance_info,
+            Some(&mut scene_resources),
+        )?;
 
-        for scene_entity in scene.entities.iter() {
-            let entity = *instance_info
-                .entity_map
-                // TODO: use Entity type directly in scenes to properly encode generation / avoid the need to patch things up?
-                .entry(bevy_ecs::Entity::new(scene_entity.entity))
-                .or_insert_with(|| world.reserve_entity());
-            for component in scene_entity.components.iter() {
-                let registration = type_registry
-                    .get_with_name(component.type_name())
-                    .ok_or_else(|| SceneSpawnError::UnregisteredType {
-                        type_name: component.type_name().to_string(),
-                    })?;
-                let reflect_component =
-                    registration.data::<ReflectComponent>().ok_or_else(|| {
-                        SceneSpawnError::UnregisteredComponent {
-                            type_name: component.type_name().to_string(),
-                        }
-                    })?;
-                if world.has_component_type(entity, registration.type_id()) {
-                    if registration.short_name() != "Camera" {
-                        reflect_component.apply_component(world, entity, &**component);
-                    }
-                } else {
-                    reflect_component.add_component(world, resources, entity, &**component);
+        for resource in scene_resources {
+            let registration = type_registry
+                .get_with_name(resource.type_name())
+                .ok_or_else(|| SceneSpawnError::UnregisteredType {
+                    type_name: resource.type_name().to_string(),
+                })?;
+            let reflect_resource = registration.data::<ReflectResource>().ok_or_else(|| {
+                SceneSpawnError::UnregisteredResource {
+                    type_name: resource.type_name().to_string(),
                 }
+            })?;
+            if reflect_resource.contains_resource(resources) {
+                reflect_resource.apply_resource(resources, &*resource);
+            } else {
+                // inserting a resource requires exclusive access to Resources
+                resources_to_insert.push(resource);
             }
         }
         Ok(())
@@ -155,8 +315,10 @@ impl SceneSpawner {
         world: &mut World,
         resources: &Resources,
         scene_handle: Handle<Scene>,
-    ) -> Result<(), SceneSpawnError> {
-        self.spawn_sync_internal(world, resources, scene_handle, InstanceId::new())
+    ) -> Result<InstanceId, SceneSpawnError> {
+        let instance_id = InstanceId::new();
+        self.spawn_sync_internal(world, resources, scene_handle, instance_id)?;
+        Ok(instance_id)
     }
 
     fn spawn_sync_internal(
@@ -166,9 +328,7 @@ impl SceneSpawner {
         scene_handle: Handle<Scene>,
         instance_id: InstanceId,
     ) -> Result<(), SceneSpawnError> {
-        let mut instance_info = InstanceInfo {
-            entity_map: EntityMap::default(),
-        };
+        let mut instance_info = InstanceInfo::new();
         let type_registry = resources.get::<TypeRegistryArc>().unwrap();
         let type_registry = type_registry.read();
         let scenes = resources.get::<Assets<Scene>>().unwrap();
@@ -214,12 +374,14 @@ impl SceneSpawner {
                     .unwrap();
             }
         }
+        instance_info.roots = find_roots(world, &instance_info);
         self.spawned_instances.insert(instance_id, instance_info);
         let spawned = self
             .spawned_scenes
             .entry(scene_handle)
             .or_insert_with(Vec::new);
         spawned.push(instance_id);
+        self.instances_ready.push(instance_id);
         Ok(())
     }
 
@@ -238,7 +400,10 @@ impl SceneSpawner {
                             resources,
                             scene_handle,
                             instance_info,
+                            &mut self.resources_to_insert,
                         )?;
+                        // entities added by the new version of the scene can be roots
+                        instance_info.roots = find_roots(world, instance_info);
                     }
                 }
             }
@@ -252,6 +417,27 @@ impl SceneSpawner {
         for scene_handle in scenes_to_despawn {
             self.despawn_sync(world, scene_handle)?;
         }
+
+        let instances_to_despawn = std::mem::take(&mut self.instances_to_despawn);
+
+        for instance_id in instances_to_despawn {
+            self.despawn_instance_sync(world, instance_id);
+        }
+
+        // the scenes of respawned instances are spawned again with the other queued scenes
+        let instances_to_respawn = std::mem::take(&mut self.instances_to_respawn);
+
+        for instance_id in instances_to_respawn {
+            match self.despawn_for_respawn(world, instance_id) {
+                Some(InstanceScene::Dynamic(handle)) => {
+                    self.dynamic_scenes_to_spawn.push((handle, instance_id))
+                }
+                Some(InstanceScene::Scene(handle)) => {
+                    self.scenes_to_spawn.push((handle, instance_id))
+                }
+                None => {}
+            }
+        }
         Ok(())
     }
 
@@ -262,12 +448,13 @@ impl SceneSpawner {
     ) -> Result<(), SceneSpawnError> {
         let scenes_to_spawn = std::mem::take(&mut self.dynamic_scenes_to_spawn);
 
-        for scene_handle in scenes_to_spawn {
-            match self.spawn_dynamic_sync(world, resources, &scene_handle) {
+        for (scene_handle, instance_id) in scenes_to_spawn {
+            match self.spawn_dynamic_sync_internal(world, resources, &scene_handle, instance_id) {
                 Ok(_) => {}
-                Err(SceneSpawnError::NonExistentScene { .. }) => {
-                    self.dynamic_scenes_to_spawn.push(scene_handle)
-                }
+                Err(SceneSpawnError::NonExistentScene { .. })
+                | Err(SceneSpawnError::NonExistentPrefab { .. }) => self
+                    .dynamic_scenes_to_spawn
+                    .push((scene_handle, instance_id)),
                 Err(err) => return Err(err),
             }
         }
@@ -287,11 +474,34 @@ impl SceneSpawner {
         Ok(())
     }
 
+    /// Returns true once all entities of the instance have been spawned
+    pub fn instance_is_ready(&self, instance_id: InstanceId) -> bool {
+        self.spawned_instances.contains_key(&instance_id)
+    }
+
+    /// Returns the entities of the instance that have no parent in the scene, or `None` if the instance isn't ready
+    pub fn instance_roots(&self, instance_id: InstanceId) -> Option<&[Entity]> {
+        self.spawned_instances
+            .get(&instance_id)
+            .map(|instance| &instance.roots[..])
+    }
+
+    /// Iterates the entities spawned for the instance, or returns `None` if the instance isn't ready
+    pub fn iter_instance_entities(
+        &self,
+        instance_id: InstanceId,
+    ) -> Option<impl Iterator<Item = Entity>> {
+        self.spawned_instances
+            .get(&instance_id)
+            .map(|instance| instance.entities().into_iter())
+    }
+
     pub(crate) fn set_scene_instance_parent_sync(&mut self, world: &mut World) {
         let scenes_with_parent = std::mem::take(&mut self.scenes_with_parent);
 
         for (instance_id, parent) in scenes_with_parent {
-            if let Some(instance) = self.spawned_instances.get(&instance_id) {
+            if let Some(instance) = self.spawned_instances.get_mut(&instance_id) {
+                instance.parent = Some(parent);
                 for entity in instance.entity_map.values() {
                     if let Err(bevy_ecs::ComponentError::MissingComponent(_)) =
                         world.get::<Parent>(entity)
@@ -309,6 +519,7 @@ impl SceneSpawner {
 pub fn scene_spawner_system(world: &mut World, resources: &mut Resources) {
     let mut scene_spawner = resources.get_mut::<SceneSpawner>().unwrap();
     let scene_asset_events = resources.get::<Events<AssetEvent<DynamicScene>>>().unwrap();
+    let scenes = resources.get::<Assets<DynamicScene>>().unwrap();
 
     let mut updated_spawned_scenes = Vec::new();
     for event in scene_spawner
@@ -316,11 +527,19 @@ pub fn scene_spawner_system(world: &mut World, resources: &mut Resources) {
         .iter(&scene_asset_events)
     {
         if let AssetEvent::Modified { handle } = event {
-            if scene_spawner.spawned_dynamic_scenes.contains_key(handle) {
-                updated_spawned_scenes.push(handle.clone_weak());
+            // scenes are respawned when they change or when one of their prefabs changes
+            for spawned_handle in scene_spawner.spawned_dynamic_scenes.keys() {
+                let is_updated = spawned_handle == handle
+                    || scenes.get(spawned_handle).map_or(false, |scene| {
+                        uses_prefab(&scenes, scene, handle, &mut vec![spawned_handle.id])
+                    });
+                if is_updated && !updated_spawned_scenes.contains(spawned_handle) {
+                    updated_spawned_scenes.push(spawned_handle.clone_weak());
+                }
             }
         }
     }
+    drop(scenes);
 
     scene_spawner.despawn_queued_scenes(world).unwrap();
     scene_spawner
@@ -330,4 +549,519 @@ pub fn scene_spawner_system(world: &mut World, resources: &mut Resources) {
         .update_spawned_scenes(world, resources, &updated_spawned_scenes)
         .unwrap();
     scene_spawner.set_scene_instance_parent_sync(world);
+
+    let resources_to_insert = std::mem::take(&mut scene_spawner.resources_to_insert);
+    let instances_ready = std::mem::take(&mut scene_spawner.instances_ready);
+    drop(scene_spawner);
+    drop(scene_asset_events);
+    insert_scene_resources(resources, resources_to_insert);
+
+    let mut instance_ready_events = resources.get_mut::<Events<SceneInstanceReady>>().unwrap();
+    for instance_id in instances_ready {
+        instance_ready_events.send(SceneInstanceReady { instance_id });
+    }
+}
+
+/// Returns the spawned entities that don't have a parent
+fn find_roots(world: &World, instance_info: &InstanceInfo) -> Vec<Entity> {
+    instance_info
+        .entities()
+        .into_iter()
+        .filter(|entity| world.get::<Parent>(*entity).is_err())
+        .collect()
+}
+
+/// Returns true if `scene` references `prefab_handle`, directly or through another prefab. `visited` holds the
+/// scenes that were already searched.
+fn uses_prefab(
+    scenes: &Assets<DynamicScene>,
+    scene: &DynamicScene,
+    prefab_handle: &Handle<DynamicScene>,
+    visited: &mut Vec<HandleId>,
+) -> bool {
+    scene.prefabs.iter().any(|prefab| {
+        if prefab.handle.id == prefab_handle.id {
+            return true;
+        }
+        if visited.contains(&prefab.handle.id) {
+            return false;
+        }
+        visited.push(prefab.handle.id);
+        scenes.get(&prefab.handle).map_or(false, |prefab_scene| {
+            uses_prefab(scenes, prefab_scene, prefab_handle, visited)
+        })
+    })
+}
+
+/// Checks that the prefabs of `scene` are loaded and don't reference themselves. `ancestors` holds the scenes that
+/// reference `scene`, directly or through other prefabs.
+pub(crate) fn check_prefabs(
+    scenes: Option<&Assets<DynamicScene>>,
+    scene: &DynamicScene,
+    ancestors: &mut Vec<HandleId>,
+) -> Result<(), SceneSpawnError> {
+    for prefab in scene.prefabs.iter() {
+        if ancestors.contains(&prefab.handle.id) {
+            return Err(SceneSpawnError::PrefabCycle {
+                path: prefab.path.clone(),
+            });
+        }
+        let prefab_scene = scenes
+            .and_then(|scenes| scenes.get(&prefab.handle))
+            .ok_or_else(|| SceneSpawnError::NonExistentPrefab {
+                path: prefab.path.clone(),
+            })?;
+        ancestors.push(prefab.handle.id);
+        check_prefabs(scenes, prefab_scene, ancestors)?;
+        ancestors.pop();
+    }
+    Ok(())
+}
+
+/// Spawns the entities of `scene` and of its prefabs, and applies the prefab patches. Entities that are already
+/// in the entity maps of `instance_info` are updated instead. The prefabs must have been checked with
+/// [check_prefabs]. The resources of the scene and its prefabs are cloned into `scene_resources`, if it is given.
+pub(crate) fn spawn_dynamic_scene(
+    world: &mut World,
+    resources: &Resources,
+    type_registry: &TypeRegistry,
+    scenes: Option<&Assets<DynamicScene>>,
+    scene: &DynamicScene,
+    instance_info: &mut InstanceInfo,
+    mut scene_resources: Option<&mut Vec<Box<dyn Reflect>>>,
+) -> Result<(), SceneSpawnError> {
+    if let Some(scene_resources) = scene_resources.as_mut() {
+        scene_resources.extend(
+            scene
+                .resources
+                .iter()
+                .map(|resource| resource.clone_value()),
+        );
+    }
+
+    for scene_entity in scene.entities.iter() {
+        let entity = *instance_info
+            .entity_map
+            // TODO: use Entity type directly in scenes to properly encode generation / avoid the need to patch things up?
+            .entry(bevy_ecs::Entity::new(scene_entity.entity))
+            .or_insert_with(|| world.reserve_entity());
+        write_components(
+            world,
+            resources,
+            type_registry,
+            entity,
+            &scene_entity.components,
+        )?;
+    }
+
+    instance_info
+        .prefabs
+        .resize_with(scene.prefabs.len(), InstanceInfo::new);
+    for (prefab, prefab_info) in scene.prefabs.iter().zip(instance_info.prefabs.iter_mut()) {
+        // the prefab scenes were checked by the caller
+        let prefab_scene = scenes
+            .and_then(|scenes| scenes.get(&prefab.handle))
+            .unwrap();
+        spawn_dynamic_scene(
+            world,
+            resources,
+            type_registry,
+            scenes,
+            prefab_scene,
+            prefab_info,
+            scene_resources.as_deref_mut(),
+        )?;
+
+        // patches are applied after every (re)spawn, so they survive changes to the prefab
+        for patch in prefab.patches.iter() {
+            let entity = prefab_info
+                .entity_map
+                .get(bevy_ecs::Entity::new(patch.entity))
+                .map_err(|_| SceneSpawnError::NonExistentPrefabEntity {
+                    path: prefab.path.clone(),
+                    entity: patch.entity,
+                })?;
+            write_components(world, resources, type_registry, entity, &patch.components)?;
+        }
+    }
+    Ok(())
+}
+
+fn write_components(
+    world: &mut World,
+    resources: &Resources,
+    type_registry: &TypeRegistry,
+    entity: Entity,
+    components: &[Box<dyn Reflect>],
+) -> Result<(), SceneSpawnError> {
+    for component in components.iter() {
+        let registration = type_registry
+            .get_with_name(component.type_name())
+            .ok_or_else(|| SceneSpawnError::UnregisteredType {
+                type_name: component.type_name().to_string(),
+            })?;
+        let reflect_component = registration.data::<ReflectComponent>().ok_or_else(|| {
+            SceneSpawnError::UnregisteredComponent {
+                type_name: component.type_name().to_string(),
+            }
+        })?;
+        if world.has_component_type(entity, registration.type_id()) {
+            if registration.short_name() != "Camera" {
+                reflect_component.apply_component(world, entity, &**component);
+            }
+        } else {
+            reflect_component.add_component(world, resources, entity, &**component);
+        }
+    }
+    Ok(())
+}
+
+/// Inserts the scene resources that did not exist yet when their scene was spawned
+fn insert_scene_resources(resources: &mut Resources, resources_to_insert: Vec<Box<dyn Reflect>>) {
+    if resources_to_insert.is_empty() {
+        return;
+    }
+
+    let type_registry = (*resources.get::<TypeRegistryArc>().unwrap()).clone();
+    let type_registry = type_registry.read();
+    for resource in resources_to_insert {
+        // the type was checked when the scene was spawned
+        let reflect_resource = type_registry
+            .get_with_name(resource.type_name())
+            .and_then(|registration| registration.data::<ReflectResource>())
+            .unwrap();
+        reflect_resource.insert_resource(resources, &*resource);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ScenePlugin, ScenePrefab};
+    use bevy_asset::AssetPlugin;
+    use bevy_reflect::{ReflectPlugin, RegisterTypeBuilder};
+    use bevy_tasks::{IoTaskPool, TaskPool};
+
+    #[derive(Reflect, Default, Debug, PartialEq)]
+    #[reflect(Component)]
+    struct Health {
+        value: u32,
+    }
+
+    fn app() -> App {
+        let mut app_builder = App::build();
+        app_builder
+            .add_resource(IoTaskPool(TaskPool::new()))
+            .add_plugin(ReflectPlugin)
+            .add_plugin(AssetPlugin)
+            .add_plugin(ScenePlugin)
+            .register_type::<Health>()
+            .register_type::<Parent>();
+        app_builder.app
+    }
+
+    /// Creates a scene whose entities have the given health values
+    fn scene(healths: &[u32], prefabs: Vec<ScenePrefab>) -> DynamicScene {
+        DynamicScene {
+            entities: healths
+                .iter()
+                .enumerate()
+                .map(|(entity, value)| crate::Entity {
+                    entity: entity as u32,
+                    components: vec![Box::new(Health { value: *value })],
+                })
+                .collect(),
+            prefabs,
+            ..Default::default()
+        }
+    }
+
+    fn prefab(path: &str, patches: &[(u32, u32)]) -> ScenePrefab {
+        let patches = patches
+            .iter()
+            .map(|(entity, value)| crate::Entity {
+                entity: *entity,
+                components: vec![Box::new(Health { value: *value })],
+            })
+            .collect();
+        ScenePrefab::new(path.to_string(), patches)
+    }
+
+    fn set_scene(app: &mut App, path: &str, scene: DynamicScene) {
+        let mut scenes = app.resources.get_mut::<Assets<DynamicScene>>().unwrap();
+        scenes.set_untracked(HandleId::from(path), scene);
+    }
+
+    fn healths(app: &App) -> Vec<u32> {
+        let mut healths = app
+            .world
+            .query::<&Health>()
+            .map(|health| health.value)
+            .collect::<Vec<_>>();
+        healths.sort_unstable();
+        healths
+    }
+
+    #[test]
+    fn spawn_nested_prefabs() {
+        let mut app = app();
+        set_scene(&mut app, "c.scn", scene(&[3], Vec::new()));
+        set_scene(&mut app, "b.scn", scene(&[2], vec![prefab("c.scn", &[])]));
+        // the patch of the nested prefab's entity overrides its health
+        let handle = app
+            .resources
+            .get_mut::<Assets<DynamicScene>>()
+            .unwrap()
+            .add(scene(&[1], vec![prefab("b.scn", &[(0, 20)])]));
+
+        let instance_id = app
+            .resources
+            .get_mut::<SceneSpawner>()
+            .unwrap()
+            .spawn_dynamic(handle);
+        app.update();
+        assert_eq!(healths(&app), vec![1, 3, 20]);
+        let scene_spawner = app.resources.get::<SceneSpawner>().unwrap();
+        assert_eq!(
+            scene_spawner
+                .iter_instance_entities(instance_id)
+                .unwrap()
+                .count(),
+            3
+        );
+    }
+
+    #[test]
+    fn write_nested_prefabs_to_world() {
+        let mut app = app();
+        set_scene(&mut app, "b.scn", scene(&[2, 3], Vec::new()));
+        let mut world = World::default();
+        scene(&[1], vec![prefab("b.scn", &[(1, 30)])])
+            .write_to_world(&mut world, &app.resources)
+            .unwrap();
+        let mut healths = world
+            .query::<&Health>()
+            .map(|health| health.value)
+            .collect::<Vec<_>>();
+        healths.sort_unstable();
+        assert_eq!(healths, vec![1, 2, 30]);
+
+        let result = scene(&[1], vec![prefab("b.scn", &[(5, 30)])])
+            .write_to_world(&mut World::default(), &app.resources);
+        assert!(matches!(
+            result,
+            Err(crate::DynamicSceneToWorldError::NonExistentPrefabEntity { entity: 5, .. })
+        ));
+    }
+
+    #[test]
+    fn reapply_prefabs_on_hot_reload() {
+        let mut app = app();
+        set_scene(&mut app, "b.scn", scene(&[2, 3], Vec::new()));
+        let handle = app
+            .resources
+            .get_mut::<Assets<DynamicScene>>()
+            .unwrap()
+            .add(scene(&[1], vec![prefab("b.scn", &[(0, 20)])]));
+        app.resources
+            .get_mut::<SceneSpawner>()
+            .unwrap()
+            .spawn_dynamic(handle);
+        app.update();
+        assert_eq!(healths(&app), vec![1, 3, 20]);
+
+        // the modified prefab is applied to the spawned entities, and the patch is applied again on top of it
+        {
+            let mut scenes = app.resources.get_mut::<Assets<DynamicScene>>().unwrap();
+            scenes.set(HandleId::from("b.scn"), scene(&[4, 5], Vec::new()));
+        }
+        // asset events are sent at the end of a frame
+        app.update();
+        app.update();
+        assert_eq!(healths(&app), vec![1, 5, 20]);
+    }
+
+    #[test]
+    fn prefab_cycles_are_errors() {
+        let mut app = app();
+        set_scene(&mut app, "a.scn", scene(&[1], vec![prefab("b.scn", &[])]));
+        set_scene(&mut app, "b.scn", scene(&[2], vec![prefab("a.scn", &[])]));
+        set_scene(&mut app, "c.scn", scene(&[3], vec![prefab("c.scn", &[])]));
+
+        let result = scene(&[1], vec![prefab("b.scn", &[])])
+            .write_to_world(&mut World::default(), &app.resources);
+        assert!(matches!(
+            result,
+            Err(crate::DynamicSceneToWorldError::PrefabCycle { .. })
+        ));
+
+        let mut scene_spawner = SceneSpawner::default();
+        for path in ["a.scn", "c.scn"].iter() {
+            let result = scene_spawner.spawn_dynamic_sync(
+                &mut app.world,
+                &app.resources,
+                &Handle::weak(HandleId::from(*path)),
+            );
+            assert!(matches!(result, Err(SceneSpawnError::PrefabCycle { .. })));
+        }
+        assert!(healths(&app).is_empty());
+
+        // finding the scenes that use a modified prefab terminates as well
+        let scenes = app.resources.get::<Assets<DynamicScene>>().unwrap();
+        let a = scenes.get(HandleId::from("a.scn")).unwrap();
+        assert!(!uses_prefab(
+            &scenes,
+            a,
+            &Handle::weak(HandleId::from("d.scn")),
+            &mut Vec::new()
+        ));
+    }
+
+    fn ready_instances(app: &App, reader: &mut EventReader<SceneInstanceReady>) -> Vec<InstanceId> {
+        let events = app.resources.get::<Events<SceneInstanceReady>>().unwrap();
+        reader
+            .iter(&events)
+            .map(|event| event.instance_id)
+            .collect()
+    }
+
+    #[test]
+    fn query_and_despawn_instances() {
+        let mut app = app();
+        // the second entity is a child of the first
+        let mut scene = scene(&[1, 2], Vec::new());
+        scene.entities[1]
+            .components
+            .push(Box::new(Parent(Entity::new(0))));
+        let handle = app
+            .resources
+            .get_mut::<Assets<DynamicScene>>()
+            .unwrap()
+            .add(scene);
+        let mut ready_reader = EventReader::<SceneInstanceReady>::default();
+
+        let (first, second) = {
+            let mut scene_spawner = app.resources.get_mut::<SceneSpawner>().unwrap();
+            let first = scene_spawner.spawn_dynamic(handle.clone());
+            let second = scene_spawner.spawn_dynamic(handle);
+            assert!(!scene_spawner.instance_is_ready(first));
+            assert!(scene_spawner.instance_roots(first).is_none());
+            assert!(scene_spawner.iter_instance_entities(first).is_none());
+            (first, second)
+        };
+        assert_ne!(first, second);
+
+        app.update();
+        assert_eq!(
+            ready_instances(&app, &mut ready_reader),
+            vec![first, second]
+        );
+        assert_eq!(healths(&app), vec![1, 1, 2, 2]);
+        {
+            let scene_spawner = app.resources.get::<SceneSpawner>().unwrap();
+            assert!(scene_spawner.instance_is_ready(first));
+            let roots = scene_spawner.instance_roots(first).unwrap();
+            assert_eq!(roots.len(), 1);
+            assert_eq!(app.world.get::<Health>(roots[0]).unwrap().value, 1);
+            assert_eq!(
+                scene_spawner.iter_instance_entities(first).unwrap().count(),
+                2
+            );
+        }
+
+        app.resources
+            .get_mut::<SceneSpawner>()
+            .unwrap()
+            .despawn_instance(first);
+        app.update();
+        assert!(ready_instances(&app, &mut ready_reader).is_empty());
+        assert_eq!(healths(&app), vec![1, 2]);
+        let scene_spawner = app.resources.get::<SceneSpawner>().unwrap();
+        assert!(!scene_spawner.instance_is_ready(first));
+        assert!(scene_spawner.instance_is_ready(second));
+    }
+
+    #[test]
+    fn respawn_instances() {
+        let mut app = app();
+        let mut scene_world = World::default();
+        scene_world.spawn((Health { value: 1 },));
+        let handle = app
+            .resources
+            .get_mut::<Assets<Scene>>()
+            .unwrap()
+            .add(Scene::new(scene_world));
+        let parent = app.world.spawn(());
+        let mut ready_reader = EventReader::<SceneInstanceReady>::default();
+
+        let instance_id = app
+            .resources
+            .get_mut::<SceneSpawner>()
+            .unwrap()
+            .spawn_as_child(handle, parent);
+        app.update();
+        assert_eq!(ready_instances(&app, &mut ready_reader), vec![instance_id]);
+        let root = app
+            .resources
+            .get::<SceneSpawner>()
+            .unwrap()
+            .instance_roots(instance_id)
+            .unwrap()[0];
+        app.world.get_mut::<Health>(root).unwrap().value = 10;
+
+        // the respawned instance keeps its id and its parent, and gets new entities
+        app.resources
+            .get_mut::<SceneSpawner>()
+            .unwrap()
+            .respawn_instance(instance_id);
+        app.update();
+        assert_eq!(ready_instances(&app, &mut ready_reader), vec![instance_id]);
+        assert_eq!(healths(&app), vec![1]);
+        assert!(app.world.get::<Health>(root).is_err());
+        let respawned_root = app
+            .resources
+            .get::<SceneSpawner>()
+            .unwrap()
+            .instance_roots(instance_id)
+            .unwrap()[0];
+        assert_ne!(respawned_root, root);
+        assert_eq!(
+            *app.world.get::<Parent>(respawned_root).unwrap(),
+            Parent(parent)
+        );
+    }
+
+    #[test]
+    fn update_roots_on_hot_reload() {
+        let mut app = app();
+        set_scene(&mut app, "a.scn", scene(&[1], Vec::new()));
+        let handle = Handle::weak(HandleId::from("a.scn"));
+        let instance_id = {
+            let mut scene_spawner = app.resources.get_mut::<SceneSpawner>().unwrap();
+            scene_spawner
+                .spawn_dynamic_sync(&mut app.world, &app.resources, &handle)
+                .unwrap()
+        };
+        let roots = |app: &App| {
+            let scene_spawner = app.resources.get::<SceneSpawner>().unwrap();
+            scene_spawner.instance_roots(instance_id).unwrap().len()
+        };
+        assert_eq!(roots(&app), 1);
+
+        {
+            let mut scenes = app.resources.get_mut::<Assets<DynamicScene>>().unwrap();
+            scenes.set(HandleId::from("a.scn"), scene(&[1, 2], Vec::new()));
+        }
+        app.update();
+        app.update();
+        assert_eq!(healths(&app), vec![1, 2]);
+        assert_eq!(roots(&app), 2);
+
+        // respawning synchronously spawns the new version of the scene as well
+        let mut scene_spawner = app.resources.get_mut::<SceneSpawner>().unwrap();
+        scene_spawner
+            .respawn_instance_sync(&mut app.world, &app.resources, instance_id)
+            .unwrap();
+        assert_eq!(scene_spawner.instance_roots(instance_id).unwrap().len(), 2);
+    }
 }
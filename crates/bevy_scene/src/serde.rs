@@ -1,8 +1,8 @@
-use crate::{DynamicScene, Entity};
+use crate::{DynamicScene, Entity, ScenePrefab};
 use anyhow::Result;
 use bevy_reflect::{
     serde::{ReflectDeserializer, ReflectSerializer},
-    Reflect, TypeRegistry, TypeRegistryArc,
+    Reflect, SkipSerializing, TypeRegistry, TypeRegistryArc,
 };
 use serde::{
     de::{DeserializeSeed, Error, MapAccess, SeqAccess, Visitor},
@@ -26,8 +26,93 @@ impl<'a> Serialize for SceneSerializer<'a> {
     where
         S: serde::Serializer,
     {
-        let mut state = serializer.serialize_seq(Some(self.scene.entities.len()))?;
-        for entity in self.scene.entities.iter() {
+        let has_prefabs = !self.scene.prefabs.is_empty();
+        let mut state = serializer.serialize_struct(SCENE_STRUCT, 2 + has_prefabs as usize)?;
+        state.serialize_field(
+            SCENE_FIELD_RESOURCES,
+            &ComponentsSerializer {
+                components: &self.scene.resources,
+                registry: self.registry,
+            },
+        )?;
+        state.serialize_field(
+            SCENE_FIELD_ENTITIES,
+            &EntitiesSerializer {
+                entities: &self.scene.entities,
+                registry: self.registry,
+            },
+        )?;
+        if has_prefabs {
+            state.serialize_field(
+                SCENE_FIELD_PREFABS,
+                &PrefabsSerializer {
+                    prefabs: &self.scene.prefabs,
+                    registry: self.registry,
+                },
+            )?;
+        } else {
+            state.skip_field(SCENE_FIELD_PREFABS)?;
+        }
+        state.end()
+    }
+}
+
+pub struct PrefabsSerializer<'a> {
+    pub prefabs: &'a [ScenePrefab],
+    pub registry: &'a TypeRegistryArc,
+}
+
+impl<'a> Serialize for PrefabsSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_seq(Some(self.prefabs.len()))?;
+        for prefab in self.prefabs.iter() {
+            state.serialize_element(&PrefabSerializer {
+                prefab,
+                registry: self.registry,
+            })?;
+        }
+        state.end()
+    }
+}
+
+pub struct PrefabSerializer<'a> {
+    pub prefab: &'a ScenePrefab,
+    pub registry: &'a TypeRegistryArc,
+}
+
+impl<'a> Serialize for PrefabSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_struct(PREFAB_STRUCT, 2)?;
+        state.serialize_field(PREFAB_FIELD_PATH, &self.prefab.path)?;
+        state.serialize_field(
+            PREFAB_FIELD_PATCHES,
+            &EntitiesSerializer {
+                entities: &self.prefab.patches,
+                registry: self.registry,
+            },
+        )?;
+        state.end()
+    }
+}
+
+pub struct EntitiesSerializer<'a> {
+    pub entities: &'a [Entity],
+    pub registry: &'a TypeRegistryArc,
+}
+
+impl<'a> Serialize for EntitiesSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_seq(Some(self.entities.len()))?;
+        for entity in self.entities.iter() {
             state.serialize_element(&EntitySerializer {
                 entity,
                 registry: self.registry,
@@ -70,12 +155,22 @@ impl<'a> Serialize for ComponentsSerializer<'a> {
     where
         S: serde::Serializer,
     {
-        let mut state = serializer.serialize_seq(Some(self.components.len()))?;
-        for component in self.components.iter() {
-            state.serialize_element(&ReflectSerializer::new(
-                &**component,
-                &*self.registry.read(),
-            ))?;
+        let registry = self.registry.read();
+        // types marked with `#[reflect(skip_serializing)]` are left out of scene files
+        let components = self
+            .components
+            .iter()
+            .filter(|component| {
+                registry
+                    .get_with_name(component.type_name())
+                    .map_or(true, |registration| {
+                        registration.data::<SkipSerializing>().is_none()
+                    })
+            })
+            .collect::<Vec<_>>();
+        let mut state = serializer.serialize_seq(Some(components.len()))?;
+        for component in components {
+            state.serialize_element(&ReflectSerializer::new(&**component, &*registry))?;
         }
         state.end()
     }
@@ -92,10 +187,247 @@ impl<'a, 'de> DeserializeSeed<'de> for SceneDeserializer<'a> {
     where
         D: serde::Deserializer<'de>,
     {
+        // scenes written before resources and prefabs were added are a plain list of entities
+        deserializer.deserialize_any(SceneVisitor {
+            type_registry: self.type_registry,
+        })
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(field_identifier, rename_all = "lowercase")]
+enum SceneField {
+    Resources,
+    Entities,
+    Prefabs,
+}
+
+pub const SCENE_STRUCT: &str = "Scene";
+pub const SCENE_FIELD_RESOURCES: &str = "resources";
+pub const SCENE_FIELD_ENTITIES: &str = "entities";
+pub const SCENE_FIELD_PREFABS: &str = "prefabs";
+
+struct SceneVisitor<'a> {
+    pub type_registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> Visitor<'de> for SceneVisitor<'a> {
+    type Value = DynamicScene;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("scene struct or list of entities")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut entities = Vec::new();
+        while let Some(entity) = seq.next_element_seed(SceneEntityDeserializer {
+            type_registry: self.type_registry,
+        })? {
+            entities.push(entity);
+        }
+
         Ok(DynamicScene {
-            entities: deserializer.deserialize_seq(SceneEntitySeqVisitor {
+            entities,
+            ..Default::default()
+        })
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut resources = None;
+        let mut entities = None;
+        let mut prefabs = None;
+        while let Some(key) = map.next_key()? {
+            match key {
+                SceneField::Resources => {
+                    if resources.is_some() {
+                        return Err(Error::duplicate_field(SCENE_FIELD_RESOURCES));
+                    }
+                    resources = Some(map.next_value_seed(ComponentVecDeserializer {
+                        registry: self.type_registry,
+                    })?);
+                }
+                SceneField::Entities => {
+                    if entities.is_some() {
+                        return Err(Error::duplicate_field(SCENE_FIELD_ENTITIES));
+                    }
+                    entities = Some(map.next_value_seed(SceneEntitySeqDeserializer {
+                        type_registry: self.type_registry,
+                    })?);
+                }
+                SceneField::Prefabs => {
+                    if prefabs.is_some() {
+                        return Err(Error::duplicate_field(SCENE_FIELD_PREFABS));
+                    }
+                    prefabs = Some(map.next_value_seed(PrefabSeqDeserializer {
+                        type_registry: self.type_registry,
+                    })?);
+                }
+            }
+        }
+
+        // scenes without global state or prefabs can leave out their resources and prefabs
+        let resources = resources.unwrap_or_default();
+        let prefabs = prefabs.unwrap_or_default();
+        let entities = entities.ok_or_else(|| Error::missing_field(SCENE_FIELD_ENTITIES))?;
+        Ok(DynamicScene {
+            resources,
+            entities,
+            prefabs,
+        })
+    }
+}
+
+struct PrefabSeqDeserializer<'a> {
+    pub type_registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for PrefabSeqDeserializer<'a> {
+    type Value = Vec<ScenePrefab>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(PrefabSeqVisitor {
+            type_registry: self.type_registry,
+        })
+    }
+}
+
+struct PrefabSeqVisitor<'a> {
+    pub type_registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> Visitor<'de> for PrefabSeqVisitor<'a> {
+    type Value = Vec<ScenePrefab>;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("list of prefabs")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut prefabs = Vec::new();
+        while let Some(prefab) = seq.next_element_seed(PrefabDeserializer {
+            type_registry: self.type_registry,
+        })? {
+            prefabs.push(prefab);
+        }
+
+        Ok(prefabs)
+    }
+}
+
+pub struct PrefabDeserializer<'a> {
+    pub type_registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for PrefabDeserializer<'a> {
+    type Value = ScenePrefab;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_struct(
+            PREFAB_STRUCT,
+            &[PREFAB_FIELD_PATH, PREFAB_FIELD_PATCHES],
+            PrefabVisitor {
+                type_registry: self.type_registry,
+            },
+        )
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(field_identifier, rename_all = "lowercase")]
+enum PrefabField {
+    Path,
+    Patches,
+}
+
+pub const PREFAB_STRUCT: &str = "Prefab";
+pub const PREFAB_FIELD_PATH: &str = "path";
+pub const PREFAB_FIELD_PATCHES: &str = "patches";
+
+struct PrefabVisitor<'a> {
+    pub type_registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> Visitor<'de> for PrefabVisitor<'a> {
+    type Value = ScenePrefab;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("prefab struct")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let path = seq
+            .next_element::<String>()?
+            .ok_or_else(|| Error::invalid_length(0, &self))?;
+        let patches = seq
+            .next_element_seed(SceneEntitySeqDeserializer {
                 type_registry: self.type_registry,
-            })?,
+            })?
+            .ok_or_else(|| Error::invalid_length(1, &self))?;
+        Ok(ScenePrefab::new(path, patches))
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut path = None;
+        let mut patches = None;
+        while let Some(key) = map.next_key()? {
+            match key {
+                PrefabField::Path => {
+                    if path.is_some() {
+                        return Err(Error::duplicate_field(PREFAB_FIELD_PATH));
+                    }
+                    path = Some(map.next_value::<String>()?);
+                }
+                PrefabField::Patches => {
+                    if patches.is_some() {
+                        return Err(Error::duplicate_field(PREFAB_FIELD_PATCHES));
+                    }
+                    patches = Some(map.next_value_seed(SceneEntitySeqDeserializer {
+                        type_registry: self.type_registry,
+                    })?);
+                }
+            }
+        }
+
+        let path = path.ok_or_else(|| Error::missing_field(PREFAB_FIELD_PATH))?;
+        let patches = patches.unwrap_or_default();
+        Ok(ScenePrefab::new(path, patches))
+    }
+}
+
+struct SceneEntitySeqDeserializer<'a> {
+    pub type_registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for SceneEntitySeqDeserializer<'a> {
+    type Value = Vec<Entity>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(SceneEntitySeqVisitor {
+            type_registry: self.type_registry,
         })
     }
 }
@@ -249,3 +581,156 @@ impl<'a, 'de> Visitor<'de> for ComponentSeqVisitor<'a> {
         Ok(dynamic_properties)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::DynamicScene;
+    use bevy_ecs::{Resources, World};
+    use bevy_reflect::{Reflect, ReflectComponent, ReflectResource, TypeRegistryArc};
+
+    #[derive(Reflect, Default, Debug, PartialEq)]
+    #[reflect(Component)]
+    struct Health {
+        value: u32,
+    }
+
+    #[derive(Reflect, Default, Debug, PartialEq)]
+    #[reflect(Resource)]
+    struct Score {
+        value: u32,
+    }
+
+    fn resources() -> Resources {
+        let registry = TypeRegistryArc::default();
+        {
+            let mut registry = registry.write();
+            registry.register::<u32>();
+            registry.register::<Health>();
+            registry.register::<Score>();
+        }
+        let mut resources = Resources::default();
+        resources.insert(registry);
+        resources
+    }
+
+    fn healths(world: &World) -> Vec<u32> {
+        world
+            .query::<&Health>()
+            .map(|health| health.value)
+            .collect()
+    }
+
+    #[test]
+    fn round_trip_resources() {
+        let mut resources = resources();
+        resources.insert(Score { value: 10 });
+        let registry = (*resources.get::<TypeRegistryArc>().unwrap()).clone();
+        let mut world = World::default();
+        world.spawn((Health { value: 5 },));
+        let scene = DynamicScene::from_world_with_resources(&world, &resources, &registry);
+        assert_eq!(scene.resources.len(), 1);
+
+        let ron = scene.serialize_ron(&registry).unwrap();
+        let binary = scene.serialize_binary(&registry).unwrap();
+        let from_ron = DynamicScene::deserialize_ron(ron.as_bytes(), &registry.read()).unwrap();
+        let from_binary = DynamicScene::deserialize_binary(&binary, &registry.read()).unwrap();
+        for scene in [from_ron, from_binary].iter() {
+            let mut resources = self::resources();
+            let mut world = World::default();
+            scene
+                .write_to_world_with_resources(&mut world, &mut resources)
+                .unwrap();
+            assert_eq!(healths(&world), vec![5]);
+            assert_eq!(*resources.get::<Score>().unwrap(), Score { value: 10 });
+
+            // write_to_world only spawns entities
+            let resources = self::resources();
+            scene.write_to_world(&mut world, &resources).unwrap();
+            assert_eq!(healths(&world), vec![5, 5]);
+            assert!(resources.get::<Score>().is_none());
+        }
+    }
+
+    #[derive(Reflect, Default)]
+    #[reflect(Component, skip_serializing)]
+    struct Cache {
+        value: u32,
+    }
+
+    #[test]
+    fn skip_serializing_components() {
+        let registry = TypeRegistryArc::default();
+        {
+            let mut registry = registry.write();
+            registry.register::<u32>();
+            registry.register::<Health>();
+            registry.register::<Cache>();
+        }
+        let mut world = World::default();
+        world.spawn((Health { value: 5 }, Cache { value: 7 }));
+        let scene = DynamicScene::from_world(&world, &registry);
+        // the component is captured, but left out of scene files
+        assert_eq!(scene.entities[0].components.len(), 2);
+
+        let ron = scene.serialize_ron(&registry).unwrap();
+        assert!(ron.contains("Health"));
+        assert!(!ron.contains("Cache"));
+        let binary = scene.serialize_binary(&registry).unwrap();
+        let from_binary = DynamicScene::deserialize_binary(&binary, &registry.read()).unwrap();
+        assert_eq!(from_binary.entities[0].components.len(), 1);
+    }
+
+    #[test]
+    fn resources_and_prefabs_are_optional() {
+        let resources = resources();
+        let registry = resources.get::<TypeRegistryArc>().unwrap();
+        let scene = DynamicScene::deserialize_ron(
+            br#"(
+              entities: [
+                (
+                  entity: 0,
+                  components: [],
+                ),
+              ],
+            )"#,
+            &registry.read(),
+        )
+        .unwrap();
+        assert_eq!(scene.entities.len(), 1);
+        assert!(scene.resources.is_empty());
+        assert!(scene.prefabs.is_empty());
+    }
+
+    #[test]
+    fn load_legacy_entity_list() {
+        let resources = resources();
+        let registry = resources.get::<TypeRegistryArc>().unwrap();
+        let scene = DynamicScene::deserialize_ron(
+            br#"[
+              (
+                entity: 3,
+                components: [
+                  {
+                    "type": "bevy_scene::serde::tests::Health",
+                    "struct": {
+                      "value": {
+                        "type": "u32",
+                        "value": 7,
+                      },
+                    },
+                  },
+                ],
+              ),
+            ]"#,
+            &registry.read(),
+        )
+        .unwrap();
+        assert!(scene.resources.is_empty());
+        assert_eq!(scene.entities.len(), 1);
+        assert_eq!(scene.entities[0].entity, 3);
+
+        let mut world = World::default();
+        scene.write_to_world(&mut world, &resources).unwrap();
+        assert_eq!(healths(&world), vec![7]);
+    }
+}
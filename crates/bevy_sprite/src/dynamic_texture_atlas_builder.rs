@@ -84,6 +84,8 @@ impl DynamicTextureAtlasBuilder {
             atlas_texture.data[begin..end]
                 .copy_from_slice(&texture.data[texture_begin..texture_end]);
         }
+        // mip levels downsampled from the atlas before the texture was added are stale
+        atlas_texture.mipmaps.clear();
     }
 }
 
@@ -5,6 +5,7 @@ use crate::{
 use bevy_asset::Handle;
 use bevy_ecs::Bundle;
 use bevy_render::{
+    camera::NoFrustumCulling,
     mesh::Mesh,
     pipeline::{RenderPipeline, RenderPipelines},
     prelude::{Draw, Visible},
@@ -21,6 +22,7 @@ pub struct SpriteBundle {
     pub draw: Draw,
     pub visible: Visible,
     pub render_pipelines: RenderPipelines,
+    pub no_frustum_culling: NoFrustumCulling,
     pub transform: Transform,
     pub global_transform: GlobalTransform,
 }
@@ -29,6 +31,7 @@ impl Default for SpriteBundle {
     fn default() -> Self {
         Self {
             mesh: QUAD_HANDLE.typed(),
+            no_frustum_culling: NoFrustumCulling,
             render_pipelines: RenderPipelines::from_pipelines(vec![RenderPipeline::new(
                 SPRITE_PIPELINE_HANDLE.typed(),
             )]),
@@ -58,6 +61,7 @@ pub struct SpriteSheetBundle {
     pub draw: Draw,
     pub visible: Visible,
     pub render_pipelines: RenderPipelines,
+    pub no_frustum_culling: NoFrustumCulling,
     pub main_pass: MainPass,
     pub mesh: Handle<Mesh>, // TODO: maybe abstract this out
     pub transform: Transform,
@@ -76,6 +80,7 @@ impl Default for SpriteSheetBundle {
             },
             main_pass: MainPass,
             mesh: QUAD_HANDLE.typed(),
+            no_frustum_culling: NoFrustumCulling,
             draw: Default::default(),
             sprite: Default::default(),
             texture_atlas: Default::default(),
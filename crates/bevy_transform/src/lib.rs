@@ -20,6 +20,15 @@ impl Plugin for TransformPlugin {
             .register_type::<PreviousParent>()
             .register_type::<Transform>()
             .register_type::<GlobalTransform>()
+            .register_method("look_at", Transform::look_at)
+            .register_method("rotate", Transform::rotate)
+            .register_method("forward", Transform::forward)
+            .register_method("mul_vec3", Transform::mul_vec3)
+            .register_method("mul_transform", Transform::mul_transform)
+            .register_method(
+                "apply_non_uniform_scale",
+                Transform::apply_non_uniform_scale,
+            )
             // add transform systems to startup so the first update is "correct"
             .add_startup_system_to_stage(startup_stage::POST_STARTUP, parent_update_system)
             .add_startup_system_to_stage(
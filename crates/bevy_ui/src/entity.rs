@@ -8,7 +8,7 @@ use bevy_asset::Handle;
 use bevy_ecs::Bundle;
 use bevy_math::Vec3;
 use bevy_render::{
-    camera::{Camera, OrthographicProjection, VisibleEntities, WindowOrigin},
+    camera::{Camera, NoFrustumCulling, OrthographicProjection, VisibleEntities, WindowOrigin},
     draw::Draw,
     mesh::Mesh,
     pipeline::{RenderPipeline, RenderPipelines},
@@ -26,6 +26,7 @@ pub struct NodeBundle {
     pub draw: Draw,
     pub visible: Visible,
     pub render_pipelines: RenderPipelines,
+    pub no_frustum_culling: NoFrustumCulling,
     pub transform: Transform,
     pub global_transform: GlobalTransform,
 }
@@ -34,6 +35,7 @@ impl Default for NodeBundle {
     fn default() -> Self {
         NodeBundle {
             mesh: QUAD_HANDLE.typed(),
+            no_frustum_culling: NoFrustumCulling,
             render_pipelines: RenderPipelines::from_pipelines(vec![RenderPipeline::new(
                 UI_PIPELINE_HANDLE.typed(),
             )]),
@@ -59,6 +61,7 @@ pub struct ImageBundle {
     pub draw: Draw,
     pub visible: Visible,
     pub render_pipelines: RenderPipelines,
+    pub no_frustum_culling: NoFrustumCulling,
     pub transform: Transform,
     pub global_transform: GlobalTransform,
 }
@@ -67,6 +70,7 @@ impl Default for ImageBundle {
     fn default() -> Self {
         ImageBundle {
             mesh: QUAD_HANDLE.typed(),
+            no_frustum_culling: NoFrustumCulling,
             render_pipelines: RenderPipelines::from_pipelines(vec![RenderPipeline::new(
                 UI_PIPELINE_HANDLE.typed(),
             )]),
@@ -129,6 +133,7 @@ pub struct ButtonBundle {
     pub draw: Draw,
     pub visible: Visible,
     pub render_pipelines: RenderPipelines,
+    pub no_frustum_culling: NoFrustumCulling,
     pub transform: Transform,
     pub global_transform: GlobalTransform,
 }
@@ -138,6 +143,7 @@ impl Default for ButtonBundle {
         ButtonBundle {
             button: Button,
             mesh: QUAD_HANDLE.typed(),
+            no_frustum_culling: NoFrustumCulling,
             render_pipelines: RenderPipelines::from_pipelines(vec![RenderPipeline::new(
                 UI_PIPELINE_HANDLE.typed(),
             )]),
@@ -147,6 +147,10 @@ impl UiRenderGraphBuilder for RenderGraph {
         self.add_node_edge(base::node::MAIN_PASS, node::UI_PASS)
             .unwrap();
 
+        // include the ui in screenshots of the primary window
+        self.add_node_edge(node::UI_PASS, base::node::PRIMARY_SCREENSHOT)
+            .unwrap();
+
         // setup ui camera
         self.add_system_node(node::CAMERA_UI, CameraNode::new(camera::CAMERA_UI));
         self.add_node_edge(node::CAMERA_UI, node::UI_PASS).unwrap();
@@ -12,7 +12,10 @@ pub use wgpu_resources::*;
 
 use bevy_app::prelude::*;
 use bevy_ecs::{Resources, World};
-use bevy_render::renderer::{shared_buffers_update_system, RenderResourceContext, SharedBuffers};
+use bevy_render::{
+    renderer::{shared_buffers_update_system, RenderResourceContext, SharedBuffers},
+    texture::CompressedTextureSupport,
+};
 use renderer::WgpuRenderResourceContext;
 
 #[derive(Default)]
@@ -34,6 +37,14 @@ pub fn get_wgpu_render_system(resources: &mut Resources) -> impl FnMut(&mut Worl
         .get_cloned::<WgpuOptions>()
         .unwrap_or_else(WgpuOptions::default);
     let mut wgpu_renderer = future::block_on(WgpuRenderer::new(options));
+    if let Some(compressed_texture_support) = resources.get::<CompressedTextureSupport>() {
+        compressed_texture_support.set_bc(
+            wgpu_renderer
+                .device
+                .features()
+                .contains(wgpu::Features::TEXTURE_COMPRESSION_BC),
+        );
+    }
     let resource_context = WgpuRenderResourceContext::new(wgpu_renderer.device.clone());
     resources.insert::<Box<dyn RenderResourceContext>>(Box::new(resource_context));
     resources.insert(SharedBuffers::new(4096));
@@ -104,6 +104,56 @@ impl WgpuRenderResourceContext {
         );
     }
 
+    fn map_buffer_with_mode(&self, id: BufferId, mode: wgpu::MapMode) {
+        let buffers = self.resources.buffers.read();
+        let buffer = buffers.get(&id).unwrap();
+        let buffer_slice = buffer.slice(..);
+        let data = buffer_slice.map_async(mode);
+        self.device.poll(wgpu::Maintain::Wait);
+        if future::block_on(data).is_err() {
+            panic!("Failed to map buffer to host.");
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn copy_texture_to_buffer(
+        &self,
+        command_encoder: &mut wgpu::CommandEncoder,
+        source_texture: TextureId,
+        source_origin: [u32; 3], // TODO: replace with math type
+        source_mip_level: u32,
+        destination_buffer: BufferId,
+        destination_offset: u64,
+        destination_bytes_per_row: u32,
+        size: Extent3d,
+    ) {
+        let buffers = self.resources.buffers.read();
+        let textures = self.resources.textures.read();
+
+        let source = textures.get(&source_texture).unwrap();
+        let destination = buffers.get(&destination_buffer).unwrap();
+        command_encoder.copy_texture_to_buffer(
+            wgpu::TextureCopyView {
+                texture: source,
+                mip_level: source_mip_level,
+                origin: wgpu::Origin3d {
+                    x: source_origin[0],
+                    y: source_origin[1],
+                    z: source_origin[2],
+                },
+            },
+            wgpu::BufferCopyView {
+                buffer: destination,
+                layout: wgpu::TextureDataLayout {
+                    offset: destination_offset,
+                    bytes_per_row: destination_bytes_per_row,
+                    rows_per_image: size.height,
+                },
+            },
+            size.wgpu_into(),
+        );
+    }
+
     pub fn create_bind_group_layout(&self, descriptor: &BindGroupDescriptor) {
         if self
             .resources
@@ -179,7 +229,10 @@ impl RenderResourceContext for WgpuRenderResourceContext {
 
         let descriptor: wgpu::TextureDescriptor = (&texture_descriptor).wgpu_into();
         let texture = self.device.create_texture(&descriptor);
-        let texture_view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let texture_view = texture.create_view(&wgpu::TextureViewDescriptor {
+            dimension: Some(texture_descriptor.view_dimension.wgpu_into()),
+            ..Default::default()
+        });
 
         let id = TextureId::new();
         texture_descriptors.insert(id, texture_descriptor);
@@ -545,15 +598,27 @@ impl RenderResourceContext for WgpuRenderResourceContext {
         write(&mut data, self);
     }
 
+    fn read_mapped_buffer(
+        &self,
+        id: BufferId,
+        range: Range<u64>,
+        read: &mut dyn FnMut(&[u8], &dyn RenderResourceContext),
+    ) {
+        let buffer = {
+            let buffers = self.resources.buffers.read();
+            buffers.get(&id).unwrap().clone()
+        };
+        let buffer_slice = buffer.slice(range);
+        let data = buffer_slice.get_mapped_range();
+        read(&data, self);
+    }
+
     fn map_buffer(&self, id: BufferId) {
-        let buffers = self.resources.buffers.read();
-        let buffer = buffers.get(&id).unwrap();
-        let buffer_slice = buffer.slice(..);
-        let data = buffer_slice.map_async(wgpu::MapMode::Write);
-        self.device.poll(wgpu::Maintain::Wait);
-        if future::block_on(data).is_err() {
-            panic!("Failed to map buffer to host.");
-        }
+        self.map_buffer_with_mode(id, wgpu::MapMode::Write);
+    }
+
+    fn map_buffer_for_read(&self, id: BufferId) {
+        self.map_buffer_with_mode(id, wgpu::MapMode::Read);
     }
 
     fn unmap_buffer(&self, id: BufferId) {
@@ -44,7 +44,8 @@ impl WgpuRenderer {
         let (device, queue) = adapter
             .request_device(
                 &wgpu::DeviceDescriptor {
-                    features: wgpu::Features::empty(),
+                    // compressed textures are passed to the gpu as-is when it supports them
+                    features: adapter.features() & wgpu::Features::TEXTURE_COMPRESSION_BC,
                     limits: wgpu::Limits::default(),
                     shader_validation: true,
                 },
@@ -0,0 +1,61 @@
+use bevy::{
+    diagnostic::{FrameTimeDiagnosticsPlugin, PrintDiagnosticsPlugin},
+    prelude::*,
+};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+/// This example draws 10000 cubes that share a mesh and a material. Because they are `Instanced`, they are drawn
+/// with a single instanced draw call instead of one draw call per cube.
+/// For the best results, run it in release mode: ```cargo run --example instancing --release
+fn main() {
+    App::build()
+        .add_plugins(DefaultPlugins)
+        .add_plugin(FrameTimeDiagnosticsPlugin::default())
+        .add_plugin(PrintDiagnosticsPlugin::default())
+        .add_startup_system(setup)
+        .add_system(rotate_cubes)
+        .run();
+}
+
+fn rotate_cubes(time: Res<Time>, mut query: Query<&mut Transform, With<Instanced>>) {
+    for mut transform in query.iter_mut() {
+        transform.rotate(Quat::from_rotation_y(time.delta_seconds()));
+    }
+}
+
+fn setup(
+    commands: &mut Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    commands
+        // light
+        .spawn(LightBundle {
+            transform: Transform::from_translation(Vec3::new(4.0, -4.0, 5.0)),
+            ..Default::default()
+        })
+        // camera
+        .spawn(Camera3dBundle {
+            transform: Transform::from_translation(Vec3::new(0.0, 15.0, 150.0))
+                .looking_at(Vec3::default(), Vec3::unit_y()),
+            ..Default::default()
+        });
+
+    let mut rng = StdRng::from_entropy();
+    let cube_handle = meshes.add(Mesh::from(shape::Cube { size: 1.0 }));
+    let material_handle = materials.add(Color::rgb(0.8, 0.7, 0.6).into());
+    for _ in 0..10000 {
+        commands
+            .spawn(PbrBundle {
+                mesh: cube_handle.clone(),
+                material: material_handle.clone(),
+                transform: Transform::from_translation(Vec3::new(
+                    rng.gen_range(-50.0, 50.0),
+                    rng.gen_range(-50.0, 50.0),
+                    rng.gen_range(-50.0, 50.0),
+                )),
+                ..Default::default()
+            })
+            .with(Instanced);
+    }
+}
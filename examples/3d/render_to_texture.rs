@@ -0,0 +1,79 @@
+use bevy::{
+    prelude::*,
+    render::{
+        camera::{Camera, RenderLayers, RenderTarget},
+        texture::Extent3d,
+    },
+};
+
+/// This example renders a rotating cube with a second camera into a texture, which is then shown on a quad
+fn main() {
+    App::build()
+        .add_plugins(DefaultPlugins)
+        .add_startup_system(setup)
+        .add_system(rotator_system)
+        .run();
+}
+
+struct Rotator;
+
+fn setup(
+    commands: &mut Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut textures: ResMut<Assets<Texture>>,
+) {
+    let render_target = textures.add(Texture::new_render_target(Extent3d::new(512, 512, 1)));
+
+    commands
+        // cube, seen by both cameras
+        .spawn(PbrBundle {
+            mesh: meshes.add(Mesh::from(shape::Cube { size: 1.0 })),
+            material: materials.add(Color::rgb(0.8, 0.7, 0.6).into()),
+            transform: Transform::from_translation(Vec3::new(-1.5, 0.5, 0.0)),
+            ..Default::default()
+        })
+        .with(Rotator)
+        // quad showing the render target. it is on its own layer so the texture camera doesn't draw it into the
+        // texture it is sampling
+        .spawn(PbrBundle {
+            mesh: meshes.add(Mesh::from(shape::Quad::new(Vec2::new(2.0, 2.0)))),
+            material: materials.add(StandardMaterial {
+                albedo_texture: Some(render_target.clone()),
+                shaded: false,
+                ..Default::default()
+            }),
+            transform: Transform::from_translation(Vec3::new(1.5, 1.0, 0.0)),
+            ..Default::default()
+        })
+        .with(RenderLayers::layer(1))
+        // light
+        .spawn(LightBundle {
+            transform: Transform::from_translation(Vec3::new(4.0, 8.0, 4.0)),
+            ..Default::default()
+        })
+        // camera rendering to the texture
+        .spawn(Camera3dBundle {
+            camera: Camera {
+                name: Some("TextureCamera".to_string()),
+                target: RenderTarget::Texture(render_target),
+                ..Default::default()
+            },
+            transform: Transform::from_translation(Vec3::new(-1.5, 2.0, 3.0))
+                .looking_at(Vec3::new(-1.5, 0.5, 0.0), Vec3::unit_y()),
+            ..Default::default()
+        })
+        // main camera, which sees both layers
+        .spawn(Camera3dBundle {
+            transform: Transform::from_translation(Vec3::new(0.0, 1.0, 6.0))
+                .looking_at(Vec3::new(0.0, 0.5, 0.0), Vec3::unit_y()),
+            ..Default::default()
+        })
+        .with(RenderLayers::layer(0).with(1));
+}
+
+fn rotator_system(time: Res<Time>, mut query: Query<&mut Transform, With<Rotator>>) {
+    for mut transform in query.iter_mut() {
+        transform.rotate(Quat::from_rotation_y(time.delta_seconds()));
+    }
+}
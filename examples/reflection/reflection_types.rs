@@ -71,14 +71,23 @@ fn setup() {
         // `TupleStruct` is a trait automatically implemented for tuple structs that derive Reflect. This trait allows you
         // to interact with fields via their indices
         ReflectRef::TupleStruct(_) => {}
+        // `Tuple` is a special trait that is implemented for rust tuples like (f32, f32). This trait allows you to
+        // interact with fields via their indices
+        ReflectRef::Tuple(_) => {}
         // `List` is a special trait that can be manually implemented (instead of deriving Reflect). This exposes "list"
         // operations on your type, such as indexing and insertion. List is automatically implemented for relevant core
         // types like Vec<T>
         ReflectRef::List(_) => {}
+        // `Array` is a special trait that is implemented for fixed-size rust arrays like [u8; 16]. It exposes indexing
+        // operations, but unlike `List` its length can't change
+        ReflectRef::Array(_) => {}
         // `Map` is a special trait that can be manually implemented (instead of deriving Reflect). This exposes "map"
         // operations on your type, such as getting / inserting by key. Map is automatically implemented for relevant core
         // types like HashMap<K, V>
         ReflectRef::Map(_) => {}
+        // `Enum` is a trait automatically implemented for enums that derive Reflect. This trait allows you to read the
+        // current variant and interact with the fields of that variant via their names or indices
+        ReflectRef::Enum(_) => {}
         // `Value` types do not implement any of the other traits above. They are simply a Reflect implementation. Value
         // is implemented for core types like i32, usize, f32, and String.
         ReflectRef::Value(_) => {}
@@ -53,6 +53,7 @@ fn load_scene_system(asset_server: Res<AssetServer>, mut scene_spawner: ResMut<S
 
     // SceneSpawner can "spawn" scenes. "Spawning" a scene creates a new instance of the scene in the World with new entity ids.
     // This guarantees that it will not overwrite existing entities.
+    // Resources in the scene (like the ClearColor in load_scene_example.scn) are inserted, or patched if they already exist.
     scene_spawner.spawn_dynamic(scene_handle);
 
     // This tells the AssetServer to watch for changes to assets.
@@ -87,7 +88,8 @@ fn save_scene_system(_world: &mut World, resources: &mut Resources) {
 
     // The TypeRegistry resource contains information about all registered types (including components). This is used to construct scenes.
     let type_registry = resources.get::<TypeRegistry>().unwrap();
-    let scene = DynamicScene::from_world(&world, &type_registry);
+    // Resources that are registered with `#[reflect(Resource)]` (like ClearColor) can be captured as well.
+    let scene = DynamicScene::from_world_with_resources(&world, resources, &type_registry);
 
     // Scenes can be serialized like this:
     println!("{}", scene.serialize_ron(&type_registry).unwrap());
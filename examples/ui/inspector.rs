@@ -0,0 +1,61 @@
+use bevy::{
+    inspector::{InspectorPlugin, InspectorUi, InspectorUiPlugin},
+    prelude::*,
+};
+
+/// This example shows the reflected components of an entity in an inspector panel.
+/// Numbers, checkboxes and enums can be edited with the buttons next to them.
+/// Run it with `cargo run --example inspector --features inspector_ui`.
+fn main() {
+    App::build()
+        .add_plugins(DefaultPlugins)
+        .add_plugin(InspectorPlugin)
+        .add_plugin(InspectorUiPlugin)
+        .register_type::<Player>()
+        .register_type::<Stance>()
+        .add_startup_system(setup)
+        .run();
+}
+
+/// A player character
+#[derive(Reflect, Default)]
+#[reflect(Component)]
+struct Player {
+    /// Remaining health points
+    #[reflect(range(0, 100))]
+    health: u32,
+    /// Movement speed in units per second
+    #[reflect(range(0.0, 10.0))]
+    speed: f32,
+    invincible: bool,
+    stance: Stance,
+}
+
+#[derive(Reflect)]
+enum Stance {
+    Standing,
+    Crouching,
+    Prone,
+}
+
+impl Default for Stance {
+    fn default() -> Self {
+        Stance::Standing
+    }
+}
+
+fn setup(
+    commands: &mut Commands,
+    asset_server: Res<AssetServer>,
+    mut inspector_ui: ResMut<InspectorUi>,
+) {
+    commands.spawn(CameraUiBundle::default()).spawn((Player {
+        health: 80,
+        speed: 2.5,
+        invincible: false,
+        stance: Stance::Standing,
+    },));
+
+    inspector_ui.entity = commands.current_entity();
+    inspector_ui.font = asset_server.load("fonts/FiraMono-Medium.ttf");
+}
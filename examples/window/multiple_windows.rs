@@ -1,13 +1,16 @@
 use bevy::{
     prelude::*,
     render::{
-        camera::{ActiveCameras, Camera},
+        camera::{ActiveCameras, Camera, RenderTarget},
         pass::*,
         render_graph::{
             base::MainPass, CameraNode, PassNode, RenderGraph, WindowSwapChainNode,
             WindowTextureNode,
         },
-        texture::{Extent3d, TextureDescriptor, TextureDimension, TextureFormat, TextureUsage},
+        texture::{
+            Extent3d, TextureDescriptor, TextureDimension, TextureFormat, TextureUsage,
+            TextureViewDimension,
+        },
     },
     window::{CreateWindow, WindowDescriptor, WindowId},
 };
@@ -134,6 +137,7 @@ fn setup(
                     mip_level_count: 1,
                     sample_count: msaa.samples,
                     dimension: TextureDimension::D2,
+                    view_dimension: TextureViewDimension::D2,
                     format: TextureFormat::default(),
                     usage: TextureUsage::OUTPUT_ATTACHMENT,
                 },
@@ -170,7 +174,7 @@ fn setup(
         .spawn(Camera3dBundle {
             camera: Camera {
                 name: Some("Secondary".to_string()),
-                window: window_id,
+                target: RenderTarget::Window(window_id),
                 ..Default::default()
             },
             transform: Transform::from_translation(Vec3::new(6.0, 0.0, 0.0))
@@ -0,0 +1,68 @@
+use bevy::{
+    prelude::*,
+    render::{
+        camera::RenderTarget,
+        screenshot::{ScreenshotCaptured, ScreenshotFailed, Screenshots},
+    },
+    window::WindowId,
+};
+
+/// This example saves a screenshot of the primary window as a png when space is pressed
+fn main() {
+    App::build()
+        .add_plugins(DefaultPlugins)
+        .add_startup_system(setup)
+        .add_system(screenshot_system)
+        .add_system(screenshot_captured_system)
+        .add_system(screenshot_failed_system)
+        .run();
+}
+
+fn setup(
+    commands: &mut Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    commands
+        .spawn(PbrBundle {
+            mesh: meshes.add(Mesh::from(shape::Cube { size: 1.0 })),
+            material: materials.add(Color::rgb(0.8, 0.7, 0.6).into()),
+            ..Default::default()
+        })
+        .spawn(LightBundle {
+            transform: Transform::from_translation(Vec3::new(4.0, 8.0, 4.0)),
+            ..Default::default()
+        })
+        .spawn(Camera3dBundle {
+            transform: Transform::from_translation(Vec3::new(-2.0, 2.5, 5.0))
+                .looking_at(Vec3::default(), Vec3::unit_y()),
+            ..Default::default()
+        });
+}
+
+fn screenshot_system(input: Res<Input<KeyCode>>, mut screenshots: ResMut<Screenshots>) {
+    if input.just_pressed(KeyCode::Space) {
+        screenshots.take_and_save(RenderTarget::Window(WindowId::primary()), "screenshot.png");
+    }
+}
+
+fn screenshot_captured_system(
+    mut state: Local<EventReader<ScreenshotCaptured>>,
+    events: Res<Events<ScreenshotCaptured>>,
+) {
+    for event in state.iter(&events) {
+        info!(
+            "saved a {}x{} screenshot",
+            event.texture.size.width, event.texture.size.height
+        );
+    }
+}
+
+fn screenshot_failed_system(
+    mut state: Local<EventReader<ScreenshotFailed>>,
+    events: Res<Events<ScreenshotFailed>>,
+) {
+    for event in state.iter(&events) {
+        warn!("failed to take a screenshot: {}", event.error);
+    }
+}
@@ -26,6 +26,7 @@ crates=(
     bevy_sprite
     bevy_text
     bevy_ui
+    bevy_inspector
     bevy_winit
     bevy_wgpu
 )